@@ -0,0 +1,116 @@
+//! Benchmarks for the hot paths: a single exchange, whole encounters, wound
+//! bookkeeping and catalog lookups
+//!
+//! Run with `cargo bench`; `cargo bench -- --save-baseline main` records a
+//! baseline and `--baseline main` compares against it. The numbers last
+//! recorded are in `benches/BASELINES.md`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use steelkilt::modules::*;
+use steelkilt::*;
+
+fn fighter(name: &str) -> Character {
+    Character::new(
+        name,
+        Attributes::new(7, 6, 7, 5, 5, 5, 5, 5, 5),
+        6,
+        5,
+        Weapon::long_sword(),
+        Armor::leather(),
+    )
+}
+
+fn combat_round_bench(c: &mut Criterion) {
+    let rules = Ruleset::default().with_dice(DiceMode::seeded(7));
+    c.bench_function("combat_round", |b| {
+        b.iter_batched(
+            || (fighter("Aldric"), fighter("Brannoc")),
+            |(mut attacker, mut defender)| {
+                combat_round_with_rules(
+                    &mut attacker,
+                    &mut defender,
+                    DefenseAction::Parry,
+                    black_box(&rules),
+                )
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn encounter_bench(c: &mut Criterion) {
+    let roster = generate_batch(20, &NpcConstraints::new().no_magic(), 11).unwrap();
+    let mut template = Encounter::new();
+    for (i, npc) in roster.into_iter().enumerate() {
+        template.add(npc, (i % 2) as u32 + 1);
+    }
+    // Setup is timed too: rebuilding the fight is what the pool saves
+    c.bench_function("encounter_10v10", |b| {
+        b.iter(|| {
+            let mut encounter = template.clone();
+            encounter.rules.dice = DiceMode::seeded(3);
+            while !encounter.is_over() && encounter.round <= 50 {
+                encounter.run_round();
+            }
+            encounter.winner()
+        })
+    });
+    let mut pool = EncounterPool::new(template);
+    c.bench_function("encounter_pool_10v10", |b| {
+        b.iter(|| pool.run_fight(DiceMode::seeded(3), 50))
+    });
+}
+
+fn wound_stacking_bench(c: &mut Criterion) {
+    let levels = [WoundLevel::Light, WoundLevel::Severe, WoundLevel::Light];
+    c.bench_function("wound_stacking", |b| {
+        b.iter_batched(
+            || fighter("Cedric"),
+            |mut character| {
+                for level in levels.iter().cycle().take(12) {
+                    character.inflict_wound(WoundDescription::new(*level, "Long Sword"));
+                }
+                black_box(character.wounds.is_dead());
+                character.heal_wounds()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn catalog_bench(c: &mut Criterion) {
+    let catalog = EquipmentCatalog::standard();
+    let names = ["Dagger", "Long Sword", "Two-Handed Sword", "Club"];
+    c.bench_function("catalog_lookup", |b| {
+        b.iter(|| {
+            names
+                .iter()
+                .filter_map(|name| catalog.weapon_handle(black_box(name)))
+                .filter_map(|handle| catalog.weapon(handle))
+                .map(|weapon| weapon.damage.get())
+                .sum::<i32>()
+        })
+    });
+}
+
+fn batch_rolls_bench(c: &mut Criterion) {
+    let attacker = fighter("Aldric");
+    let defender = fighter("Brannoc");
+    let profile = AttackProfile::new(&attacker, &defender, DefenseAction::Parry);
+    c.bench_function("rolls_scalar_10k", |b| {
+        b.iter(|| resolve_scalar(black_box(&profile), 10_000, 5))
+    });
+    c.bench_function("rolls_batch_10k", |b| {
+        b.iter(|| resolve_batch(black_box(&profile), 10_000, 5))
+    });
+}
+
+criterion_group!(
+    benches,
+    combat_round_bench,
+    batch_rolls_bench,
+    encounter_bench,
+    wound_stacking_bench,
+    catalog_bench
+);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use steelkilt::Character;
+
+/// A combatant driven by the steelkilt rules
+///
+/// While a fight is under way the character lives in the `CombatState`
+/// encounter; after every step it is copied back here for display.
+#[derive(Component, Debug, Clone)]
+pub struct Fighter {
+    pub character: Character,
+}
+
+impl Fighter {
+    pub fn new(character: Character) -> Self {
+        Self { character }
+    }
+
+    /// Check if this fighter can still take turns
+    pub fn can_act(&self) -> bool {
+        self.character.can_act()
+    }
+}
+
+/// Side a fighter belongs to; combat ends when only one team can still act
+///
+/// A fighter without a team fights alone against everyone.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Team(pub u8);
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use steelkilt::modules::positioning::Battlefield;
+use steelkilt::modules::ruleset::Ruleset;
+use steelkilt::{DefenseAction, WoundLevel};
+
+use crate::components::Team;
+
+/// Request to start a fight between the given fighters
+#[derive(Event, Debug, Clone)]
+pub struct BeginCombat {
+    pub fighters: Vec<Entity>,
+    pub rules: Ruleset,
+    /// Where the fight takes place; fighters are placed by character name
+    pub battlefield: Option<Battlefield>,
+}
+
+impl BeginCombat {
+    pub fn new(fighters: Vec<Entity>) -> Self {
+        Self {
+            fighters,
+            rules: Ruleset::default(),
+            battlefield: None,
+        }
+    }
+
+    pub fn with_rules(mut self, rules: Ruleset) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_battlefield(mut self, battlefield: Battlefield) -> Self {
+        self.battlefield = Some(battlefield);
+        self
+    }
+}
+
+/// Request to resolve the next turn of the fight with `Encounter::step`
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct StepRequest {
+    /// How whoever is attacked this turn defends; `None` lets the AI decide
+    pub defense: Option<DefenseAction>,
+}
+
+/// Outcome notifications emitted by the combat systems
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum CombatEvent {
+    /// An attack between two fighters was resolved
+    AttackResolved {
+        attacker: Entity,
+        defender: Entity,
+        attack_roll: i32,
+        defense_roll: i32,
+        hit: bool,
+        damage: i32,
+        wound_level: Option<WoundLevel>,
+    },
+    /// A fighter can no longer act (incapacitated or dead)
+    FighterDefeated { entity: Entity, dead: bool },
+    /// It is now this fighter's turn
+    TurnStarted { entity: Entity, round: u32 },
+    /// At most one team is left standing
+    CombatEnded { winner: Option<Team> },
+}
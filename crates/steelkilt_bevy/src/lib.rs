@@ -0,0 +1,196 @@
+//! # steelkilt_bevy - Bevy plugin for the Draft RPG combat system
+//!
+//! Runs a steelkilt `Encounter` inside Bevy, so games get the library's
+//! initiative, targeting, maneuvers, bosses, minions and optional rules
+//! without rebuilding the turn loop themselves.
+//!
+//! - [`Fighter`] and [`Team`] components hold each combatant's state
+//! - [`BeginCombat`] events start a fight between fighters
+//! - [`CombatState`] holds the encounter, its fighters' entities and their
+//!   legal actions
+//! - [`StepRequest`] events resolve the next turn with `Encounter::step`
+//! - [`CombatEvent`] events report results, defeats, turns, and the end of combat
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use steelkilt_bevy::prelude::*;
+//!
+//! App::new().add_plugins((MinimalPlugins, SteelkiltPlugin)).run();
+//! ```
+
+mod components;
+mod events;
+mod resources;
+mod systems;
+
+use bevy::prelude::*;
+
+pub use components::{Fighter, Team};
+pub use events::{BeginCombat, CombatEvent, StepRequest};
+pub use resources::CombatState;
+pub use systems::{begin_combat, step_combat};
+
+/// Commonly used types for glob import
+pub mod prelude {
+    pub use crate::{
+        BeginCombat, CombatEvent, CombatState, Fighter, SteelkiltPlugin, SteelkiltSet, StepRequest,
+        Team,
+    };
+    pub use steelkilt::modules::encounter::ActionKind;
+    pub use steelkilt::DefenseAction;
+}
+
+/// System set containing the steelkilt combat systems, for ordering host systems
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SteelkiltSet;
+
+/// Plugin that registers the steelkilt combat resources, events and systems
+///
+/// Registers:
+/// - CombatState resource holding the encounter under way
+/// - BeginCombat, StepRequest and CombatEvent events
+/// - begin_combat and step_combat systems (chained, in `SteelkiltSet`)
+pub struct SteelkiltPlugin;
+
+impl Plugin for SteelkiltPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CombatState>()
+            .add_event::<BeginCombat>()
+            .add_event::<StepRequest>()
+            .add_event::<CombatEvent>()
+            .add_systems(
+                Update,
+                (begin_combat, step_combat).chain().in_set(SteelkiltSet),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::event::Events;
+    use steelkilt::modules::dice::DiceMode;
+    use steelkilt::modules::encounter::ActionKind;
+    use steelkilt::modules::modifiers::{Modifier, ModifierTarget};
+    use steelkilt::modules::ruleset::Ruleset;
+    use steelkilt::{Armor, Attributes, Character, DefenseAction, Weapon};
+
+    fn fighter(name: &str) -> Fighter {
+        Fighter::new(Character::new(
+            name,
+            Attributes::new(7, 7, 7, 7, 7, 7, 7, 7, 7),
+            7,
+            5,
+            Weapon::long_sword(),
+            Armor::leather(),
+        ))
+    }
+
+    fn drain_events(app: &mut App) -> Vec<CombatEvent> {
+        app.world_mut()
+            .resource_mut::<Events<CombatEvent>>()
+            .drain()
+            .collect()
+    }
+
+    fn setup(a: Fighter, b: Fighter) -> (App, Entity, Entity) {
+        let mut app = App::new();
+        app.add_plugins(SteelkiltPlugin);
+        let a = app.world_mut().spawn((a, Team(1))).id();
+        let b = app.world_mut().spawn((b, Team(2))).id();
+        let rules = Ruleset::default().with_dice(DiceMode::seeded(7));
+        app.world_mut()
+            .send_event(BeginCombat::new(vec![a, b]).with_rules(rules));
+        app.update();
+        (app, a, b)
+    }
+
+    #[test]
+    fn test_step_resolves_and_passes_turn() {
+        let (mut app, a, b) = setup(fighter("Aldric"), fighter("Grimwald"));
+        assert_eq!(
+            drain_events(&mut app),
+            vec![CombatEvent::TurnStarted {
+                entity: a,
+                round: 1
+            }]
+        );
+        let state = app.world().resource::<CombatState>();
+        assert!(state.legal_actions(a).contains(&ActionKind::Attack));
+
+        app.world_mut().send_event(StepRequest {
+            defense: Some(DefenseAction::Parry),
+        });
+        app.update();
+
+        let events = drain_events(&mut app);
+        assert!(matches!(
+            events[0],
+            CombatEvent::AttackResolved { attacker, defender, .. } if attacker == a && defender == b
+        ));
+        assert!(events.contains(&CombatEvent::TurnStarted {
+            entity: b,
+            round: 1
+        }));
+        let state = app.world().resource::<CombatState>();
+        assert_eq!(state.current_fighter(), Some(b));
+        let fighter_b = &app.world().get::<Fighter>(b).unwrap().character;
+        assert_eq!(
+            fighter_b.injuries.active,
+            state.encounter.combatants[1].character.injuries.active
+        );
+
+        // The round wraps back to the first fighter
+        app.world_mut().send_event(StepRequest::default());
+        app.update();
+        assert!(drain_events(&mut app).contains(&CombatEvent::TurnStarted {
+            entity: a,
+            round: 2
+        }));
+    }
+
+    #[test]
+    fn test_steps_ignored_without_a_fight() {
+        let mut app = App::new();
+        app.add_plugins(SteelkiltPlugin);
+        app.world_mut().spawn((fighter("Aldric"), Team(1)));
+
+        app.world_mut().send_event(StepRequest::default());
+        app.update();
+
+        assert!(drain_events(&mut app).is_empty());
+        assert_eq!(
+            app.world().resource::<CombatState>().current_fighter(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_combat_ends_when_one_team_remains() {
+        let mut brute = fighter("Aldric");
+        for target in [ModifierTarget::Attack, ModifierTarget::Damage] {
+            brute
+                .character
+                .add_modifier(Modifier::new("Fury", target, 100));
+        }
+        let (mut app, a, b) = setup(brute, fighter("Grimwald"));
+        drain_events(&mut app);
+
+        app.world_mut().send_event(StepRequest::default());
+        app.update();
+
+        let events = drain_events(&mut app);
+        assert!(matches!(
+            events[0],
+            CombatEvent::AttackResolved { attacker, hit: true, .. } if attacker == a
+        ));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, CombatEvent::FighterDefeated { entity, .. } if *entity == b)));
+        assert!(events.contains(&CombatEvent::CombatEnded {
+            winner: Some(Team(1))
+        }));
+        assert!(!app.world().resource::<CombatState>().is_active());
+        assert!(!app.world().get::<Fighter>(b).unwrap().can_act());
+    }
+}
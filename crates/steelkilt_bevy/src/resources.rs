@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use steelkilt::modules::encounter::{ActionKind, CombatantId, Encounter};
+use steelkilt::modules::turn_timer::DefenseProvider;
+use steelkilt::{Character, DefenseAction};
+
+use crate::components::Team;
+
+/// The fight under way, as a steelkilt `Encounter`
+///
+/// Fighters keep their entities in Bevy while the encounter holds their
+/// characters and runs initiative, targeting and every rule it supports.
+/// Names must be unique within a fight: attack results name their fighters.
+#[derive(Resource, Debug, Default)]
+pub struct CombatState {
+    pub encounter: Encounter,
+    /// Each combatant's entity, indexed by `CombatantId`
+    pub entities: Vec<Entity>,
+    pub finished: bool,
+    defense: Arc<Mutex<ChosenDefense>>,
+}
+
+/// Hands the defense picked in a `StepRequest` to the encounter
+#[derive(Debug, Default)]
+pub(crate) struct ChosenDefense(pub(crate) Option<DefenseAction>);
+
+impl DefenseProvider for ChosenDefense {
+    fn choose_defense(
+        &mut self,
+        _defender: &Character,
+        _attacker: &Character,
+        _deadline: Option<Duration>,
+    ) -> Option<DefenseAction> {
+        self.0
+    }
+}
+
+impl CombatState {
+    /// Start a new fight in `encounter`, whose combatants are `entities` in order
+    pub fn begin(&mut self, encounter: Encounter, entities: Vec<Entity>) {
+        let defense: Arc<Mutex<dyn DefenseProvider>> = self.defense.clone();
+        self.encounter = encounter.with_defense_provider(defense);
+        self.entities = entities;
+        self.finished = self.encounter.is_over();
+    }
+
+    /// Check if a fight is in progress
+    pub fn is_active(&self) -> bool {
+        !self.finished && !self.entities.is_empty()
+    }
+
+    /// The round being fought
+    pub fn round(&self) -> u32 {
+        self.encounter.round
+    }
+
+    /// Get the fighter whose turn is next
+    pub fn current_fighter(&self) -> Option<Entity> {
+        if !self.is_active() {
+            return None;
+        }
+        let id = match self.encounter.initiative.first() {
+            Some(&id) => id,
+            None => *self.encounter.turn_order().first()?,
+        };
+        self.entity(id)
+    }
+
+    pub fn entity(&self, id: CombatantId) -> Option<Entity> {
+        self.entities.get(id).copied()
+    }
+
+    pub fn combatant(&self, entity: Entity) -> Option<CombatantId> {
+        self.entities.iter().position(|&e| e == entity)
+    }
+
+    /// The fighter with this character name
+    pub fn entity_named(&self, name: &str) -> Option<Entity> {
+        self.entity(self.encounter.find(name)?)
+    }
+
+    /// What the fighter can be told to do right now (see `Encounter::legal_actions`)
+    pub fn legal_actions(&self, entity: Entity) -> Vec<ActionKind> {
+        self.combatant(entity)
+            .map(|id| self.encounter.legal_actions(id))
+            .unwrap_or_default()
+    }
+
+    /// The team that won, if the fight is over and it had a `Team`
+    pub fn winner(&self) -> Option<Team> {
+        let team = self.encounter.winner()?;
+        u8::try_from(team).ok().map(Team)
+    }
+
+    pub(crate) fn choose_defense(&self, defense: Option<DefenseAction>) {
+        self.defense
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .0 = defense;
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use steelkilt::modules::encounter::Encounter;
+
+use crate::components::{Fighter, Team};
+use crate::events::{BeginCombat, CombatEvent, StepRequest};
+use crate::resources::CombatState;
+
+/// Start a fight for each `BeginCombat`, building its `Encounter` from the fighters
+///
+/// Fighters keep their `Team`; one without a team gets a side of its own.
+pub fn begin_combat(
+    mut requests: EventReader<BeginCombat>,
+    mut events: EventWriter<CombatEvent>,
+    fighters: Query<(&Fighter, Option<&Team>)>,
+    mut state: ResMut<CombatState>,
+) {
+    for request in requests.read() {
+        let mut encounter = Encounter::new().with_rules(request.rules.clone());
+        if let Some(field) = &request.battlefield {
+            encounter = encounter.with_battlefield(field.clone());
+        }
+        let mut entities = Vec::with_capacity(request.fighters.len());
+        for (i, &entity) in request.fighters.iter().enumerate() {
+            let Ok((fighter, team)) = fighters.get(entity) else {
+                continue;
+            };
+            let team = team.map_or(u32::from(u8::MAX) + 1 + i as u32, |t| u32::from(t.0));
+            encounter.add(fighter.character.clone(), team);
+            entities.push(entity);
+        }
+        state.begin(encounter, entities);
+        if state.finished {
+            events.send(CombatEvent::CombatEnded {
+                winner: state.winner(),
+            });
+        } else {
+            announce_turn(&state, &mut events);
+        }
+    }
+}
+
+/// Resolve one turn of the fight with `Encounter::step` for each `StepRequest`
+///
+/// The encounter picks who acts and whom they attack; the request only says
+/// how the defender answers. Results are reported as `CombatEvent`s and the
+/// fighters' characters are copied back from the encounter.
+pub fn step_combat(
+    mut requests: EventReader<StepRequest>,
+    mut events: EventWriter<CombatEvent>,
+    mut fighters: Query<&mut Fighter>,
+    mut state: ResMut<CombatState>,
+) {
+    for request in requests.read() {
+        if !state.is_active() {
+            continue;
+        }
+        let standing: Vec<bool> = state
+            .encounter
+            .combatants
+            .iter()
+            .map(|c| c.character.can_act())
+            .collect();
+
+        state.choose_defense(request.defense);
+        let results = state.encounter.step();
+        state.choose_defense(None);
+
+        for result in &results {
+            let (Some(attacker), Some(defender)) = (
+                state.entity_named(&result.attacker),
+                state.entity_named(&result.defender),
+            ) else {
+                continue;
+            };
+            events.send(CombatEvent::AttackResolved {
+                attacker,
+                defender,
+                attack_roll: result.attack_roll,
+                defense_roll: result.defense_roll,
+                hit: result.hit,
+                damage: result.damage.get(),
+                wound_level: result.wound_level,
+            });
+        }
+
+        for (id, combatant) in state.encounter.combatants.iter().enumerate() {
+            let Some(entity) = state.entity(id) else {
+                continue;
+            };
+            if let Ok(mut fighter) = fighters.get_mut(entity) {
+                fighter.character.clone_from(&combatant.character);
+            }
+            if standing.get(id) == Some(&true) && !combatant.character.can_act() {
+                events.send(CombatEvent::FighterDefeated {
+                    entity,
+                    dead: !combatant.character.is_alive(),
+                });
+            }
+        }
+
+        if state.encounter.is_over() {
+            state.finished = true;
+            events.send(CombatEvent::CombatEnded {
+                winner: state.winner(),
+            });
+        } else {
+            announce_turn(&state, &mut events);
+        }
+    }
+}
+
+fn announce_turn(state: &CombatState, events: &mut EventWriter<CombatEvent>) {
+    if let Some(entity) = state.current_fighter() {
+        events.send(CombatEvent::TurnStarted {
+            entity,
+            round: state.round(),
+        });
+    }
+}
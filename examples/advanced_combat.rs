@@ -97,9 +97,6 @@ fn main() {
     let mut knight_exhaustion = Exhaustion::new(knight.attributes.stamina());
     let mut barbarian_exhaustion = Exhaustion::new(barbarian.attributes.stamina());
 
-    let mut knight_locations: Vec<LocationalDamage> = Vec::new();
-    let mut barbarian_locations: Vec<LocationalDamage> = Vec::new();
-
     print_fighter_status(&knight, &knight_skills, &knight_exhaustion);
     print_fighter_status(&barbarian, &barbarian_skills, &barbarian_exhaustion);
 
@@ -125,7 +122,9 @@ fn main() {
             CombatManeuver::Normal,
         );
 
-        knight_stance.set_maneuver(result).unwrap();
+        if let Err(e) = knight_stance.set_maneuver(result, &knight) {
+            println!("{}", e);
+        }
 
         // Knights tactical choice based on round
         // match round {
@@ -176,7 +175,6 @@ fn main() {
                 &knight_skills,
                 &knight_stance,
                 &knight_exhaustion,
-                &mut barbarian_locations,
                 round,
             );
 
@@ -196,7 +194,6 @@ fn main() {
                 &barbarian_skills,
                 &barbarian_stance,
                 &barbarian_exhaustion,
-                &mut knight_locations,
                 round,
             );
 
@@ -217,8 +214,6 @@ fn main() {
             &barbarian,
             &knight_exhaustion,
             &barbarian_exhaustion,
-            &knight_locations,
-            &barbarian_locations,
         );
     }
 
@@ -227,8 +222,8 @@ fn main() {
     println!("COMBAT CONCLUDED");
     println!("{}\n", "=".repeat(70));
 
-    print_final_status(&knight, &knight_exhaustion, &knight_locations);
-    print_final_status(&barbarian, &barbarian_exhaustion, &barbarian_locations);
+    print_final_status(&knight, &knight_exhaustion);
+    print_final_status(&barbarian, &barbarian_exhaustion);
 }
 
 fn create_knight() -> Character {
@@ -283,7 +278,6 @@ fn perform_advanced_attack(
     _attacker_skills: &SkillSet,
     attacker_stance: &CombatStance,
     attacker_exhaustion: &Exhaustion,
-    defender_locations: &mut Vec<LocationalDamage>,
     round: usize,
 ) {
     // Calculate total modifiers
@@ -300,8 +294,6 @@ fn perform_advanced_attack(
         AttackDirection::Front
     };
 
-    let hit_location = HitLocation::determine(direction);
-
     // Simulate attack roll (in real use, this would use combat_round with modifiers)
     println!(
         "\n{} attacks {} (Stance: {}, Exhaustion: {}, Total Mod: {:+})",
@@ -312,59 +304,42 @@ fn perform_advanced_attack(
         total_attack_mod
     );
 
-    // For demonstration, use basic combat_round
-    let result = combat_round(attacker, defender, DefenseAction::Parry);
-
-    if result.hit {
-        // Apply stance damage modifier
-        let stance_damage_mod = attacker_stance.total_damage_modifier();
-        let location_damage_mult = hit_location.damage_multiplier();
-        let adjusted_damage = (result.damage as f32 * location_damage_mult) as i32 + stance_damage_mod;
+    // Rolls the hit location, scales the damage and records the wound there
+    let context = CombatContext::new(attacker_stance.clone(), CombatStance::new());
+    let Some(result) = combat_round_with_locations(
+        attacker,
+        defender,
+        DefenseAction::Parry,
+        direction,
+        &context,
+        &ruleset::Ruleset::default(),
+        &mut dice::RandomDice,
+    ) else {
+        println!("  → {} holds their position", attacker.name);
+        return;
+    };
 
+    if let Some(hit_location) = result.location {
         println!(
-            "  → HIT to {}! Base damage: {}, Location mult: {:.2}x, Stance bonus: {:+}, Final: {}",
+            "  → HIT to {}! Damage: {} (location mult: {:.2}x, stance bonus: {:+})",
             hit_location,
             result.damage,
-            location_damage_mult,
-            stance_damage_mod,
-            adjusted_damage
+            hit_location.damage_multiplier(),
+            attacker_stance.total_damage_modifier(),
         );
 
-        // Track locational damage
         if let Some(wound) = result.wound_level {
-            let severity = match wound {
-                WoundLevel::Light => hit_location::WoundSeverity::Light,
-                WoundLevel::Severe => hit_location::WoundSeverity::Severe,
-                WoundLevel::Critical => hit_location::WoundSeverity::Critical,
-            };
-
-            // Find or create location tracker
-            let mut found = false;
-            for loc_dmg in defender_locations.iter_mut() {
-                if loc_dmg.location == hit_location {
-                    loc_dmg.add_wound(severity);
-                    found = true;
-
-                    if loc_dmg.disabled {
-                        println!("  → {} is DISABLED!", hit_location);
-
-                        if hit_location.causes_weapon_drop() {
-                            println!("  → {} drops their weapon!", defender.name);
-                        }
-                    }
-                    break;
-                }
-            }
-
-            if !found {
-                let mut new_loc = LocationalDamage::new(hit_location);
-                new_loc.add_wound(severity);
-
-                if new_loc.disabled && hit_location.causes_weapon_drop() {
+            let disabled = defender
+                .injuries
+                .damaged_locations()
+                .iter()
+                .any(|l| l.location == hit_location && l.disabled);
+            if disabled {
+                println!("  → {} is DISABLED!", hit_location);
+
+                if hit_location.causes_weapon_drop() {
                     println!("  → {} drops their weapon!", defender.name);
                 }
-
-                defender_locations.push(new_loc);
             }
 
             println!("  → {} wound inflicted", wound);
@@ -402,8 +377,6 @@ fn print_round_status(
     barbarian: &Character,
     knight_exhaustion: &Exhaustion,
     barbarian_exhaustion: &Exhaustion,
-    knight_locations: &Vec<LocationalDamage>,
-    barbarian_locations: &Vec<LocationalDamage>,
 ) {
     println!("\n{}", "-".repeat(70));
     println!("Status:");
@@ -415,7 +388,7 @@ fn print_round_status(
         knight.wounds.critical,
         knight_exhaustion.points,
         knight_exhaustion.status(),
-        knight_locations.iter().filter(|l| l.disabled).count(),
+        knight.injuries.damaged_locations().iter().filter(|l| l.disabled).count(),
     );
 
     println!("  {}: Wounds(L:{} S:{} C:{}) Exhaustion({} {}) Disabled:{}",
@@ -425,15 +398,15 @@ fn print_round_status(
         barbarian.wounds.critical,
         barbarian_exhaustion.points,
         barbarian_exhaustion.status(),
-        barbarian_locations.iter().filter(|l| l.disabled).count(),
+        barbarian.injuries.damaged_locations().iter().filter(|l| l.disabled).count(),
     );
 }
 
 fn print_final_status(
     character: &Character,
     exhaustion: &Exhaustion,
-    locations: &Vec<LocationalDamage>,
 ) {
+    let locations = character.injuries.damaged_locations();
     println!("{}: {}", character.name, if character.is_alive() { "ALIVE" } else { "DEAD" });
     println!("  Total Wounds: {} Light, {} Severe, {} Critical",
         character.wounds.light,
@@ -444,7 +417,7 @@ fn print_final_status(
 
     if !locations.is_empty() {
         println!("  Injured Locations:");
-        for loc in locations {
+        for loc in &locations {
             println!("    {}: L:{} S:{} C:{}, penalty: {}, disabled: {}",
                 loc.location,
                 loc.light_wounds,
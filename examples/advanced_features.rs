@@ -93,6 +93,14 @@ fn demo_skills() {
 
 fn demo_maneuvers() {
     let mut stance = CombatStance::new();
+    let fighter = steelkilt::Character::new(
+        "Aldric",
+        steelkilt::Attributes::new(7, 7, 7, 6, 6, 6, 5, 6, 5),
+        7,
+        5,
+        steelkilt::Weapon::long_sword(),
+        steelkilt::Armor::leather(),
+    );
 
     println!("Available maneuvers:");
     let maneuvers = vec![
@@ -101,6 +109,7 @@ fn demo_maneuvers() {
         CombatManeuver::Charge,
         CombatManeuver::AllOutAttack,
         CombatManeuver::AimedAttack,
+        CombatManeuver::Riposte,
     ];
 
     for maneuver in &maneuvers {
@@ -112,7 +121,9 @@ fn demo_maneuvers() {
     }
 
     println!("\nUsing Charge maneuver:");
-    stance.set_maneuver(CombatManeuver::Charge).unwrap();
+    stance
+        .set_maneuver(CombatManeuver::Charge, &fighter)
+        .unwrap();
     println!(
         "  Total attack modifier: {:+}",
         stance.total_attack_modifier()
@@ -129,7 +140,7 @@ fn demo_maneuvers() {
     println!("\nAiming for Aimed Attack:");
     stance.start_aiming();
     println!("  Started aiming...");
-    match stance.set_maneuver(CombatManeuver::AimedAttack) {
+    match stance.set_maneuver(CombatManeuver::AimedAttack, &fighter) {
         Ok(()) => println!("  Aimed attack ready!"),
         Err(e) => println!("  Error: {}", e),
     }
@@ -275,6 +286,7 @@ fn demo_magic() {
         casting_time: 1,
         range: magic::SpellRange::Short(20),
         duration: magic::SpellDuration::Minutes(10),
+        components: magic::SpellComponents::default(),
     };
 
     println!("Learning spell: {}", spell.name);
@@ -130,11 +130,11 @@ fn display_character(character: &Character) {
     println!("╠═══════════════════════════════════════╣");
     println!(
         "║ Weapon Skill: {:<2}                     ║",
-        character.weapon_skill
+        character.weapon_skill()
     );
     println!(
         "║ Dodge Skill:  {:<2}                     ║",
-        character.dodge_skill
+        character.dodge_skill()
     );
     println!("║ Weapon: {:<28} ║", character.weapon.name);
     println!(
@@ -129,6 +129,7 @@ fn create_elara() -> Combatant {
         casting_time: 1,
         range: magic::SpellRange::Medium(50),
         duration: magic::SpellDuration::Instant,
+        components: magic::SpellComponents::default(),
     };
     magic_user.magic.learn_spell(fireball, 6).unwrap();
 
@@ -143,6 +144,7 @@ fn create_elara() -> Combatant {
         casting_time: 1,
         range: magic::SpellRange::Touch,
         duration: magic::SpellDuration::Instant,
+        components: magic::SpellComponents::default(),
     };
     magic_user.magic.learn_spell(heal, 5).unwrap();
 
@@ -176,6 +178,7 @@ fn create_malachar() -> Combatant {
         casting_time: 1,
         range: magic::SpellRange::Medium(40),
         duration: magic::SpellDuration::Instant,
+        components: magic::SpellComponents::default(),
     };
     magic_user.magic.learn_spell(death_bolt, 5).unwrap();
 
@@ -190,6 +193,7 @@ fn create_malachar() -> Combatant {
         casting_time: 1,
         range: magic::SpellRange::Short(30),
         duration: magic::SpellDuration::Instant,
+        components: magic::SpellComponents::default(),
     };
     magic_user.magic.learn_spell(mind_blast, 4).unwrap();
 
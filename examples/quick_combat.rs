@@ -27,7 +27,7 @@ fn main() {
         knight.name,
         knight.attributes.strength,
         knight.attributes.constitution,
-        knight.weapon_skill,
+        knight.weapon_skill(),
         knight.armor.name
     );
 
@@ -36,7 +36,7 @@ fn main() {
         barbarian.name,
         barbarian.attributes.strength,
         barbarian.attributes.constitution,
-        barbarian.weapon_skill,
+        barbarian.weapon_skill(),
         barbarian.armor.name
     );
 
@@ -0,0 +1,22 @@
+use steelkilt::modules::positioning::{Battlefield, Facing, GridKind, GridPos, CELL_METERS};
+
+use crate::state::Distance;
+
+/// Lays out a one-row arena with the two fighters `distance` apart, facing each other
+pub fn arena(fighter1: &str, fighter2: &str, distance: Distance) -> Battlefield {
+    let kind = GridKind::Square;
+    let gap = (distance.meters() / CELL_METERS).max(1);
+    let mut field = Battlefield::new(kind, gap + 1, 1);
+    let east = Facing::NORTH.turn_right(kind).turn_right(kind);
+    // Both cells are free on a fresh field, so neither placement can fail
+    let _ = field.place(fighter1, GridPos::new(0, 0), east);
+    let _ = field.place(fighter2, GridPos::new(gap, 0), east.opposite(kind));
+    field
+}
+
+/// Meters between two fighters on the arena
+pub fn meters_apart(field: &Battlefield, fighter1: &str, fighter2: &str) -> Option<i32> {
+    let a = field.placement(fighter1)?.pos;
+    let b = field.placement(fighter2)?.pos;
+    Some(field.kind.distance(a, b) * CELL_METERS)
+}
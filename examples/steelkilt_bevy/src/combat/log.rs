@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use steelkilt_bevy::prelude::*;
+
+use crate::state::DuelState;
+
+/// Writes the plugin's combat events into the combat log.
+pub fn log_combat_events(
+    mut events: EventReader<CombatEvent>,
+    mut duel_state: ResMut<DuelState>,
+    fighters: Query<(&Fighter, &Team)>,
+) {
+    let name = |entity: Entity| {
+        fighters
+            .get(entity)
+            .map_or_else(|_| "Unknown".to_string(), |(f, _)| f.character.name.clone())
+    };
+
+    for event in events.read() {
+        match *event {
+            CombatEvent::AttackResolved {
+                attacker,
+                defender,
+                attack_roll,
+                defense_roll,
+                hit,
+                damage,
+                wound_level,
+            } => {
+                duel_state.attacked = true;
+                let log = &mut duel_state.combat_log;
+                log.push(format!(
+                    "\n>>> Attack: {} rolls {} vs {}'s defense {}",
+                    name(attacker),
+                    attack_roll,
+                    name(defender),
+                    defense_roll
+                ));
+                if hit {
+                    log.push(format!(">>> HIT! {} damage dealt", damage));
+                    if let Some(level) = wound_level {
+                        log.push(format!(">>> {} wound inflicted!", level));
+                    }
+                } else {
+                    log.push(">>> MISS! The attack was successfully defended.".to_string());
+                }
+            }
+            CombatEvent::FighterDefeated { entity, dead } => {
+                let fate = if dead {
+                    "has been slain"
+                } else {
+                    "is incapacitated"
+                };
+                duel_state
+                    .combat_log
+                    .push(format!("\n{} {}!", name(entity), fate));
+            }
+            CombatEvent::TurnStarted { entity, round } => {
+                if let Some(previous) = duel_state.turn {
+                    if !duel_state.attacked {
+                        duel_state
+                            .combat_log
+                            .push(format!("{} makes no attack", name(previous)));
+                    }
+                }
+                if round > duel_state.logged_round {
+                    duel_state.logged_round = round;
+                    duel_state
+                        .combat_log
+                        .push(format!("\n--- ROUND {} ---", round));
+                }
+                duel_state.turn = Some(entity);
+                duel_state.attacked = false;
+            }
+            CombatEvent::CombatEnded { winner } => {
+                duel_state.game_over = true;
+                let victor = fighters
+                    .iter()
+                    .find(|(_, team)| Some(**team) == winner)
+                    .map(|(f, _)| f.character.name.clone());
+                let line = match victor {
+                    Some(victor) => format!("{} is victorious!", victor),
+                    None => "No one is left standing!".to_string(),
+                };
+                duel_state.combat_log.push(line);
+            }
+        }
+    }
+}
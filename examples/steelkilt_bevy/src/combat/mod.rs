@@ -1,26 +1,27 @@
 // Combat module organization
 //
-// This module is organized into focused submodules for maintainability:
+// The fight itself runs in steelkilt_bevy's SteelkiltPlugin; this module is
+// the duel screen around it, organized into focused submodules:
 // - plugin: Bevy plugin for combat system integration
 // - ui: UI spawning and updating (spawn_combat_ui, update_combat_ui)
 // - input_handler: Main combat input system (handle_combat_input)
-// - helpers: Shared utilities (fighter queries, turn management, logging)
-// - melee: Melee combat execution
-// - ranged: Ranged combat execution
+// - log: Turns the plugin's CombatEvents into combat log lines
+// - arena: Battlefield setup for the chosen distance
 
-mod helpers;
+mod arena;
 mod input_handler;
-mod melee;
+mod log;
 mod plugin;
-mod ranged;
 mod ui;
 
 // Re-export the combat plugin (preferred interface)
 pub use plugin::CombatPlugin;
 
-// Re-export UI spawner for direct use (needed for setup)
+// Re-export UI spawner and arena for starting a fight from the selection screen
+pub use arena::arena;
 pub use ui::spawn_combat_ui;
 
 // Keep private exports for internal use
 use input_handler::handle_combat_input;
-use ui::{update_combat, update_combat_ui};
+use log::log_combat_events;
+use ui::update_combat_ui;
@@ -1,28 +1,30 @@
 use bevy::prelude::*;
+use steelkilt_bevy::SteelkiltSet;
 
-use crate::state::CombatState;
+use crate::state::DuelState;
 
-use super::{handle_combat_input, update_combat, update_combat_ui};
+use super::{handle_combat_input, log_combat_events, update_combat_ui};
 
-/// Plugin that handles all combat-related functionality
+/// Plugin that handles the duel screen around steelkilt_bevy's `SteelkiltPlugin`
 ///
 /// Registers:
-/// - CombatState resource for tracking combat progression
-/// - handle_combat_input system for processing player input
-/// - update_combat system for combat automation (placeholder)
+/// - DuelState resource for the combat log and screen state
+/// - handle_combat_input system for turning keys into `StepRequest`s
+/// - log_combat_events system for logging the plugin's `CombatEvent`s
 /// - update_combat_ui system for refreshing combat display
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<CombatState>()
+            .init_resource::<DuelState>()
             .add_systems(
                 Update,
                 (
-                    handle_combat_input,
-                    update_combat,
-                    update_combat_ui,
+                    handle_combat_input.before(SteelkiltSet),
+                    (log_combat_events, update_combat_ui)
+                        .chain()
+                        .after(SteelkiltSet),
                 ),
             );
     }
@@ -1,7 +1,11 @@
 use bevy::prelude::*;
+use steelkilt::modules::encounter::Wielding;
+use steelkilt_bevy::prelude::*;
 
-use crate::components::{CombatLogText, CombatUI, Fighter, InstructionText, StatusText};
-use crate::state::{CombatMode, CombatState, GameState, GameStateEnum, RangedAttackPhase};
+use crate::components::{CombatLogText, CombatUI, InstructionText, StatusText};
+use crate::state::{DuelState, GameState, GameStateEnum};
+
+use super::arena::meters_apart;
 
 /// Spawns the combat UI hierarchy.
 pub fn spawn_combat_ui(commands: &mut Commands) {
@@ -82,7 +86,7 @@ pub fn spawn_combat_ui(commands: &mut Commands) {
             // Instructions
             parent.spawn((
                 Text::new(
-                    "Press [P] for Parry or [D] for Dodge\nPress [SPACE] to continue | [Q] to quit",
+                    "Press [P] for Parry or [D] for Dodge\nPress [SPACE] to let them decide | [Q] to quit",
                 ),
                 TextFont {
                     font_size: 20.0,
@@ -94,19 +98,13 @@ pub fn spawn_combat_ui(commands: &mut Commands) {
         });
 }
 
-/// Updates combat state (placeholder for future combat automation logic).
-pub fn update_combat(combat_state: Res<CombatState>, _fighters: Query<&Fighter>) {
-    if combat_state.is_changed() {
-        // Log state changes if needed
-    }
-}
-
 /// Updates the combat UI (status displays, combat log, instructions).
 #[allow(clippy::type_complexity)]
 pub fn update_combat_ui(
-    combat_state: Res<CombatState>,
+    duel_state: Res<DuelState>,
+    combat: Res<CombatState>,
     game_state: Res<GameState>,
-    fighters: Query<&Fighter>,
+    fighters: Query<(Entity, &Fighter, &Team)>,
     mut log_query: Query<
         &mut Text,
         (
@@ -134,7 +132,7 @@ pub fn update_combat_ui(
 
     // Update combat log
     if let Ok(mut log_text) = log_query.get_single_mut() {
-        let log_lines: Vec<String> = combat_state
+        let log_lines: Vec<String> = duel_state
             .combat_log
             .iter()
             .rev()
@@ -147,11 +145,8 @@ pub fn update_combat_ui(
 
     // Update fighter status
     for (mut text, status) in status_query.iter_mut() {
-        for fighter in fighters.iter() {
-            let is_match = (status.fighter_id == 1 && fighter.is_player_one)
-                || (status.fighter_id == 2 && !fighter.is_player_one);
-
-            if is_match {
+        for (_, fighter, team) in fighters.iter() {
+            if team.0 == status.fighter_id {
                 let c = &fighter.character;
                 let status_str = if !c.is_alive() {
                     "DEAD"
@@ -173,8 +168,8 @@ pub fn update_combat_ui(
                     c.weapon.damage,
                     c.armor.name,
                     c.armor.protection,
-                    c.weapon_skill,
-                    c.dodge_skill,
+                    c.weapon_skill(),
+                    c.dodge_skill(),
                     c.wounds.light,
                     c.wounds.severe,
                     c.wounds.critical
@@ -192,7 +187,7 @@ pub fn update_combat_ui(
 
                 // Add ranged weapon info if character has one
                 if let Some(ref ranged) = c.ranged_weapon {
-                    let ranged_skill = c.ranged_skill.unwrap_or(0);
+                    let ranged_skill = c.ranged_skill.map_or(0, |s| s.get());
                     display.push_str(&format!(
                         "\nRanged: {} (Dmg: {}, Range: {}-{}m, Skill: {})",
                         ranged.name,
@@ -211,86 +206,56 @@ pub fn update_combat_ui(
 
     // Update instructions
     if let Ok(mut instruction_text) = instruction_query.get_single_mut() {
-        if combat_state.game_over {
+        if duel_state.game_over {
             **instruction_text = "Combat Over! Press [Q] to return to main menu".to_string();
-        } else if combat_state.waiting_for_defense {
-            // Check if current attacker has ranged weapon
-            let attacker_has_ranged = fighters
+        } else if let Some(attacker) = combat.current_fighter() {
+            let defender = fighters
                 .iter()
-                .find(|f| {
-                    (combat_state.current_attacker == 1 && f.is_player_one)
-                        || (combat_state.current_attacker == 2 && !f.is_player_one)
-                })
-                .map(|f| f.character.ranged_weapon.is_some())
-                .unwrap_or(false);
+                .find(|(entity, _, _)| *entity != attacker)
+                .map_or("Unknown", |(_, f, _)| f.character.name.as_str());
 
             let mut instructions = String::new();
 
-            // Show combat mode and distance
-            instructions.push_str(&format!(
-                "Mode: {:?} | Distance: {:?} ({}m)\n",
-                combat_state.combat_mode,
-                combat_state.distance,
-                combat_state.distance.meters()
-            ));
-
-            if combat_state.combat_mode == CombatMode::Ranged {
-                if attacker_has_ranged {
-                    // Ranged combat instructions
-                    if let Some(phase) = combat_state.ranged_phase {
-                        match phase {
-                            RangedAttackPhase::Preparing => {
-                                instructions.push_str("Ranged weapon ready!\n");
-                                instructions.push_str(
-                                    "[A] Aim for bonus | [F] Fire immediately | [M] Switch to melee",
-                                );
-                            }
-                            RangedAttackPhase::Aiming => {
-                                instructions.push_str(&format!(
-                                    "Aiming... (+{} bonus)\n",
-                                    combat_state.aiming_rounds
-                                ));
-                                instructions.push_str("[A] Continue aiming | [F] Fire shot");
-                            }
-                            RangedAttackPhase::ReadyToFire => {
-                                instructions.push_str("Firing ranged weapon...");
-                            }
-                            _ => {
-                                instructions.push_str("Ranged combat in progress...");
-                            }
-                        }
-                    }
-                }
-                else {
-                    instructions.push_str("Attacker cannot equip a ranged weapon\n");
+            // Show the weapon in hand and distance
+            let combatant = combat
+                .combatant(attacker)
+                .and_then(|id| combat.encounter.get(id));
+            if let Some(combatant) = combatant {
+                let c = &combatant.character;
+                let (weapon, other) = match (combatant.wielding, &c.ranged_weapon) {
+                    (Wielding::Ranged, Some(ranged)) => (&ranged.name, Some(&c.weapon.name)),
+                    (_, ranged) => (&c.weapon.name, ranged.as_ref().map(|r| &r.name)),
+                };
+                let apart = combat
+                    .encounter
+                    .battlefield
+                    .as_ref()
+                    .and_then(|field| meters_apart(field, &c.name, defender));
+                instructions.push_str(&format!("{} attacks with {}", c.name, weapon));
+                if let Some(meters) = apart {
+                    instructions.push_str(&format!(" | Distance: {}m", meters));
                 }
-
-            } else {
-                // Melee combat instructions
-                let defender_name = fighters
-                    .iter()
-                    .find(|f| {
-                        (combat_state.current_attacker == 1 && !f.is_player_one)
-                            || (combat_state.current_attacker == 2 && f.is_player_one)
-                    })
-                    .map(|f| f.character.name.as_str())
-                    .unwrap_or("Unknown");
+                instructions.push('\n');
 
                 instructions.push_str(&format!(
-                    "How does {} defend? [P] Parry | [D] Dodge\n",
-                    defender_name
+                    "How does {} defend? [P] Parry | [D] Dodge | [SPACE] Let them decide\n",
+                    defender
                 ));
 
-                if attacker_has_ranged {
-                    instructions.push_str("[R] Switch to ranged combat | ");
+                let legal = combat.legal_actions(attacker);
+                if let Some(other) = other.filter(|_| legal.contains(&ActionKind::SwitchWeapon)) {
+                    instructions.push_str(&format!(
+                        "[R] {} switches to {} | ",
+                        c.name, other
+                    ));
                 }
-                instructions.push_str("[1/2/3] Change distance | [Q] Quit");
             }
+            if !duel_state.started {
+                instructions.push_str("[1/2/3] Change distance | ");
+            }
+            instructions.push_str("[Q] Quit");
 
             **instruction_text = instructions;
-        } else if combat_state.paused {
-            **instruction_text =
-                "Press [SPACE] to continue to next round | [Q] to return to main menu".to_string();
         }
     }
 }
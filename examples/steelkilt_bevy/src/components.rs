@@ -1,16 +1,4 @@
 use bevy::prelude::*;
-use steelkilt::modules::ranged_combat::RangedAttackState;
-use steelkilt::Character;
-
-// ===== GAME ENTITIES =====
-
-#[derive(Component, Clone)]
-pub struct Fighter {
-    pub character: Character,
-    pub is_player_one: bool,
-    #[allow(dead_code)]
-    pub ranged_state: Option<RangedAttackState>,
-}
 
 // ===== UI MARKERS =====
 
@@ -32,6 +20,7 @@ pub struct CombatUI;
 #[derive(Component)]
 pub struct CombatLogText;
 
+/// Status panel for the fighter on this `Team`
 #[derive(Component)]
 pub struct StatusText {
     pub fighter_id: u8,
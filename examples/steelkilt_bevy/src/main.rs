@@ -7,6 +7,7 @@ mod selection;
 mod state;
 
 use bevy::prelude::*;
+use steelkilt_bevy::SteelkiltPlugin;
 
 use combat::CombatPlugin;
 use main_menu::{spawn_main_menu_ui, MainMenuPlugin};
@@ -26,6 +27,8 @@ fn main() {
         }))
         // Global game state
         .init_resource::<GameState>()
+        // Steelkilt combat rules (encounter, turn resolution, combat events)
+        .add_plugins(SteelkiltPlugin)
         // Feature plugins (each handles its own systems and resources)
         .add_plugins((
             MainMenuPlugin,
@@ -76,8 +76,8 @@ pub fn update_management_ui(
                     character.attributes.charisma,
                     character.attributes.perception,
                     character.attributes.empathy,
-                    character.weapon_skill,
-                    character.dodge_skill,
+                    character.weapon_skill(),
+                    character.dodge_skill(),
                     character.weapon.name,
                     character.weapon.damage,
                     character.armor.name,
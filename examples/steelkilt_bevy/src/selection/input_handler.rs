@@ -1,18 +1,20 @@
 use bevy::prelude::*;
 
-use crate::combat::spawn_combat_ui;
-use crate::components::{Fighter, SelectionUI};
+use steelkilt_bevy::prelude::*;
+
+use crate::combat::{arena, spawn_combat_ui};
+use crate::components::SelectionUI;
 use crate::file_ops::{load_available_combatants, load_character_from_file};
 use crate::main_menu::spawn_main_menu_ui;
-use crate::state::{CombatState, GameState, GameStateEnum};
-use steelkilt::modules::ranged_combat::RangedAttackState;
+use crate::state::{DuelState, GameState, GameStateEnum};
 
 /// Handles character selection keyboard input.
 pub fn handle_selection_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut game_state: ResMut<GameState>,
-    mut combat_state: ResMut<CombatState>,
+    mut combat_state: ResMut<DuelState>,
     mut commands: Commands,
+    mut begin: EventWriter<BeginCombat>,
     selection_ui: Query<Entity, With<SelectionUI>>,
 ) {
     if !game_state.is_in(GameStateEnum::Selection) {
@@ -57,7 +59,7 @@ pub fn handle_selection_input(
             let name1 = &combatants[idx1];
             let name2 = &combatants[idx2];
 
-            if let (Ok(char1), Ok(char2)) = (
+            if let (Ok(char1), Ok(mut char2)) = (
                 load_character_from_file(name1),
                 load_character_from_file(name2),
             ) {
@@ -66,28 +68,15 @@ pub fn handle_selection_input(
                     commands.entity(entity).despawn_recursive();
                 }
 
-                // Spawn fighters with ranged state if they have ranged weapons
-                let ranged_state1 = if char1.ranged_weapon.is_some() {
-                    Some(RangedAttackState::new())
-                } else {
-                    None
-                };
-                let ranged_state2 = if char2.ranged_weapon.is_some() {
-                    Some(RangedAttackState::new())
-                } else {
-                    None
-                };
+                // The encounter reports fighters by name, so two of a kind need telling apart
+                if char2.name == char1.name {
+                    char2.name.push_str(" II");
+                }
+                let field = arena(&char1.name, &char2.name, combat_state.distance);
 
-                commands.spawn(Fighter {
-                    character: char1,
-                    is_player_one: true,
-                    ranged_state: ranged_state1,
-                });
-                commands.spawn(Fighter {
-                    character: char2,
-                    is_player_one: false,
-                    ranged_state: ranged_state2,
-                });
+                let fighter1 = commands.spawn((Fighter::new(char1), Team(1))).id();
+                let fighter2 = commands.spawn((Fighter::new(char2), Team(2))).id();
+                begin.send(BeginCombat::new(vec![fighter1, fighter2]).with_battlefield(field));
 
                 // Transition to combat
                 game_state.transition_to(GameStateEnum::Combat);
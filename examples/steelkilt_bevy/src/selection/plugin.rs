@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use steelkilt_bevy::SteelkiltSet;
 
 use super::input_handler::handle_selection_input;
 use super::ui::update_selection_ui;
@@ -6,7 +7,8 @@ use super::ui::update_selection_ui;
 /// Plugin that handles character selection functionality
 ///
 /// Registers:
-/// - handle_selection_input system for processing selection commands
+/// - handle_selection_input system for processing selection commands (it
+///   spawns the fighters, so it runs before the plugin begins the fight)
 /// - update_selection_ui system for refreshing selection display
 pub struct SelectionPlugin;
 
@@ -15,7 +17,7 @@ impl Plugin for SelectionPlugin {
         app.add_systems(
             Update,
             (
-                handle_selection_input,
+                handle_selection_input.before(SteelkiltSet),
                 update_selection_ui,
             ),
         );
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::components::{SelectionText, SelectionUI};
 use crate::file_ops::load_available_combatants;
-use crate::state::{CombatState, GameState, GameStateEnum};
+use crate::state::{DuelState, GameState, GameStateEnum};
 
 /// Spawns the character selection UI hierarchy.
 pub fn spawn_selection_ui(commands: &mut Commands) {
@@ -47,7 +47,7 @@ pub fn spawn_selection_ui(commands: &mut Commands) {
 
 /// Updates the character selection UI based on current state.
 pub fn update_selection_ui(
-    combat_state: Res<CombatState>,
+    combat_state: Res<DuelState>,
     game_state: Res<GameState>,
     mut query: Query<&mut Text, With<SelectionText>>,
 ) {
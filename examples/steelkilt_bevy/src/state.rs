@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use steelkilt::modules::positioning::CELL_METERS;
 
 use crate::file_ops::load_available_combatants;
 
@@ -66,11 +67,12 @@ impl Default for ManagementState {
     }
 }
 
-// ===== COMBAT STATE =====
+// ===== DUEL STATE =====
 
+/// How far apart the fighters start
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Distance {
-    Close,  // Within point blank range
+    Close,  // Within reach of each other
     Medium, // Beyond point blank, within max range
     Long,   // Near max range
 }
@@ -78,63 +80,44 @@ pub enum Distance {
 impl Distance {
     pub fn meters(&self) -> i32 {
         match self {
-            Distance::Close => 15,
+            Distance::Close => CELL_METERS,
             Distance::Medium => 40,
             Distance::Long => 80,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum CombatMode {
-    Melee,
-    Ranged,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[allow(dead_code)]
-pub enum RangedAttackPhase {
-    ChoosingMode, // Deciding whether to use ranged or melee
-    Preparing,    // Drawing and readying weapon
-    Aiming,       // Optional aiming phase
-    ReadyToFire,  // Can fire this round
-    Fired,        // Already fired this round
-}
-
+/// Screen state for the duel; the fight itself is the plugin's `CombatState`
 #[derive(Resource)]
-pub struct CombatState {
-    pub round: u32,
-    pub waiting_for_defense: bool,
-    pub current_attacker: u8,
+pub struct DuelState {
     pub combat_log: Vec<String>,
     pub game_over: bool,
-    pub paused: bool,
     pub selected_fighter1: Option<usize>,
     pub selected_fighter2: Option<usize>,
     pub selection_cursor: usize,
-    // Ranged combat additions
-    pub combat_mode: CombatMode,
     pub distance: Distance,
-    pub ranged_phase: Option<RangedAttackPhase>,
-    pub aiming_rounds: i32,
+    /// Whether a turn has been fought; the distance is fixed from then on
+    pub started: bool,
+    /// Last round announced in the log
+    pub logged_round: u32,
+    /// Whose turn it is, and whether they have attacked yet
+    pub turn: Option<Entity>,
+    pub attacked: bool,
 }
 
-impl Default for CombatState {
+impl Default for DuelState {
     fn default() -> Self {
         Self {
-            round: 1,
-            waiting_for_defense: true,
-            current_attacker: 1,
             combat_log: vec!["=== DRAFT RPG COMBAT SIMULATOR ===".to_string()],
             game_over: false,
-            paused: false,
             selected_fighter1: None,
             selected_fighter2: None,
             selection_cursor: 0,
-            combat_mode: CombatMode::Melee,
             distance: Distance::Close, // Start in melee range
-            ranged_phase: None,
-            aiming_rounds: 0,
+            started: false,
+            logged_round: 0,
+            turn: None,
+            attacked: false,
         }
     }
 }
@@ -0,0 +1,45 @@
+//! Terminal combat simulator using the `tui` feature
+//!
+//! Run with: cargo run --example tui_combat --features tui
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use steelkilt::tui::CombatTui;
+use steelkilt::*;
+
+fn main() -> std::io::Result<()> {
+    let aldric = Character::new(
+        "Aldric the Bold",
+        Attributes::new(8, 6, 7, 5, 6, 5, 5, 7, 4),
+        7, // weapon skill
+        5, // dodge skill
+        Weapon::long_sword(),
+        Armor::chain_mail(),
+    );
+
+    let grimwald = Character::new(
+        "Grimwald Ironfist",
+        Attributes::new(9, 5, 8, 4, 5, 6, 4, 6, 3),
+        6, // weapon skill
+        4, // dodge skill
+        Weapon::two_handed_sword(),
+        Armor::leather(),
+    );
+
+    let mut app = CombatTui::new(aldric, grimwald);
+    let mut terminal = ratatui::init();
+
+    let result = (|| {
+        while app.is_running() {
+            terminal.draw(|frame| app.render(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    })();
+
+    ratatui::restore();
+    result
+}
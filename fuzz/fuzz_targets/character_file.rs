@@ -0,0 +1,30 @@
+//! Loading character files, scenarios and saved runs from arbitrary text
+//!
+//! Malformed TOML must come back as an error, never a panic, and anything
+//! that does load must survive being written out and read back.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use steelkilt::modules::scenario::{Scenario, ScenarioRun};
+use steelkilt::Character;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(character) = toml::from_str::<Character>(source) {
+        if let Ok(saved) = toml::to_string(&character) {
+            toml::from_str::<Character>(&saved).expect("saved character reloads");
+        }
+    }
+
+    let _ = Scenario::from_toml(source);
+
+    if let Ok(run) = ScenarioRun::resume(source) {
+        if let Ok(saved) = run.save() {
+            ScenarioRun::resume(&saved).expect("saved run resumes");
+        }
+    }
+});
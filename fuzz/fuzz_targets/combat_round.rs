@@ -0,0 +1,136 @@
+//! Combat rounds between arbitrary characters
+//!
+//! Attributes take any value (the constructor clamps them); skills, fatigue,
+//! modifiers and weapon damage and armor protection range far past anything
+//! a character sheet allows, and fighters may start the fight already wounded. Each round must resolve
+//! without panicking and keep to the basic rules: a hit needs the attack to
+//! beat the defense, misses do nothing, and damage is never negative.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use steelkilt::modules::dice::ScriptedDice;
+use steelkilt::modules::modifiers::{Modifier, ModifierTarget};
+use steelkilt::modules::ruleset::Ruleset;
+use steelkilt::{
+    combat_round_with_dice, Armor, Attributes, Character, Damage, DefenseAction, Fatigue, Weapon,
+};
+
+#[derive(Debug, Arbitrary)]
+struct Fighter {
+    attributes: [i32; 9],
+    weapon_skill: i16,
+    dodge_skill: i16,
+    weapon: u8,
+    armor: u8,
+    /// Replaces the preset weapon's damage
+    damage: Option<i32>,
+    /// Replaces the preset armor's protection
+    protection: Option<i32>,
+    wounds: [u8; 3],
+    fatigue: i32,
+    modifiers: Vec<(u8, i16)>,
+}
+
+impl Fighter {
+    fn build(&self, name: &str) -> Character {
+        let [str, dex, con, rea, int, wil, cha, per, emp] = self.attributes;
+        let weapons = [
+            Weapon::dagger,
+            Weapon::long_sword,
+            Weapon::two_handed_sword,
+            Weapon::club,
+            Weapon::mace,
+            Weapon::war_hammer,
+            Weapon::spear,
+            Weapon::shield,
+        ];
+        let armors = [
+            Armor::none,
+            Armor::heavy_cloth,
+            Armor::leather,
+            Armor::chain_mail,
+            Armor::plate,
+            Armor::full_plate,
+        ];
+        let mut character = Character::new(
+            name,
+            Attributes::new(str, dex, con, rea, int, wil, cha, per, emp),
+            self.weapon_skill.into(),
+            self.dodge_skill.into(),
+            weapons[self.weapon as usize % weapons.len()](),
+            armors[self.armor as usize % armors.len()](),
+        );
+        let [light, severe, critical] = self.wounds;
+        character.wounds.light = (light % 4).into();
+        character.wounds.severe = (severe % 3).into();
+        character.wounds.critical = (critical % 2).into();
+        character.fatigue = Fatigue::clamped(self.fatigue);
+        if let Some(damage) = self.damage {
+            character.weapon.damage = Damage::clamped(damage);
+        }
+        if let Some(protection) = self.protection {
+            character.armor.protection = Damage::clamped(protection);
+        }
+        let targets = [
+            ModifierTarget::Attack,
+            ModifierTarget::Parry,
+            ModifierTarget::Dodge,
+            ModifierTarget::Damage,
+            ModifierTarget::Movement,
+        ];
+        for &(target, value) in &self.modifiers {
+            character.add_modifier(Modifier::new(
+                "Fuzz",
+                targets[target as usize % targets.len()],
+                value.into(),
+            ));
+        }
+        character
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    attacker: Fighter,
+    defender: Fighter,
+    dodge: bool,
+    dice: Vec<u8>,
+    rounds: u8,
+    sparring: bool,
+    dying_state: bool,
+    blood_loss: bool,
+    exhaustion: bool,
+}
+
+fuzz_target!(|input: Input| {
+    let mut attacker = input.attacker.build("Attacker");
+    let mut defender = input.defender.build("Defender");
+    let action = if input.dodge {
+        DefenseAction::Dodge
+    } else {
+        DefenseAction::Parry
+    };
+    let ruleset = Ruleset::new()
+        .with_sparring(input.sparring)
+        .with_dying_state(input.dying_state)
+        .with_blood_loss(input.blood_loss)
+        .with_exhaustion(input.exhaustion);
+    let mut dice = ScriptedDice::new(input.dice.iter().map(|&d| i32::from(d)));
+
+    for _ in 0..input.rounds % 16 {
+        let result =
+            combat_round_with_dice(&mut attacker, &mut defender, action, &ruleset, &mut dice);
+        assert_eq!(result.hit, result.attack_roll > result.defense_roll);
+        assert!(result.damage >= 0);
+        if !result.hit {
+            assert_eq!(result.damage, 0);
+            assert!(result.wound_level.is_none() && !result.defender_died);
+        }
+        if result.defender_died || result.defender_dying {
+            break;
+        }
+        std::mem::swap(&mut attacker, &mut defender);
+    }
+});
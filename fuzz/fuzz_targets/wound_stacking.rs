@@ -0,0 +1,57 @@
+//! Long sequences of wounds, with the odd full heal
+//!
+//! Stacking must never lose or invent wounds: four light make a severe and
+//! three severe a critical, so the total in light-wound terms always matches
+//! what was dealt. Death is exactly more than one critical wound.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use steelkilt::modules::injuries::WoundDescription;
+use steelkilt::{Armor, Attributes, Character, Weapon, WoundLevel, Wounds};
+
+/// A wound's worth in light wounds
+fn weight(level: WoundLevel) -> i64 {
+    match level {
+        WoundLevel::Light => 1,
+        WoundLevel::Severe => 4,
+        WoundLevel::Critical => 12,
+    }
+}
+
+fn total(wounds: &Wounds) -> i64 {
+    i64::from(wounds.light) + 4 * i64::from(wounds.severe) + 12 * i64::from(wounds.critical)
+}
+
+fuzz_target!(|sequence: Vec<u8>| {
+    let mut character = Character::new(
+        "Target",
+        Attributes::new(5, 5, 5, 5, 5, 5, 5, 5, 5),
+        5,
+        5,
+        Weapon::long_sword(),
+        Armor::leather(),
+    );
+    let mut dealt = 0i64;
+    for step in sequence {
+        let level = match step % 4 {
+            0 => WoundLevel::Light,
+            1 => WoundLevel::Severe,
+            2 => WoundLevel::Critical,
+            _ => {
+                character.heal_wounds();
+                dealt = 0;
+                continue;
+            }
+        };
+        character.inflict_wound(WoundDescription::new(level, "Fuzz"));
+        dealt += weight(level);
+
+        let wounds = &character.wounds;
+        assert!((0..4).contains(&wounds.light));
+        assert!((0..3).contains(&wounds.severe));
+        assert_eq!(total(wounds), dealt);
+        assert_eq!(wounds.is_dead(), wounds.critical > 1);
+        assert!(wounds.melee_penalty() <= 0 && wounds.movement_penalty() <= 0);
+    }
+});
@@ -0,0 +1,314 @@
+//! Ready-made egui widgets for GM dashboards and debugging tools
+//!
+//! Enabled with the `egui` feature. Each widget only reads library state;
+//! hosts decide how to apply any actions a widget returns.
+
+use crate::modules::exhaustion::Exhaustion;
+#[cfg(feature = "ranged")]
+use crate::SkillLevel;
+use crate::{Character, CombatResult, Wounds};
+use egui::{Color32, ProgressBar, Response, Ui, Widget};
+
+/// Light-wound equivalents that add up to death (2 Critical wounds)
+const WOUNDS_TO_DEATH: f32 = 24.0;
+
+/// Fraction of the way from unhurt (0.0) to dead (1.0), in light-wound equivalents
+pub fn wound_fraction(wounds: &Wounds) -> f32 {
+    let load = wounds.light + wounds.severe * 4 + wounds.critical * 12;
+    (load as f32 / WOUNDS_TO_DEATH).clamp(0.0, 1.0)
+}
+
+/// Fraction of the way from fresh (0.0) to completely drained (1.0)
+pub fn exhaustion_fraction(exhaustion: &Exhaustion) -> f32 {
+    let limit = (exhaustion.stamina_threshold * 3).max(1);
+    (exhaustion.points as f32 / limit as f32).clamp(0.0, 1.0)
+}
+
+/// Progress bar showing how close a character is to death from wounds
+pub struct WoundBar<'a> {
+    wounds: &'a Wounds,
+}
+
+impl<'a> WoundBar<'a> {
+    pub fn new(wounds: &'a Wounds) -> Self {
+        Self { wounds }
+    }
+}
+
+impl Widget for WoundBar<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let fill = if self.wounds.is_dead() {
+            Color32::DARK_GRAY
+        } else if self.wounds.is_incapacitated() {
+            Color32::DARK_RED
+        } else if self.wounds.severe > 0 {
+            Color32::from_rgb(200, 120, 40)
+        } else {
+            Color32::from_rgb(180, 160, 60)
+        };
+        let text = format!(
+            "Wounds L{} S{} C{} ({:+})",
+            self.wounds.light,
+            self.wounds.severe,
+            self.wounds.critical,
+            self.wounds.movement_penalty()
+        );
+        ui.add(
+            ProgressBar::new(wound_fraction(self.wounds))
+                .fill(fill)
+                .text(text),
+        )
+    }
+}
+
+/// Progress bar showing exhaustion toward the Critical level
+pub struct ExhaustionBar<'a> {
+    exhaustion: &'a Exhaustion,
+}
+
+impl<'a> ExhaustionBar<'a> {
+    pub fn new(exhaustion: &'a Exhaustion) -> Self {
+        Self { exhaustion }
+    }
+}
+
+impl Widget for ExhaustionBar<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let text = format!(
+            "{} - {} pts ({:+})",
+            self.exhaustion.status(),
+            self.exhaustion.points,
+            self.exhaustion.penalty()
+        );
+        ui.add(
+            ProgressBar::new(exhaustion_fraction(self.exhaustion))
+                .fill(Color32::from_rgb(70, 110, 190))
+                .text(text),
+        )
+    }
+}
+
+/// Attribute, skill, equipment and wound overview for a single character
+pub struct CharacterInspector<'a> {
+    character: &'a Character,
+}
+
+impl<'a> CharacterInspector<'a> {
+    pub fn new(character: &'a Character) -> Self {
+        Self { character }
+    }
+}
+
+impl Widget for CharacterInspector<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let c = self.character;
+        ui.group(|ui| {
+            ui.heading(&c.name);
+            let a = &c.attributes;
+            egui::Grid::new(("steelkilt_attributes", &c.name))
+                .striped(true)
+                .show(ui, |ui| {
+                    for (label, value) in [
+                        ("STR", a.strength),
+                        ("DEX", a.dexterity),
+                        ("CON", a.constitution),
+                    ] {
+                        ui.label(label);
+                        ui.label(value.to_string());
+                    }
+                    ui.end_row();
+                    for (label, value) in [
+                        ("REA", a.reason),
+                        ("INT", a.intuition),
+                        ("WIL", a.willpower),
+                    ] {
+                        ui.label(label);
+                        ui.label(value.to_string());
+                    }
+                    ui.end_row();
+                    for (label, value) in [
+                        ("CHA", a.charisma),
+                        ("PER", a.perception),
+                        ("EMP", a.empathy),
+                    ] {
+                        ui.label(label);
+                        ui.label(value.to_string());
+                    }
+                    ui.end_row();
+                });
+            ui.separator();
+            ui.label(format!(
+                "Weapon skill {}  Dodge {}",
+                c.weapon_skill(),
+                c.dodge_skill()
+            ));
+            ui.label(format!(
+                "{} (damage {})  {} (protection {}, {:+})",
+                c.weapon.name,
+                c.weapon.damage,
+                c.armor.name,
+                c.armor.protection,
+                c.armor.movement_penalty
+            ));
+            #[cfg(feature = "ranged")]
+            if let Some(ranged) = &c.ranged_weapon {
+                ui.label(format!(
+                    "{} (damage {}, skill {})",
+                    ranged.name,
+                    ranged.damage,
+                    c.ranged_skill.map_or(0, SkillLevel::get)
+                ));
+            }
+            #[cfg(feature = "magic")]
+            if let Some(magic) = &c.magic {
+                ui.label(format!(
+                    "Magic: {} lores, {} spells, exhaustion {}",
+                    magic.lores.len(),
+                    magic.spells.len(),
+                    magic.exhaustion_points
+                ));
+            }
+            ui.add(WoundBar::new(&c.wounds));
+            let status = if !c.is_alive() {
+                "Dead"
+            } else if !c.can_act() {
+                "Incapacitated"
+            } else {
+                "Ready"
+            };
+            ui.label(status);
+        })
+        .response
+    }
+}
+
+/// Scrolling list of combat round summaries, newest at the bottom
+pub struct CombatLogView<'a> {
+    entries: &'a [CombatResult],
+    max_height: f32,
+}
+
+impl<'a> CombatLogView<'a> {
+    pub fn new(entries: &'a [CombatResult]) -> Self {
+        Self {
+            entries,
+            max_height: 200.0,
+        }
+    }
+
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+}
+
+impl Widget for CombatLogView<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            egui::ScrollArea::vertical()
+                .max_height(self.max_height)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for result in self.entries {
+                        ui.label(result.to_string());
+                    }
+                });
+        })
+        .response
+    }
+}
+
+/// Action requested through an `EncounterStepper`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepperAction {
+    Step,
+    AutoResolve,
+    Reset,
+}
+
+/// Round counter with step / auto-resolve / reset buttons
+pub struct EncounterStepper {
+    pub round: u32,
+    pub finished: bool,
+}
+
+impl EncounterStepper {
+    pub fn new(round: u32, finished: bool) -> Self {
+        Self { round, finished }
+    }
+
+    /// Draw the stepper and return the button pressed this frame, if any
+    pub fn show(&self, ui: &mut Ui) -> Option<StepperAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.label(format!("Round {}", self.round));
+            ui.add_enabled_ui(!self.finished, |ui| {
+                if ui.button("Step").clicked() {
+                    action = Some(StepperAction::Step);
+                }
+                if ui.button("Auto-resolve").clicked() {
+                    action = Some(StepperAction::AutoResolve);
+                }
+            });
+            if ui.button("Reset").clicked() {
+                action = Some(StepperAction::Reset);
+            }
+        });
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+
+    fn fighter() -> Character {
+        Character::new(
+            "Aldric",
+            Attributes::new(8, 6, 7, 5, 6, 5, 5, 7, 4),
+            7,
+            5,
+            Weapon::long_sword(),
+            Armor::chain_mail(),
+        )
+    }
+
+    #[test]
+    fn test_wound_fraction() {
+        let mut wounds = Wounds::new();
+        assert_eq!(wound_fraction(&wounds), 0.0);
+
+        wounds.add_wound(WoundLevel::Critical);
+        assert_eq!(wound_fraction(&wounds), 0.5);
+
+        wounds.add_wound(WoundLevel::Critical);
+        assert_eq!(wound_fraction(&wounds), 1.0);
+    }
+
+    #[test]
+    fn test_exhaustion_fraction() {
+        let mut exhaustion = Exhaustion::new(5);
+        exhaustion.add_points(5);
+        assert!((exhaustion_fraction(&exhaustion) - 1.0 / 3.0).abs() < f32::EPSILON);
+
+        exhaustion.add_points(20);
+        assert_eq!(exhaustion_fraction(&exhaustion), 1.0);
+    }
+
+    #[test]
+    fn test_widgets_render_headless() {
+        let character = fighter();
+        let exhaustion = Exhaustion::new(character.attributes.stamina());
+        let ctx = egui::Context::default();
+
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.add(CharacterInspector::new(&character));
+                ui.add(ExhaustionBar::new(&exhaustion));
+                ui.add(CombatLogView::new(&[]));
+                assert_eq!(EncounterStepper::new(1, false).show(ui), None);
+            });
+        });
+    }
+}
@@ -17,11 +17,22 @@
 //! - Hit location tracking
 //! - Ranged combat mechanics
 //! - Magic system
+//!
+//! ## Optional Integrations
+//!
+//! - `egui` feature: inspector widgets in [`egui_widgets`]
+//! - `tui` feature: ratatui combat front-end in [`tui`]
 
+#[cfg(feature = "egui")]
+pub mod egui_widgets;
 pub mod modules;
+#[cfg(feature = "tui")]
+pub mod tui;
 
+use modules::modifiers::ModifierTarget;
 use rand::Rng;
 use std::fmt;
+use std::sync::Arc;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -31,22 +42,193 @@ pub fn d10() -> i32 {
     rand::thread_rng().gen_range(1..=10)
 }
 
+/// A number outside the range its type allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundsError {
+    Attribute(i32),
+    Skill(i32),
+    Damage(i32),
+    Fatigue(i32),
+}
+
+impl fmt::Display for BoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoundsError::Attribute(value) => write!(
+                f,
+                "Attribute score {} is not within {}-{}",
+                value,
+                AttributeScore::MIN,
+                AttributeScore::MAX
+            ),
+            BoundsError::Skill(value) => write!(
+                f,
+                "Skill level {} is not within {}-{}",
+                value,
+                SkillLevel::MIN,
+                SkillLevel::MAX
+            ),
+            BoundsError::Damage(value) => write!(
+                f,
+                "Damage {} is not within {}-{}",
+                value,
+                Damage::MIN,
+                Damage::MAX
+            ),
+            BoundsError::Fatigue(value) => write!(
+                f,
+                "Fatigue {} is not within {}-{}",
+                value,
+                Fatigue::MIN,
+                Fatigue::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoundsError {}
+
+/// Integer newtype held between `$min` and `$max`
+///
+/// `new` and `TryFrom<i32>` reject values out of range, `clamped` pulls them
+/// in. Arithmetic is `checked_add` (out of range is `None`) or
+/// `saturating_add` (stops at the bounds); `get` gives the plain number.
+/// The default is `$min`.
+macro_rules! bounded_int {
+    ($(#[$doc:meta])* $name:ident, $min:expr, $max:expr, $error:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(Serialize, Deserialize),
+            serde(try_from = "i32", into = "i32")
+        )]
+        pub struct $name(i32);
+
+        impl $name {
+            pub const MIN: i32 = $min;
+            pub const MAX: i32 = $max;
+
+            pub const fn new(value: i32) -> Result<Self, BoundsError> {
+                if value < Self::MIN || value > Self::MAX {
+                    Err(BoundsError::$error(value))
+                } else {
+                    Ok(Self(value))
+                }
+            }
+
+            /// The nearest value in range
+            pub const fn clamped(value: i32) -> Self {
+                Self(if value < Self::MIN {
+                    Self::MIN
+                } else if value > Self::MAX {
+                    Self::MAX
+                } else {
+                    value
+                })
+            }
+
+            pub const fn get(self) -> i32 {
+                self.0
+            }
+
+            pub fn checked_add(self, delta: i32) -> Option<Self> {
+                self.0.checked_add(delta).and_then(|v| Self::new(v).ok())
+            }
+
+            pub fn saturating_add(self, delta: i32) -> Self {
+                Self::clamped(self.0.saturating_add(delta))
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self($min)
+            }
+        }
+
+        impl TryFrom<i32> for $name {
+            type Error = BoundsError;
+
+            fn try_from(value: i32) -> Result<Self, BoundsError> {
+                Self::new(value)
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(value: $name) -> i32 {
+                value.0
+            }
+        }
+
+        impl PartialEq<i32> for $name {
+            fn eq(&self, other: &i32) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialOrd<i32> for $name {
+            fn partial_cmp(&self, other: &i32) -> Option<std::cmp::Ordering> {
+                self.0.partial_cmp(other)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+bounded_int!(
+    /// An attribute from 1 to 10
+    AttributeScore,
+    1,
+    10,
+    Attribute
+);
+
+bounded_int!(
+    /// A combat skill level from 0 (untrained) to 10
+    SkillLevel,
+    0,
+    10,
+    Skill
+);
+
+bounded_int!(
+    /// Damage dealt by a blow, from 0 up to a cap far past any kill
+    Damage,
+    0,
+    999,
+    Damage
+);
+
+bounded_int!(
+    /// Exhaustion points, from 0 (fresh) up to a cap far past collapse
+    Fatigue,
+    0,
+    999,
+    Fatigue
+);
+
 /// Character attributes as defined in Draft RPG
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Attributes {
     // Physical
-    pub strength: i32,     // STR
-    pub dexterity: i32,    // DEX
-    pub constitution: i32, // CON
+    pub strength: AttributeScore,     // STR
+    pub dexterity: AttributeScore,    // DEX
+    pub constitution: AttributeScore, // CON
     // Mental
-    pub reason: i32,    // REA
-    pub intuition: i32, // INT
-    pub willpower: i32, // WIL
+    pub reason: AttributeScore,    // REA
+    pub intuition: AttributeScore, // INT
+    pub willpower: AttributeScore, // WIL
     // Interactive
-    pub charisma: i32,   // CHA
-    pub perception: i32, // PER
-    pub empathy: i32,    // EMP
+    pub charisma: AttributeScore,   // CHA
+    pub perception: AttributeScore, // PER
+    pub empathy: AttributeScore,    // EMP
 }
 
 impl Attributes {
@@ -63,21 +245,47 @@ impl Attributes {
         emp: i32,
     ) -> Self {
         Self {
-            strength: str.clamp(1, 10),
-            dexterity: dex.clamp(1, 10),
-            constitution: con.clamp(1, 10),
-            reason: rea.clamp(1, 10),
-            intuition: int.clamp(1, 10),
-            willpower: wil.clamp(1, 10),
-            charisma: cha.clamp(1, 10),
-            perception: per.clamp(1, 10),
-            empathy: emp.clamp(1, 10),
+            strength: AttributeScore::clamped(str),
+            dexterity: AttributeScore::clamped(dex),
+            constitution: AttributeScore::clamped(con),
+            reason: AttributeScore::clamped(rea),
+            intuition: AttributeScore::clamped(int),
+            willpower: AttributeScore::clamped(wil),
+            charisma: AttributeScore::clamped(cha),
+            perception: AttributeScore::clamped(per),
+            empathy: AttributeScore::clamped(emp),
         }
     }
 
+    /// Like `new`, but scores outside 1-10 are an error instead of clamped
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        str: i32,
+        dex: i32,
+        con: i32,
+        rea: i32,
+        int: i32,
+        wil: i32,
+        cha: i32,
+        per: i32,
+        emp: i32,
+    ) -> Result<Self, BoundsError> {
+        Ok(Self {
+            strength: str.try_into()?,
+            dexterity: dex.try_into()?,
+            constitution: con.try_into()?,
+            reason: rea.try_into()?,
+            intuition: int.try_into()?,
+            willpower: wil.try_into()?,
+            charisma: cha.try_into()?,
+            perception: per.try_into()?,
+            empathy: emp.try_into()?,
+        })
+    }
+
     /// Combined attribute: Stamina = (STR + CON) / 2
     pub fn stamina(&self) -> i32 {
-        ((self.strength + self.constitution) as f32 / 2.0).round() as i32
+        ((self.strength.get() + self.constitution.get()) as f32 / 2.0).round() as i32
     }
 }
 
@@ -91,35 +299,375 @@ pub enum WeaponImpact {
     Huge = 4,
 }
 
+impl fmt::Display for WeaponImpact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeaponImpact::Small => write!(f, "Small"),
+            WeaponImpact::Medium => write!(f, "Medium"),
+            WeaponImpact::Large => write!(f, "Large"),
+            WeaponImpact::Huge => write!(f, "Huge"),
+        }
+    }
+}
+
+impl WeaponImpact {
+    pub const ALL: [WeaponImpact; 4] = [
+        WeaponImpact::Small,
+        WeaponImpact::Medium,
+        WeaponImpact::Large,
+        WeaponImpact::Huge,
+    ];
+
+    /// Damage before edge and bonus: impact × 2 (Draft weapon table)
+    pub const fn base_damage(self) -> i32 {
+        match self {
+            WeaponImpact::Small => 2,
+            WeaponImpact::Medium => 4,
+            WeaponImpact::Large => 6,
+            WeaponImpact::Huge => 8,
+        }
+    }
+}
+
+/// Extra damage for a sharp or pointed weapon
+pub const SHARP_DAMAGE: i32 = 1;
+
+/// Damage for a weapon: (impact × 2), +1 if sharp/pointed, plus any extra bonus
+///
+/// `None` if the bonus is too large to add.
+pub const fn weapon_damage(impact: WeaponImpact, sharp: bool, bonus: i32) -> Option<i32> {
+    let edge = if sharp { SHARP_DAMAGE } else { 0 };
+    (impact.base_damage() + edge).checked_add(bonus)
+}
+
+/// A weapon's damage, capped at `Damage::MAX` if the bonus overflows
+const fn capped_damage(impact: WeaponImpact, sharp: bool, bonus: i32) -> Damage {
+    match weapon_damage(impact, sharp, bonus) {
+        Some(damage) => Damage::clamped(damage),
+        None => Damage::clamped(Damage::MAX),
+    }
+}
+
+// Heavier impact always hits harder, and at twice the impact class
+const _: () = {
+    let mut i = 0;
+    while i < WeaponImpact::ALL.len() {
+        let impact = WeaponImpact::ALL[i];
+        assert!(impact.base_damage() == impact as i32 * 2);
+        if i > 0 {
+            assert!(impact.base_damage() > WeaponImpact::ALL[i - 1].base_damage());
+        }
+        i += 1;
+    }
+};
+
+/// Reach of a standard one-handed weapon
+pub const DEFAULT_REACH: i32 = 1;
+/// Longest reach a weapon can have (pikes)
+pub const MAX_REACH: i32 = 3;
+
+#[cfg(feature = "serde")]
+fn default_reach() -> i32 {
+    DEFAULT_REACH
+}
+
+/// Set of weapon traits that unlock maneuvers (see `maneuvers::available_maneuvers`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeaponTags(u8);
+
+impl WeaponTags {
+    pub const NONE: WeaponTags = WeaponTags(0);
+    /// Pointed weapons that can lunge
+    pub const THRUSTING: WeaponTags = WeaponTags(1);
+    /// Crossguard and pommel, for pommel strikes
+    pub const HILTED: WeaponTags = WeaponTags(1 << 1);
+    pub const SHIELD: WeaponTags = WeaponTags(1 << 2);
+    pub const TWO_HANDED: WeaponTags = WeaponTags(1 << 3);
+
+    const NAMES: [(WeaponTags, &'static str); 4] = [
+        (WeaponTags::THRUSTING, "Thrusting"),
+        (WeaponTags::HILTED, "Hilted"),
+        (WeaponTags::SHIELD, "Shield"),
+        (WeaponTags::TWO_HANDED, "Two-Handed"),
+    ];
+
+    pub const fn with(self, other: WeaponTags) -> Self {
+        WeaponTags(self.0 | other.0)
+    }
+
+    /// Check if every tag in `other` is present
+    pub const fn contains(self, other: WeaponTags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The tag shown as `name` (e.g. "Two-Handed"), ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|(tag, _)| *tag)
+    }
+}
+
+impl fmt::Display for WeaponTags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = Self::NAMES
+            .iter()
+            .filter(|(tag, _)| self.contains(*tag))
+            .map(|(_, name)| *name)
+            .collect();
+        if names.is_empty() {
+            write!(f, "None")
+        } else {
+            write!(f, "{}", names.join(", "))
+        }
+    }
+}
+
 /// Weapon types
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Weapon {
     pub name: String,
     pub impact: WeaponImpact,
-    pub damage: i32, // (impact × 2) + bonus
+    pub damage: Damage, // (impact × 2) + bonus
+    /// Reach in abstract steps (0 = short, 1 = medium, 2 = long)
+    #[cfg_attr(feature = "serde", serde(default = "default_reach"))]
+    pub reach: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: WeaponTags,
 }
 
 impl Weapon {
+    /// Create a sharp/pointed weapon (+1 damage) with medium reach
     pub fn new(name: &str, impact: WeaponImpact) -> Self {
-        let damage = (impact as i32) * 2 + 1; // +1 for pointed/sharp weapons
-        Self {
-            name: name.to_string(),
-            impact,
-            damage,
-        }
+        Self::builder().name(name).impact(impact).build()
+    }
+
+    /// Create a blunt weapon (no sharp bonus) with medium reach
+    pub fn blunt(name: &str, impact: WeaponImpact) -> Self {
+        Self::builder().name(name).impact(impact).blunt().build()
+    }
+
+    pub fn builder() -> WeaponBuilder {
+        WeaponBuilder::default()
     }
 
     pub fn dagger() -> Self {
-        Self::new("Dagger", WeaponImpact::Small)
+        WeaponSpec::DAGGER.build()
     }
 
     pub fn long_sword() -> Self {
-        Self::new("Long Sword", WeaponImpact::Medium)
+        WeaponSpec::LONG_SWORD.build()
     }
 
     pub fn two_handed_sword() -> Self {
-        Self::new("Two-Handed Sword", WeaponImpact::Large)
+        WeaponSpec::TWO_HANDED_SWORD.build()
+    }
+
+    pub fn club() -> Self {
+        WeaponSpec::CLUB.build()
+    }
+
+    pub fn mace() -> Self {
+        WeaponSpec::MACE.build()
+    }
+
+    pub fn war_hammer() -> Self {
+        WeaponSpec::WAR_HAMMER.build()
+    }
+
+    pub fn spear() -> Self {
+        WeaponSpec::SPEAR.build()
+    }
+
+    pub fn shield() -> Self {
+        WeaponSpec::SHIELD.build()
+    }
+
+    pub fn unarmed() -> Self {
+        WeaponSpec::UNARMED.build()
+    }
+
+    pub fn has_tag(&self, tag: WeaponTags) -> bool {
+        self.tags.contains(tag)
+    }
+}
+
+/// Step-by-step weapon construction for custom gear
+///
+/// Defaults to an unnamed sharp Medium weapon with no extra bonus and medium reach.
+#[derive(Debug, Clone)]
+pub struct WeaponBuilder {
+    name: String,
+    impact: WeaponImpact,
+    sharp: bool,
+    bonus: i32,
+    reach: i32,
+    tags: WeaponTags,
+}
+
+impl Default for WeaponBuilder {
+    fn default() -> Self {
+        Self {
+            name: "Weapon".to_string(),
+            impact: WeaponImpact::Medium,
+            sharp: true,
+            bonus: 0,
+            reach: DEFAULT_REACH,
+            tags: WeaponTags::NONE,
+        }
+    }
+}
+
+impl WeaponBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn impact(mut self, impact: WeaponImpact) -> Self {
+        self.impact = impact;
+        self
+    }
+
+    /// Drop the +1 sharp/pointed bonus
+    pub fn blunt(mut self) -> Self {
+        self.sharp = false;
+        self
+    }
+
+    /// Extra damage on top of impact and sharpness (may be negative)
+    pub fn bonus(mut self, bonus: i32) -> Self {
+        self.bonus = bonus;
+        self
+    }
+
+    pub fn reach(mut self, reach: i32) -> Self {
+        self.reach = reach;
+        self
+    }
+
+    /// Add tags to the weapon
+    pub fn tags(mut self, tags: WeaponTags) -> Self {
+        self.tags = self.tags.with(tags);
+        self
+    }
+
+    pub fn build(self) -> Weapon {
+        Weapon {
+            damage: capped_damage(self.impact, self.sharp, self.bonus),
+            name: self.name,
+            impact: self.impact,
+            reach: self.reach,
+            tags: self.tags,
+        }
+    }
+}
+
+/// Compile-time weapon definition, usable in `const` and `static` items
+///
+/// ```
+/// use steelkilt::{WeaponImpact, WeaponSpec};
+///
+/// const FLAIL: WeaponSpec = WeaponSpec::new("Flail", WeaponImpact::Large).blunt().bonus(1);
+/// assert_eq!(FLAIL.damage(), 7);
+/// assert_eq!(FLAIL.build().name, "Flail");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeaponSpec {
+    pub name: &'static str,
+    pub impact: WeaponImpact,
+    pub sharp: bool,
+    pub bonus: i32,
+    pub reach: i32,
+    pub tags: WeaponTags,
+}
+
+impl WeaponSpec {
+    pub const DAGGER: WeaponSpec = WeaponSpec::new("Dagger", WeaponImpact::Small)
+        .reach(0)
+        .tags(WeaponTags::THRUSTING.with(WeaponTags::HILTED));
+    pub const LONG_SWORD: WeaponSpec = WeaponSpec::new("Long Sword", WeaponImpact::Medium)
+        .tags(WeaponTags::THRUSTING.with(WeaponTags::HILTED));
+    pub const TWO_HANDED_SWORD: WeaponSpec =
+        WeaponSpec::new("Two-Handed Sword", WeaponImpact::Large)
+            .reach(2)
+            .tags(WeaponTags::HILTED.with(WeaponTags::TWO_HANDED));
+    pub const CLUB: WeaponSpec = WeaponSpec::new("Club", WeaponImpact::Small).blunt();
+    pub const MACE: WeaponSpec = WeaponSpec::new("Mace", WeaponImpact::Medium).blunt();
+    pub const WAR_HAMMER: WeaponSpec = WeaponSpec::new("War Hammer", WeaponImpact::Large)
+        .blunt()
+        .bonus(1)
+        .tags(WeaponTags::TWO_HANDED);
+    pub const SPEAR: WeaponSpec = WeaponSpec::new("Spear", WeaponImpact::Medium)
+        .reach(2)
+        .tags(WeaponTags::THRUSTING.with(WeaponTags::TWO_HANDED));
+    pub const SHIELD: WeaponSpec = WeaponSpec::new("Shield", WeaponImpact::Small)
+        .blunt()
+        .reach(0)
+        .tags(WeaponTags::SHIELD);
+    /// Fists and feet, for a fighter with nothing in hand
+    pub const UNARMED: WeaponSpec = WeaponSpec::new("Bare Hands", WeaponImpact::Small)
+        .blunt()
+        .reach(0);
+
+    /// A sharp weapon with no extra bonus and medium reach
+    pub const fn new(name: &'static str, impact: WeaponImpact) -> Self {
+        Self {
+            name,
+            impact,
+            sharp: true,
+            bonus: 0,
+            reach: DEFAULT_REACH,
+            tags: WeaponTags::NONE,
+        }
+    }
+
+    pub const fn blunt(mut self) -> Self {
+        self.sharp = false;
+        self
+    }
+
+    pub const fn bonus(mut self, bonus: i32) -> Self {
+        self.bonus = bonus;
+        self
+    }
+
+    pub const fn reach(mut self, reach: i32) -> Self {
+        self.reach = reach;
+        self
+    }
+
+    /// Add tags to the weapon
+    pub const fn tags(mut self, tags: WeaponTags) -> Self {
+        self.tags = self.tags.with(tags);
+        self
+    }
+
+    pub const fn damage(&self) -> Damage {
+        capped_damage(self.impact, self.sharp, self.bonus)
+    }
+
+    pub fn build(&self) -> Weapon {
+        Weapon {
+            name: self.name.to_string(),
+            impact: self.impact,
+            damage: self.damage(),
+            reach: self.reach,
+            tags: self.tags,
+        }
+    }
+}
+
+impl From<WeaponSpec> for Weapon {
+    fn from(spec: WeaponSpec) -> Self {
+        spec.build()
     }
 }
 
@@ -134,97 +682,341 @@ pub enum ArmorType {
     FullPlate = 5,
 }
 
+impl ArmorType {
+    pub const ALL: [ArmorType; 5] = [
+        ArmorType::HeavyCloth,
+        ArmorType::Leather,
+        ArmorType::Chain,
+        ArmorType::Plate,
+        ArmorType::FullPlate,
+    ];
+
+    /// Default protection for this armor type (Draft armor table)
+    pub const fn protection(self) -> i32 {
+        match self {
+            ArmorType::HeavyCloth => 1,
+            ArmorType::Leather => 2,
+            ArmorType::Chain => 3,
+            ArmorType::Plate => 4,
+            ArmorType::FullPlate => 5,
+        }
+    }
+
+    /// Default movement penalty for this armor type (Draft armor table)
+    pub const fn movement_penalty(self) -> i32 {
+        match self {
+            ArmorType::HeavyCloth | ArmorType::Leather => 0,
+            ArmorType::Chain | ArmorType::Plate => -1,
+            ArmorType::FullPlate => -2,
+        }
+    }
+
+    /// Default spellcasting penalty for this armor type (see `ArmorInterference`)
+    pub const fn casting_penalty(self) -> i32 {
+        match self {
+            ArmorType::HeavyCloth => 0,
+            ArmorType::Leather => -1,
+            ArmorType::Chain => -2,
+            ArmorType::Plate => -3,
+            ArmorType::FullPlate => -5,
+        }
+    }
+}
+
+// Heavier armor protects more and never hinders less
+const _: () = {
+    let mut i = 1;
+    while i < ArmorType::ALL.len() {
+        let (lighter, heavier) = (ArmorType::ALL[i - 1], ArmorType::ALL[i]);
+        assert!(heavier.protection() > lighter.protection());
+        assert!(heavier.movement_penalty() <= lighter.movement_penalty());
+        assert!(heavier.casting_penalty() <= lighter.casting_penalty());
+        i += 1;
+    }
+    assert!(ArmorType::HeavyCloth.movement_penalty() == 0);
+};
+
+impl fmt::Display for ArmorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArmorType::HeavyCloth => write!(f, "Heavy Cloth"),
+            ArmorType::Leather => write!(f, "Leather"),
+            ArmorType::Chain => write!(f, "Chain"),
+            ArmorType::Plate => write!(f, "Plate"),
+            ArmorType::FullPlate => write!(f, "Full Plate"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Armor {
     pub name: String,
     pub armor_type: ArmorType,
-    pub protection: i32,
+    /// Damage absorbed from each blow
+    pub protection: Damage,
     pub movement_penalty: i32,
 }
 
 impl Armor {
+    /// Create armor with protection from its type and the given movement penalty
+    #[deprecated(note = "use `Armor::of_type`, and `with_penalty` to override its penalty")]
     pub fn new(name: &str, armor_type: ArmorType, movement_penalty: i32) -> Self {
-        Self {
-            name: name.to_string(),
-            armor_type,
-            protection: armor_type as i32,
-            movement_penalty,
-        }
+        Self::of_type(name, armor_type).with_penalty(movement_penalty)
+    }
+
+    /// Create armor with protection and movement penalty taken from its type
+    pub fn of_type(name: &str, armor_type: ArmorType) -> Self {
+        Self::builder().name(name).armor_type(armor_type).build()
+    }
+
+    /// Override the movement penalty derived from the armor type
+    pub fn with_penalty(mut self, movement_penalty: i32) -> Self {
+        self.movement_penalty = movement_penalty;
+        self
+    }
+
+    pub fn builder() -> ArmorBuilder {
+        ArmorBuilder::default()
     }
 
     pub fn none() -> Self {
-        Self {
-            name: "None".to_string(),
-            armor_type: ArmorType::HeavyCloth,
-            protection: 0,
-            movement_penalty: 0,
-        }
+        ArmorSpec::NONE.build()
+    }
+
+    pub fn heavy_cloth() -> Self {
+        ArmorSpec::HEAVY_CLOTH.build()
     }
 
     pub fn leather() -> Self {
-        Self::new("Leather Armor", ArmorType::Leather, 0)
+        ArmorSpec::LEATHER.build()
     }
 
     pub fn chain_mail() -> Self {
-        Self::new("Chain Mail", ArmorType::Chain, -1)
+        ArmorSpec::CHAIN_MAIL.build()
     }
 
     pub fn plate() -> Self {
-        Self::new("Plate Armor", ArmorType::Plate, -1)
+        ArmorSpec::PLATE.build()
     }
-}
-
-/// Wound severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum WoundLevel {
-    Light,
-    Severe,
-    Critical,
-}
 
-impl fmt::Display for WoundLevel {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            WoundLevel::Light => write!(f, "Light"),
-            WoundLevel::Severe => write!(f, "Severe"),
-            WoundLevel::Critical => write!(f, "Critical"),
-        }
+    pub fn full_plate() -> Self {
+        ArmorSpec::FULL_PLATE.build()
     }
 }
 
-/// Character wounds tracking
+/// Step-by-step armor construction for custom gear
+///
+/// Protection and movement penalty follow the armor type unless set explicitly.
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Wounds {
-    pub light: i32,
-    pub severe: i32,
-    pub critical: i32,
+pub struct ArmorBuilder {
+    name: String,
+    armor_type: ArmorType,
+    protection: Option<i32>,
+    movement_penalty: Option<i32>,
 }
 
-impl Wounds {
-    pub fn new() -> Self {
+impl Default for ArmorBuilder {
+    fn default() -> Self {
         Self {
-            light: 0,
-            severe: 0,
-            critical: 0,
+            name: "Armor".to_string(),
+            armor_type: ArmorType::Leather,
+            protection: None,
+            movement_penalty: None,
         }
     }
+}
 
-    /// Add a wound, handling stacking (4th light becomes severe, etc.)
-    pub fn add_wound(&mut self, level: WoundLevel) {
-        match level {
-            WoundLevel::Light => {
-                self.light += 1;
-                if self.light >= 4 {
-                    self.light = 0;
-                    self.add_wound(WoundLevel::Severe);
-                }
+impl ArmorBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn armor_type(mut self, armor_type: ArmorType) -> Self {
+        self.armor_type = armor_type;
+        self
+    }
+
+    /// Override the protection normally given by the armor type
+    pub fn protection(mut self, protection: i32) -> Self {
+        self.protection = Some(protection);
+        self
+    }
+
+    /// Override the movement penalty normally given by the armor type
+    pub fn movement_penalty(mut self, movement_penalty: i32) -> Self {
+        self.movement_penalty = Some(movement_penalty);
+        self
+    }
+
+    pub fn build(self) -> Armor {
+        Armor {
+            name: self.name,
+            armor_type: self.armor_type,
+            protection: Damage::clamped(self.protection.unwrap_or(self.armor_type.protection())),
+            movement_penalty: self
+                .movement_penalty
+                .unwrap_or(self.armor_type.movement_penalty()),
+        }
+    }
+}
+
+/// Compile-time armor definition, usable in `const` and `static` items
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArmorSpec {
+    pub name: &'static str,
+    pub armor_type: ArmorType,
+    pub protection: i32,
+    pub movement_penalty: i32,
+}
+
+impl ArmorSpec {
+    pub const NONE: ArmorSpec = ArmorSpec::new("None", ArmorType::HeavyCloth).protection(0);
+    pub const HEAVY_CLOTH: ArmorSpec = ArmorSpec::new("Heavy Cloth", ArmorType::HeavyCloth);
+    pub const LEATHER: ArmorSpec = ArmorSpec::new("Leather Armor", ArmorType::Leather);
+    pub const CHAIN_MAIL: ArmorSpec = ArmorSpec::new("Chain Mail", ArmorType::Chain);
+    pub const PLATE: ArmorSpec = ArmorSpec::new("Plate Armor", ArmorType::Plate);
+    pub const FULL_PLATE: ArmorSpec = ArmorSpec::new("Full Plate", ArmorType::FullPlate);
+
+    /// Armor with the protection and movement penalty given by its type
+    pub const fn new(name: &'static str, armor_type: ArmorType) -> Self {
+        Self {
+            name,
+            armor_type,
+            protection: armor_type.protection(),
+            movement_penalty: armor_type.movement_penalty(),
+        }
+    }
+
+    pub const fn protection(mut self, protection: i32) -> Self {
+        self.protection = protection;
+        self
+    }
+
+    pub const fn with_penalty(mut self, movement_penalty: i32) -> Self {
+        self.movement_penalty = movement_penalty;
+        self
+    }
+
+    pub fn build(&self) -> Armor {
+        Armor {
+            name: self.name.to_string(),
+            armor_type: self.armor_type,
+            protection: Damage::clamped(self.protection),
+            movement_penalty: self.movement_penalty,
+        }
+    }
+}
+
+impl From<ArmorSpec> for Armor {
+    fn from(spec: ArmorSpec) -> Self {
+        spec.build()
+    }
+}
+
+/// Wound severity levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WoundLevel {
+    Light,
+    Severe,
+    Critical,
+}
+
+impl fmt::Display for WoundLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WoundLevel::Light => write!(f, "Light"),
+            WoundLevel::Severe => write!(f, "Severe"),
+            WoundLevel::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+impl WoundLevel {
+    pub const ALL: [WoundLevel; 3] = [WoundLevel::Light, WoundLevel::Severe, WoundLevel::Critical];
+
+    /// Damage a wound of this level must exceed against `constitution` (Draft wound table)
+    pub const fn threshold(self, constitution: i32) -> i32 {
+        match self {
+            WoundLevel::Light => 1,
+            WoundLevel::Severe => constitution / 2,
+            WoundLevel::Critical => constitution,
+        }
+    }
+
+    /// The wound `damage` inflicts on a defender with `constitution`, if any
+    pub const fn from_damage(damage: i32, constitution: i32) -> Option<WoundLevel> {
+        if damage <= WoundLevel::Light.threshold(constitution) {
+            None
+        } else if damage > WoundLevel::Critical.threshold(constitution) {
+            Some(WoundLevel::Critical)
+        } else if damage > WoundLevel::Severe.threshold(constitution) {
+            Some(WoundLevel::Severe)
+        } else {
+            Some(WoundLevel::Light)
+        }
+    }
+}
+
+/// Damage over this multiple of CON kills outright
+pub const LETHAL_DAMAGE_MULTIPLE: i32 = 2;
+/// Light wounds that stack into a Severe one
+pub const LIGHT_WOUNDS_PER_SEVERE: i32 = 4;
+/// Severe wounds that stack into a Critical one
+pub const SEVERE_WOUNDS_PER_CRITICAL: i32 = 3;
+
+/// Whether `damage` kills a defender with `constitution` outright
+pub const fn is_lethal_damage(damage: i32, constitution: i32) -> bool {
+    damage > constitution * LETHAL_DAMAGE_MULTIPLE
+}
+
+// For every legal CON, a Critical wound needs more damage than a Severe one
+// and killing outright more than either
+const _: () = {
+    let mut con = AttributeScore::MIN;
+    while con <= AttributeScore::MAX {
+        let severe = WoundLevel::Severe.threshold(con);
+        let critical = WoundLevel::Critical.threshold(con);
+        assert!(severe < critical);
+        assert!(!is_lethal_damage(critical, con));
+        con += 1;
+    }
+    assert!(LIGHT_WOUNDS_PER_SEVERE > 1 && SEVERE_WOUNDS_PER_CRITICAL > 1);
+};
+
+/// Character wounds tracking
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Wounds {
+    pub light: i32,
+    pub severe: i32,
+    pub critical: i32,
+}
+
+impl Wounds {
+    pub fn new() -> Self {
+        Self {
+            light: 0,
+            severe: 0,
+            critical: 0,
+        }
+    }
+
+    /// Add a wound, handling stacking (4th light becomes severe, etc.)
+    pub fn add_wound(&mut self, level: WoundLevel) {
+        match level {
+            WoundLevel::Light => {
+                self.light += 1;
+                if self.light >= LIGHT_WOUNDS_PER_SEVERE {
+                    self.light = 0;
+                    self.add_wound(WoundLevel::Severe);
+                }
             }
             WoundLevel::Severe => {
                 self.severe += 1;
-                if self.severe >= 3 {
+                if self.severe >= SEVERE_WOUNDS_PER_CRITICAL {
                     self.severe = 0;
                     self.add_wound(WoundLevel::Critical);
                 }
@@ -245,9 +1037,33 @@ impl Wounds {
         self.critical >= 1
     }
 
-    /// Total penalty from wounds for movement-based actions
+    /// Wound penalty for moving and dodging (-1 / -2 / -4 per Light / Severe / Critical)
     pub fn movement_penalty(&self) -> i32 {
-        -(self.light + self.severe * 2 + self.critical * 4)
+        self.weighted(1, 2, 4)
+    }
+
+    /// Wound penalty for melee attacks and parries (-1 / -2 / -4)
+    pub fn melee_penalty(&self) -> i32 {
+        self.weighted(1, 2, 4)
+    }
+
+    /// Wound penalty for aiming ranged weapons; serious wounds spoil a steady aim (-1 / -3 / -4)
+    pub fn ranged_penalty(&self) -> i32 {
+        self.weighted(1, 3, 4)
+    }
+
+    /// Wound penalty for casting; pain breaks concentration (-1 / -3 / -6)
+    pub fn casting_penalty(&self) -> i32 {
+        self.weighted(1, 3, 6)
+    }
+
+    /// Wound penalty for noticing things; light wounds don't distract (0 / -1 / -2)
+    pub fn perception_penalty(&self) -> i32 {
+        self.weighted(0, 1, 2)
+    }
+
+    fn weighted(&self, light: i32, severe: i32, critical: i32) -> i32 {
+        -(self.light * light + self.severe * severe + self.critical * critical)
     }
 }
 
@@ -257,26 +1073,273 @@ impl Default for Wounds {
     }
 }
 
+/// Equipment slot affected by an equip change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EquipmentSlot {
+    Weapon,
+    Armor,
+    RangedWeapon,
+}
+
+impl fmt::Display for EquipmentSlot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EquipmentSlot::Weapon => write!(f, "Weapon"),
+            EquipmentSlot::Armor => write!(f, "Armor"),
+            EquipmentSlot::RangedWeapon => write!(f, "Ranged Weapon"),
+        }
+    }
+}
+
+/// A state change reported to a character's observers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharacterChange {
+    WoundAdded { level: WoundLevel },
+    EffectApplied { effect: String },
+    ItemEquipped { slot: EquipmentSlot, item: String },
+    SkillRaised { skill: String, level: SkillLevel },
+}
+
+/// Callback invoked with the character's name and the change that happened
+pub type ChangeCallback = Arc<dyn Fn(&str, &CharacterChange) + Send + Sync>;
+
+/// Registered change callbacks; never serialized, shared between clones
+#[derive(Clone, Default)]
+pub struct ChangeObservers(Vec<ChangeCallback>);
+
+impl ChangeObservers {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for ChangeObservers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChangeObservers({})", self.0.len())
+    }
+}
+
+/// Skill levels used in combat, split out of `Character`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CombatSkills {
+    pub weapon_skill: SkillLevel,
+    pub dodge_skill: SkillLevel,
+    #[cfg(feature = "ranged")]
+    pub ranged_skill: Option<SkillLevel>,
+}
+
+/// Carried weapons and worn armor, split out of `Character`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Equipment {
+    pub weapon: Weapon,
+    pub armor: Armor,
+    #[cfg(feature = "hit-location")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub armor_pieces: modules::hit_location::PiecemealArmor,
+    #[cfg(feature = "ranged")]
+    pub ranged_weapon: Option<modules::ranged_combat::RangedWeapon>,
+}
+
+/// Wound state, split out of `Character`
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Health {
+    pub wounds: Wounds,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub injuries: modules::injuries::Injuries,
+    pub dying: Option<modules::dying::DyingState>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stun: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fatigue: Fatigue,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blood_loss: i32,
+}
+
+impl Health {
+    /// Check if alive (see `Character::is_alive`)
+    pub fn is_alive(&self) -> bool {
+        !self.wounds.is_dead()
+    }
+
+    /// Check if able to act, ignoring stun (see `Character::can_act`)
+    pub fn can_act(&self) -> bool {
+        self.is_alive() && !self.wounds.is_incapacitated()
+    }
+}
+
+/// Magic capabilities, split out of `Character`
+#[cfg(feature = "magic")]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MagicState {
+    pub magic: Option<modules::magic::MagicUser>,
+}
+
+/// Front-end metadata the rules never read
+///
+/// Carried through serialization untouched, so a game can keep portraits
+/// and token colors with the character rather than in a file of its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Presentation {
+    /// Path or URL of a portrait image
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub portrait: Option<String>,
+    /// Map token color, in whatever notation the front-end uses
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub token_color: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub faction: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "String::is_empty")
+    )]
+    pub notes: String,
+}
+
+impl Presentation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_portrait(mut self, portrait: &str) -> Self {
+        self.portrait = Some(portrait.to_string());
+        self
+    }
+
+    pub fn with_token_color(mut self, color: &str) -> Self {
+        self.token_color = Some(color.to_string());
+        self
+    }
+
+    pub fn with_faction(mut self, faction: &str) -> Self {
+        self.faction = Some(faction.to_string());
+        self
+    }
+
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.notes = notes.to_string();
+        self
+    }
+
+    /// Nothing set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A `Character` decomposed into independent pieces, e.g. for ECS components
+///
+/// Observers registered on the character are not carried over.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharacterParts {
+    pub name: String,
+    pub attributes: Attributes,
+    pub skills: CombatSkills,
+    pub equipment: Equipment,
+    pub health: Health,
+    #[cfg(feature = "magic")]
+    pub magic: MagicState,
+    pub modifiers: modules::modifiers::Modifiers,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub traits: modules::traits::Traits,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ancestry: Option<modules::ancestry::Ancestry>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub presentation: Presentation,
+}
+
 /// A character in the Draft RPG system
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character {
     pub name: String,
     pub attributes: Attributes,
+    /// Raw weapon skill, kept as `i32` for old code; `weapon_skill()` clamps it
+    #[deprecated(note = "use `weapon_skill()` and `set_weapon_skill()`")]
     pub weapon_skill: i32,
+    /// Raw dodge skill, kept as `i32` for old code; `dodge_skill()` clamps it
+    #[deprecated(note = "use `dodge_skill()` and `set_dodge_skill()`")]
     pub dodge_skill: i32,
     pub weapon: Weapon,
     pub armor: Armor,
+    /// Helmets, shields and other pieces worn over `armor` (see `damage_at`)
+    #[cfg(feature = "hit-location")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "modules::hit_location::PiecemealArmor::is_empty"
+        )
+    )]
+    pub armor_pieces: modules::hit_location::PiecemealArmor,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub wounds: Wounds,
+    /// Narrative record of open wounds and scars
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub injuries: modules::injuries::Injuries,
+    /// Set while the character is dying (see `Ruleset::dying_state`)
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub dying: Option<modules::dying::DyingState>,
+    /// Stun points from blunted blows (see `Ruleset::sparring`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stun: i32,
+    /// Exhaustion points from exertion and casting (see `Character::exhaustion`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fatigue: Fatigue,
+    /// Blood lost to wounds (see `Ruleset::blood_loss`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blood_loss: i32,
+    /// Lasting bonuses and penalties from injuries, traits and effects
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modifiers: modules::modifiers::Modifiers,
+    /// Advantages and disadvantages chosen at creation
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub traits: modules::traits::Traits,
+    /// Species, if not plain human (see `CharacterBuilder::ancestry`)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub ancestry: Option<modules::ancestry::Ancestry>,
+    #[cfg(feature = "magic")]
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub magic: Option<modules::magic::MagicUser>,
+    #[cfg(feature = "ranged")]
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub ranged_weapon: Option<modules::ranged_combat::RangedWeapon>,
+    #[cfg(feature = "ranged")]
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
-    pub ranged_skill: Option<i32>,
+    pub ranged_skill: Option<SkillLevel>,
+    /// Portrait, token color and the like, for front-ends only
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Presentation::is_empty")
+    )]
+    pub presentation: Presentation,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub observers: ChangeObservers,
 }
 
 impl Character {
+    #[allow(deprecated)]
     pub fn new(
         name: &str,
         attributes: Attributes,
@@ -288,17 +1351,33 @@ impl Character {
         Self {
             name: name.to_string(),
             attributes,
-            weapon_skill: weapon_skill.clamp(0, 10),
-            dodge_skill: dodge_skill.clamp(0, 10),
+            weapon_skill: SkillLevel::clamped(weapon_skill).get(),
+            dodge_skill: SkillLevel::clamped(dodge_skill).get(),
             weapon,
             armor,
+            #[cfg(feature = "hit-location")]
+            armor_pieces: modules::hit_location::PiecemealArmor::new(),
             wounds: Wounds::new(),
+            injuries: modules::injuries::Injuries::new(),
+            dying: None,
+            stun: 0,
+            fatigue: Fatigue::default(),
+            blood_loss: 0,
+            modifiers: modules::modifiers::Modifiers::new(),
+            traits: modules::traits::Traits::new(),
+            ancestry: None,
+            #[cfg(feature = "magic")]
             magic: None,
+            #[cfg(feature = "ranged")]
             ranged_weapon: None,
+            #[cfg(feature = "ranged")]
             ranged_skill: None,
+            presentation: Presentation::default(),
+            observers: ChangeObservers::default(),
         }
     }
 
+    #[cfg(feature = "magic")]
     pub fn new_with_magic(
         name: &str,
         attributes: Attributes,
@@ -309,127 +1388,1574 @@ impl Character {
         magic: modules::magic::MagicUser,
     ) -> Self {
         Self {
-            name: name.to_string(),
+            magic: Some(magic),
+            ..Self::new(name, attributes, weapon_skill, dodge_skill, weapon, armor)
+        }
+    }
+
+    /// Make this character a copy of `template`, reusing its allocations
+    ///
+    /// Same result as `*self = template.clone()`, but the name, modifier and
+    /// injury lists keep their buffers, which adds up when a roster is
+    /// refought thousands of times (see `EncounterPool`).
+    #[allow(deprecated)]
+    pub fn reset_to(&mut self, template: &Character) {
+        let Character {
+            name,
             attributes,
-            weapon_skill: weapon_skill.clamp(0, 10),
-            dodge_skill: dodge_skill.clamp(0, 10),
+            weapon_skill,
+            dodge_skill,
             weapon,
             armor,
-            wounds: Wounds::new(),
-            magic: Some(magic),
-            ranged_weapon: None,
-            ranged_skill: None,
+            #[cfg(feature = "hit-location")]
+            armor_pieces,
+            wounds,
+            injuries,
+            dying,
+            stun,
+            fatigue,
+            blood_loss,
+            modifiers,
+            traits,
+            ancestry,
+            #[cfg(feature = "magic")]
+            magic,
+            #[cfg(feature = "ranged")]
+            ranged_weapon,
+            #[cfg(feature = "ranged")]
+            ranged_skill,
+            presentation,
+            observers,
+        } = template;
+        self.name.clone_from(name);
+        self.attributes = *attributes;
+        self.weapon_skill = *weapon_skill;
+        self.dodge_skill = *dodge_skill;
+        self.weapon.clone_from(weapon);
+        self.armor.clone_from(armor);
+        #[cfg(feature = "hit-location")]
+        self.armor_pieces.clone_from(armor_pieces);
+        self.wounds.clone_from(wounds);
+        self.injuries.active.clone_from(&injuries.active);
+        self.injuries.scars.clone_from(&injuries.scars);
+        self.injuries.permanent.clone_from(&injuries.permanent);
+        self.dying = *dying;
+        self.stun = *stun;
+        self.fatigue = *fatigue;
+        self.blood_loss = *blood_loss;
+        self.modifiers.clone_from(modifiers);
+        self.traits.clone_from(traits);
+        self.ancestry.clone_from(ancestry);
+        self.presentation.clone_from(presentation);
+        #[cfg(feature = "magic")]
+        self.magic.clone_from(magic);
+        #[cfg(feature = "ranged")]
+        {
+            self.ranged_weapon.clone_from(ranged_weapon);
+            self.ranged_skill = *ranged_skill;
         }
+        self.observers.clone_from(observers);
     }
 
-    /// Get strength bonus for damage (STR >= 7 gives +1, STR >= 9 gives +2)
-    pub fn strength_bonus(&self) -> i32 {
-        if self.attributes.strength >= 9 {
-            2
-        } else if self.attributes.strength >= 7 {
-            1
-        } else if self.attributes.strength <= 2 {
-            -1
-        } else {
-            0
-        }
+    pub fn builder() -> CharacterBuilder {
+        CharacterBuilder::default()
     }
 
-    /// Make an attack roll
-    pub fn attack_roll(&self) -> i32 {
-        let base = self.weapon_skill + d10();
-        let penalty = self.armor.movement_penalty + self.wounds.movement_penalty();
-        base + penalty
+    /// Give the character a ranged weapon and the skill to use it
+    #[cfg(feature = "ranged")]
+    pub fn with_ranged(mut self, weapon: modules::ranged_combat::RangedWeapon, skill: i32) -> Self {
+        self.ranged_weapon = Some(weapon);
+        self.ranged_skill = Some(SkillLevel::clamped(skill));
+        self
     }
 
-    /// Make a parry roll
-    pub fn parry_roll(&self) -> i32 {
-        let base = self.weapon_skill + d10();
-        let penalty = self.armor.movement_penalty + self.wounds.movement_penalty();
-        base + penalty
+    pub fn with_presentation(mut self, presentation: Presentation) -> Self {
+        self.presentation = presentation;
+        self
     }
 
-    /// Make a dodge roll
-    pub fn dodge_roll(&self) -> i32 {
-        let base = self.dodge_skill + d10();
-        let penalty = self.armor.movement_penalty + self.wounds.movement_penalty();
-        base + penalty
+    /// Put on a helmet, shield or other piece over the body armor
+    #[cfg(feature = "hit-location")]
+    pub fn with_armor_piece(mut self, piece: modules::hit_location::ArmorPiece) -> Self {
+        self.armor_pieces.pieces.push(piece);
+        self
     }
 
-    /// Check if character is alive and able to fight
-    pub fn is_alive(&self) -> bool {
-        !self.wounds.is_dead()
+    /// Give the character a trait at creation
+    pub fn with_trait(mut self, t: modules::traits::Trait) -> Self {
+        self.gain_trait(t);
+        self
     }
 
-    /// Check if character can still act
-    pub fn can_act(&self) -> bool {
-        self.is_alive() && !self.wounds.is_incapacitated()
+    /// Record a trait and register its modifiers
+    pub fn gain_trait(&mut self, t: modules::traits::Trait) {
+        for modifier in t.modifiers() {
+            self.add_modifier(modifier);
+        }
+        self.traits.add(t);
     }
-}
 
-/// Combat action result
-#[derive(Debug)]
-pub struct CombatResult {
-    pub attacker: String,
-    pub defender: String,
-    pub attack_roll: i32,
-    pub defense_roll: i32,
-    pub hit: bool,
-    pub damage: i32,
-    pub wound_level: Option<WoundLevel>,
-    pub defender_died: bool,
-}
+    /// Split the character into separately storable parts
+    #[allow(deprecated)]
+    pub fn into_parts(self) -> CharacterParts {
+        CharacterParts {
+            name: self.name,
+            attributes: self.attributes,
+            skills: CombatSkills {
+                weapon_skill: SkillLevel::clamped(self.weapon_skill),
+                dodge_skill: SkillLevel::clamped(self.dodge_skill),
+                #[cfg(feature = "ranged")]
+                ranged_skill: self.ranged_skill,
+            },
+            equipment: Equipment {
+                weapon: self.weapon,
+                armor: self.armor,
+                #[cfg(feature = "hit-location")]
+                armor_pieces: self.armor_pieces,
+                #[cfg(feature = "ranged")]
+                ranged_weapon: self.ranged_weapon,
+            },
+            health: Health {
+                wounds: self.wounds,
+                injuries: self.injuries,
+                dying: self.dying,
+                stun: self.stun,
+                fatigue: self.fatigue,
+                blood_loss: self.blood_loss,
+            },
+            #[cfg(feature = "magic")]
+            magic: MagicState { magic: self.magic },
+            modifiers: self.modifiers,
+            traits: self.traits,
+            ancestry: self.ancestry,
+            presentation: self.presentation,
+        }
+    }
 
-/// Execute a combat round between two characters
-pub fn combat_round(
-    attacker: &mut Character,
-    defender: &mut Character,
-    defender_action: DefenseAction,
-) -> CombatResult {
-    let attack_roll = attacker.attack_roll();
-    let defense_roll = match defender_action {
-        DefenseAction::Parry => defender.parry_roll(),
-        DefenseAction::Dodge => defender.dodge_roll(),
-    };
+    /// Reassemble a character from parts
+    ///
+    /// Skills need no clamping, since `SkillLevel` already keeps them in range,
+    /// and a magic user's empathy is re-synced with the attributes so the
+    /// pieces can't drift apart.
+    #[allow(deprecated)]
+    pub fn from_parts(parts: CharacterParts) -> Self {
+        #[cfg(feature = "magic")]
+        let mut magic = parts.magic.magic;
+        #[cfg(feature = "magic")]
+        if let Some(m) = magic.as_mut() {
+            m.empathy = parts.attributes.empathy.get();
+        }
+        Self {
+            name: parts.name,
+            attributes: parts.attributes,
+            weapon_skill: parts.skills.weapon_skill.get(),
+            dodge_skill: parts.skills.dodge_skill.get(),
+            weapon: parts.equipment.weapon,
+            armor: parts.equipment.armor,
+            #[cfg(feature = "hit-location")]
+            armor_pieces: parts.equipment.armor_pieces,
+            wounds: parts.health.wounds,
+            injuries: parts.health.injuries,
+            dying: parts.health.dying,
+            stun: parts.health.stun,
+            fatigue: parts.health.fatigue,
+            blood_loss: parts.health.blood_loss,
+            modifiers: parts.modifiers,
+            traits: parts.traits,
+            ancestry: parts.ancestry,
+            #[cfg(feature = "magic")]
+            magic,
+            #[cfg(feature = "ranged")]
+            ranged_weapon: parts.equipment.ranged_weapon,
+            #[cfg(feature = "ranged")]
+            ranged_skill: parts.skills.ranged_skill,
+            presentation: parts.presentation,
+            observers: ChangeObservers::default(),
+        }
+    }
 
-    let hit = attack_roll > defense_roll;
-    let mut damage = 0;
-    let mut wound_level = None;
-    let mut defender_died = false;
+    /// Register a callback notified of wounds, effects, equipment and skill changes
+    pub fn observe<F>(&mut self, callback: F)
+    where
+        F: Fn(&str, &CharacterChange) + Send + Sync + 'static,
+    {
+        self.observers.0.push(Arc::new(callback));
+    }
 
-    if hit {
-        // Calculate damage: attack_roll - defense_roll + strength_bonus + weapon_damage - armor_protection
-        damage = (attack_roll - defense_roll) + attacker.strength_bonus() + attacker.weapon.damage
-            - defender.armor.protection;
+    /// Report a change to all registered observers
+    pub fn notify(&self, change: CharacterChange) {
+        for callback in &self.observers.0 {
+            callback(&self.name, &change);
+        }
+    }
 
-        damage = damage.max(0); // No negative damage
+    /// Add a wound (with stacking) and notify observers
+    pub fn add_wound(&mut self, level: WoundLevel) {
+        self.wounds.add_wound(level);
+        self.notify(CharacterChange::WoundAdded { level });
+    }
 
-        if damage > 1 {
-            // Determine wound level based on damage vs constitution
-            let con = defender.attributes.constitution;
-            let level = if damage > con * 2 {
-                defender_died = true;
-                WoundLevel::Critical
-            } else if damage > con {
-                WoundLevel::Critical
-            } else if damage > con / 2 {
-                WoundLevel::Severe
-            } else {
-                WoundLevel::Light
-            };
+    /// Add a wound along with its narrative description
+    pub fn inflict_wound(&mut self, description: modules::injuries::WoundDescription) {
+        self.add_wound(description.severity);
+        self.injuries.record(description);
+    }
 
-            defender.wounds.add_wound(level);
-            wound_level = Some(level);
+    /// Heal all wounds, returning any new scars they left
+    pub fn heal_wounds(&mut self) -> Vec<modules::injuries::Scar> {
+        self.wounds = Wounds::new();
+        self.dying = None;
+        self.blood_loss = 0;
+        self.modifiers.remove_source("Blood Loss");
+        self.injuries.heal_all()
+    }
 
-            // Check if stacking caused death
-            if defender.wounds.is_dead() {
-                defender_died = true;
-            }
+    /// Date by which every open wound will have closed (see `healing`)
+    pub fn recovery_date(
+        &self,
+        today: modules::calendar::CampaignDate,
+    ) -> modules::calendar::CampaignDate {
+        self.recovery_date_with_care(today, modules::healing::CareQuality::FieldCamp)
+    }
+
+    /// Recovery date when recuperating under `care`
+    pub fn recovery_date_with_care(
+        &self,
+        today: modules::calendar::CampaignDate,
+        care: modules::healing::CareQuality,
+    ) -> modules::calendar::CampaignDate {
+        let days = self
+            .injuries
+            .active
+            .iter()
+            .map(|w| care.recovery_days(w.days_to_heal()))
+            .max()
+            .unwrap_or(0);
+        today.add_days(days)
+    }
+
+    /// Register a lasting modifier and notify observers
+    pub fn add_modifier(&mut self, modifier: modules::modifiers::Modifier) {
+        let effect = modifier.source.clone();
+        self.modifiers.add(modifier);
+        self.notify(CharacterChange::EffectApplied { effect });
+    }
+
+    /// Record a permanent injury and register its modifiers
+    pub fn suffer_permanent_injury(&mut self, injury: modules::permanent_injury::PermanentInjury) {
+        for modifier in injury.modifiers() {
+            self.add_modifier(modifier);
+        }
+        self.injuries.permanent.push(injury);
+    }
+
+    /// Wear a prosthetic or aid, registering its residual effects as modifiers
+    pub fn fit_prosthetic(&mut self, prosthetic: &modules::prosthetics::Prosthetic) {
+        for modifier in prosthetic.modifiers() {
+            self.add_modifier(modifier);
+        }
+    }
+
+    /// Take off a prosthetic or aid, returning true if it was worn
+    pub fn remove_prosthetic(&mut self, prosthetic: &modules::prosthetics::Prosthetic) -> bool {
+        self.modifiers.remove_source(&prosthetic.name) > 0
+    }
+
+    /// Maintain concentration spells for a round, returning the exhaustion added
+    ///
+    /// A character who can no longer act loses concentration on everything.
+    #[cfg(feature = "magic")]
+    pub fn maintain_concentration(&mut self) -> i32 {
+        let can_act = self.can_act();
+        let Some(magic) = self.magic.as_mut() else {
+            return 0;
+        };
+        if can_act {
+            return magic.maintain_concentration();
+        }
+        for spell in std::mem::take(&mut magic.concentration) {
+            self.modifiers.remove_source(&spell);
+        }
+        magic.suspended.clear();
+        0
+    }
+
+    /// Stop maintaining a spell, removing the modifiers it granted (sourced by spell name)
+    #[cfg(feature = "magic")]
+    pub fn drop_concentration(&mut self, spell_name: &str) -> bool {
+        let Some(magic) = self.magic.as_mut() else {
+            return false;
+        };
+        let dropped = magic.drop_concentration(spell_name);
+        if dropped {
+            self.modifiers.remove_source(spell_name);
+            magic.suspended.retain(|m| m.source != spell_name);
+        }
+        dropped
+    }
+
+    /// Bring this character's magic in line with the anti-magic zone they stand in
+    ///
+    /// A dampened zone adds its penalty as an `ANTI_MAGIC` casting modifier.
+    /// In a null field every casting fails and concentration effects (the
+    /// modifiers sourced by those spells) are suspended until the caster
+    /// leaves it. Call with `None` outside any zone.
+    #[cfg(feature = "magic")]
+    pub fn apply_anti_magic(&mut self, zone: Option<modules::positioning::AntiMagic>) {
+        use modules::magic::ANTI_MAGIC;
+        use modules::positioning::AntiMagic;
+
+        self.modifiers.remove_source(ANTI_MAGIC);
+        if let Some(AntiMagic::Dampened(penalty)) = zone {
+            self.modifiers.add(modules::modifiers::Modifier::new(
+                ANTI_MAGIC,
+                ModifierTarget::Casting,
+                penalty,
+            ));
+        }
+        let Some(magic) = self.magic.as_mut() else {
+            return;
+        };
+        let nulled = zone == Some(AntiMagic::Null);
+        if nulled && !magic.nulled {
+            for spell in &magic.concentration {
+                magic.suspended.extend(self.modifiers.take_source(spell));
+            }
+        } else if !nulled {
+            for modifier in magic.suspended.drain(..) {
+                self.modifiers.add(modifier);
+            }
+        }
+        magic.nulled = nulled;
+    }
+
+    /// Cast a spell as this character
+    ///
+    /// The roll is adjusted by wounds, fatigue, armor interference and
+    /// Casting modifiers, and the casting's exhaustion is moved into the
+    /// character's fatigue so it also weighs on fighting. Hosts tracking a
+    /// carried shield can call `MagicUser::cast_spell_in_armor` directly.
+    /// Components aren't checked; see `cast_spell_with`.
+    #[cfg(feature = "magic")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(caster = %self.name, spell = spell_name))
+    )]
+    pub fn cast_spell(
+        &mut self,
+        spell_name: &str,
+        roll: i32,
+    ) -> Result<modules::magic::CastingResult, modules::magic::MagicError> {
+        let roll = roll + self.casting_adjustment();
+        let armor_type = self.armor.armor_type;
+        let magic = self
+            .magic
+            .as_mut()
+            .ok_or_else(|| modules::magic::MagicError::SpellNotKnown(spell_name.to_string()))?;
+        let result = magic.cast_spell_in_armor(spell_name, armor_type, false, roll);
+        self.absorb_casting_exhaustion();
+        result
+    }
+
+    /// Cast a spell, first checking its components
+    ///
+    /// A gagged caster can't speak, and one who is grappled or has both arms
+    /// disabled can't gesture. Missing words, gestures or materials fail
+    /// before any roll or exhaustion; consumed materials are taken from
+    /// `supplies` only when the casting succeeds.
+    #[cfg(feature = "magic")]
+    pub fn cast_spell_with(
+        &mut self,
+        spell_name: &str,
+        restraints: modules::magic::CasterRestraints,
+        supplies: &mut modules::supplies::Supplies,
+        roll: i32,
+    ) -> Result<modules::magic::CastingResult, modules::magic::MagicError> {
+        let components = self
+            .magic
+            .as_ref()
+            .and_then(|m| m.spells.get(spell_name))
+            .map(|learned| learned.spell.components.clone())
+            .ok_or_else(|| modules::magic::MagicError::SpellNotKnown(spell_name.to_string()))?;
+        let can_gesture = !restraints.grappled && self.has_working_arm();
+        components.check(!restraints.gagged, can_gesture, supplies)?;
+        let result = self.cast_spell(spell_name, roll)?;
+        if result.success {
+            components.consume(supplies);
+        }
+        Ok(result)
+    }
+
+    /// Check at least one arm is free of disabling wounds
+    pub fn has_working_arm(&self) -> bool {
+        use modules::hit_location::HitLocation;
+        [HitLocation::LeftArm, HitLocation::RightArm]
+            .into_iter()
+            .any(|arm| !self.injuries.is_disabled(arm))
+    }
+
+    /// Cast a spell at a target, checking range and line of sight first
+    ///
+    /// Adjusted and recorded like `cast_spell`; an illegal target fails
+    /// before any roll or exhaustion.
+    #[cfg(feature = "magic")]
+    pub fn cast(
+        &mut self,
+        spell_name: &str,
+        target: modules::magic::SpellTarget,
+        info: &modules::magic::TargetInfo,
+        roll: i32,
+    ) -> Result<modules::magic::CastingResult, modules::magic::MagicError> {
+        let learned = self
+            .magic
+            .as_ref()
+            .and_then(|m| m.spells.get(spell_name))
+            .ok_or_else(|| modules::magic::MagicError::SpellNotKnown(spell_name.to_string()))?;
+        modules::magic::validate_target(&learned.spell, &target, info)?;
+        let mut result = self.cast_spell(spell_name, roll)?;
+        result.aimed_at = Some(target);
+        Ok(result)
+    }
+
+    #[cfg(feature = "magic")]
+    fn casting_adjustment(&self) -> i32 {
+        self.felt(self.wounds.casting_penalty())
+            + self.fatigue_penalty()
+            + self.modifiers.total(ModifierTarget::Casting)
+    }
+
+    /// Move any exhaustion recorded on the magic user into `fatigue`
+    #[cfg(feature = "magic")]
+    fn absorb_casting_exhaustion(&mut self) {
+        let mut exhaustion = self.physical_exhaustion();
+        if let Some(magic) = self.magic.as_mut() {
+            exhaustion.absorb_magic(magic);
+            self.fatigue = Fatigue::clamped(exhaustion.points);
+        }
+    }
+
+    fn physical_exhaustion(&self) -> modules::exhaustion::Exhaustion {
+        modules::exhaustion::Exhaustion {
+            points: self.fatigue.get(),
+            stamina_threshold: self.attributes.stamina(),
+        }
+    }
+
+    /// Current fatigue, physical and magical, measured against stamina
+    pub fn exhaustion(&self) -> modules::exhaustion::Exhaustion {
+        let exhaustion = self.physical_exhaustion();
+        #[cfg(feature = "magic")]
+        if let Some(magic) = &self.magic {
+            return exhaustion.combined(magic);
+        }
+        exhaustion
+    }
+
+    /// Penalty to attacks, defenses and casting from fatigue
+    pub fn fatigue_penalty(&self) -> i32 {
+        self.exhaustion().penalty()
+    }
+
+    /// Add exhaustion points from combat or exertion
+    pub fn exert(&mut self, points: i32) {
+        self.fatigue = self.fatigue.saturating_add(points);
+    }
+
+    /// Rest for `rounds`, recovering physical fatigue and then magical
+    pub fn rest(&mut self, rounds: i32) {
+        let mut exhaustion = self.physical_exhaustion();
+        #[cfg(feature = "magic")]
+        if let Some(magic) = self.magic.as_mut() {
+            exhaustion.rest_with_magic(magic, rounds);
+            self.fatigue = Fatigue::clamped(exhaustion.points);
+            return;
+        }
+        exhaustion.rest(rounds);
+        self.fatigue = Fatigue::clamped(exhaustion.points);
+    }
+
+    /// Heal all wounds, rolling on `table` for each located Critical wound
+    ///
+    /// Returns the permanent injuries suffered; scars are recorded as in
+    /// `heal_wounds`.
+    pub fn heal_wounds_with_table(
+        &mut self,
+        table: &modules::permanent_injury::InjuryTable,
+    ) -> Vec<modules::permanent_injury::PermanentInjury> {
+        let lasting: Vec<_> = self
+            .injuries
+            .active
+            .iter()
+            .filter(|w| w.severity == WoundLevel::Critical)
+            .filter_map(|w| w.location)
+            .filter_map(|location| table.roll(location))
+            .collect();
+        self.heal_wounds();
+        for injury in &lasting {
+            self.suffer_permanent_injury(injury.clone());
+        }
+        lasting
+    }
+
+    /// Equip a melee weapon, returning the one previously held
+    pub fn equip_weapon(&mut self, weapon: Weapon) -> Weapon {
+        let item = weapon.name.clone();
+        let previous = std::mem::replace(&mut self.weapon, weapon);
+        self.notify(CharacterChange::ItemEquipped {
+            slot: EquipmentSlot::Weapon,
+            item,
+        });
+        previous
+    }
+
+    /// Equip armor, returning the armor previously worn
+    pub fn equip_armor(&mut self, armor: Armor) -> Armor {
+        let item = armor.name.clone();
+        let previous = std::mem::replace(&mut self.armor, armor);
+        self.notify(CharacterChange::ItemEquipped {
+            slot: EquipmentSlot::Armor,
+            item,
+        });
+        previous
+    }
+
+    /// Equip a ranged weapon, returning the one previously carried
+    #[cfg(feature = "ranged")]
+    pub fn equip_ranged_weapon(
+        &mut self,
+        weapon: modules::ranged_combat::RangedWeapon,
+    ) -> Option<modules::ranged_combat::RangedWeapon> {
+        let item = weapon.name.clone();
+        let previous = self.ranged_weapon.replace(weapon);
+        self.notify(CharacterChange::ItemEquipped {
+            slot: EquipmentSlot::RangedWeapon,
+            item,
+        });
+        previous
+    }
+
+    /// Skill used for melee attacks and parries
+    #[allow(deprecated)]
+    pub fn weapon_skill(&self) -> SkillLevel {
+        SkillLevel::clamped(self.weapon_skill)
+    }
+
+    #[allow(deprecated)]
+    pub fn set_weapon_skill(&mut self, level: SkillLevel) {
+        self.weapon_skill = level.get();
+    }
+
+    /// Skill used for dodging
+    #[allow(deprecated)]
+    pub fn dodge_skill(&self) -> SkillLevel {
+        SkillLevel::clamped(self.dodge_skill)
+    }
+
+    #[allow(deprecated)]
+    pub fn set_dodge_skill(&mut self, level: SkillLevel) {
+        self.dodge_skill = level.get();
+    }
+
+    /// Copy weapon and dodge skill into `skills`, adding them if missing
+    ///
+    /// They are kept under `WEAPON_SKILL` and `DODGE_SKILL`, so points spent
+    /// through the `SkillSet` can be brought back with `sync_skills_from`.
+    #[cfg(feature = "skills")]
+    pub fn sync_skills_to(&self, skills: &mut modules::skills::SkillSet) {
+        use modules::skills::{Skill, SkillCategory, SkillDifficulty, DODGE_SKILL, WEAPON_SKILL};
+        let dexterity = self.attributes.dexterity.get();
+        for (name, level) in [
+            (WEAPON_SKILL, self.weapon_skill()),
+            (DODGE_SKILL, self.dodge_skill()),
+        ] {
+            match skills.get_skill_mut(name) {
+                Some(skill) => skill.level = level.get(),
+                None => skills.add_skill(
+                    Skill::new(name, dexterity, SkillDifficulty::Normal)
+                        .with_category(SkillCategory::Combat)
+                        .with_level(level.get()),
+                ),
+            }
+        }
+    }
+
+    /// Take weapon and dodge skill from `skills` where it has them
+    #[cfg(feature = "skills")]
+    pub fn sync_skills_from(&mut self, skills: &modules::skills::SkillSet) {
+        use modules::skills::{DODGE_SKILL, WEAPON_SKILL};
+        if let Some(skill) = skills.get_skill(WEAPON_SKILL) {
+            self.set_weapon_skill(SkillLevel::clamped(skill.level));
+        }
+        if let Some(skill) = skills.get_skill(DODGE_SKILL) {
+            self.set_dodge_skill(SkillLevel::clamped(skill.level));
+        }
+    }
+
+    /// Raise weapon skill by one level (max 10), returning the new level
+    pub fn raise_weapon_skill(&mut self) -> SkillLevel {
+        if let Some(level) = self.weapon_skill().checked_add(1) {
+            self.set_weapon_skill(level);
+            self.notify(CharacterChange::SkillRaised {
+                skill: "Weapon".to_string(),
+                level,
+            });
+        }
+        self.weapon_skill()
+    }
+
+    /// Raise dodge skill by one level (max 10), returning the new level
+    pub fn raise_dodge_skill(&mut self) -> SkillLevel {
+        if let Some(level) = self.dodge_skill().checked_add(1) {
+            self.set_dodge_skill(level);
+            self.notify(CharacterChange::SkillRaised {
+                skill: "Dodge".to_string(),
+                level,
+            });
+        }
+        self.dodge_skill()
+    }
+
+    /// Raise ranged skill by one level (max 10), returning the new level
+    ///
+    /// A character without ranged training starts at level 1.
+    #[cfg(feature = "ranged")]
+    pub fn raise_ranged_skill(&mut self) -> SkillLevel {
+        let current = self.ranged_skill.unwrap_or(SkillLevel::clamped(0));
+        if let Some(level) = current.checked_add(1) {
+            self.ranged_skill = Some(level);
+            self.notify(CharacterChange::SkillRaised {
+                skill: "Ranged".to_string(),
+                level,
+            });
+        }
+        self.ranged_skill.unwrap_or(current)
+    }
+
+    /// Get strength bonus for damage (STR >= 7 gives +1, STR >= 9 gives +2)
+    pub fn strength_bonus(&self) -> i32 {
+        if self.attributes.strength >= 9 {
+            2
+        } else if self.attributes.strength >= 7 {
+            1
+        } else if self.attributes.strength <= 2 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// Make an attack roll
+    pub fn attack_roll(&self) -> i32 {
+        self.attack_roll_with(d10())
+    }
+
+    /// Attack total for a given d10 result
+    pub fn attack_roll_with(&self, die: i32) -> i32 {
+        self.attack_breakdown(die).total()
+    }
+
+    /// Each term of the attack total for a given d10 result
+    pub fn attack_breakdown(&self, die: i32) -> RollBreakdown {
+        self.breakdown(
+            self.weapon_skill().get(),
+            die,
+            self.wounds.melee_penalty(),
+            &[ModifierTarget::Attack],
+        )
+    }
+
+    /// Make a parry roll
+    pub fn parry_roll(&self) -> i32 {
+        self.parry_roll_with(d10())
+    }
+
+    /// Parry total for a given d10 result
+    pub fn parry_roll_with(&self, die: i32) -> i32 {
+        self.parry_breakdown(die).total()
+    }
+
+    /// Each term of the parry total for a given d10 result
+    pub fn parry_breakdown(&self, die: i32) -> RollBreakdown {
+        self.breakdown(
+            self.weapon_skill().get(),
+            die,
+            self.wounds.melee_penalty(),
+            &[ModifierTarget::Parry],
+        )
+    }
+
+    /// Make a dodge roll
+    pub fn dodge_roll(&self) -> i32 {
+        self.dodge_roll_with(d10())
+    }
+
+    /// Dodge total for a given d10 result
+    pub fn dodge_roll_with(&self, die: i32) -> i32 {
+        self.dodge_breakdown(die).total()
+    }
+
+    /// Each term of the dodge total for a given d10 result
+    pub fn dodge_breakdown(&self, die: i32) -> RollBreakdown {
+        self.breakdown(
+            self.dodge_skill().get(),
+            die,
+            self.wounds.movement_penalty(),
+            &[ModifierTarget::Dodge, ModifierTarget::Movement],
+        )
+    }
+
+    /// Ranged attack total for a given d10 result, before range and cover
+    #[cfg(feature = "ranged")]
+    pub fn ranged_roll_with(&self, die: i32) -> i32 {
+        self.ranged_breakdown(die).total()
+    }
+
+    /// Each term of the ranged attack total for a given d10 result
+    #[cfg(feature = "ranged")]
+    pub fn ranged_breakdown(&self, die: i32) -> RollBreakdown {
+        self.breakdown(
+            self.ranged_skill.map_or(0, SkillLevel::get),
+            die,
+            self.wounds.ranged_penalty(),
+            &[ModifierTarget::Ranged],
+        )
+    }
+
+    /// Split a roll into its terms, counting maneuver modifiers as stance
+    fn breakdown(
+        &self,
+        skill: i32,
+        die: i32,
+        wound_penalty: i32,
+        targets: &[ModifierTarget],
+    ) -> RollBreakdown {
+        let sum = |total: fn(&modules::modifiers::Modifiers, ModifierTarget) -> i32| {
+            targets.iter().fold(0i32, |sum, &target| {
+                sum.saturating_add(total(&self.modifiers, target))
+            })
+        };
+        let modifiers = sum(modules::modifiers::Modifiers::total);
+        let stance_modifier = sum(|modifiers, target| {
+            modifiers.total_from(modules::maneuvers::MANEUVER_SOURCE, target)
+        });
+        RollBreakdown {
+            skill,
+            die,
+            armor_penalty: self.armor.movement_penalty,
+            wound_penalty: self.felt(wound_penalty),
+            stance_modifier,
+            exhaustion_penalty: self.fatigue_penalty(),
+            other: modifiers.saturating_sub(stance_modifier),
+        }
+    }
+
+    /// `wound_penalty`, or nothing for the undead (see `TraitRule::Undead`)
+    fn felt(&self, wound_penalty: i32) -> i32 {
+        if self.traits.is_undead() {
+            0
+        } else {
+            wound_penalty
+        }
+    }
+
+    /// Perception check total for a given d10 result
+    pub fn perception_roll_with(&self, die: i32) -> i32 {
+        (self.attributes.perception.get() + self.felt(self.wounds.perception_penalty()))
+            .saturating_add(die)
+            .saturating_add(self.modifiers.total(ModifierTarget::Perception))
+    }
+
+    /// Size for missile targeting, from ancestry (Medium if none)
+    pub fn size(&self) -> modules::ancestry::TargetSize {
+        self.ancestry
+            .as_ref()
+            .map_or(modules::ancestry::TargetSize::Medium, |a| a.size)
+    }
+
+    /// Body plan for hit locations, from ancestry (Humanoid if none)
+    pub fn body_plan(&self) -> modules::hit_location::BodyPlan {
+        self.ancestry
+            .as_ref()
+            .map(|a| a.body_plan)
+            .unwrap_or_default()
+    }
+
+    /// Body armor plus the pieces guarding `location` against `direction`
+    #[cfg(feature = "hit-location")]
+    pub fn protection_at(
+        &self,
+        location: modules::hit_location::HitLocation,
+        direction: modules::hit_location::AttackDirection,
+    ) -> i32 {
+        self.armor
+            .protection
+            .get()
+            .saturating_add(self.armor_pieces.protection_at(location, direction))
+    }
+
+    /// Damage a blow of `damage` deals at `location`: scaled by the
+    /// location's multiplier, less the armor there, never negative
+    #[cfg(feature = "hit-location")]
+    pub fn damage_at(
+        &self,
+        damage: i32,
+        location: modules::hit_location::HitLocation,
+        direction: modules::hit_location::AttackDirection,
+    ) -> i32 {
+        location
+            .scale(damage)
+            .saturating_sub(self.protection_at(location, direction))
+            .max(0)
+    }
+
+    /// Check if the character's ancestry grants a special sense
+    pub fn has_sense(&self, sense: modules::ancestry::Sense) -> bool {
+        self.ancestry.as_ref().is_some_and(|a| a.has_sense(sense))
+    }
+
+    /// Penalty to sight-based rolls in darkness (none with darkvision)
+    pub fn darkness_penalty(&self) -> i32 {
+        if self.has_sense(modules::ancestry::Sense::Darkvision) {
+            0
+        } else {
+            modules::traits::DARKNESS_PENALTY + self.traits.darkness_penalty()
+        }
+    }
+
+    /// Willpower check total for a given d10 result
+    pub fn willpower_roll_with(&self, die: i32) -> i32 {
+        (self.attributes.willpower.get() + self.traits.willpower_bonus()).saturating_add(die)
+    }
+
+    /// Check if character is alive and able to fight
+    pub fn is_alive(&self) -> bool {
+        !self.wounds.is_dead()
+    }
+
+    /// Check if character can still act
+    pub fn can_act(&self) -> bool {
+        self.is_alive()
+            && !self.wounds.is_incapacitated()
+            && !self.is_knocked_out()
+            && self.blood_loss_level() != modules::blood_loss::BloodLossLevel::Unconscious
+    }
+
+    /// How badly the character has bled, measured against CON
+    pub fn blood_loss_level(&self) -> modules::blood_loss::BloodLossLevel {
+        modules::blood_loss::BloodLossLevel::from_points(
+            self.blood_loss,
+            self.attributes.constitution.get(),
+        )
+    }
+
+    /// Lose blood; each threshold passed adds fatigue and a worse dizziness penalty
+    pub fn lose_blood(&mut self, points: i32) {
+        use modules::blood_loss::BLOOD_LOSS_EXHAUSTION;
+        let before = self.blood_loss_level();
+        self.blood_loss = self.blood_loss.saturating_add(points.max(0));
+        let after = self.blood_loss_level();
+        if after == before {
+            return;
+        }
+        self.exert(BLOOD_LOSS_EXHAUSTION * (after as i32 - before as i32));
+        self.modifiers.remove_source("Blood Loss");
+        for target in [
+            ModifierTarget::Attack,
+            ModifierTarget::Parry,
+            ModifierTarget::Dodge,
+            ModifierTarget::Ranged,
+            ModifierTarget::Casting,
+        ] {
+            self.add_modifier(modules::modifiers::Modifier::new(
+                "Blood Loss",
+                target,
+                after.penalty(),
+            ));
+        }
+    }
+
+    /// A round of bleeding from every open bleeding wound; returns the blood lost
+    ///
+    /// The undead (see `TraitRule::Undead`) have no blood to lose.
+    pub fn bleed(&mut self) -> i32 {
+        if self.traits.is_undead() {
+            return 0;
+        }
+        let bleeding = self.injuries.active.iter().filter(|w| w.bleeding).count() as i32;
+        let lost = bleeding * modules::blood_loss::BLEED_PER_ROUND;
+        self.lose_blood(lost);
+        lost
+    }
+
+    /// Check if stun has reached twice CON, knocking the character out
+    pub fn is_knocked_out(&self) -> bool {
+        self.stun > 0 && self.stun >= self.attributes.constitution.get() * 2
+    }
+
+    /// Shake off `points` of stun
+    pub fn recover_stun(&mut self, points: i32) {
+        self.stun = (self.stun - points.max(0)).max(0);
+    }
+
+    /// Check if character is dying (alive, but making death checks)
+    pub fn is_dying(&self) -> bool {
+        self.dying.is_some()
+    }
+
+    /// Turn a lethal wound into the dying state: one Critical wound remains
+    fn start_dying(&mut self) {
+        self.wounds.critical = self.wounds.critical.min(1);
+        self.dying = Some(modules::dying::DyingState::new());
+    }
+
+    /// Make this round's CON check while dying, with `roll` from `d10()`
+    ///
+    /// Returns `None` if the character is not dying.
+    pub fn death_check(
+        &mut self,
+        roll: i32,
+        ruleset: &modules::ruleset::Ruleset,
+    ) -> Option<modules::dying::DeathCheck> {
+        use modules::dying::DeathCheck;
+
+        let state = self.dying.as_mut()?;
+        state.rounds += 1;
+        if roll <= self.attributes.constitution.get() {
+            return Some(DeathCheck::Holding);
+        }
+        state.failed_checks += 1;
+        if state.failed_checks >= ruleset.death_check_failures {
+            self.dying = None;
+            self.add_wound(WoundLevel::Critical);
+            return Some(DeathCheck::Died);
+        }
+        Some(DeathCheck::Failed {
+            remaining: ruleset.death_check_failures - state.failed_checks - 1,
+        })
+    }
+
+    /// Stop the dying process, returning true if the character was dying
+    pub fn stabilize(&mut self) -> bool {
+        self.dying.take().is_some()
+    }
+
+    /// First aid from an ally: healer skill + `roll` against `FIRST_AID_DIFFICULTY`
+    pub fn first_aid(&mut self, healer_skill: i32, roll: i32) -> bool {
+        self.is_dying()
+            && healer_skill + roll >= modules::dying::FIRST_AID_DIFFICULTY
+            && self.stabilize()
+    }
+
+    /// Stabilize with a successfully cast healing spell
+    #[cfg(feature = "magic")]
+    pub fn stabilize_with_magic(&mut self, casting: &modules::magic::CastingResult) -> bool {
+        casting.success && self.stabilize()
+    }
+}
+
+/// Step-by-step character creation
+///
+/// Defaults to a human with average attributes (5), no skills, a dagger and no armor.
+/// A `background` sets skills, a trait and equipment; later calls override it.
+#[derive(Debug, Clone)]
+pub struct CharacterBuilder {
+    name: String,
+    attributes: Attributes,
+    weapon_skill: i32,
+    dodge_skill: i32,
+    weapon: Weapon,
+    armor: Armor,
+    #[cfg(feature = "ranged")]
+    ranged: Option<(modules::ranged_combat::RangedWeapon, i32)>,
+    #[cfg(feature = "magic")]
+    magic: Option<modules::magic::MagicUser>,
+    ancestry: Option<modules::ancestry::Ancestry>,
+    traits: Vec<modules::traits::Trait>,
+    presentation: Presentation,
+    #[cfg(feature = "skills")]
+    skills: modules::skills::SkillSet,
+}
+
+impl Default for CharacterBuilder {
+    fn default() -> Self {
+        Self {
+            name: "Character".to_string(),
+            attributes: Attributes::new(5, 5, 5, 5, 5, 5, 5, 5, 5),
+            weapon_skill: 0,
+            dodge_skill: 0,
+            weapon: Weapon::dagger(),
+            armor: Armor::none(),
+            #[cfg(feature = "ranged")]
+            ranged: None,
+            #[cfg(feature = "magic")]
+            magic: None,
+            ancestry: None,
+            traits: Vec::new(),
+            presentation: Presentation::default(),
+            #[cfg(feature = "skills")]
+            skills: modules::skills::SkillSet::new(0),
+        }
+    }
+}
+
+impl CharacterBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn attributes(mut self, attributes: Attributes) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    pub fn weapon_skill(mut self, skill: i32) -> Self {
+        self.weapon_skill = skill;
+        self
+    }
+
+    pub fn dodge_skill(mut self, skill: i32) -> Self {
+        self.dodge_skill = skill;
+        self
+    }
+
+    pub fn weapon(mut self, weapon: Weapon) -> Self {
+        self.weapon = weapon;
+        self
+    }
+
+    pub fn armor(mut self, armor: Armor) -> Self {
+        self.armor = armor;
+        self
+    }
+
+    #[cfg(feature = "ranged")]
+    pub fn ranged(mut self, weapon: modules::ranged_combat::RangedWeapon, skill: i32) -> Self {
+        self.ranged = Some((weapon, skill));
+        self
+    }
+
+    #[cfg(feature = "magic")]
+    pub fn magic(mut self, magic: modules::magic::MagicUser) -> Self {
+        self.magic = Some(magic);
+        self
+    }
+
+    pub fn with_trait(mut self, t: modules::traits::Trait) -> Self {
+        self.traits.push(t);
+        self
+    }
+
+    pub fn presentation(mut self, presentation: Presentation) -> Self {
+        self.presentation = presentation;
+        self
+    }
+
+    /// Apply an ancestry's attribute adjustments and traits
+    ///
+    /// Call after `attributes`. Fails if an adjusted attribute leaves 1-10.
+    pub fn ancestry(
+        mut self,
+        ancestry: &modules::ancestry::Ancestry,
+    ) -> Result<Self, modules::ancestry::AncestryError> {
+        self.attributes = ancestry.adjustments.apply(&self.attributes)?;
+        self.traits.extend(ancestry.traits.iter().cloned());
+        self.ancestry = Some(ancestry.clone());
+        Ok(self)
+    }
+
+    /// Non-combat skills, e.g. languages and lore
+    #[cfg(feature = "skills")]
+    pub fn skills(mut self, skills: modules::skills::SkillSet) -> Self {
+        self.skills = skills;
+        self
+    }
+
+    /// Apply a background: combat skills are raised to its levels, its skills,
+    /// trait and equipment are added
+    #[cfg(feature = "skills")]
+    pub fn background(mut self, background: &modules::backgrounds::Background) -> Self {
+        self.weapon_skill = self.weapon_skill.max(background.weapon_skill);
+        self.dodge_skill = self.dodge_skill.max(background.dodge_skill);
+        background.grant_skills(&mut self.skills);
+        if let Some(t) = &background.perk {
+            self.traits.push(t.clone());
+        }
+        if let Some(weapon) = &background.weapon {
+            self.weapon = weapon.clone();
+        }
+        if let Some(armor) = &background.armor {
+            self.armor = armor.clone();
+        }
+        #[cfg(feature = "ranged")]
+        if let (Some(weapon), Some(skill)) = (&background.ranged_weapon, background.ranged_skill) {
+            let skill = self.ranged.as_ref().map_or(skill, |(_, s)| skill.max(*s));
+            self.ranged = Some((weapon.clone(), skill));
+        }
+        self
+    }
+
+    pub fn build(self) -> Character {
+        let mut character = Character::new(
+            &self.name,
+            self.attributes,
+            self.weapon_skill,
+            self.dodge_skill,
+            self.weapon,
+            self.armor,
+        );
+        #[cfg(feature = "magic")]
+        {
+            character.magic = self.magic;
+        }
+        #[cfg(feature = "ranged")]
+        if let Some((weapon, skill)) = self.ranged {
+            character = character.with_ranged(weapon, skill);
+        }
+        for t in self.traits {
+            character.gain_trait(t);
+        }
+        character.ancestry = self.ancestry;
+        character.presentation = self.presentation;
+        character
+    }
+
+    /// Build the character along with its non-combat skills
+    #[cfg(feature = "skills")]
+    pub fn build_with_skills(mut self) -> (Character, modules::skills::SkillSet) {
+        let skills = std::mem::replace(&mut self.skills, modules::skills::SkillSet::new(0));
+        (self.build(), skills)
+    }
+}
+
+/// How a roll's total was reached, term by term
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollBreakdown {
+    pub skill: i32,
+    /// The d10 as rolled
+    pub die: i32,
+    pub armor_penalty: i32,
+    pub wound_penalty: i32,
+    /// From the maneuver in play (see `MANEUVER_SOURCE`)
+    pub stance_modifier: i32,
+    pub exhaustion_penalty: i32,
+    /// Every other modifier: traits, terrain, range and cover, effects
+    pub other: i32,
+}
+
+impl RollBreakdown {
+    /// The roll's total
+    pub fn total(&self) -> i32 {
+        self.skill
+            .saturating_add(self.die)
+            .saturating_add(self.armor_penalty + self.wound_penalty + self.exhaustion_penalty)
+            .saturating_add(self.stance_modifier)
+            .saturating_add(self.other)
+    }
+}
+
+impl fmt::Display for RollBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} skill + {} die", self.skill, self.die)?;
+        let terms = [
+            (self.armor_penalty, "armor"),
+            (self.wound_penalty, "wounds"),
+            (self.stance_modifier, "stance"),
+            (self.exhaustion_penalty, "exhaustion"),
+            (self.other, "other"),
+        ];
+        for (value, label) in terms.into_iter().filter(|(v, _)| *v != 0) {
+            let sign = if value < 0 { '-' } else { '+' };
+            write!(f, " {} {} {}", sign, value.unsigned_abs(), label)?;
+        }
+        write!(f, " = {}", self.total())
+    }
+}
+
+/// Combat action result
+#[derive(Debug)]
+pub struct CombatResult {
+    pub attacker: String,
+    pub defender: String,
+    pub attack_roll: i32,
+    pub defense_roll: i32,
+    pub hit: bool,
+    pub damage: Damage,
+    pub wound_level: Option<WoundLevel>,
+    pub defender_died: bool,
+    /// The defender was left dying instead of dead (see `Ruleset::dying_state`)
+    pub defender_dying: bool,
+    /// Where the blow landed, if rolled (see `combat_round_with_locations`)
+    pub location: Option<modules::hit_location::HitLocation>,
+    /// What a located hit did to the defender's armor pieces
+    #[cfg(feature = "hit-location")]
+    pub armor_events: Vec<modules::hit_location::ArmorEvent>,
+    /// The terms of `attack_roll`
+    pub attack_breakdown: RollBreakdown,
+    /// The terms of `defense_roll`
+    pub defense_breakdown: RollBreakdown,
+}
+
+impl fmt::Display for CombatResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.hit {
+            return write!(
+                f,
+                "{} misses {} ({} vs {})",
+                self.attacker, self.defender, self.attack_roll, self.defense_roll
+            );
+        }
+        write!(f, "{} hits {}", self.attacker, self.defender)?;
+        if let Some(location) = self.location {
+            write!(f, " in the {}", location)?;
+        }
+        write!(
+            f,
+            " for {} damage ({} vs {})",
+            self.damage, self.attack_roll, self.defense_roll
+        )?;
+        if let Some(level) = self.wound_level {
+            write!(f, " - {} wound", level)?;
+        }
+        #[cfg(feature = "hit-location")]
+        for event in &self.armor_events {
+            write!(f, " - {}", event)?;
+        }
+        if self.defender_died {
+            write!(f, " - {} dies", self.defender)?;
+        } else if self.defender_dying {
+            write!(f, " - {} is dying", self.defender)?;
+        }
+        Ok(())
+    }
+}
+
+/// Execute a combat round between two characters
+pub fn combat_round(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+) -> CombatResult {
+    combat_round_with_rules(
+        attacker,
+        defender,
+        defender_action,
+        &modules::ruleset::Ruleset::default(),
+    )
+}
+
+/// Execute a combat round using optional rules from `ruleset`
+///
+/// Dice are rolled as `ruleset.dice` selects.
+pub fn combat_round_with_rules(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+    ruleset: &modules::ruleset::Ruleset,
+) -> CombatResult {
+    let mut dice = ruleset.dice;
+    combat_round_with_dice(attacker, defender, defender_action, ruleset, &mut dice)
+}
+
+/// Execute a combat round taking every d10 from `dice` (attacker's roll first)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(attacker = %attacker.name, defender = %defender.name, defense = ?defender_action)
+    )
+)]
+pub fn combat_round_with_dice(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+    ruleset: &modules::ruleset::Ruleset,
+    dice: &mut dyn modules::dice::Dice,
+) -> CombatResult {
+    exchange(
+        attacker,
+        defender,
+        defender_action,
+        &CombatContext::default(),
+        None,
+        ruleset,
+        dice,
+    )
+}
+
+/// The stances both sides bring to an exchange (see `combat_round_in_context`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CombatContext {
+    pub attacker: modules::maneuvers::CombatStance,
+    pub defender: modules::maneuvers::CombatStance,
+}
+
+impl CombatContext {
+    pub fn new(
+        attacker: modules::maneuvers::CombatStance,
+        defender: modules::maneuvers::CombatStance,
+    ) -> Self {
+        Self { attacker, defender }
+    }
+}
+
+/// Execute a combat round with both sides' stances in play
+///
+/// The attacker's maneuver (and any off-balance recovery) adjusts the attack
+/// and damage, the defender's the parry or dodge; both show up as
+/// `stance_modifier` in the breakdowns. A charge also costs
+/// `CHARGE_EXHAUSTION` under `Ruleset::exhaustion`. Returns `None` if the
+/// attacker's maneuver doesn't allow attacking (Defensive Position, Guard).
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(
+            attacker = %attacker.name,
+            defender = %defender.name,
+            defense = ?defender_action,
+            maneuver = %context.attacker.current_maneuver
+        )
+    )
+)]
+pub fn combat_round_in_context(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+    context: &CombatContext,
+    ruleset: &modules::ruleset::Ruleset,
+    dice: &mut dyn modules::dice::Dice,
+) -> Option<CombatResult> {
+    if !context.attacker.current_maneuver.can_attack() {
+        return None;
+    }
+    Some(exchange(
+        attacker,
+        defender,
+        defender_action,
+        context,
+        None,
+        ruleset,
+        dice,
+    ))
+}
+
+/// One melee exchange; with a `direction`, a hit also rolls where it lands
+fn exchange(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+    context: &CombatContext,
+    direction: Option<modules::hit_location::AttackDirection>,
+    ruleset: &modules::ruleset::Ruleset,
+    dice: &mut dyn modules::dice::Dice,
+) -> CombatResult {
+    let mut attack = attacker.attack_breakdown(dice.d10());
+    let mut defense = match defender_action {
+        DefenseAction::Parry => defender.parry_breakdown(dice.d10()),
+        DefenseAction::Dodge => defender.dodge_breakdown(dice.d10()),
+    };
+    attack.stance_modifier = attack
+        .stance_modifier
+        .saturating_add(context.attacker.total_attack_modifier());
+    defense.stance_modifier = defense
+        .stance_modifier
+        .saturating_add(context.defender.total_defense_modifier());
+    let (attack_roll, defense_roll) = (attack.total(), defense.total());
+    #[cfg(feature = "hit-location")]
+    let direction = direction.or(ruleset
+        .hit_locations
+        .then_some(modules::hit_location::AttackDirection::Front));
+    #[cfg(feature = "hit-location")]
+    let located = direction.filter(|_| attack_roll > defense_roll).map(|d| {
+        let plan = defender.body_plan();
+        (
+            modules::hit_location::HitLocation::determine_with(plan, d, dice),
+            d,
+        )
+    });
+    #[cfg(not(feature = "hit-location"))]
+    let located = direction.and(None);
+
+    let bonus = attacker
+        .strength_bonus()
+        .saturating_add(attacker.weapon.damage.get())
+        .saturating_add(attacker.modifiers.total(ModifierTarget::Damage))
+        .saturating_add(context.attacker.total_damage_modifier());
+    let mut result = resolve_hit(
+        &attacker.name,
+        &attacker.weapon.name,
+        bonus,
+        defender,
+        attack_roll,
+        defense_roll,
+        located,
+        ruleset,
+    );
+    #[cfg(feature = "hit-location")]
+    if let Some((location, direction)) = located.filter(|_| result.hit) {
+        // The pieces in the way take the located blow they just blunted
+        let margin = attack_roll.saturating_sub(defense_roll);
+        let critical_parry = defender_action == DefenseAction::Parry
+            && margin >= modules::hit_location::CRITICAL_PARRY_MARGIN;
+        result.armor_events = defender.armor_pieces.take_hit(
+            location.scale(margin.saturating_add(bonus)),
+            location,
+            direction,
+            critical_parry,
+        );
+    }
+    result.attack_breakdown = attack;
+    result.defense_breakdown = defense;
+    tire(attacker, defender, defender_action, ruleset);
+    if context.attacker.current_maneuver == modules::maneuvers::CombatManeuver::Charge
+        && ruleset.exhaustion
+    {
+        attacker.exert(modules::exhaustion::CHARGE_EXHAUSTION);
+    }
+    result
+}
+
+/// Execute a combat round in context that also rolls where a hit lands
+///
+/// Like `combat_round_in_context`, but a hit takes one more d10 from `dice`
+/// on the defender's body plan table for `direction`. The location's damage
+/// multiplier scales the damage before armor, the wound is recorded at that
+/// location (see `Injuries::damaged_locations`) and the result carries it.
+#[cfg(feature = "hit-location")]
+pub fn combat_round_with_locations(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+    direction: modules::hit_location::AttackDirection,
+    context: &CombatContext,
+    ruleset: &modules::ruleset::Ruleset,
+    dice: &mut dyn modules::dice::Dice,
+) -> Option<CombatResult> {
+    if !context.attacker.current_maneuver.can_attack() {
+        return None;
+    }
+    Some(exchange(
+        attacker,
+        defender,
+        defender_action,
+        context,
+        Some(direction),
+        ruleset,
+        dice,
+    ))
+}
+
+/// Charge both sides of a melee exchange their exertion, under `Ruleset::exhaustion`
+fn tire(
+    attacker: &mut Character,
+    defender: &mut Character,
+    defender_action: DefenseAction,
+    ruleset: &modules::ruleset::Ruleset,
+) {
+    if !ruleset.exhaustion {
+        return;
+    }
+    attacker.exert(modules::exhaustion::ATTACK_EXHAUSTION);
+    if defender_action == DefenseAction::Dodge {
+        defender.exert(modules::exhaustion::DODGE_EXHAUSTION);
+    }
+}
+
+/// Apply an attack's outcome to the defender
+///
+/// On a hit, damage is the margin plus `damage_bonus` less armor, wounding (or,
+/// when sparring, stunning) the defender; wounds are described by `weapon`.
+/// A `located` hit scales the damage by the location's multiplier before
+/// armor and places the wound. Breakdowns are left empty for the caller.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resolve_hit(
+    attacker: &str,
+    weapon: &str,
+    damage_bonus: i32,
+    defender: &mut Character,
+    attack_roll: i32,
+    defense_roll: i32,
+    located: Option<(
+        modules::hit_location::HitLocation,
+        modules::hit_location::AttackDirection,
+    )>,
+    ruleset: &modules::ruleset::Ruleset,
+) -> CombatResult {
+    let location = located.map(|(location, _)| location);
+    let hit = attack_roll > defense_roll;
+    let mut damage = Damage::clamped(0);
+    let mut wound_level = None;
+    let mut defender_died = false;
+    let mut defender_dying = false;
+
+    if hit {
+        // Calculate damage: attack_roll - defense_roll + damage_bonus (scaled by
+        // the location) - armor_protection, never negative and capped however
+        // extreme the rolls
+        let raw = attack_roll
+            .saturating_sub(defense_roll)
+            .saturating_add(damage_bonus);
+        damage = Damage::clamped(match located {
+            #[cfg(feature = "hit-location")]
+            Some((location, direction)) => defender.damage_at(raw, location, direction),
+            _ => raw.saturating_sub(defender.armor.protection.get()),
+        });
+
+        if damage > 1 && ruleset.sparring {
+            // Blunted blows only stun; nobody is wounded or killed
+            defender.stun = defender.stun.saturating_add(damage.get());
+        } else if damage > 1 {
+            // Determine wound level based on damage vs constitution
+            let damage = damage.get();
+            let con = defender.attributes.constitution.get();
+            let level = WoundLevel::from_damage(damage, con).unwrap_or(WoundLevel::Light);
+            defender_died = is_lethal_damage(damage, con);
+
+            let mut wound = modules::injuries::WoundDescription::new(level, weapon);
+            wound.location = location;
+            defender.inflict_wound(wound);
+            if ruleset.blood_loss {
+                defender.lose_blood(modules::blood_loss::blood_cost(level));
+            }
+            wound_level = Some(level);
+
+            // Check if stacking caused death
+            if defender.wounds.is_dead() {
+                defender_died = true;
+            }
+
+            if defender_died && ruleset.dying_state && !defender.traits.is_undead() {
+                if defender.is_dying() {
+                    // Wounded again while dying: no further grace
+                    defender.dying = None;
+                    defender.wounds.critical = defender.wounds.critical.max(2);
+                } else {
+                    defender.start_dying();
+                    defender_died = false;
+                    defender_dying = true;
+                }
+            }
         }
     }
 
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        attack_roll,
+        defense_roll,
+        hit,
+        damage = damage.get(),
+        wound = ?wound_level,
+        defender_died,
+        defender_dying,
+        "hit resolved"
+    );
     CombatResult {
-        attacker: attacker.name.clone(),
+        attacker: attacker.to_string(),
         defender: defender.name.clone(),
         attack_roll,
         defense_roll,
@@ -437,11 +2963,18 @@ pub fn combat_round(
         damage,
         wound_level,
         defender_died,
+        defender_dying,
+        location,
+        #[cfg(feature = "hit-location")]
+        armor_events: Vec::new(),
+        attack_breakdown: RollBreakdown::default(),
+        defense_breakdown: RollBreakdown::default(),
     }
 }
 
 /// Defense action options
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DefenseAction {
     Parry,
     Dodge,
@@ -455,7 +2988,7 @@ mod tests {
     fn test_d10_range() {
         for _ in 0..100 {
             let roll = d10();
-            assert!(roll >= 1 && roll <= 10);
+            assert!((1..=10).contains(&roll));
         }
     }
 
@@ -466,6 +2999,78 @@ mod tests {
         assert_eq!(attrs.stamina(), 8); // (8+7)/2 = 7.5 rounded to 8
     }
 
+    #[test]
+    fn test_bounded_scores_and_overflow_safe_damage() {
+        assert_eq!(Attributes::new(99, 0, 7, 5, 5, 5, 5, 5, 5).strength, 10);
+        assert_eq!(
+            Attributes::try_new(5, 5, 11, 5, 5, 5, 5, 5, 5).unwrap_err(),
+            BoundsError::Attribute(11)
+        );
+        assert_eq!(SkillLevel::try_from(-1), Err(BoundsError::Skill(-1)));
+        let skill = SkillLevel::new(9).unwrap();
+        assert_eq!(skill.checked_add(1), SkillLevel::new(10).ok());
+        assert_eq!(skill.checked_add(2), None);
+        assert_eq!(skill.saturating_add(i32::MAX), 10);
+        assert_eq!(format!("{:>3}", AttributeScore::clamped(7)), "  7");
+
+        // Stacked enchantments and absurd rolls saturate instead of overflowing
+        let mut hero = Character::new(
+            "Hero",
+            Attributes::new(10, 5, 5, 5, 5, 5, 5, 5, 5),
+            10,
+            5,
+            Weapon::war_hammer(),
+            Armor::none(),
+        );
+        for _ in 0..3 {
+            hero.add_modifier(modules::modifiers::Modifier::new(
+                "Enchantment",
+                ModifierTarget::Damage,
+                i32::MAX,
+            ));
+        }
+        assert_eq!(hero.modifiers.total(ModifierTarget::Damage), i32::MAX);
+        let mut foe = hero.clone();
+        let result = resolve_hit(
+            "Hero",
+            "War Hammer",
+            i32::MAX,
+            &mut foe,
+            i32::MAX,
+            i32::MIN,
+            None,
+            &modules::ruleset::Ruleset::default(),
+        );
+        assert_eq!(result.damage, Damage::MAX);
+        assert!(result.defender_died);
+
+        // Weapons, armor and fatigue hold their caps through a full exchange
+        let mut brute = hero.clone();
+        brute.weapon.damage = Damage::clamped(i32::MAX);
+        brute.fatigue = Fatigue::clamped(i32::MAX);
+        let mut wall = foe.clone();
+        wall.armor.protection = Damage::clamped(i32::MAX);
+        let rules = modules::ruleset::Ruleset::new().with_exhaustion(true);
+        let mut dice = modules::dice::ScriptedDice::new([10, 1]);
+        let result = combat_round_with_dice(
+            &mut brute,
+            &mut wall,
+            DefenseAction::Dodge,
+            &rules,
+            &mut dice,
+        );
+        assert!(result.hit);
+        assert_eq!(brute.fatigue, Fatigue::MAX);
+
+        #[cfg(feature = "toml")]
+        {
+            let saved = toml::to_string(&hero.attributes).unwrap();
+            assert!(toml::from_str::<Attributes>(&saved).is_ok());
+            let tampered = saved.replace("strength = 10", "strength = 400");
+            assert!(toml::from_str::<Attributes>(&tampered).is_err());
+        }
+    }
+
     #[test]
     fn test_wound_stacking() {
         let mut wounds = Wounds::new();
@@ -490,4 +3095,1115 @@ mod tests {
         wounds.add_wound(WoundLevel::Critical);
         assert!(wounds.is_dead());
     }
+
+    #[test]
+    fn test_wound_penalties_by_action() {
+        let mut wounds = Wounds::new();
+        wounds.add_wound(WoundLevel::Light);
+        assert_eq!(wounds.melee_penalty(), -1);
+        assert_eq!(wounds.perception_penalty(), 0);
+
+        wounds.add_wound(WoundLevel::Severe);
+        assert_eq!(wounds.movement_penalty(), -3);
+        assert_eq!(wounds.melee_penalty(), -3);
+        assert_eq!(wounds.ranged_penalty(), -4);
+        assert_eq!(wounds.casting_penalty(), -4);
+        assert_eq!(wounds.perception_penalty(), -1);
+
+        #[cfg(feature = "ranged")]
+        {
+            let mut archer = Character::new(
+                "Lyra",
+                Attributes::new(5, 7, 5, 5, 6, 5, 5, 8, 5),
+                4,
+                6,
+                Weapon::dagger(),
+                Armor::none(),
+            )
+            .with_ranged(modules::ranged_combat::RangedWeapon::long_bow(), 7);
+            assert_eq!(archer.ranged_roll_with(5), 12);
+            assert_eq!(archer.perception_roll_with(5), 13);
+
+            archer.add_wound(WoundLevel::Severe);
+            assert_eq!(archer.attack_roll_with(5), 4 + 5 - 2);
+            assert_eq!(archer.ranged_roll_with(5), 12 - 3);
+            assert_eq!(archer.perception_roll_with(5), 13 - 1);
+        }
+    }
+
+    #[test]
+    fn test_weapon_builder_and_specs() {
+        assert_eq!(Weapon::long_sword().damage, 5); // 2×2 + 1 sharp
+        assert_eq!(Weapon::mace().damage, 4); // blunt: no sharp bonus
+
+        let axe = Weapon::builder()
+            .name("Great Axe")
+            .impact(WeaponImpact::Large)
+            .bonus(1)
+            .reach(2)
+            .tags(WeaponTags::TWO_HANDED)
+            .build();
+        assert_eq!(axe.damage, 8);
+        assert_eq!(axe.reach, 2);
+        assert_eq!(axe.tags.to_string(), "Two-Handed");
+        assert_eq!(Weapon::long_sword().tags.to_string(), "Thrusting, Hilted");
+        assert!(!Weapon::club().has_tag(WeaponTags::HILTED));
+
+        const CUDGEL: WeaponSpec = WeaponSpec::new("Cudgel", WeaponImpact::Small).blunt();
+        assert_eq!(CUDGEL.damage(), 2);
+        assert_eq!(Weapon::from(CUDGEL).damage, CUDGEL.damage());
+        assert_eq!(Weapon::dagger().reach, 0);
+    }
+
+    #[test]
+    fn test_armor_builder_and_specs() {
+        let padded = Armor::builder()
+            .name("Padded Jack")
+            .armor_type(ArmorType::HeavyCloth)
+            .build();
+        assert_eq!(padded.protection, 1);
+
+        let warded = Armor::builder()
+            .armor_type(ArmorType::Chain)
+            .protection(4)
+            .movement_penalty(-1)
+            .build();
+        assert_eq!(warded.protection, 4);
+        assert_eq!(Armor::none().protection, 0);
+        assert_eq!(Armor::from(ArmorSpec::CHAIN_MAIL).movement_penalty, -1);
+    }
+
+    #[test]
+    fn test_armor_penalty_derived_from_type() {
+        assert_eq!(Armor::heavy_cloth().movement_penalty, 0);
+        assert_eq!(
+            Armor::of_type("Brigandine", ArmorType::Plate).movement_penalty,
+            -1
+        );
+        assert_eq!(Armor::full_plate().protection, 5);
+        assert_eq!(Armor::full_plate().movement_penalty, -2);
+
+        let elven = Armor::of_type("Elven Mail", ArmorType::Chain).with_penalty(0);
+        assert_eq!(elven.protection, 3);
+        assert_eq!(elven.movement_penalty, 0);
+
+        // The old three-argument form still sets the penalty it is given
+        #[allow(deprecated)]
+        let old = Armor::new("Elven Mail", ArmorType::Chain, 0);
+        assert_eq!(old.protection, elven.protection);
+        assert_eq!(old.movement_penalty, 0);
+    }
+
+    #[test]
+    fn test_combat_result_display() {
+        let mut result = CombatResult {
+            attacker: "Aldric".to_string(),
+            defender: "Grimwald".to_string(),
+            attack_roll: 14,
+            defense_roll: 9,
+            hit: true,
+            damage: Damage::clamped(6),
+            wound_level: Some(WoundLevel::Severe),
+            defender_died: false,
+            defender_dying: false,
+            location: None,
+            #[cfg(feature = "hit-location")]
+            armor_events: Vec::new(),
+            attack_breakdown: RollBreakdown::default(),
+            defense_breakdown: RollBreakdown::default(),
+        };
+        assert_eq!(
+            result.to_string(),
+            "Aldric hits Grimwald for 6 damage (14 vs 9) - Severe wound"
+        );
+
+        result.hit = false;
+        assert_eq!(result.to_string(), "Aldric misses Grimwald (14 vs 9)");
+    }
+
+    #[test]
+    fn test_healed_critical_leaves_permanent_injury() {
+        use modules::hit_location::HitLocation;
+        use modules::injuries::WoundDescription;
+        use modules::permanent_injury::{InjuryTable, PermanentInjury};
+
+        let table = InjuryTable::new().with_entry(
+            &[HitLocation::LeftLeg],
+            1,
+            10,
+            PermanentInjury::new("Limp").with_effect(ModifierTarget::Movement, -2),
+        );
+        let mut fighter = Character::new(
+            "Aldric",
+            Attributes::new(8, 6, 7, 5, 6, 5, 5, 7, 4),
+            7,
+            5,
+            Weapon::long_sword(),
+            Armor::leather(),
+        );
+        fighter.inflict_wound(
+            WoundDescription::new(WoundLevel::Critical, "Axe").at(HitLocation::LeftLeg),
+        );
+        fighter.inflict_wound(
+            WoundDescription::new(WoundLevel::Critical, "Mace").at(HitLocation::Torso),
+        );
+        fighter.wounds = Wounds::new(); // keep the character standing for the test
+
+        let lasting = fighter.heal_wounds_with_table(&table);
+        assert_eq!(lasting.len(), 1);
+        assert_eq!(fighter.injuries.permanent, lasting);
+        assert_eq!(fighter.injuries.scars.len(), 2);
+        assert_eq!(fighter.modifiers.total(ModifierTarget::Movement), -2);
+
+        // A crutch eases the limp at the cost of a busy hand
+        let crutch = modules::prosthetics::Prosthetic::crutch();
+        fighter.fit_prosthetic(&crutch);
+        assert_eq!(fighter.modifiers.total(ModifierTarget::Movement), -1);
+        assert_eq!(fighter.modifiers.total(ModifierTarget::Attack), -1);
+        assert!(fighter.remove_prosthetic(&crutch));
+        assert!(!fighter.remove_prosthetic(&crutch));
+
+        assert_eq!(fighter.modifiers.remove_source("Limp (Left Leg)"), 1);
+    }
+
+    #[test]
+    fn test_deterministic_dice() {
+        use modules::dice::{DiceMode, ScriptedDice};
+        use modules::ruleset::Ruleset;
+
+        let fighter = |name| {
+            Character::new(
+                name,
+                Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+                7,
+                4,
+                Weapon::long_sword(),
+                Armor::leather(),
+            )
+        };
+
+        // Average dice: 7 + 5 attack vs 5 + 5 dodge, every time
+        let rules = Ruleset::new().with_dice(DiceMode::Average);
+        let (mut a, mut b) = (fighter("Aldric"), fighter("Grimwald"));
+        let result = combat_round_with_rules(&mut a, &mut b, DefenseAction::Dodge, &rules);
+        assert_eq!((result.attack_roll, result.defense_roll), (12, 9));
+        assert!(result.hit);
+
+        // A script replays exactly: attacker rolls 1, defender rolls 10
+        let mut dice = ScriptedDice::new([1, 10]);
+        let (mut a, mut b) = (fighter("Aldric"), fighter("Grimwald"));
+        let result = combat_round_with_dice(
+            &mut a,
+            &mut b,
+            DefenseAction::Parry,
+            &Ruleset::default(),
+            &mut dice,
+        );
+        assert_eq!((result.attack_roll, result.defense_roll), (8, 17));
+        assert!(!result.hit);
+        assert_eq!(dice.rolled(), 2);
+    }
+
+    #[test]
+    fn test_stances_change_rolls_and_damage_in_context() {
+        use modules::dice::ScriptedDice;
+        use modules::maneuvers::{CombatManeuver, CombatStance};
+        use modules::ruleset::Ruleset;
+
+        let fighter = |name: &str| {
+            Character::new(
+                name,
+                Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+                6,
+                4,
+                Weapon::long_sword(),
+                Armor::none(),
+            )
+        };
+        let stance = |maneuver| CombatStance {
+            current_maneuver: maneuver,
+            ..CombatStance::new()
+        };
+        let rules = Ruleset::new();
+        let round = |context: &CombatContext| {
+            let (mut attacker, mut defender) = (fighter("Aldric"), fighter("Grimwald"));
+            let mut dice = ScriptedDice::new([8, 4]);
+            combat_round_in_context(
+                &mut attacker,
+                &mut defender,
+                DefenseAction::Parry,
+                context,
+                &rules,
+                &mut dice,
+            )
+        };
+
+        let plain = round(&CombatContext::default()).unwrap();
+        let reckless = round(&CombatContext::new(
+            stance(CombatManeuver::AllOutAttack),
+            stance(CombatManeuver::DefensivePosition),
+        ))
+        .unwrap();
+        assert_eq!(reckless.attack_roll, plain.attack_roll + 2);
+        assert_eq!(reckless.defense_roll, plain.defense_roll + 2);
+        assert_eq!(reckless.attack_breakdown.stance_modifier, 2);
+        assert_eq!(reckless.defense_breakdown.stance_modifier, 2);
+
+        // A charge hits harder: +1 to the attack and +1 damage
+        let charge = round(&CombatContext::new(
+            stance(CombatManeuver::Charge),
+            CombatStance::new(),
+        ))
+        .unwrap();
+        assert!(plain.hit && charge.hit);
+        assert_eq!(charge.damage.get(), plain.damage.get() + 2);
+
+        // Holding a defensive position means no attack at all
+        assert!(round(&CombatContext::new(
+            stance(CombatManeuver::DefensivePosition),
+            CombatStance::new()
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn test_roll_breakdown_explains_totals() {
+        use modules::dice::ScriptedDice;
+        use modules::maneuvers::MANEUVER_SOURCE;
+        use modules::modifiers::Modifier;
+        use modules::ruleset::Ruleset;
+
+        let mut aldric = Character::new(
+            "Aldric",
+            Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+            7,
+            4,
+            Weapon::long_sword(),
+            Armor::chain_mail(),
+        );
+        aldric.wounds.add_wound(WoundLevel::Severe);
+        aldric.exert(20);
+        aldric.add_modifier(Modifier::new(MANEUVER_SOURCE, ModifierTarget::Attack, 2));
+        aldric.add_modifier(Modifier::new("Dim Light", ModifierTarget::Attack, -1));
+
+        let attack = aldric.attack_breakdown(6);
+        assert_eq!((attack.skill, attack.die, attack.armor_penalty), (7, 6, -1));
+        assert_eq!(attack.wound_penalty, aldric.wounds.melee_penalty());
+        assert_eq!(attack.exhaustion_penalty, aldric.fatigue_penalty());
+        assert!(attack.wound_penalty < 0 && attack.exhaustion_penalty < 0);
+        assert_eq!((attack.stance_modifier, attack.other), (2, -1));
+        assert_eq!(attack.total(), aldric.attack_roll_with(6));
+        assert_eq!(
+            attack.to_string(),
+            format!(
+                "7 skill + 6 die - 1 armor - {} wounds + 2 stance - {} exhaustion - 1 other = {}",
+                -attack.wound_penalty,
+                -attack.exhaustion_penalty,
+                attack.total()
+            )
+        );
+
+        // Results carry both breakdowns, matching the totals
+        let mut grimwald = aldric.clone();
+        let result = combat_round_with_dice(
+            &mut aldric,
+            &mut grimwald,
+            DefenseAction::Dodge,
+            &Ruleset::default(),
+            &mut ScriptedDice::new([6, 3]),
+        );
+        assert_eq!(result.attack_breakdown, attack);
+        assert_eq!(result.defense_breakdown.die, 3);
+        assert_eq!(result.defense_breakdown.skill, 4);
+        assert_eq!(result.defense_breakdown.stance_modifier, 0);
+        assert_eq!(result.defense_roll, result.defense_breakdown.total());
+    }
+
+    #[cfg(feature = "hit-location")]
+    #[test]
+    fn test_combat_round_with_locations() {
+        use modules::dice::ScriptedDice;
+        use modules::hit_location::{
+            ArmorEvent, ArmorPiece, AttackDirection, HitLocation, CRITICAL_PARRY_MARGIN,
+        };
+        use modules::maneuvers::{CombatManeuver, CombatStance};
+        use modules::ruleset::Ruleset;
+
+        let fighter = |name: &str| {
+            Character::new(
+                name,
+                Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+                7,
+                4,
+                Weapon::long_sword(),
+                Armor::leather(),
+            )
+        };
+        let rules = Ruleset::default();
+        let located = |rolls: [i32; 3], context: &CombatContext| {
+            let (mut a, mut b) = (fighter("Aldric"), fighter("Grimwald"));
+            let mut dice = ScriptedDice::new(rolls);
+            let result = combat_round_with_locations(
+                &mut a,
+                &mut b,
+                DefenseAction::Dodge,
+                AttackDirection::Front,
+                context,
+                &rules,
+                &mut dice,
+            );
+            (result, b, dice.rolled())
+        };
+        let strike = |rolls: [i32; 3]| {
+            let (result, b, rolled) = located(rolls, &CombatContext::default());
+            (result.unwrap(), b, rolled)
+        };
+        let (mut a, mut b) = (fighter("Aldric"), fighter("Grimwald"));
+        let plain = combat_round_with_dice(
+            &mut a,
+            &mut b,
+            DefenseAction::Dodge,
+            &rules,
+            &mut ScriptedDice::new([6, 1]),
+        );
+        assert_eq!(plain.location, None);
+
+        // A 10 on the front table lands on the head: half again the damage,
+        // before the leather takes its share
+        let leather = Armor::leather().protection.get();
+        let raw = plain.damage.get() + leather;
+        let (head, grimwald, rolled) = strike([6, 1, 10]);
+        assert_eq!(rolled, 3);
+        assert_eq!(head.location, Some(HitLocation::Head));
+        assert_eq!(head.damage, HitLocation::Head.scale(raw) - leather);
+        assert!(head
+            .to_string()
+            .starts_with("Aldric hits Grimwald in the Head for"));
+        assert_eq!(
+            grimwald.injuries.active[0].location,
+            Some(HitLocation::Head)
+        );
+
+        // A 7 finds the left arm, and the defender's wounds there tally up
+        let (arm, grimwald, _) = strike([6, 1, 7]);
+        assert_eq!(arm.location, Some(HitLocation::LeftArm));
+        assert_eq!(arm.damage, HitLocation::LeftArm.scale(raw) - leather);
+        let locations = grimwald.injuries.damaged_locations();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].location, HitLocation::LeftArm);
+        assert_eq!(
+            locations[0].light_wounds + locations[0].severe_wounds + locations[0].critical_wounds,
+            1
+        );
+
+        // A miss never rolls for location
+        let (miss, grimwald, rolled) = strike([1, 10, 10]);
+        assert!(!miss.hit);
+        assert_eq!((miss.location, rolled), (None, 2));
+        assert!(grimwald.injuries.damaged_locations().is_empty());
+
+        // Stances count here as in any other exchange
+        let stance = |maneuver| CombatStance {
+            current_maneuver: maneuver,
+            ..CombatStance::new()
+        };
+        let charge = CombatContext::new(stance(CombatManeuver::Charge), CombatStance::new());
+        let (charged, _, _) = located([6, 1, 10], &charge);
+        assert_eq!(charged.unwrap().attack_roll, head.attack_roll + 1);
+        let holding = CombatContext::new(
+            stance(CombatManeuver::DefensivePosition),
+            CombatStance::new(),
+        );
+        let (held, _, rolled) = located([6, 1, 10], &holding);
+        assert!(held.is_none());
+        assert_eq!(rolled, 0);
+
+        // With the rule on, every exchange rolls a location and a helm
+        // guards only the head
+        let helmed = |rolls: [i32; 3]| {
+            let mut aldric = fighter("Aldric");
+            let mut grimwald = fighter("Grimwald").with_armor_piece(ArmorPiece::great_helm());
+            let rules = Ruleset::new().with_hit_locations(true);
+            let mut dice = ScriptedDice::new(rolls);
+            combat_round_with_dice(
+                &mut aldric,
+                &mut grimwald,
+                DefenseAction::Dodge,
+                &rules,
+                &mut dice,
+            )
+        };
+        let helm = ArmorPiece::great_helm().protection;
+        assert_eq!(helmed([6, 1, 10]).damage, (head.damage.get() - helm).max(0));
+        assert_eq!(helmed([6, 1, 7]).damage, arm.damage);
+
+        // A head blow wears the helmet down, and one that beats a parry by
+        // the critical margin knocks the loose helmet off
+        let parried = |rolls: [i32; 3]| {
+            let mut aldric = fighter("Aldric");
+            let mut grimwald = fighter("Grimwald").with_armor_piece(ArmorPiece::helmet());
+            let rules = Ruleset::new().with_hit_locations(true);
+            let result = combat_round_with_dice(
+                &mut aldric,
+                &mut grimwald,
+                DefenseAction::Parry,
+                &rules,
+                &mut ScriptedDice::new(rolls),
+            );
+            (result, grimwald)
+        };
+        let (dented, grimwald) = parried([6, 4, 10]);
+        assert!(dented.attack_roll - dented.defense_roll < CRITICAL_PARRY_MARGIN);
+        assert!(matches!(
+            dented.armor_events[..],
+            [ArmorEvent::Damaged { .. }]
+        ));
+        assert_eq!(grimwald.armor_pieces.pieces[0].durability, 3);
+        let (knocked, grimwald) = parried([10, 1, 10]);
+        assert!(knocked.attack_roll - knocked.defense_roll >= CRITICAL_PARRY_MARGIN);
+        assert_eq!(
+            knocked.armor_events,
+            vec![ArmorEvent::KnockedOff {
+                piece: "Helmet".to_string()
+            }]
+        );
+        assert!(knocked.to_string().contains("Helmet knocked off"));
+        assert_eq!(
+            grimwald.protection_at(HitLocation::Head, AttackDirection::Front),
+            leather
+        );
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_concentration_ends_effects() {
+        use modules::magic::{
+            MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration,
+            SpellRange,
+        };
+
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Animation, 5);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Stoneskin".to_string(),
+                    branch: MagicBranch::Animation,
+                    difficulty: SpellDifficulty::Easy,
+                    preparation_time: 0,
+                    casting_time: 1,
+                    range: SpellRange::Personal,
+                    duration: SpellDuration::Concentration,
+                    components: SpellComponents::default(),
+                },
+                4,
+            )
+            .unwrap();
+        let mut mage = Character::new(
+            "Elara",
+            Attributes::new(5, 6, 6, 7, 6, 6, 5, 6, 6),
+            4,
+            5,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+        mage.magic = Some(magic);
+
+        assert!(
+            mage.magic
+                .as_mut()
+                .unwrap()
+                .cast_spell("Stoneskin", 5)
+                .unwrap()
+                .success
+        );
+        mage.add_modifier(modules::modifiers::Modifier::new(
+            "Stoneskin",
+            ModifierTarget::Parry,
+            2,
+        ));
+        assert_eq!(mage.maintain_concentration(), 1);
+        assert_eq!(mage.modifiers.total(ModifierTarget::Parry), 2);
+
+        // Knocked down: concentration breaks and the effect ends
+        mage.wounds.add_wound(WoundLevel::Critical);
+        assert_eq!(mage.maintain_concentration(), 0);
+        assert_eq!(mage.modifiers.total(ModifierTarget::Parry), 0);
+        assert!(!mage.drop_concentration("Stoneskin"));
+    }
+
+    #[cfg(feature = "ranged")]
+    #[test]
+    fn test_ranged_loadout() {
+        use modules::ranged_combat::RangedWeapon;
+
+        let mut archer = Character::new(
+            "Lyra",
+            Attributes::new(6, 8, 6, 6, 7, 6, 6, 8, 5),
+            5,
+            6,
+            Weapon::dagger(),
+            Armor::leather(),
+        )
+        .with_ranged(RangedWeapon::long_bow(), 12);
+        assert_eq!(archer.ranged_skill.map(SkillLevel::get), Some(10));
+        assert_eq!(archer.raise_ranged_skill(), 10);
+
+        let mut peasant = Character::new(
+            "Tam",
+            Attributes::new(6, 6, 6, 5, 5, 5, 5, 5, 5),
+            3,
+            3,
+            Weapon::club(),
+            Armor::none(),
+        );
+        assert_eq!(peasant.raise_ranged_skill(), 1);
+
+        #[cfg(feature = "toml")]
+        {
+            archer.ranged_skill = Some(SkillLevel::clamped(7));
+            let saved = toml::to_string(&archer).unwrap();
+            let loaded: Character = toml::from_str(&saved).unwrap();
+            assert_eq!(loaded.ranged_skill.map(SkillLevel::get), Some(7));
+            assert_eq!(loaded.ranged_weapon.unwrap().name, "Long Bow");
+            assert!(!saved.contains("presentation"));
+        }
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_casting_in_armor() {
+        use modules::magic::{
+            MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration,
+            SpellRange,
+        };
+
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Elementalism, 4);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Spark".to_string(),
+                    branch: MagicBranch::Elementalism,
+                    difficulty: SpellDifficulty::Normal,
+                    preparation_time: 0,
+                    casting_time: 1,
+                    range: SpellRange::Touch,
+                    duration: SpellDuration::Instant,
+                    components: SpellComponents::default(),
+                },
+                4,
+            )
+            .unwrap();
+        let mut knight = Character::new(
+            "Sir Caelan",
+            Attributes::new(7, 6, 7, 6, 6, 6, 5, 6, 6),
+            6,
+            4,
+            Weapon::long_sword(),
+            Armor::plate(),
+        );
+        knight.magic = Some(magic);
+
+        // 4 skill + 6 empathy + 5 roll - 3 plate
+        assert_eq!(knight.cast_spell("Spark", 5).unwrap().total, 12);
+        knight.add_modifier(modules::modifiers::Modifier::new(
+            "Ring of Focus",
+            ModifierTarget::Casting,
+            1,
+        ));
+        knight.equip_armor(Armor::leather());
+        assert_eq!(knight.cast_spell("Spark", 5).unwrap().total, 15);
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_anti_magic_dampens_and_nulls_casting() {
+        use modules::magic::{
+            MagicBranch, MagicError, MagicUser, Spell, SpellComponents, SpellDifficulty,
+            SpellDuration, SpellRange,
+        };
+        use modules::positioning::AntiMagic;
+
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Animation, 5);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Stoneskin".to_string(),
+                    branch: MagicBranch::Animation,
+                    difficulty: SpellDifficulty::Easy,
+                    preparation_time: 0,
+                    casting_time: 1,
+                    range: SpellRange::Personal,
+                    duration: SpellDuration::Concentration,
+                    components: SpellComponents::default(),
+                },
+                4,
+            )
+            .unwrap();
+        let mut mage = Character::new(
+            "Elara",
+            Attributes::new(5, 6, 6, 7, 6, 6, 5, 6, 6),
+            4,
+            5,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+        mage.magic = Some(magic);
+        assert_eq!(mage.cast_spell("Stoneskin", 5).unwrap().total, 15);
+        mage.add_modifier(modules::modifiers::Modifier::new(
+            "Stoneskin",
+            ModifierTarget::Parry,
+            2,
+        ));
+
+        mage.apply_anti_magic(Some(AntiMagic::Dampened(-3)));
+        mage.apply_anti_magic(Some(AntiMagic::Dampened(-3)));
+        assert_eq!(mage.cast_spell("Stoneskin", 5).unwrap().total, 12);
+        assert_eq!(mage.modifiers.total(ModifierTarget::Parry), 2);
+
+        // A null field stops casting and suspends the effect until left
+        mage.apply_anti_magic(Some(AntiMagic::Null));
+        assert_eq!(
+            mage.cast_spell("Stoneskin", 5).unwrap_err(),
+            MagicError::Nulled
+        );
+        assert_eq!(mage.modifiers.total(ModifierTarget::Casting), 0);
+        assert_eq!(mage.modifiers.total(ModifierTarget::Parry), 0);
+        mage.apply_anti_magic(None);
+        assert_eq!(mage.modifiers.total(ModifierTarget::Parry), 2);
+        assert_eq!(mage.cast_spell("Stoneskin", 5).unwrap().total, 15);
+
+        // Dropping a suspended effect keeps it from coming back
+        mage.apply_anti_magic(Some(AntiMagic::Null));
+        assert!(mage.drop_concentration("Stoneskin"));
+        mage.apply_anti_magic(None);
+        assert_eq!(mage.modifiers.total(ModifierTarget::Parry), 0);
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_spell_components_need_voice_hands_and_materials() {
+        use modules::hit_location::HitLocation;
+        use modules::injuries::WoundDescription;
+        use modules::magic::{
+            CasterRestraints, MagicBranch, MagicError, MagicUser, Spell, SpellComponents,
+            SpellDifficulty, SpellDuration, SpellRange,
+        };
+        use modules::supplies::Supplies;
+
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Elementalism, 4);
+        let spark = Spell {
+            name: "Spark".to_string(),
+            branch: MagicBranch::Elementalism,
+            difficulty: SpellDifficulty::Normal,
+            preparation_time: 0,
+            casting_time: 1,
+            range: SpellRange::Touch,
+            duration: SpellDuration::Instant,
+            components: SpellComponents::default(),
+        }
+        .with_components(
+            SpellComponents::new()
+                .verbal()
+                .somatic()
+                .with_material("Sulfur", 1)
+                .with_focus("Amber Rod"),
+        );
+        magic.learn_spell(spark, 4).unwrap();
+        let mut mage = Character::new(
+            "Ysolde",
+            Attributes::new(5, 6, 5, 6, 6, 6, 5, 6, 6),
+            3,
+            4,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+        mage.magic = Some(magic);
+        let free = CasterRestraints::default();
+        let mut pouch = Supplies::new(0, 0).with_material("Sulfur", 1);
+
+        assert_eq!(
+            mage.cast_spell_with("Spark", free, &mut pouch, 5)
+                .unwrap_err(),
+            MagicError::MissingMaterial {
+                name: "Amber Rod".to_string(),
+                needed: 1,
+                available: 0,
+            }
+        );
+        pouch = pouch.with_material("Amber Rod", 1);
+        let gagged = CasterRestraints {
+            gagged: true,
+            ..free
+        };
+        let grappled = CasterRestraints {
+            grappled: true,
+            ..free
+        };
+        assert_eq!(
+            mage.cast_spell_with("Spark", gagged, &mut pouch, 5)
+                .unwrap_err(),
+            MagicError::CannotSpeak
+        );
+        assert_eq!(
+            mage.cast_spell_with("Spark", grappled, &mut pouch, 5)
+                .unwrap_err(),
+            MagicError::CannotGesture
+        );
+        assert_eq!(mage.fatigue, 0);
+
+        // A failed casting keeps the sulfur; a success burns it, not the rod
+        assert!(
+            !mage
+                .cast_spell_with("Spark", free, &mut pouch, -2)
+                .unwrap()
+                .success
+        );
+        assert_eq!(pouch.material_count("Sulfur"), 1);
+        assert!(
+            mage.cast_spell_with("Spark", free, &mut pouch, 5)
+                .unwrap()
+                .success
+        );
+        assert_eq!(pouch.material_count("Sulfur"), 0);
+        assert_eq!(pouch.material_count("Amber Rod"), 1);
+
+        // One working arm is enough to gesture; none is not
+        pouch = pouch.with_material("Sulfur", 2);
+        for arm in [HitLocation::LeftArm, HitLocation::RightArm] {
+            mage.injuries
+                .record(WoundDescription::new(WoundLevel::Severe, "Axe").at(arm));
+            let gesture = mage
+                .cast_spell_with("Spark", free, &mut pouch, 5)
+                .map(|_| ());
+            let expected = match arm {
+                HitLocation::LeftArm => Ok(()),
+                _ => Err(MagicError::CannotGesture),
+            };
+            assert_eq!(gesture, expected);
+        }
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_casting_shares_wounds_and_fatigue() {
+        use modules::magic::{
+            MagicBranch, MagicError, MagicUser, Spell, SpellComponents, SpellDifficulty,
+            SpellDuration, SpellRange, SpellTarget, TargetInfo,
+        };
+
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Elementalism, 5);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Fire Bolt".to_string(),
+                    branch: MagicBranch::Elementalism,
+                    difficulty: SpellDifficulty::Hard,
+                    preparation_time: 0,
+                    casting_time: 1,
+                    range: SpellRange::Short(30),
+                    duration: SpellDuration::Instant,
+                    components: SpellComponents::default(),
+                },
+                4,
+            )
+            .unwrap();
+        // Stamina (STR + CON) / 2 = 4
+        let mut mage = Character::new_with_magic(
+            "Elara",
+            Attributes::new(4, 6, 4, 7, 6, 4, 5, 6, 6),
+            3,
+            4,
+            Weapon::dagger(),
+            Armor::none(),
+            magic,
+        );
+        let target = SpellTarget::Character("Grimwald".to_string());
+
+        assert_eq!(
+            mage.cast("Fire Bolt", target.clone(), &TargetInfo::new(40, true), 5)
+                .unwrap_err(),
+            MagicError::OutOfRange {
+                range: 30,
+                distance: 40
+            }
+        );
+        assert_eq!(mage.fatigue, 0);
+
+        // Hard casting costs 3 of 6 empathy, i.e. 2 of 4 stamina
+        let result = mage
+            .cast("Fire Bolt", target.clone(), &TargetInfo::new(10, true), 5)
+            .unwrap();
+        assert_eq!(result.total, 15);
+        assert_eq!(result.aimed_at, Some(target.clone()));
+        assert_eq!(mage.fatigue, 2);
+        assert_eq!(mage.magic.as_ref().unwrap().exhaustion_points, 0);
+
+        // More exertion pushes fatigue past stamina: -1 to fighting and casting
+        mage.exert(3);
+        let fresh_attack = mage.weapon_skill().get() + 5;
+        assert_eq!(mage.attack_roll_with(5), fresh_attack - 1);
+        mage.add_wound(WoundLevel::Light);
+        let result = mage
+            .cast("Fire Bolt", target, &TargetInfo::new(10, true), 5)
+            .unwrap();
+        assert_eq!(result.total, 13);
+
+        mage.rest(100);
+        assert_eq!(mage.fatigue, 0);
+        assert_eq!(mage.fatigue_penalty(), 0);
+    }
+
+    #[test]
+    fn test_sparring_stuns_instead_of_wounding() {
+        use modules::ruleset::Ruleset;
+
+        let rules = Ruleset::new().with_sparring(true);
+        let mut attacker = Character::new(
+            "Aldric",
+            Attributes::new(10, 10, 10, 5, 5, 5, 5, 5, 5),
+            10,
+            5,
+            Weapon::two_handed_sword(),
+            Armor::none(),
+        );
+        let mut defender = Character::new(
+            "Grimwald",
+            Attributes::new(5, 5, 3, 5, 5, 5, 5, 5, 5),
+            0,
+            0,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+
+        for _ in 0..50 {
+            let result =
+                combat_round_with_rules(&mut attacker, &mut defender, DefenseAction::Dodge, &rules);
+            assert!(result.wound_level.is_none() && !result.defender_died);
+            if defender.is_knocked_out() {
+                break;
+            }
+        }
+        assert!(defender.is_knocked_out());
+        assert!(!defender.can_act() && defender.is_alive());
+        assert_eq!(defender.wounds.light + defender.wounds.severe, 0);
+
+        defender.recover_stun(defender.stun);
+        assert!(defender.can_act());
+    }
+
+    #[test]
+    fn test_dying_state_and_death_checks() {
+        use modules::dying::DeathCheck;
+        use modules::ruleset::Ruleset;
+
+        let rules = Ruleset::new().with_dying_state(true);
+        let mut attacker = Character::new(
+            "Aldric",
+            Attributes::new(10, 10, 10, 5, 5, 5, 5, 5, 5),
+            10,
+            5,
+            Weapon::two_handed_sword(),
+            Armor::none(),
+        );
+        let mut defender = Character::new(
+            "Grimwald",
+            Attributes::new(5, 5, 3, 5, 5, 5, 5, 5, 5),
+            0,
+            0,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+        defender.wounds.add_wound(WoundLevel::Critical);
+
+        // Any hit now is lethal under the standard rules
+        let mut result =
+            combat_round_with_rules(&mut attacker, &mut defender, DefenseAction::Dodge, &rules);
+        while !result.hit || result.wound_level.is_none() {
+            result =
+                combat_round_with_rules(&mut attacker, &mut defender, DefenseAction::Dodge, &rules);
+        }
+        assert!(result.defender_dying);
+        assert!(!result.defender_died);
+        assert!(defender.is_alive() && defender.is_dying());
+        assert!(!defender.can_act());
+
+        assert_eq!(defender.death_check(2, &rules), Some(DeathCheck::Holding));
+        assert_eq!(
+            defender.death_check(9, &rules),
+            Some(DeathCheck::Failed { remaining: 1 })
+        );
+        assert!(!defender.first_aid(3, 5)); // 8 < 12
+        assert!(defender.first_aid(6, 7));
+        assert!(!defender.is_dying());
+        assert_eq!(defender.death_check(9, &rules), None);
+
+        defender.dying = Some(modules::dying::DyingState::new());
+        let rules = rules.with_death_check_failures(1);
+        assert_eq!(defender.death_check(10, &rules), Some(DeathCheck::Died));
+        assert!(!defender.is_alive());
+    }
+
+    #[test]
+    fn test_observers_notified_of_changes() {
+        use std::sync::Mutex;
+
+        let mut fighter = Character::new(
+            "Aldric",
+            Attributes::new(8, 6, 7, 5, 6, 5, 5, 7, 4),
+            9,
+            5,
+            Weapon::long_sword(),
+            Armor::leather(),
+        );
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        fighter.observe(move |name, change| {
+            sink.lock()
+                .unwrap()
+                .push((name.to_string(), change.clone()));
+        });
+
+        fighter.add_wound(WoundLevel::Light);
+        let old = fighter.equip_weapon(Weapon::dagger());
+        assert_eq!(old.name, "Long Sword");
+        assert_eq!(fighter.raise_weapon_skill(), 10);
+        assert_eq!(fighter.raise_weapon_skill(), 10); // Capped, no event
+        fighter.notify(CharacterChange::EffectApplied {
+            effect: "Blessed".to_string(),
+        });
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 4);
+        assert!(seen.iter().all(|(name, _)| name == "Aldric"));
+        assert_eq!(
+            seen[0].1,
+            CharacterChange::WoundAdded {
+                level: WoundLevel::Light
+            }
+        );
+        assert_eq!(
+            seen[1].1,
+            CharacterChange::ItemEquipped {
+                slot: EquipmentSlot::Weapon,
+                item: "Dagger".to_string()
+            }
+        );
+        assert_eq!(
+            seen[2].1,
+            CharacterChange::SkillRaised {
+                skill: "Weapon".to_string(),
+                level: SkillLevel::clamped(10)
+            }
+        );
+        assert_eq!(fighter.wounds.light, 1);
+    }
+
+    #[cfg(all(feature = "magic", feature = "ranged"))]
+    #[test]
+    fn test_into_parts_round_trip() {
+        let mut mage = Character::new_with_magic(
+            "Morgana",
+            Attributes::new(5, 6, 6, 8, 7, 7, 6, 7, 9),
+            4,
+            6,
+            Weapon::dagger(),
+            Armor::none(),
+            modules::magic::MagicUser::new(9),
+        );
+        mage.ranged_skill = Some(SkillLevel::clamped(5));
+        mage.add_wound(WoundLevel::Severe);
+
+        let mut parts = mage.into_parts();
+        assert_eq!(parts.skills.weapon_skill, 4);
+        assert_eq!(parts.skills.ranged_skill.map(SkillLevel::get), Some(5));
+        assert_eq!(parts.health.wounds.severe, 1);
+        assert!(parts.health.can_act());
+        assert_eq!(parts.equipment.weapon.name, "Dagger");
+
+        // Edit parts independently, then reassemble
+        parts.attributes.empathy = AttributeScore::clamped(10);
+        parts.skills.dodge_skill = SkillLevel::clamped(15);
+        let rebuilt = Character::from_parts(parts);
+
+        assert_eq!(rebuilt.name, "Morgana");
+        assert_eq!(rebuilt.dodge_skill(), 10); // Clamped
+        assert_eq!(rebuilt.magic.as_ref().unwrap().empathy, 10); // Re-synced
+        assert_eq!(rebuilt.wounds.severe, 1);
+    }
+
+    #[test]
+    fn test_presentation_is_carried_but_not_read() {
+        let look = Presentation::new()
+            .with_portrait("portraits/sigrun.png")
+            .with_token_color("#b03a2e")
+            .with_faction("Iron Company")
+            .with_notes("Owes the party a favor");
+        let plain = Character::builder().name("Sigrun").build();
+        let dressed = Character::builder()
+            .name("Sigrun")
+            .presentation(look.clone())
+            .build();
+        assert!(plain.presentation.is_empty());
+        assert_eq!(dressed.attack_roll_with(5), plain.attack_roll_with(5));
+        assert_eq!(dressed.clone().into_parts().presentation, look);
+
+        #[cfg(feature = "toml")]
+        {
+            let saved = toml::to_string(&dressed).unwrap();
+            let loaded: Character = toml::from_str(&saved).unwrap();
+            assert_eq!(loaded.presentation, look);
+        }
+    }
+
+    #[test]
+    fn test_inflict_and_heal_wounds() {
+        use modules::hit_location::HitLocation;
+        use modules::injuries::WoundDescription;
+
+        let mut fighter = Character::new(
+            "Aldric",
+            Attributes::new(8, 6, 7, 5, 6, 5, 5, 7, 4),
+            7,
+            5,
+            Weapon::long_sword(),
+            Armor::leather(),
+        );
+        fighter.inflict_wound(
+            WoundDescription::new(WoundLevel::Severe, "Spear").at(HitLocation::Torso),
+        );
+        fighter.inflict_wound(WoundDescription::new(WoundLevel::Light, "Dagger"));
+        assert_eq!(fighter.wounds.severe, 1);
+        assert_eq!(fighter.injuries.active.len(), 2);
+        assert!(fighter.injuries.is_bleeding());
+
+        let scars = fighter.heal_wounds();
+        assert_eq!(scars.len(), 1);
+        assert_eq!(scars[0].location, Some(HitLocation::Torso));
+        assert_eq!(fighter.wounds.severe, 0);
+        assert_eq!(fighter.injuries.scars.len(), 1);
+    }
+
+    #[test]
+    fn test_rule_tables_match_the_draft_tables() {
+        let protection: Vec<i32> = ArmorType::ALL.iter().map(|t| t.protection()).collect();
+        assert_eq!(protection, [1, 2, 3, 4, 5]);
+        assert_eq!(Armor::chain_mail().protection, 3);
+        let damage: Vec<i32> = WeaponImpact::ALL.iter().map(|i| i.base_damage()).collect();
+        assert_eq!(damage, [2, 4, 6, 8]);
+        assert_eq!(Weapon::long_sword().damage, 5);
+
+        // CON 6: Light 2-3, Severe 4-6, Critical 7-12, dead from 13
+        let levels: Vec<_> = (1..=13).map(|d| WoundLevel::from_damage(d, 6)).collect();
+        assert_eq!(levels[0], None);
+        assert_eq!(levels[2], Some(WoundLevel::Light));
+        assert_eq!(levels[3], Some(WoundLevel::Severe));
+        assert_eq!(levels[6], Some(WoundLevel::Critical));
+        assert!(!is_lethal_damage(12, 6));
+        assert!(is_lethal_damage(13, 6));
+    }
 }
@@ -0,0 +1,594 @@
+//! NPC decision making: threat assessment and utility AI
+//!
+//! `evaluate_threat` estimates how much harm one character can do to another
+//! each round from weapon damage, skill, wounds and remaining magic.
+//! `choose_target` weighs that against how close each foe is to going down,
+//! so NPCs focus on dangerous, nearly-finished targets such as a wounded mage.
+//!
+//! An `Archetype` is a data-driven list of `ActionScorer`s. Each scores one
+//! option (attack, defend, cast, flee, heal an ally) as a base value plus
+//! weighted `Consideration`s read from the `AiContext`; the best option wins.
+//! A barbarian flies into a berserk rage (see `berserk`) once badly hurt.
+//! With the `serde` feature archetypes can be shipped as data in content packs.
+//!
+//! `recommend_defense` picks Parry or Dodge by comparing the expected damage
+//! of an incoming attack against each.
+
+use crate::modules::berserk::is_raging;
+use crate::modules::modifiers::ModifierTarget;
+use crate::modules::odds::expected_damage;
+use crate::{Character, DefenseAction};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weight of a caster's best spell level relative to expected weapon damage
+pub const MAGIC_THREAT_WEIGHT: f32 = 0.5;
+
+/// Light-wound equivalents that add up to death (2 Critical wounds)
+const WOUNDS_TO_DEATH: f32 = 24.0;
+
+/// Expected harm `target` can do to `observer` per round (0.0 if it can't act)
+pub fn evaluate_threat(observer: &Character, target: &Character) -> f32 {
+    if !target.can_act() {
+        return 0.0;
+    }
+
+    let protection = observer.armor.protection.get();
+    let melee = target
+        .weapon
+        .damage
+        .get()
+        .saturating_add(target.strength_bonus())
+        .saturating_add(target.modifiers.total(ModifierTarget::Damage))
+        .saturating_sub(protection);
+    #[cfg(feature = "ranged")]
+    let (ranged, ranged_skill) = (
+        target
+            .ranged_weapon
+            .as_ref()
+            .map_or(0, |w| w.damage.saturating_sub(protection)),
+        target.ranged_skill.unwrap_or(target.weapon_skill()),
+    );
+    #[cfg(not(feature = "ranged"))]
+    let (ranged, ranged_skill) = (0, target.weapon_skill());
+    let damage = melee.max(ranged).max(1) as f32;
+
+    let skill = target.weapon_skill().max(ranged_skill).get()
+        + target.modifiers.total(ModifierTarget::Attack)
+        + target.wounds.melee_penalty();
+    let defense = observer.weapon_skill().max(observer.dodge_skill()).get();
+    let hit_chance = ((skill - defense + 10) as f32 / 20.0).clamp(0.05, 0.95);
+
+    hit_chance * damage + magic_threat(target)
+}
+
+/// Best spell level scaled by how much casting capacity remains
+#[cfg(feature = "magic")]
+fn magic_threat(target: &Character) -> f32 {
+    let Some(magic) = &target.magic else {
+        return 0.0;
+    };
+    let best = magic
+        .spells
+        .values()
+        .map(|s| s.skill_level)
+        .max()
+        .unwrap_or(0);
+    let capacity = (magic.empathy * 3).max(1);
+    let reserve = (capacity - magic.exhaustion_points).max(0) as f32 / capacity as f32;
+    best as f32 * reserve * MAGIC_THREAT_WEIGHT
+}
+
+#[cfg(not(feature = "magic"))]
+fn magic_threat(_target: &Character) -> f32 {
+    0.0
+}
+
+/// Fraction of casting capacity left (0.0 for non-casters)
+#[cfg(feature = "magic")]
+fn magic_reserve(character: &Character) -> f32 {
+    character.magic.as_ref().map_or(0.0, |m| {
+        let capacity = (m.empathy * 3).max(1);
+        ((capacity - m.exhaustion_points).max(0) as f32 / capacity as f32).min(1.0)
+    })
+}
+
+#[cfg(not(feature = "magic"))]
+fn magic_reserve(_character: &Character) -> f32 {
+    0.0
+}
+
+/// How close a character is to dropping: 0.0 unhurt, 1.0 dead
+pub fn vulnerability(target: &Character) -> f32 {
+    let w = &target.wounds;
+    ((w.light + w.severe * 4 + w.critical * 12) as f32 / WOUNDS_TO_DEATH).clamp(0.0, 1.0)
+}
+
+/// Threat weighted toward foes that are close to going down
+pub fn target_priority(observer: &Character, target: &Character) -> f32 {
+    evaluate_threat(observer, target) * (1.0 + vulnerability(target))
+}
+
+/// Index of the highest-priority target that can still act
+pub fn choose_target<'a>(
+    observer: &Character,
+    candidates: impl IntoIterator<Item = &'a Character>,
+) -> Option<usize> {
+    candidates
+        .into_iter()
+        .enumerate()
+        .filter(|(_, c)| c.can_act())
+        .map(|(i, c)| (i, target_priority(observer, c)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+/// The defense that lets the least expected damage through (Parry on ties)
+pub fn recommend_defense(defender: &Character, attacker: &Character) -> DefenseAction {
+    let parry = expected_damage(attacker, defender, DefenseAction::Parry);
+    let dodge = expected_damage(attacker, defender, DefenseAction::Dodge);
+    if dodge < parry {
+        DefenseAction::Dodge
+    } else {
+        DefenseAction::Parry
+    }
+}
+
+/// Threat at which the `TopThreat` consideration saturates
+const THREAT_SCALE: f32 = 10.0;
+
+/// Options an NPC can choose between
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AiAction {
+    Attack,
+    Defend,
+    Cast,
+    Flee,
+    HealAlly,
+    /// Go berserk (see `berserk::Berserk`)
+    Rage,
+}
+
+impl fmt::Display for AiAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AiAction::Attack => write!(f, "Attack"),
+            AiAction::Defend => write!(f, "Defend"),
+            AiAction::Cast => write!(f, "Cast"),
+            AiAction::Flee => write!(f, "Flee"),
+            AiAction::HealAlly => write!(f, "Heal ally"),
+            AiAction::Rage => write!(f, "Rage"),
+        }
+    }
+}
+
+/// What an NPC knows when deciding: itself, its side and the other side
+#[derive(Debug, Clone, Copy)]
+pub struct AiContext<'a> {
+    pub me: &'a Character,
+    pub allies: &'a [Character],
+    pub enemies: &'a [Character],
+}
+
+impl<'a> AiContext<'a> {
+    pub fn new(me: &'a Character, allies: &'a [Character], enemies: &'a [Character]) -> Self {
+        Self {
+            me,
+            allies,
+            enemies,
+        }
+    }
+
+    fn most_wounded_ally(&self) -> Option<usize> {
+        self.allies
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.is_alive() && vulnerability(a) > 0.0)
+            .max_by(|a, b| vulnerability(a.1).total_cmp(&vulnerability(b.1)))
+            .map(|(i, _)| i)
+    }
+
+    #[cfg(feature = "magic")]
+    fn can_cast(&self) -> bool {
+        self.me.magic.as_ref().is_some_and(|m| !m.spells.is_empty())
+    }
+
+    #[cfg(not(feature = "magic"))]
+    fn can_cast(&self) -> bool {
+        false
+    }
+}
+
+/// A game-state reading scaled to 0.0..=1.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Consideration {
+    /// How hurt this NPC is (`vulnerability`)
+    OwnWounds,
+    /// The worst wounds among living allies
+    AllyWounds,
+    /// Threat of the most dangerous enemy
+    TopThreat,
+    /// Share of active combatants on the enemy side
+    Outnumbered,
+    /// Fraction of magical capacity left (0.0 for non-casters)
+    MagicReserve,
+}
+
+impl Consideration {
+    pub fn evaluate(&self, ctx: &AiContext) -> f32 {
+        match self {
+            Consideration::OwnWounds => vulnerability(ctx.me),
+            Consideration::AllyWounds => ctx
+                .most_wounded_ally()
+                .map_or(0.0, |i| vulnerability(&ctx.allies[i])),
+            Consideration::TopThreat => {
+                let top = ctx
+                    .enemies
+                    .iter()
+                    .map(|e| evaluate_threat(ctx.me, e))
+                    .fold(0.0, f32::max);
+                (top / THREAT_SCALE).min(1.0)
+            }
+            Consideration::Outnumbered => {
+                let foes = ctx.enemies.iter().filter(|e| e.can_act()).count();
+                let friends = 1 + ctx.allies.iter().filter(|a| a.can_act()).count();
+                foes as f32 / (foes + friends) as f32
+            }
+            Consideration::MagicReserve => magic_reserve(ctx.me),
+        }
+    }
+}
+
+/// Scores one action: `base` plus the sum of each consideration times its weight
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ActionScorer {
+    pub action: AiAction,
+    pub base: f32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub considerations: Vec<(Consideration, f32)>,
+}
+
+impl ActionScorer {
+    pub fn new(action: AiAction, base: f32) -> Self {
+        Self {
+            action,
+            base,
+            considerations: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, consideration: Consideration, weight: f32) -> Self {
+        self.considerations.push((consideration, weight));
+        self
+    }
+
+    pub fn score(&self, ctx: &AiContext) -> f32 {
+        self.base
+            + self
+                .considerations
+                .iter()
+                .map(|(c, weight)| c.evaluate(ctx) * weight)
+                .sum::<f32>()
+    }
+}
+
+/// The option an NPC settled on, with the index of its target if any
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AiDecision {
+    pub action: AiAction,
+    /// Index into `enemies` (Attack, Cast) or `allies` (HealAlly)
+    pub target: Option<usize>,
+    pub score: f32,
+}
+
+/// A named NPC behavior: the actions it considers and how it weighs them
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Archetype {
+    pub name: String,
+    pub scorers: Vec<ActionScorer>,
+}
+
+impl Archetype {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            scorers: Vec::new(),
+        }
+    }
+
+    pub fn with_scorer(mut self, scorer: ActionScorer) -> Self {
+        self.scorers.push(scorer);
+        self
+    }
+
+    /// Fights to the end and only falls back to defending when badly hurt
+    pub fn brute() -> Self {
+        Self::new("Brute")
+            .with_scorer(ActionScorer::new(AiAction::Attack, 1.0))
+            .with_scorer(
+                ActionScorer::new(AiAction::Defend, 0.2).with(Consideration::OwnWounds, 1.0),
+            )
+    }
+
+    /// Attacks while winning, runs once hurt or outnumbered
+    pub fn coward() -> Self {
+        Self::new("Coward")
+            .with_scorer(ActionScorer::new(AiAction::Attack, 0.6))
+            .with_scorer(
+                ActionScorer::new(AiAction::Flee, 0.0)
+                    .with(Consideration::OwnWounds, 1.5)
+                    .with(Consideration::Outnumbered, 0.8),
+            )
+    }
+
+    /// Prefers spells while power lasts, keeps out of melee when threatened
+    pub fn caster() -> Self {
+        Self::new("Caster")
+            .with_scorer(ActionScorer::new(AiAction::Attack, 0.3))
+            .with_scorer(
+                ActionScorer::new(AiAction::Cast, 0.2).with(Consideration::MagicReserve, 1.0),
+            )
+            .with_scorer(
+                ActionScorer::new(AiAction::Defend, 0.0)
+                    .with(Consideration::TopThreat, 0.5)
+                    .with(Consideration::OwnWounds, 0.5),
+            )
+    }
+
+    /// Tends to wounded allies, otherwise fights
+    pub fn healer() -> Self {
+        Self::new("Healer")
+            .with_scorer(ActionScorer::new(AiAction::Attack, 0.4))
+            .with_scorer(
+                ActionScorer::new(AiAction::HealAlly, 0.1).with(Consideration::AllyWounds, 2.0),
+            )
+    }
+
+    /// Attacks relentlessly and goes berserk once seriously wounded
+    pub fn barbarian() -> Self {
+        Self::new("Barbarian")
+            .with_scorer(ActionScorer::new(AiAction::Attack, 1.0))
+            .with_scorer(ActionScorer::new(AiAction::Rage, 0.5).with(Consideration::OwnWounds, 4.0))
+    }
+
+    /// Pick the highest-scoring action that is possible right now
+    ///
+    /// Cast needs known spells, HealAlly a wounded living ally, Rage a
+    /// character not already raging, and Attack and Cast an enemy that can
+    /// still act.
+    pub fn decide(&self, ctx: &AiContext) -> Option<AiDecision> {
+        let enemy = choose_target(ctx.me, ctx.enemies);
+        let ally = ctx.most_wounded_ally();
+        self.scorers
+            .iter()
+            .filter_map(|scorer| {
+                let target = match scorer.action {
+                    AiAction::Attack => Some(enemy?),
+                    AiAction::Cast if ctx.can_cast() => Some(enemy?),
+                    AiAction::Cast => return None,
+                    AiAction::HealAlly => Some(ally?),
+                    AiAction::Rage if is_raging(ctx.me) => return None,
+                    AiAction::Defend | AiAction::Flee | AiAction::Rage => None,
+                };
+                Some(AiDecision {
+                    action: scorer.action,
+                    target,
+                    score: scorer.score(ctx),
+                })
+            })
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::berserk::{Berserk, RAGE_ROUNDS};
+    #[cfg(feature = "magic")]
+    use crate::modules::magic::{
+        MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration, SpellRange,
+    };
+    use crate::{Armor, Attributes, SkillLevel, Weapon, WoundLevel};
+
+    fn character(name: &str, skill: i32, weapon: Weapon) -> Character {
+        Character::new(
+            name,
+            Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 7),
+            skill,
+            4,
+            weapon,
+            Armor::leather(),
+        )
+    }
+
+    #[cfg(feature = "magic")]
+    fn mage() -> Character {
+        let mut magic = MagicUser::new(7);
+        magic.add_lore(MagicBranch::Elementalism, 6);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Fire Bolt".to_string(),
+                    branch: MagicBranch::Elementalism,
+                    difficulty: SpellDifficulty::Normal,
+                    preparation_time: 0,
+                    casting_time: 1,
+                    range: SpellRange::Short(30),
+                    duration: SpellDuration::Instant,
+                    components: SpellComponents::default(),
+                },
+                6,
+            )
+            .unwrap();
+        let mut mage = character("Mage", 3, Weapon::dagger());
+        mage.magic = Some(magic);
+        mage
+    }
+
+    #[test]
+    #[cfg(feature = "magic")]
+    fn test_threat_grows_with_weapon_skill_and_magic() {
+        let me = character("Aldric", 6, Weapon::long_sword());
+        let novice = character("Novice", 2, Weapon::dagger());
+        let veteran = character("Veteran", 8, Weapon::two_handed_sword());
+
+        assert!(evaluate_threat(&me, &veteran) > evaluate_threat(&me, &novice));
+        assert!(evaluate_threat(&me, &mage()) > evaluate_threat(&me, &novice));
+
+        let mut spent = mage();
+        spent.magic.as_mut().unwrap().exhaustion_points = 21;
+        assert_eq!(
+            evaluate_threat(&me, &spent),
+            evaluate_threat(&me, &character("Mage", 3, Weapon::dagger()))
+        );
+    }
+
+    #[test]
+    fn test_downed_targets_are_no_threat() {
+        let me = character("Aldric", 6, Weapon::long_sword());
+        let mut foe = character("Orc", 6, Weapon::long_sword());
+        foe.wounds.add_wound(WoundLevel::Critical);
+        assert_eq!(evaluate_threat(&me, &foe), 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "magic")]
+    fn test_choose_target_finishes_the_wounded_mage() {
+        let me = character("Aldric", 6, Weapon::long_sword());
+        let soldier = character("Soldier", 6, Weapon::long_sword());
+        let mut wounded_mage = mage();
+        wounded_mage.wounds.add_wound(WoundLevel::Severe);
+        wounded_mage.wounds.add_wound(WoundLevel::Severe);
+
+        let foes = [soldier, wounded_mage];
+        assert_eq!(choose_target(&me, &foes), Some(1));
+        assert_eq!(choose_target(&me, std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_archetypes_weigh_options() {
+        let mut goblin = character("Goblin", 4, Weapon::dagger());
+        let foes = [character("Aldric", 7, Weapon::long_sword())];
+
+        let fresh = AiContext::new(&goblin, &[], &foes);
+        assert_eq!(
+            Archetype::coward().decide(&fresh).unwrap(),
+            AiDecision {
+                action: AiAction::Attack,
+                target: Some(0),
+                score: 0.6
+            }
+        );
+
+        goblin.wounds.add_wound(WoundLevel::Severe);
+        goblin.wounds.add_wound(WoundLevel::Light);
+        let hurt = AiContext::new(&goblin, &[], &foes);
+        assert_eq!(
+            Archetype::coward().decide(&hurt).unwrap().action,
+            AiAction::Flee
+        );
+        assert_eq!(
+            Archetype::brute().decide(&hurt).unwrap().action,
+            AiAction::Attack
+        );
+        assert!(Archetype::brute()
+            .decide(&AiContext::new(&goblin, &[], &[]))
+            .is_some_and(|d| d.action == AiAction::Defend));
+    }
+
+    #[test]
+    fn test_barbarian_rages_once_when_hurt() {
+        let mut barbarian = character("Korg", 7, Weapon::two_handed_sword());
+        let foes = [character("Aldric", 7, Weapon::long_sword())];
+        let decide = |me: &Character| {
+            Archetype::barbarian()
+                .decide(&AiContext::new(me, &[], &foes))
+                .unwrap()
+                .action
+        };
+
+        barbarian.wounds.add_wound(WoundLevel::Light);
+        assert_eq!(decide(&barbarian), AiAction::Attack);
+        barbarian.wounds.add_wound(WoundLevel::Severe);
+        assert_eq!(decide(&barbarian), AiAction::Rage);
+
+        Berserk::enter(&mut barbarian, RAGE_ROUNDS);
+        assert_eq!(decide(&barbarian), AiAction::Attack);
+    }
+
+    #[test]
+    #[cfg(feature = "magic")]
+    fn test_casting_and_healing_need_the_means() {
+        let foes = [character("Aldric", 7, Weapon::long_sword())];
+        let mut ally = character("Orc", 5, Weapon::long_sword());
+
+        let shaman = mage();
+        let ctx = AiContext::new(&shaman, std::slice::from_ref(&ally), &foes);
+        assert_eq!(
+            Archetype::caster().decide(&ctx).unwrap().action,
+            AiAction::Cast
+        );
+        assert_eq!(
+            Archetype::healer().decide(&ctx).unwrap().action,
+            AiAction::Attack
+        );
+
+        ally.wounds.add_wound(WoundLevel::Severe);
+        ally.wounds.add_wound(WoundLevel::Severe);
+        let ctx = AiContext::new(&shaman, std::slice::from_ref(&ally), &foes);
+        let decision = Archetype::healer().decide(&ctx).unwrap();
+        assert_eq!(decision.action, AiAction::HealAlly);
+        assert_eq!(decision.target, Some(0));
+
+        let warrior = character("Grunt", 5, Weapon::long_sword());
+        let ctx = AiContext::new(&warrior, &[], &foes);
+        assert_eq!(
+            Archetype::caster().decide(&ctx).unwrap().action,
+            AiAction::Attack
+        );
+    }
+
+    #[test]
+    fn test_recommend_defense_accounts_for_armor_and_modifiers() {
+        let attacker = character("Aldric", 7, Weapon::long_sword());
+        let mut defender = character("Grimwald", 6, Weapon::long_sword());
+        // Parry 6 beats dodge 4
+        assert_eq!(
+            recommend_defense(&defender, &attacker),
+            DefenseAction::Parry
+        );
+        assert!(
+            expected_damage(&attacker, &defender, DefenseAction::Parry)
+                < expected_damage(&attacker, &defender, DefenseAction::Dodge)
+        );
+
+        // A nimble fighter whose sword arm is hurt should dodge
+        defender.set_dodge_skill(SkillLevel::clamped(8));
+        defender.add_modifier(crate::modules::modifiers::Modifier::new(
+            "Broken Wrist",
+            ModifierTarget::Parry,
+            -3,
+        ));
+        assert_eq!(
+            recommend_defense(&defender, &attacker),
+            DefenseAction::Dodge
+        );
+
+        // Heavy footwork penalties push back toward parrying
+        defender.add_modifier(crate::modules::modifiers::Modifier::new(
+            "Mud",
+            ModifierTarget::Movement,
+            -6,
+        ));
+        assert_eq!(
+            recommend_defense(&defender, &attacker),
+            DefenseAction::Parry
+        );
+    }
+}
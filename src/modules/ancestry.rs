@@ -0,0 +1,305 @@
+//! Species and ancestry
+//!
+//! An `Ancestry` adjusts attributes, sets the character's size (how easy they
+//! are to hit with missiles) and body plan (where blows land), grants special
+//! senses and traits. It is applied
+//! with `CharacterBuilder::ancestry`, which refuses adjustments that would push
+//! an attribute outside 1-10. Presets ship as `data/ancestries.toml`.
+
+use crate::modules::hit_location::BodyPlan;
+use crate::modules::modifiers::ModifierTarget;
+use crate::modules::traits::Trait;
+use crate::{AttributeScore, Attributes};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Lowest attribute score a character can have
+pub const ATTRIBUTE_MIN: i32 = AttributeScore::MIN;
+/// Highest attribute score a character can have
+pub const ATTRIBUTE_MAX: i32 = AttributeScore::MAX;
+
+/// Target size modifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TargetSize {
+    Tiny,     // -4 (rat, small bird)
+    Small,    // -2 (cat, small dog)
+    Medium,   // 0 (human)
+    Large,    // +2 (horse, car)
+    Huge,     // +4 (dragon, tank)
+    Gigantic, // +6 (whale, building)
+}
+
+impl TargetSize {
+    pub fn modifier(&self) -> i32 {
+        match self {
+            TargetSize::Tiny => -4,
+            TargetSize::Small => -2,
+            TargetSize::Medium => 0,
+            TargetSize::Large => 2,
+            TargetSize::Huge => 4,
+            TargetSize::Gigantic => 6,
+        }
+    }
+}
+
+/// A sense beyond ordinary human sight and hearing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Sense {
+    /// Sees without light; ignores darkness penalties
+    Darkvision,
+    /// Tracks and notices by smell
+    Scent,
+    /// Hears what others miss
+    KeenHearing,
+}
+
+impl fmt::Display for Sense {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Sense::Darkvision => write!(f, "Darkvision"),
+            Sense::Scent => write!(f, "Scent"),
+            Sense::KeenHearing => write!(f, "Keen Hearing"),
+        }
+    }
+}
+
+/// Amounts added to each attribute
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AttributeAdjustments {
+    pub strength: i32,
+    pub dexterity: i32,
+    pub constitution: i32,
+    pub reason: i32,
+    pub intuition: i32,
+    pub willpower: i32,
+    pub charisma: i32,
+    pub perception: i32,
+    pub empathy: i32,
+}
+
+impl AttributeAdjustments {
+    /// Adjusted attributes, or the first attribute pushed outside 1-10
+    pub fn apply(&self, attributes: &Attributes) -> Result<Attributes, AncestryError> {
+        let check = |name: &'static str, base: AttributeScore, adjustment: i32| {
+            base.checked_add(adjustment)
+                .ok_or(AncestryError::AttributeCap {
+                    attribute: name,
+                    value: base.get().saturating_add(adjustment),
+                })
+        };
+        let a = attributes;
+        Ok(Attributes {
+            strength: check("Strength", a.strength, self.strength)?,
+            dexterity: check("Dexterity", a.dexterity, self.dexterity)?,
+            constitution: check("Constitution", a.constitution, self.constitution)?,
+            reason: check("Reason", a.reason, self.reason)?,
+            intuition: check("Intuition", a.intuition, self.intuition)?,
+            willpower: check("Willpower", a.willpower, self.willpower)?,
+            charisma: check("Charisma", a.charisma, self.charisma)?,
+            perception: check("Perception", a.perception, self.perception)?,
+            empathy: check("Empathy", a.empathy, self.empathy)?,
+        })
+    }
+}
+
+/// A playable species such as Dwarf or Halfling
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ancestry {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adjustments: AttributeAdjustments,
+    #[cfg_attr(feature = "serde", serde(default = "default_size"))]
+    pub size: TargetSize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub body_plan: BodyPlan,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub senses: Vec<Sense>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub traits: Vec<Trait>,
+}
+
+impl Ancestry {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            adjustments: AttributeAdjustments::default(),
+            size: TargetSize::Medium,
+            body_plan: BodyPlan::Humanoid,
+            senses: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    pub fn with_adjustments(mut self, adjustments: AttributeAdjustments) -> Self {
+        self.adjustments = adjustments;
+        self
+    }
+
+    pub fn with_size(mut self, size: TargetSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_body_plan(mut self, body_plan: BodyPlan) -> Self {
+        self.body_plan = body_plan;
+        self
+    }
+
+    pub fn with_sense(mut self, sense: Sense) -> Self {
+        self.senses.push(sense);
+        self
+    }
+
+    pub fn with_trait(mut self, t: Trait) -> Self {
+        self.traits.push(t);
+        self
+    }
+
+    pub fn human() -> Self {
+        Self::new("Human")
+    }
+
+    /// Quick and sharp-eyed, but slight
+    pub fn elf() -> Self {
+        Self::new("Elf")
+            .with_adjustments(AttributeAdjustments {
+                dexterity: 1,
+                perception: 1,
+                constitution: -1,
+                ..Default::default()
+            })
+            .with_sense(Sense::KeenHearing)
+    }
+
+    /// Stout, stubborn and at home underground
+    pub fn dwarf() -> Self {
+        Self::new("Dwarf")
+            .with_adjustments(AttributeAdjustments {
+                constitution: 2,
+                dexterity: -1,
+                charisma: -1,
+                ..Default::default()
+            })
+            .with_sense(Sense::Darkvision)
+            .with_trait(Trait::iron_will())
+    }
+
+    /// Small, nimble and hard to hit with arrows
+    pub fn halfling() -> Self {
+        Self::new("Halfling")
+            .with_adjustments(AttributeAdjustments {
+                strength: -2,
+                dexterity: 2,
+                ..Default::default()
+            })
+            .with_size(TargetSize::Small)
+            .with_trait(Trait::new("Lucky").with_effect(ModifierTarget::Dodge, 1))
+    }
+
+    /// The built-in ancestries
+    pub fn standard() -> Vec<Self> {
+        vec![Self::human(), Self::elf(), Self::dwarf(), Self::halfling()]
+    }
+
+    /// Parse a list of `[[ancestry]]` tables from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Vec<Self>, AncestryError> {
+        #[derive(Deserialize)]
+        struct File {
+            ancestry: Vec<Ancestry>,
+        }
+        toml::from_str::<File>(source)
+            .map(|file| file.ancestry)
+            .map_err(|e| AncestryError::Parse(e.to_string()))
+    }
+
+    pub fn has_sense(&self, sense: Sense) -> bool {
+        self.senses.contains(&sense)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_size() -> TargetSize {
+    TargetSize::Medium
+}
+
+/// Errors from applying or loading ancestries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AncestryError {
+    /// An adjustment would take an attribute outside 1-10
+    AttributeCap {
+        attribute: &'static str,
+        value: i32,
+    },
+    Parse(String),
+}
+
+impl fmt::Display for AncestryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AncestryError::AttributeCap { attribute, value } => write!(
+                f,
+                "{} would be {}; attributes must be {}-{}",
+                attribute, value, ATTRIBUTE_MIN, ATTRIBUTE_MAX
+            ),
+            AncestryError::Parse(e) => write!(f, "Invalid ancestry data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AncestryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::traits::DARKNESS_PENALTY;
+    use crate::Character;
+
+    #[test]
+    fn test_builder_applies_ancestry_within_caps() {
+        let dwarf = Character::builder()
+            .attributes(Attributes::new(7, 5, 8, 5, 5, 6, 4, 6, 3))
+            .ancestry(&Ancestry::dwarf())
+            .unwrap()
+            .build();
+        assert_eq!(dwarf.attributes.constitution, 10);
+        assert_eq!(dwarf.attributes.dexterity, 4);
+        assert!(dwarf.traits.has("Iron Will"));
+        assert!(dwarf.has_sense(Sense::Darkvision));
+        assert_eq!(dwarf.darkness_penalty(), 0);
+        assert_eq!(dwarf.size(), TargetSize::Medium);
+
+        let halfling = Character::builder()
+            .ancestry(&Ancestry::halfling())
+            .unwrap()
+            .build();
+        assert_eq!(halfling.size(), TargetSize::Small);
+        assert_eq!(halfling.darkness_penalty(), DARKNESS_PENALTY);
+
+        let err = Character::builder()
+            .attributes(Attributes::new(7, 5, 9, 5, 5, 6, 4, 6, 3))
+            .ancestry(&Ancestry::dwarf())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            AncestryError::AttributeCap {
+                attribute: "Constitution",
+                value: 11
+            }
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_data_file_matches_presets() {
+        let loaded = Ancestry::from_toml(include_str!("../../data/ancestries.toml")).unwrap();
+        assert_eq!(loaded, Ancestry::standard());
+    }
+}
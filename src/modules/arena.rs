@@ -0,0 +1,245 @@
+//! Seeded procedural arenas
+//!
+//! An `ArenaGenerator` builds a random `Battlefield` from a seed: its size,
+//! patches of rough ground, brush and water, cover objects (crates and low
+//! walls give cover, boulders block), hazards and lighting. The same seed and
+//! settings always give the same field. `ArenaDifficulty` sets how cluttered,
+//! dangerous and dark it gets. The first and last columns are always left
+//! open as deployment zones, and boulders never touch, so the field can't be
+//! walled off.
+
+use crate::modules::positioning::{
+    Battlefield, CellTerrain, Cover, GridKind, GridPos, Hazard, Lighting,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Path cost added by generated hazards
+pub const ARENA_HAZARD_COST: i32 = 4;
+
+/// How hostile a generated arena is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArenaDifficulty {
+    /// Plenty of cover, open ground, daylight
+    Easy,
+    #[default]
+    Normal,
+    /// Bad footing, little cover, hazards and poor light
+    Hard,
+}
+
+impl ArenaDifficulty {
+    /// Patches of rough ground, brush or water
+    pub fn terrain_patches(&self) -> u32 {
+        match self {
+            ArenaDifficulty::Easy => 2,
+            ArenaDifficulty::Normal => 4,
+            ArenaDifficulty::Hard => 6,
+        }
+    }
+
+    pub fn cover_objects(&self) -> u32 {
+        match self {
+            ArenaDifficulty::Easy => 8,
+            ArenaDifficulty::Normal => 5,
+            ArenaDifficulty::Hard => 3,
+        }
+    }
+
+    pub fn hazards(&self) -> u32 {
+        match self {
+            ArenaDifficulty::Easy => 0,
+            ArenaDifficulty::Normal => 1,
+            ArenaDifficulty::Hard => 3,
+        }
+    }
+
+    /// Chance in 10 that the arena is dim (dark on a second such roll)
+    pub fn gloom_chance(&self) -> u32 {
+        match self {
+            ArenaDifficulty::Easy => 1,
+            ArenaDifficulty::Normal => 3,
+            ArenaDifficulty::Hard => 6,
+        }
+    }
+}
+
+impl fmt::Display for ArenaDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArenaDifficulty::Easy => write!(f, "Easy"),
+            ArenaDifficulty::Normal => write!(f, "Normal"),
+            ArenaDifficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+/// Settings for generating battlefields from a seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArenaGenerator {
+    pub seed: u64,
+    pub kind: GridKind,
+    /// Smallest width or height, in cells
+    pub min_size: i32,
+    /// Largest width or height, in cells
+    pub max_size: i32,
+    pub difficulty: ArenaDifficulty,
+}
+
+impl ArenaGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            kind: GridKind::Square,
+            min_size: 8,
+            max_size: 16,
+            difficulty: ArenaDifficulty::Normal,
+        }
+    }
+
+    pub fn with_grid(mut self, kind: GridKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sides between `min` and `max` cells (at least 5)
+    pub fn with_size(mut self, min: i32, max: i32) -> Self {
+        self.min_size = min.max(5);
+        self.max_size = max.max(self.min_size);
+        self
+    }
+
+    pub fn with_difficulty(mut self, difficulty: ArenaDifficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// Build the battlefield for this seed
+    pub fn generate(&self) -> Battlefield {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let width = rng.gen_range(self.min_size..=self.max_size);
+        let height = rng.gen_range(self.min_size..=self.max_size);
+        let mut field = Battlefield::new(self.kind, width, height);
+        let difficulty = self.difficulty;
+
+        // Keep the deployment columns clear
+        let random_cell =
+            |rng: &mut StdRng| GridPos::new(rng.gen_range(1..width - 1), rng.gen_range(0..height));
+
+        for _ in 0..difficulty.terrain_patches() {
+            let center = random_cell(&mut rng);
+            let radius = rng.gen_range(1..=2);
+            let terrain = match rng.gen_range(0..10) {
+                0..=4 => CellTerrain::Rough,
+                5..=7 => CellTerrain::Difficult,
+                _ => CellTerrain::Water,
+            };
+            let patch: Vec<GridPos> = field
+                .cells()
+                .filter(|&pos| pos.x > 0 && pos.x < width - 1)
+                .filter(|&pos| field.distance(center, pos) <= radius)
+                .collect();
+            for pos in patch {
+                field.set_terrain(pos, terrain);
+            }
+        }
+
+        for _ in 0..difficulty.cover_objects() {
+            let pos = random_cell(&mut rng);
+            match rng.gen_range(0..3) {
+                0 => field = field.with_cover(pos, Cover::Partial),
+                1 => field = field.with_cover(pos, Cover::ThreeQuarters),
+                _ => {
+                    let crowded = self
+                        .kind
+                        .neighbors(pos)
+                        .into_iter()
+                        .any(|n| field.terrain_at(n) == CellTerrain::Wall);
+                    if !crowded {
+                        field.set_terrain(pos, CellTerrain::Wall);
+                    }
+                }
+            }
+        }
+
+        for _ in 0..difficulty.hazards() {
+            let pos = random_cell(&mut rng);
+            if field.terrain_at(pos) == CellTerrain::Wall {
+                continue;
+            }
+            let name = ["Fire", "Caltrops", "Pit Edge"][rng.gen_range(0..3)];
+            field = field.with_hazard(pos, Hazard::new(name, ARENA_HAZARD_COST));
+        }
+
+        let chance = difficulty.gloom_chance();
+        field.lighting = if rng.gen_range(0..10) >= chance {
+            Lighting::Bright
+        } else if rng.gen_range(0..10) >= chance {
+            Lighting::Dim
+        } else {
+            Lighting::Dark
+        };
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(field: &Battlefield) -> Vec<(GridPos, CellTerrain, Cover, bool)> {
+        field
+            .cells()
+            .map(|pos| {
+                (
+                    pos,
+                    field.terrain_at(pos),
+                    field.cover_at(pos),
+                    field.hazard_at(pos).is_some(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seed_reproduces_arena_and_difficulty_shapes_it() {
+        let generator = ArenaGenerator::new(42).with_size(10, 14);
+        let field = generator.generate();
+        assert!((10..=14).contains(&field.width) && (10..=14).contains(&field.height));
+        assert_eq!(layout(&field), layout(&generator.generate()));
+        assert_eq!(field.lighting, generator.generate().lighting);
+        assert_ne!(layout(&field), layout(&ArenaGenerator::new(43).generate()));
+
+        let hazards = |difficulty| {
+            (0..20)
+                .map(|seed| {
+                    let field = ArenaGenerator::new(seed)
+                        .with_difficulty(difficulty)
+                        .generate();
+                    layout(&field).iter().filter(|cell| cell.3).count()
+                })
+                .sum::<usize>()
+        };
+        assert_eq!(hazards(ArenaDifficulty::Easy), 0);
+        assert!(hazards(ArenaDifficulty::Hard) > hazards(ArenaDifficulty::Normal));
+
+        let hex = ArenaGenerator::new(7)
+            .with_grid(GridKind::Hex)
+            .with_difficulty(ArenaDifficulty::Hard)
+            .generate();
+        assert_eq!(hex.kind, GridKind::Hex);
+        for y in 0..hex.height {
+            for x in [0, hex.width - 1] {
+                let pos = GridPos::new(x, y);
+                assert_eq!(hex.move_cost(pos), Some(1));
+                assert!(hex.hazard_at(pos).is_none());
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Character backgrounds and professions
+//!
+//! A `Background` is a starting package: combat skill levels, a bundle of
+//! general skills, a trait and equipment. Pick one with
+//! `CharacterBuilder::background`. The built-in presets are also shipped as
+//! `data/backgrounds.toml`, which is the place to start for your own.
+
+use crate::modules::modifiers::ModifierTarget;
+#[cfg(feature = "ranged")]
+use crate::modules::ranged_combat::RangedWeapon;
+use crate::modules::skills::{Skill, SkillDifficulty, SkillSet};
+use crate::modules::traits::Trait;
+use crate::{Armor, Weapon};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A general skill granted at a starting level
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SkillGrant {
+    pub name: String,
+    pub level: i32,
+}
+
+/// A starting package such as Soldier or Street Thief
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Background {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weapon_skill: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dodge_skill: i32,
+    #[cfg(feature = "ranged")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ranged_skill: Option<i32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub skills: Vec<SkillGrant>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub perk: Option<Trait>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weapon: Option<Weapon>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub armor: Option<Armor>,
+    #[cfg(feature = "ranged")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ranged_weapon: Option<RangedWeapon>,
+}
+
+impl Background {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            weapon_skill: 0,
+            dodge_skill: 0,
+            #[cfg(feature = "ranged")]
+            ranged_skill: None,
+            skills: Vec::new(),
+            perk: None,
+            weapon: None,
+            armor: None,
+            #[cfg(feature = "ranged")]
+            ranged_weapon: None,
+        }
+    }
+
+    pub fn with_combat_skills(mut self, weapon_skill: i32, dodge_skill: i32) -> Self {
+        self.weapon_skill = weapon_skill;
+        self.dodge_skill = dodge_skill;
+        self
+    }
+
+    pub fn with_skill(mut self, name: &str, level: i32) -> Self {
+        self.skills.push(SkillGrant {
+            name: name.to_string(),
+            level,
+        });
+        self
+    }
+
+    pub fn with_trait(mut self, perk: Trait) -> Self {
+        self.perk = Some(perk);
+        self
+    }
+
+    pub fn with_weapon(mut self, weapon: Weapon) -> Self {
+        self.weapon = Some(weapon);
+        self
+    }
+
+    pub fn with_armor(mut self, armor: Armor) -> Self {
+        self.armor = Some(armor);
+        self
+    }
+
+    #[cfg(feature = "ranged")]
+    pub fn with_ranged(mut self, weapon: RangedWeapon, skill: i32) -> Self {
+        self.ranged_weapon = Some(weapon);
+        self.ranged_skill = Some(skill);
+        self
+    }
+
+    /// Drilled in formation fighting; hard to break
+    pub fn soldier() -> Self {
+        Self::new("Soldier")
+            .with_combat_skills(6, 4)
+            .with_skill("Tactics", 3)
+            .with_skill("First Aid", 2)
+            .with_trait(Trait::iron_will())
+            .with_weapon(Weapon::long_sword())
+            .with_armor(Armor::chain_mail())
+    }
+
+    /// Lives off the land with bow and knife
+    pub fn hunter() -> Self {
+        let hunter = Self::new("Hunter")
+            .with_combat_skills(3, 5)
+            .with_skill("Tracking", 5)
+            .with_skill("Survival", 4)
+            .with_skill("Stealth", 3)
+            .with_trait(Trait::new("Keen Eyes").with_effect(ModifierTarget::Perception, 1))
+            .with_weapon(Weapon::dagger())
+            .with_armor(Armor::leather());
+        #[cfg(feature = "ranged")]
+        let hunter = hunter.with_ranged(RangedWeapon::short_bow(), 6);
+        hunter
+    }
+
+    /// Learned, but half blind from years of reading by candlelight
+    pub fn scholar() -> Self {
+        Self::new("Scholar")
+            .with_combat_skills(1, 3)
+            .with_skill("History", 5)
+            .with_skill("Heraldry", 3)
+            .with_skill("Monster Lore", 3)
+            .with_trait(Trait::night_blind())
+            .with_weapon(Weapon::dagger())
+            .with_armor(Armor::none())
+    }
+
+    /// Quick hands, quicker feet
+    pub fn street_thief() -> Self {
+        Self::new("Street Thief")
+            .with_combat_skills(4, 6)
+            .with_skill("Stealth", 5)
+            .with_skill("Lockpicking", 4)
+            .with_skill("Pickpocketing", 4)
+            .with_trait(Trait::ambidextrous())
+            .with_weapon(Weapon::dagger())
+            .with_armor(Armor::heavy_cloth())
+    }
+
+    /// The built-in backgrounds
+    pub fn standard() -> Vec<Self> {
+        vec![
+            Self::soldier(),
+            Self::hunter(),
+            Self::scholar(),
+            Self::street_thief(),
+        ]
+    }
+
+    /// Parse a list of `[[background]]` tables from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Vec<Self>, BackgroundError> {
+        #[derive(Deserialize)]
+        struct File {
+            background: Vec<Background>,
+        }
+        toml::from_str::<File>(source)
+            .map(|file| file.background)
+            .map_err(|e| BackgroundError::Parse(e.to_string()))
+    }
+
+    /// Add the granted skills to `skills`, raising any the character already knows
+    ///
+    /// New skills are Normal difficulty and count as learned within their attribute.
+    pub fn grant_skills(&self, skills: &mut SkillSet) {
+        for grant in &self.skills {
+            match skills.get_skill_mut(&grant.name) {
+                Some(skill) => skill.level = skill.level.max(grant.level),
+                None => skills.add_skill(
+                    Skill::new(&grant.name, grant.level, SkillDifficulty::Normal)
+                        .with_level(grant.level),
+                ),
+            }
+        }
+    }
+}
+
+/// Errors from loading background data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackgroundError {
+    Parse(String),
+}
+
+impl fmt::Display for BackgroundError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackgroundError::Parse(e) => write!(f, "Invalid background data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BackgroundError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attributes, Character};
+
+    #[test]
+    fn test_builder_applies_background() {
+        let (thief, skills) = Character::builder()
+            .name("Wren")
+            .attributes(Attributes::new(5, 8, 5, 6, 7, 5, 6, 7, 4))
+            .background(&Background::street_thief())
+            .build_with_skills();
+
+        assert_eq!(thief.weapon_skill(), 4);
+        assert_eq!(thief.dodge_skill(), 6);
+        assert_eq!(thief.weapon.name, "Dagger");
+        assert_eq!(thief.armor.name, "Heavy Cloth");
+        assert!(thief.traits.has("Ambidextrous"));
+        assert_eq!(skills.get_skill_level("Lockpicking"), 4);
+
+        let hunter = Character::builder()
+            .background(&Background::hunter())
+            .build();
+        #[cfg(feature = "ranged")]
+        assert_eq!(hunter.ranged_skill.map(crate::SkillLevel::get), Some(6));
+        assert_eq!(
+            hunter.perception_roll_with(5),
+            hunter.attributes.perception.get() + 6
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_data_file_matches_presets() {
+        let loaded = Background::from_toml(include_str!("../../data/backgrounds.toml")).unwrap();
+        let standard = Background::standard();
+        assert_eq!(loaded.len(), standard.len());
+        for (data, preset) in loaded.iter().zip(&standard) {
+            assert_eq!(data.name, preset.name);
+            assert_eq!(data.weapon_skill, preset.weapon_skill);
+            assert_eq!(data.dodge_skill, preset.dodge_skill);
+            #[cfg(feature = "ranged")]
+            assert_eq!(data.ranged_skill, preset.ranged_skill);
+            assert_eq!(data.skills, preset.skills);
+            assert_eq!(data.perk, preset.perk);
+            assert_eq!(
+                data.weapon.as_ref().map(|w| (&w.name, w.damage)),
+                preset.weapon.as_ref().map(|w| (&w.name, w.damage))
+            );
+            assert_eq!(
+                data.armor.as_ref().map(|a| (&a.name, a.protection)),
+                preset.armor.as_ref().map(|a| (&a.name, a.protection))
+            );
+        }
+
+        assert!(Background::from_toml("background = 3").is_err());
+    }
+}
@@ -0,0 +1,343 @@
+//! Seeded names, personalities and backstories
+//!
+//! A `BackstoryGenerator` fleshes out NPCs from `BackstoryTables`: a name
+//! from one culture's given and family names, a couple of personality traits
+//! and a three-sentence backstory (where they came from, what happened to
+//! them, what they want now). Fragments may use `{name}` for the given name.
+//! The same seed and tables always give the same people. The stock tables
+//! ship as `data/backstories.toml`; load your own with
+//! `BackstoryTables::from_toml`.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Personality traits given to each generated person
+pub const PERSONALITY_TRAITS: usize = 2;
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// A naming culture: the given and family names its people are born with
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Culture {
+    pub name: String,
+    pub given_names: Vec<String>,
+    /// Left empty by cultures that go by a single name
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub family_names: Vec<String>,
+}
+
+impl Culture {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            given_names: Vec::new(),
+            family_names: Vec::new(),
+        }
+    }
+
+    pub fn with_given_names(mut self, names: &[&str]) -> Self {
+        self.given_names.extend(strings(names));
+        self
+    }
+
+    pub fn with_family_names(mut self, names: &[&str]) -> Self {
+        self.family_names.extend(strings(names));
+        self
+    }
+
+    /// Hardy folk of the fjords and pine forests
+    pub fn northern() -> Self {
+        Self::new("Northern")
+            .with_given_names(&["Thorgar", "Sigrun", "Bjorn", "Astrid", "Ulf", "Ingrid"])
+            .with_family_names(&["Ironside", "Halvardsen", "Frostmane", "Ravensdottir"])
+    }
+
+    /// City-dwellers of the old empire
+    pub fn imperial() -> Self {
+        Self::new("Imperial")
+            .with_given_names(&["Aurelio", "Livia", "Cassian", "Octavia", "Marcus", "Serena"])
+            .with_family_names(&["Valerian", "Drusus", "Corvino", "Maxentia"])
+    }
+
+    /// Nomads of the southern dunes, known by one name
+    pub fn desert() -> Self {
+        Self::new("Desert")
+            .with_given_names(&["Karim", "Zahra", "Idris", "Samira", "Tariq", "Nadia"])
+    }
+
+    /// Pick a full name
+    fn name(&self, rng: &mut StdRng) -> (String, String) {
+        let given = self.given_names[rng.gen_range(0..self.given_names.len())].clone();
+        let full = match self.family_names.choose(rng) {
+            Some(family) => format!("{} {}", given, family),
+            None => given.clone(),
+        };
+        (given, full)
+    }
+}
+
+/// Everything a `BackstoryGenerator` draws from
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BackstoryTables {
+    #[cfg_attr(feature = "serde", serde(rename = "culture"))]
+    pub cultures: Vec<Culture>,
+    pub personality: Vec<String>,
+    /// Where they came from
+    pub origins: Vec<String>,
+    /// What happened to them
+    pub turning_points: Vec<String>,
+    /// What they want now
+    pub motives: Vec<String>,
+}
+
+impl BackstoryTables {
+    /// The built-in tables
+    pub fn standard() -> Self {
+        Self {
+            cultures: vec![Culture::northern(), Culture::imperial(), Culture::desert()],
+            personality: strings(&[
+                "Gruff",
+                "Cheerful",
+                "Suspicious",
+                "Pious",
+                "Reckless",
+                "Soft-spoken",
+                "Greedy",
+                "Loyal",
+            ]),
+            origins: strings(&[
+                "{name} was raised by smugglers on the river docks.",
+                "{name} grew up on a hill farm that never had a good harvest.",
+                "{name} was a temple foundling, taught letters by the priests.",
+                "{name} was the youngest child of a minor noble house.",
+            ]),
+            turning_points: strings(&[
+                "A raid took their family and left them with a scar.",
+                "They were cheated out of an inheritance by a trusted friend.",
+                "They deserted a losing army and have been running since.",
+                "A fever killed their village and somehow spared them.",
+            ]),
+            motives: strings(&[
+                "Now they fight for coin to buy back what was lost.",
+                "Now they want only to see the one who wronged them dead.",
+                "Now they look for a lord worth serving.",
+                "Now they drink to forget and take whatever work is going.",
+            ]),
+        }
+    }
+
+    /// Parse `personality`, `origins`, `turning_points`, `motives` lists and
+    /// `[[culture]]` tables from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self, BackstoryError> {
+        let tables =
+            toml::from_str::<Self>(source).map_err(|e| BackstoryError::Parse(e.to_string()))?;
+        tables.validate()?;
+        Ok(tables)
+    }
+
+    pub fn culture(&self, name: &str) -> Option<&Culture> {
+        self.cultures.iter().find(|c| c.name == name)
+    }
+
+    /// Check every table has something to draw
+    pub fn validate(&self) -> Result<(), BackstoryError> {
+        let empty = [
+            ("culture", self.cultures.is_empty()),
+            ("personality", self.personality.is_empty()),
+            ("origins", self.origins.is_empty()),
+            ("turning_points", self.turning_points.is_empty()),
+            ("motives", self.motives.is_empty()),
+        ];
+        if let Some((table, _)) = empty.iter().find(|(_, empty)| *empty) {
+            return Err(BackstoryError::EmptyTable(table.to_string()));
+        }
+        match self.cultures.iter().find(|c| c.given_names.is_empty()) {
+            Some(culture) => Err(BackstoryError::EmptyTable(format!(
+                "{} given_names",
+                culture.name
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for BackstoryTables {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+/// A generated person
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Persona {
+    /// Full name, family name included where the culture has one
+    pub name: String,
+    pub given_name: String,
+    pub culture: String,
+    pub personality: Vec<String>,
+    pub backstory: String,
+}
+
+/// Errors from generating or loading backstories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackstoryError {
+    UnknownCulture(String),
+    /// A table has nothing to draw from
+    EmptyTable(String),
+    Parse(String),
+}
+
+impl fmt::Display for BackstoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackstoryError::UnknownCulture(name) => write!(f, "Unknown culture: {}", name),
+            BackstoryError::EmptyTable(table) => write!(f, "Nothing to draw from {}", table),
+            BackstoryError::Parse(e) => write!(f, "Invalid backstory data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BackstoryError {}
+
+/// Seeded generator of NPC names and backstories
+#[derive(Debug, Clone)]
+pub struct BackstoryGenerator {
+    pub seed: u64,
+    pub tables: BackstoryTables,
+    /// Draw every name from this culture rather than a random one
+    pub culture: Option<String>,
+}
+
+impl BackstoryGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            tables: BackstoryTables::standard(),
+            culture: None,
+        }
+    }
+
+    pub fn with_tables(mut self, tables: BackstoryTables) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    pub fn with_culture(mut self, culture: &str) -> Self {
+        self.culture = Some(culture.to_string());
+        self
+    }
+
+    /// The person for this seed
+    pub fn generate(&self) -> Result<Persona, BackstoryError> {
+        Ok(self.generate_batch(1)?.remove(0))
+    }
+
+    /// `n` people, reproducibly from the seed
+    pub fn generate_batch(&self, n: usize) -> Result<Vec<Persona>, BackstoryError> {
+        self.tables.validate()?;
+        let fixed = match &self.culture {
+            Some(name) => Some(
+                self.tables
+                    .culture(name)
+                    .ok_or_else(|| BackstoryError::UnknownCulture(name.clone()))?,
+            ),
+            None => None,
+        };
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        Ok((0..n)
+            .map(|_| {
+                let culture = match fixed {
+                    Some(culture) => culture,
+                    None => &self.tables.cultures[rng.gen_range(0..self.tables.cultures.len())],
+                };
+                self.person(&mut rng, culture)
+            })
+            .collect())
+    }
+
+    fn person(&self, rng: &mut StdRng, culture: &Culture) -> Persona {
+        let (given_name, name) = culture.name(rng);
+        let personality = self
+            .tables
+            .personality
+            .choose_multiple(rng, PERSONALITY_TRAITS)
+            .cloned()
+            .collect();
+        let mut pick = |table: &[String]| table[rng.gen_range(0..table.len())].clone();
+        let backstory = [
+            pick(&self.tables.origins),
+            pick(&self.tables.turning_points),
+            pick(&self.tables.motives),
+        ]
+        .join(" ")
+        .replace("{name}", &given_name);
+        Persona {
+            name,
+            given_name,
+            culture: culture.name.clone(),
+            personality,
+            backstory,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_people() {
+        let batch = BackstoryGenerator::new(11).generate_batch(8).unwrap();
+        assert_eq!(
+            batch,
+            BackstoryGenerator::new(11).generate_batch(8).unwrap()
+        );
+        assert_ne!(
+            batch,
+            BackstoryGenerator::new(12).generate_batch(8).unwrap()
+        );
+        for person in &batch {
+            assert_eq!(person.personality.len(), PERSONALITY_TRAITS);
+            assert_ne!(person.personality[0], person.personality[1]);
+            assert!(!person.backstory.contains("{name}"));
+        }
+
+        // Desert names come without a family name
+        let nomad = BackstoryGenerator::new(3)
+            .with_culture("Desert")
+            .generate()
+            .unwrap();
+        assert_eq!(nomad.culture, "Desert");
+        assert_eq!(nomad.name, nomad.given_name);
+        assert!(nomad.backstory.starts_with(&nomad.given_name));
+        assert_eq!(
+            BackstoryGenerator::new(3).with_culture("Elvish").generate(),
+            Err(BackstoryError::UnknownCulture("Elvish".to_string()))
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_data_file_matches_presets() {
+        let loaded =
+            BackstoryTables::from_toml(include_str!("../../data/backstories.toml")).unwrap();
+        assert_eq!(loaded, BackstoryTables::standard());
+        let empty =
+            "personality = []\norigins = []\nturning_points = []\nmotives = []\nculture = []";
+        assert_eq!(
+            BackstoryTables::from_toml(empty),
+            Err(BackstoryError::EmptyTable("culture".to_string()))
+        );
+    }
+}
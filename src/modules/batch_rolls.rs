@@ -0,0 +1,237 @@
+//! Vectorized resolution of many independent attacks
+//!
+//! Balance tools and Monte Carlo runs roll the same matchup thousands of
+//! times. `resolve_batch` does that in chunks of `LANES` attacks: the seeded
+//! dice stream is counter-based, so a chunk's d10s are computed side by side,
+//! and outcomes are classified with comparisons summed as integers rather
+//! than branches, leaving loops the compiler can vectorize. `resolve_scalar`
+//! is the plain one-attack-at-a-time loop over the same rolls, for checking
+//! and benchmarking (`cargo bench`, see `benches/BASELINES.md`).
+//!
+//! Outcomes follow `odds::AttackOdds`: miss, graze, light, severe, critical
+//! and kill. Wounds are not accumulated between attacks.
+
+use crate::modules::dice::{stream_roll, Dice, DiceMode};
+use crate::modules::modifiers::ModifierTarget;
+use crate::modules::odds::AttackOdds;
+use crate::{is_lethal_damage, Character, DefenseAction, WoundLevel};
+
+/// Attacks resolved together in one chunk
+pub const LANES: usize = 8;
+
+/// Everything about a matchup that doesn't depend on the dice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackProfile {
+    /// Attack roll less the d10
+    pub attack: i32,
+    /// Defense roll less the d10
+    pub defense: i32,
+    /// Damage added to the margin, net of the defender's armor
+    pub damage_bonus: i32,
+    pub constitution: i32,
+}
+
+impl AttackProfile {
+    /// `attacker` striking `defender` as they stand, answered with `defense`
+    pub fn new(attacker: &Character, defender: &Character, defense: DefenseAction) -> Self {
+        let defense = match defense {
+            DefenseAction::Parry => defender.parry_roll_with(0),
+            DefenseAction::Dodge => defender.dodge_roll_with(0),
+        };
+        Self {
+            attack: attacker.attack_roll_with(0),
+            defense,
+            damage_bonus: attacker
+                .strength_bonus()
+                .saturating_add(attacker.weapon.damage.get())
+                .saturating_add(attacker.modifiers.total(ModifierTarget::Damage))
+                .saturating_sub(defender.armor.protection.get()),
+            constitution: defender.attributes.constitution.get(),
+        }
+    }
+
+    /// Outcome index (0 miss to 5 kill) and wounding damage for one roll pair
+    #[inline(always)]
+    fn classify(&self, attack_die: i32, defense_die: i32) -> (usize, i32) {
+        let margin = (self.attack + attack_die) - (self.defense + defense_die);
+        let damage = margin + self.damage_bonus;
+        let con = self.constitution;
+        let hit = (margin > 0) as i32;
+        let wounds = (damage > WoundLevel::Light.threshold(con)) as i32;
+        let severity = 1
+            + (damage > WoundLevel::Severe.threshold(con)) as i32
+            + (damage > WoundLevel::Critical.threshold(con)) as i32
+            + is_lethal_damage(damage, con) as i32;
+        let outcome = hit * (1 + wounds * severity);
+        (outcome as usize, hit * wounds * damage)
+    }
+}
+
+/// Counts of each outcome over a batch of attacks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchTally {
+    pub miss: u32,
+    /// Hits that do 1 damage or less
+    pub graze: u32,
+    pub light: u32,
+    pub severe: u32,
+    pub critical: u32,
+    pub kill: u32,
+    /// Damage summed over hits that wound
+    pub damage: i64,
+}
+
+impl BatchTally {
+    pub fn attacks(&self) -> u32 {
+        self.miss + self.graze + self.light + self.severe + self.critical + self.kill
+    }
+
+    /// Observed frequencies, comparable with the exact `AttackOdds`
+    pub fn odds(&self) -> AttackOdds {
+        let n = self.attacks().max(1) as f32;
+        AttackOdds {
+            miss: self.miss as f32 / n,
+            graze: self.graze as f32 / n,
+            light: self.light as f32 / n,
+            severe: self.severe as f32 / n,
+            critical: self.critical as f32 / n,
+            kill: self.kill as f32 / n,
+            expected_damage: self.damage as f32 / n,
+        }
+    }
+
+    fn from_counts(counts: [u32; 6], damage: i64) -> Self {
+        let [miss, graze, light, severe, critical, kill] = counts;
+        Self {
+            miss,
+            graze,
+            light,
+            severe,
+            critical,
+            kill,
+            damage,
+        }
+    }
+}
+
+/// Resolve `attacks` independent attacks on the seeded stream, `LANES` at a time
+///
+/// Attack `i` takes rolls `2i` (attacker) and `2i + 1` (defender) of
+/// `DiceMode::seeded(seed)`, the order `combat_round_with_dice` draws them in.
+pub fn resolve_batch(profile: &AttackProfile, attacks: usize, seed: u64) -> BatchTally {
+    let mut counts = [0u32; 6];
+    let mut damage = 0i64;
+    let chunks = attacks / LANES;
+    for chunk in 0..chunks {
+        let base = (chunk * LANES) as u64;
+        let mut attack_dice = [0i32; LANES];
+        let mut defense_dice = [0i32; LANES];
+        for lane in 0..LANES {
+            let index = (base + lane as u64) * 2;
+            attack_dice[lane] = stream_roll(seed, index);
+            defense_dice[lane] = stream_roll(seed, index + 1);
+        }
+        let mut outcomes = [0usize; LANES];
+        let mut lane_damage = [0i32; LANES];
+        for lane in 0..LANES {
+            (outcomes[lane], lane_damage[lane]) =
+                profile.classify(attack_dice[lane], defense_dice[lane]);
+        }
+        for lane in 0..LANES {
+            counts[outcomes[lane]] += 1;
+        }
+        damage += lane_damage.iter().sum::<i32>() as i64;
+    }
+    for i in chunks * LANES..attacks {
+        let index = i as u64 * 2;
+        let (outcome, dealt) =
+            profile.classify(stream_roll(seed, index), stream_roll(seed, index + 1));
+        counts[outcome] += 1;
+        damage += dealt as i64;
+    }
+    BatchTally::from_counts(counts, damage)
+}
+
+/// The same attacks as `resolve_batch`, one at a time through `DiceMode`
+pub fn resolve_scalar(profile: &AttackProfile, attacks: usize, seed: u64) -> BatchTally {
+    let mut dice = DiceMode::seeded(seed);
+    let mut tally = BatchTally::default();
+    for _ in 0..attacks {
+        let margin = (profile.attack + dice.d10()) - (profile.defense + dice.d10());
+        let damage = margin + profile.damage_bonus;
+        let con = profile.constitution;
+        if margin <= 0 {
+            tally.miss += 1;
+            continue;
+        }
+        let level = match WoundLevel::from_damage(damage, con) {
+            None => {
+                tally.graze += 1;
+                continue;
+            }
+            Some(level) => level,
+        };
+        if is_lethal_damage(damage, con) {
+            tally.kill += 1;
+        } else {
+            match level {
+                WoundLevel::Light => tally.light += 1,
+                WoundLevel::Severe => tally.severe += 1,
+                WoundLevel::Critical => tally.critical += 1,
+            }
+        }
+        tally.damage += damage as i64;
+    }
+    tally
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Armor, Attributes, Weapon};
+
+    #[test]
+    fn test_batch_matches_scalar_and_converges_on_exact_odds() {
+        let attacker = Character::new(
+            "Brute",
+            Attributes::new(8, 6, 6, 5, 5, 5, 5, 5, 5),
+            7,
+            4,
+            Weapon::two_handed_sword(),
+            Armor::leather(),
+        );
+        let defender = Character::new(
+            "Guard",
+            Attributes::new(6, 6, 5, 5, 5, 5, 5, 5, 5),
+            5,
+            6,
+            Weapon::long_sword(),
+            Armor::chain_mail(),
+        );
+        let profile = AttackProfile::new(&attacker, &defender, DefenseAction::Parry);
+
+        // Same rolls, same outcomes, including a ragged last chunk
+        for (attacks, seed) in [(0, 1), (5, 2), (1_003, 3), (4_096, 4)] {
+            assert_eq!(
+                resolve_batch(&profile, attacks, seed),
+                resolve_scalar(&profile, attacks, seed)
+            );
+        }
+
+        let tally = resolve_batch(&profile, 200_000, 42);
+        assert_eq!(tally.attacks(), 200_000);
+        let observed = tally.odds();
+        let exact = AttackOdds::new(&attacker, &defender, DefenseAction::Parry);
+        for (seen, expected) in [
+            (observed.miss, exact.miss),
+            (observed.graze, exact.graze),
+            (observed.light, exact.light),
+            (observed.severe, exact.severe),
+            (observed.critical, exact.critical),
+            (observed.kill, exact.kill),
+        ] {
+            assert!((seen - expected).abs() < 0.01, "{} vs {}", seen, expected);
+        }
+        assert!((observed.expected_damage - exact.expected_damage).abs() < 0.05);
+    }
+}
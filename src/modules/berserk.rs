@@ -0,0 +1,116 @@
+//! Berserk rage: fighting on through the pain
+//!
+//! `Berserk::enter` registers "Berserk" modifiers that cancel a character's
+//! wound penalties to fighting, moving, shooting and casting (perception still
+//! suffers). Wounds keep accumulating as usual; `tick` re-syncs the modifiers
+//! each round as new wounds land. When the rage runs out, or is ended early,
+//! the modifiers go and the character takes `RAGE_EXHAUSTION` fatigue for
+//! every round spent raging.
+
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use crate::Character;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Modifier source used while raging
+pub const BERSERK: &str = "Berserk";
+/// Default length of a rage in rounds
+pub const RAGE_ROUNDS: i32 = 6;
+/// Fatigue owed per round of rage once it ends
+pub const RAGE_EXHAUSTION: i32 = 1;
+
+/// An ongoing rage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Berserk {
+    pub rounds_left: i32,
+    pub rounds_raged: i32,
+}
+
+impl Berserk {
+    /// Fly into a rage for `rounds` rounds (at least one)
+    pub fn enter(character: &mut Character, rounds: i32) -> Self {
+        let rage = Self {
+            rounds_left: rounds.max(1),
+            rounds_raged: 0,
+        };
+        rage.sync(character);
+        rage
+    }
+
+    /// Count down one round; returns false (and ends the rage) once it runs out
+    pub fn tick(&mut self, character: &mut Character) -> bool {
+        self.rounds_left -= 1;
+        self.rounds_raged += 1;
+        if self.rounds_left <= 0 || !character.is_alive() {
+            self.end(character);
+            return false;
+        }
+        self.sync(character);
+        true
+    }
+
+    /// Calm down, paying the exhaustion owed
+    pub fn end(self, character: &mut Character) {
+        character.modifiers.remove_source(BERSERK);
+        character.exert(self.rounds_raged * RAGE_EXHAUSTION);
+    }
+
+    /// Replace the modifiers so they cancel the current wound penalties
+    fn sync(&self, character: &mut Character) {
+        character.modifiers.remove_source(BERSERK);
+        let wounds = character.wounds.clone();
+        for (target, penalty) in [
+            (ModifierTarget::Attack, wounds.melee_penalty()),
+            (ModifierTarget::Parry, wounds.melee_penalty()),
+            (ModifierTarget::Movement, wounds.movement_penalty()),
+            (ModifierTarget::Ranged, wounds.ranged_penalty()),
+            (ModifierTarget::Casting, wounds.casting_penalty()),
+        ] {
+            character.add_modifier(Modifier::new(BERSERK, target, -penalty));
+        }
+    }
+}
+
+/// Check whether a character is currently raging
+pub fn is_raging(character: &Character) -> bool {
+    character.modifiers.iter().any(|m| m.source == BERSERK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+
+    #[test]
+    fn test_rage_ignores_wound_penalties_then_exhausts() {
+        let mut barbarian = Character::new(
+            "Korg",
+            Attributes::new(9, 6, 8, 4, 5, 6, 4, 5, 3),
+            7,
+            4,
+            Weapon::two_handed_sword(),
+            Armor::none(),
+        );
+        barbarian.add_wound(WoundLevel::Severe);
+        let fresh = barbarian.weapon_skill().get() + 5;
+        assert_eq!(barbarian.attack_roll_with(5), fresh - 2);
+
+        let mut rage = Berserk::enter(&mut barbarian, 2);
+        assert!(is_raging(&barbarian));
+        assert_eq!(barbarian.attack_roll_with(5), fresh);
+
+        // New wounds still count, but the penalty stays cancelled
+        barbarian.add_wound(WoundLevel::Light);
+        assert!(rage.tick(&mut barbarian));
+        assert_eq!(barbarian.wounds.light, 1);
+        assert_eq!(barbarian.attack_roll_with(5), fresh);
+        assert_eq!(barbarian.perception_roll_with(5), 5 + 5 - 1);
+
+        assert!(!rage.tick(&mut barbarian));
+        assert!(!is_raging(&barbarian));
+        assert_eq!(barbarian.fatigue, 2 * RAGE_EXHAUSTION);
+        assert_eq!(barbarian.attack_roll_with(5), fresh - 3);
+    }
+}
@@ -0,0 +1,118 @@
+//! Blood loss from accumulated wounds
+//!
+//! With `Ruleset::blood_loss`, every wound costs blood (`blood_cost`) and each
+//! bleeding wound keeps costing `BLEED_PER_ROUND` (see `Character::bleed`).
+//! Lost blood is measured against CON: each threshold passed tires the
+//! character, makes them dizzy and finally knocks them out, even when no
+//! single wound is serious.
+
+use crate::WoundLevel;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Blood lost each round for every bleeding wound
+pub const BLEED_PER_ROUND: i32 = 1;
+/// Fatigue added each time blood loss passes a threshold
+pub const BLOOD_LOSS_EXHAUSTION: i32 = 2;
+
+/// How badly a character has bled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BloodLossLevel {
+    None,
+    /// Lost at least CON points: lightheaded
+    Woozy,
+    /// Lost at least twice CON: the world swims
+    Faint,
+    /// Lost at least three times CON: passed out
+    Unconscious,
+}
+
+impl BloodLossLevel {
+    pub fn from_points(points: i32, constitution: i32) -> Self {
+        let con = constitution.max(1);
+        if points >= con * 3 {
+            BloodLossLevel::Unconscious
+        } else if points >= con * 2 {
+            BloodLossLevel::Faint
+        } else if points >= con {
+            BloodLossLevel::Woozy
+        } else {
+            BloodLossLevel::None
+        }
+    }
+
+    /// Dizziness penalty to attacks, defenses, shooting and casting
+    pub fn penalty(&self) -> i32 {
+        match self {
+            BloodLossLevel::None => 0,
+            BloodLossLevel::Woozy => -1,
+            BloodLossLevel::Faint | BloodLossLevel::Unconscious => -3,
+        }
+    }
+}
+
+impl fmt::Display for BloodLossLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BloodLossLevel::None => write!(f, "None"),
+            BloodLossLevel::Woozy => write!(f, "Woozy"),
+            BloodLossLevel::Faint => write!(f, "Faint"),
+            BloodLossLevel::Unconscious => write!(f, "Unconscious"),
+        }
+    }
+}
+
+/// Blood lost when a wound is taken
+pub fn blood_cost(level: WoundLevel) -> i32 {
+    match level {
+        WoundLevel::Light => 1,
+        WoundLevel::Severe => 3,
+        WoundLevel::Critical => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::injuries::WoundDescription;
+    use crate::modules::modifiers::ModifierTarget;
+    use crate::modules::ruleset::Ruleset;
+    use crate::{resolve_hit, Armor, Attributes, Character, Weapon};
+
+    #[test]
+    fn test_light_wounds_bleed_a_character_out() {
+        let mut c = Character::new(
+            "Tobin",
+            Attributes::new(5, 5, 4, 5, 5, 5, 5, 5, 5),
+            5,
+            5,
+            Weapon::long_sword(),
+            Armor::none(),
+        );
+        let rules = Ruleset::default().with_blood_loss(true);
+
+        // Four light cuts: each costs a point of blood
+        for _ in 0..4 {
+            resolve_hit("Bandit", "Knife", 1, &mut c, 10, 9, None, &rules);
+        }
+        assert_eq!(c.blood_loss, 4);
+        assert_eq!(c.blood_loss_level(), BloodLossLevel::Woozy);
+        assert_eq!(c.fatigue, BLOOD_LOSS_EXHAUSTION);
+        assert_eq!(c.modifiers.total(ModifierTarget::Attack), -1);
+
+        // One cut left bleeding drains the rest
+        c.inflict_wound(WoundDescription::new(WoundLevel::Light, "Knife").with_bleeding(true));
+        while c.blood_loss_level() < BloodLossLevel::Unconscious {
+            assert_eq!(c.bleed(), BLEED_PER_ROUND);
+        }
+        assert!(c.is_alive());
+        assert!(!c.can_act());
+
+        c.heal_wounds();
+        assert_eq!(c.blood_loss_level(), BloodLossLevel::None);
+        assert!(c.can_act());
+    }
+}
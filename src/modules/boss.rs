@@ -0,0 +1,183 @@
+//! Boss mechanics: multi-phase enemies and legendary actions
+//!
+//! A `Boss` attached to an encounter combatant moves through `BossPhase`s as
+//! its wounds pass each phase's threshold. Entering a phase swaps in that
+//! phase's modifiers and its number of legendary actions: extra attacks the
+//! boss makes right after other combatants' turns, outside normal turn order.
+
+use crate::modules::ai::vulnerability;
+use crate::modules::modifiers::Modifier;
+use crate::Character;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One stage of a boss fight
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BossPhase {
+    pub name: String,
+    /// Wound fraction (see `ai::vulnerability`) at which this phase begins
+    pub threshold: f32,
+    /// Modifiers active during this phase; their source is the phase name
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modifiers: Vec<Modifier>,
+    /// Extra attacks per round outside normal turn order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub legendary_actions: u32,
+}
+
+impl BossPhase {
+    pub fn new(name: &str, threshold: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            threshold,
+            modifiers: Vec::new(),
+            legendary_actions: 0,
+        }
+    }
+
+    /// Add a modifier for this phase (its source is replaced by the phase name)
+    pub fn with_modifier(mut self, mut modifier: Modifier) -> Self {
+        modifier.source = self.name.clone();
+        self.modifiers.push(modifier);
+        self
+    }
+
+    pub fn with_legendary_actions(mut self, actions: u32) -> Self {
+        self.legendary_actions = actions;
+        self
+    }
+}
+
+/// Phase and legendary action state for a boss
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Boss {
+    /// Phases in order of rising threshold; the first is the opening phase
+    pub phases: Vec<BossPhase>,
+    /// Index of the active phase, `None` until the fight starts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub current: Option<usize>,
+    /// Legendary actions left this round
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub actions_left: u32,
+}
+
+impl Boss {
+    pub fn new(opening: BossPhase) -> Self {
+        Self {
+            phases: vec![opening],
+            current: None,
+            actions_left: 0,
+        }
+    }
+
+    pub fn with_phase(mut self, phase: BossPhase) -> Self {
+        self.phases.push(phase);
+        self
+    }
+
+    pub fn phase(&self) -> Option<&BossPhase> {
+        self.phases.get(self.current?)
+    }
+
+    /// Enter the latest phase the boss's wounds have reached
+    ///
+    /// Swaps the old phase's modifiers for the new one's on `character` and
+    /// returns the new phase, or `None` if the phase didn't change.
+    pub fn update_phase(&mut self, character: &mut Character) -> Option<&BossPhase> {
+        let wounds = vulnerability(character);
+        let reached = self
+            .phases
+            .iter()
+            .rposition(|p| wounds >= p.threshold)
+            .unwrap_or(0);
+        if self.current.is_some_and(|c| c >= reached) {
+            return None;
+        }
+        if let Some(old) = self.phase() {
+            character.modifiers.remove_source(&old.name);
+        }
+        self.current = Some(reached);
+        let phase = &self.phases[reached];
+        for modifier in &phase.modifiers {
+            character.add_modifier(modifier.clone());
+        }
+        self.actions_left = self.actions_left.min(phase.legendary_actions);
+        Some(phase)
+    }
+
+    /// Refill legendary actions at the start of a round
+    pub fn new_round(&mut self) {
+        self.actions_left = self.phase().map_or(0, |p| p.legendary_actions);
+    }
+
+    /// Spend a legendary action if one is left
+    pub fn use_action(&mut self) -> bool {
+        if self.actions_left == 0 {
+            return false;
+        }
+        self.actions_left -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::modifiers::ModifierTarget;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+
+    fn dragon() -> Boss {
+        Boss::new(BossPhase::new("Aloof", 0.0).with_legendary_actions(1))
+            .with_phase(
+                BossPhase::new("Enraged", 0.5)
+                    .with_modifier(Modifier::new("", ModifierTarget::Attack, 2))
+                    .with_legendary_actions(2),
+            )
+            .with_phase(
+                BossPhase::new("Desperate", 0.75)
+                    .with_modifier(Modifier::new("", ModifierTarget::Parry, -2))
+                    .with_legendary_actions(3),
+            )
+    }
+
+    #[test]
+    fn test_phases_follow_wounds() {
+        let mut boss = dragon();
+        let mut body = Character::new(
+            "Wyrm",
+            Attributes::new(10, 6, 10, 6, 6, 8, 6, 7, 5),
+            8,
+            4,
+            Weapon::two_handed_sword(),
+            Armor::plate(),
+        );
+
+        assert_eq!(boss.update_phase(&mut body).unwrap().name, "Aloof");
+        assert!(boss.update_phase(&mut body).is_none());
+
+        body.wounds.add_wound(WoundLevel::Critical);
+        assert_eq!(boss.update_phase(&mut body).unwrap().name, "Enraged");
+        assert_eq!(body.modifiers.total(ModifierTarget::Attack), 2);
+
+        body.wounds.add_wound(WoundLevel::Severe);
+        body.wounds.add_wound(WoundLevel::Severe);
+        assert_eq!(boss.update_phase(&mut body).unwrap().name, "Desperate");
+        assert_eq!(body.modifiers.total(ModifierTarget::Attack), 0);
+        assert_eq!(body.modifiers.total(ModifierTarget::Parry), -2);
+    }
+
+    #[test]
+    fn test_legendary_actions_refill_each_round() {
+        let mut boss = dragon();
+        boss.current = Some(1);
+        boss.new_round();
+        assert!(boss.use_action());
+        assert!(boss.use_action());
+        assert!(!boss.use_action());
+        boss.new_round();
+        assert_eq!(boss.actions_left, 2);
+    }
+}
@@ -0,0 +1,85 @@
+//! Campaign calendar
+//!
+//! A simple fantasy calendar of 12 months × 30 days used to date travel,
+//! healing and other downtime activities.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const DAYS_PER_MONTH: u32 = 30;
+pub const MONTHS_PER_YEAR: u32 = 12;
+pub const DAYS_PER_YEAR: u32 = DAYS_PER_MONTH * MONTHS_PER_YEAR;
+
+/// A day on the campaign calendar (months and days are 1-based)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CampaignDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl CampaignDate {
+    /// Create a date, clamping month and day into range
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self {
+            year,
+            month: month.clamp(1, MONTHS_PER_YEAR),
+            day: day.clamp(1, DAYS_PER_MONTH),
+        }
+    }
+
+    /// Days since the start of year 0
+    pub fn ordinal(&self) -> i64 {
+        self.year as i64 * DAYS_PER_YEAR as i64
+            + (self.month - 1) as i64 * DAYS_PER_MONTH as i64
+            + (self.day - 1) as i64
+    }
+
+    fn from_ordinal(ordinal: i64) -> Self {
+        let year = ordinal.div_euclid(DAYS_PER_YEAR as i64);
+        let rest = ordinal.rem_euclid(DAYS_PER_YEAR as i64) as u32;
+        Self {
+            year: year as i32,
+            month: rest / DAYS_PER_MONTH + 1,
+            day: rest % DAYS_PER_MONTH + 1,
+        }
+    }
+
+    /// The date `days` later (or earlier, if negative)
+    pub fn add_days(&self, days: i64) -> Self {
+        Self::from_ordinal(self.ordinal() + days)
+    }
+
+    /// Number of days from this date to `other`
+    pub fn days_until(&self, other: &CampaignDate) -> i64 {
+        other.ordinal() - self.ordinal()
+    }
+}
+
+impl fmt::Display for CampaignDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Year {}, Month {}, Day {}",
+            self.year, self.month, self.day
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_days_rolls_over_months_and_years() {
+        let date = CampaignDate::new(1012, 12, 29);
+        assert_eq!(date.add_days(1), CampaignDate::new(1012, 12, 30));
+        assert_eq!(date.add_days(2), CampaignDate::new(1013, 1, 1));
+        assert_eq!(date.add_days(-29), CampaignDate::new(1012, 11, 30));
+        assert_eq!(date.days_until(&date.add_days(45)), 45);
+        assert_eq!(date.to_string(), "Year 1012, Month 12, Day 29");
+    }
+}
@@ -0,0 +1,824 @@
+//! Shared equipment catalog with lightweight handles
+//!
+//! Large simulations can intern each weapon and armor definition once in an
+//! `EquipmentCatalog` and give combatants small `WeaponInstance` /
+//! `ArmorInstance` values instead of full owned structs.
+//!
+//! Custom arsenals live in a `WeaponCatalog`, loaded from TOML (`toml`
+//! feature) or JSON (`json` feature) so a campaign can add weapons without
+//! recompiling; `data/weapons.toml` is a starting point.
+//!
+//! A `ContentCatalog` tags weapons, armor, spells and creatures by name with
+//! setting labels ("medieval", "firearm", "undead") and a power level, so a
+//! game can `filter` its content down to what fits the campaign's era and
+//! tier. `data/content.toml` tags the built-in presets.
+
+use crate::{
+    weapon_damage, Armor, Damage, Weapon, WeaponImpact, WeaponTags, DEFAULT_REACH, MAX_REACH,
+};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Durability of a freshly made item
+pub const MAX_DURABILITY: u8 = 100;
+
+/// Handle to a weapon definition stored in an `EquipmentCatalog`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeaponHandle(u32);
+
+/// Handle to an armor definition stored in an `EquipmentCatalog`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArmorHandle(u32);
+
+/// Immutable weapon and armor definitions, deduplicated by name
+#[derive(Debug, Clone, Default)]
+pub struct EquipmentCatalog {
+    weapons: Vec<Weapon>,
+    armor: Vec<Armor>,
+    weapon_names: HashMap<String, WeaponHandle>,
+    armor_names: HashMap<String, ArmorHandle>,
+}
+
+impl EquipmentCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Catalog pre-filled with the built-in weapon and armor presets
+    pub fn standard() -> Self {
+        let mut catalog = Self::new();
+        catalog.intern_weapon(Weapon::dagger());
+        catalog.intern_weapon(Weapon::long_sword());
+        catalog.intern_weapon(Weapon::two_handed_sword());
+        catalog.intern_armor(Armor::none());
+        catalog.intern_armor(Armor::leather());
+        catalog.intern_armor(Armor::chain_mail());
+        catalog.intern_armor(Armor::plate());
+        catalog
+    }
+
+    /// Store a weapon definition, or return the existing handle for that name
+    pub fn intern_weapon(&mut self, weapon: Weapon) -> WeaponHandle {
+        if let Some(handle) = self.weapon_names.get(&weapon.name) {
+            return *handle;
+        }
+        let handle = WeaponHandle(self.weapons.len() as u32);
+        self.weapon_names.insert(weapon.name.clone(), handle);
+        self.weapons.push(weapon);
+        handle
+    }
+
+    /// Store an armor definition, or return the existing handle for that name
+    pub fn intern_armor(&mut self, armor: Armor) -> ArmorHandle {
+        if let Some(handle) = self.armor_names.get(&armor.name) {
+            return *handle;
+        }
+        let handle = ArmorHandle(self.armor.len() as u32);
+        self.armor_names.insert(armor.name.clone(), handle);
+        self.armor.push(armor);
+        handle
+    }
+
+    /// Get the weapon definition behind a handle
+    pub fn weapon(&self, handle: WeaponHandle) -> Option<&Weapon> {
+        self.weapons.get(handle.0 as usize)
+    }
+
+    /// Get the armor definition behind a handle
+    pub fn armor(&self, handle: ArmorHandle) -> Option<&Armor> {
+        self.armor.get(handle.0 as usize)
+    }
+
+    /// Look up a weapon handle by name
+    pub fn weapon_handle(&self, name: &str) -> Option<WeaponHandle> {
+        self.weapon_names.get(name).copied()
+    }
+
+    /// Look up an armor handle by name
+    pub fn armor_handle(&self, name: &str) -> Option<ArmorHandle> {
+        self.armor_names.get(name).copied()
+    }
+
+    /// Iterate over all weapon definitions in insertion order
+    pub fn weapons(&self) -> impl Iterator<Item = (WeaponHandle, &Weapon)> {
+        self.weapons
+            .iter()
+            .enumerate()
+            .map(|(i, w)| (WeaponHandle(i as u32), w))
+    }
+
+    /// Iterate over all armor definitions in insertion order
+    pub fn armors(&self) -> impl Iterator<Item = (ArmorHandle, &Armor)> {
+        self.armor
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (ArmorHandle(i as u32), a))
+    }
+
+    pub fn weapon_count(&self) -> usize {
+        self.weapons.len()
+    }
+
+    pub fn armor_count(&self) -> usize {
+        self.armor.len()
+    }
+
+    /// Intern every weapon in `catalog`, returning their handles in order
+    pub fn intern_catalog(
+        &mut self,
+        catalog: &WeaponCatalog,
+    ) -> Result<Vec<WeaponHandle>, CatalogError> {
+        catalog
+            .entries
+            .iter()
+            .map(|entry| Ok(self.intern_weapon(entry.build()?)))
+            .collect()
+    }
+}
+
+/// One weapon as written in a catalog file
+///
+/// Only `name` and `impact` are required. Weapons are sharp, one-handed and
+/// of medium reach unless stated; `tags` takes the names `WeaponTags`
+/// displays ("Thrusting", "Hilted", "Shield", "Two-Handed").
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeaponEntry {
+    pub name: String,
+    pub impact: WeaponImpact,
+    #[cfg_attr(feature = "serde", serde(default = "default_sharp"))]
+    pub sharp: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bonus: i32,
+    #[cfg_attr(feature = "serde", serde(default = "default_reach"))]
+    pub reach: i32,
+    /// 1 or 2; two hands adds the Two-Handed tag
+    #[cfg_attr(feature = "serde", serde(default = "default_hands"))]
+    pub hands: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+fn default_sharp() -> bool {
+    true
+}
+
+#[cfg(feature = "serde")]
+fn default_reach() -> i32 {
+    DEFAULT_REACH
+}
+
+#[cfg(feature = "serde")]
+fn default_hands() -> u8 {
+    1
+}
+
+impl WeaponEntry {
+    pub fn new(name: &str, impact: WeaponImpact) -> Self {
+        Self {
+            name: name.to_string(),
+            impact,
+            sharp: true,
+            bonus: 0,
+            reach: DEFAULT_REACH,
+            hands: 1,
+            tags: Vec::new(),
+        }
+    }
+
+    /// The weapon this entry describes
+    ///
+    /// The bonus must leave damage within `Damage`'s range, and reach must
+    /// be 0 to `MAX_REACH`.
+    pub fn build(&self) -> Result<Weapon, CatalogError> {
+        let out_of_range = |field: &str, value: i32| CatalogError::OutOfRange {
+            weapon: self.name.clone(),
+            field: field.to_string(),
+            value,
+        };
+        weapon_damage(self.impact, self.sharp, self.bonus)
+            .and_then(|damage| Damage::new(damage).ok())
+            .ok_or_else(|| out_of_range("bonus", self.bonus))?;
+        if !(0..=MAX_REACH).contains(&self.reach) {
+            return Err(out_of_range("reach", self.reach));
+        }
+        let mut tags = match self.hands {
+            1 => WeaponTags::NONE,
+            2 => WeaponTags::TWO_HANDED,
+            hands => {
+                return Err(CatalogError::Hands {
+                    weapon: self.name.clone(),
+                    hands,
+                })
+            }
+        };
+        for name in &self.tags {
+            let tag = WeaponTags::from_name(name).ok_or_else(|| CatalogError::UnknownTag {
+                weapon: self.name.clone(),
+                tag: name.clone(),
+            })?;
+            tags = tags.with(tag);
+        }
+        let mut builder = Weapon::builder()
+            .name(&self.name)
+            .impact(self.impact)
+            .bonus(self.bonus)
+            .reach(self.reach)
+            .tags(tags);
+        if !self.sharp {
+            builder = builder.blunt();
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Weapon definitions read from data, for arsenals beyond the built-in presets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeaponCatalog {
+    #[cfg_attr(feature = "serde", serde(rename = "weapon", default))]
+    pub entries: Vec<WeaponEntry>,
+}
+
+impl WeaponCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_entry(mut self, entry: WeaponEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Parse a list of `[[weapon]]` tables from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self, CatalogError> {
+        toml::from_str::<Self>(source)
+            .map_err(|e| CatalogError::Parse(e.to_string()))?
+            .checked()
+    }
+
+    /// Parse `{"weapon": [...]}` from JSON
+    #[cfg(feature = "json")]
+    pub fn from_json(source: &str) -> Result<Self, CatalogError> {
+        serde_json::from_str::<Self>(source)
+            .map_err(|e| CatalogError::Parse(e.to_string()))?
+            .checked()
+    }
+
+    /// Build the weapon named `name`
+    pub fn weapon(&self, name: &str) -> Result<Weapon, CatalogError> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| CatalogError::UnknownWeapon(name.to_string()))?
+            .build()
+    }
+
+    /// Build every weapon, in file order
+    pub fn weapons(&self) -> Result<Vec<Weapon>, CatalogError> {
+        self.entries.iter().map(WeaponEntry::build).collect()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// Reject duplicate names and entries that don't build
+    #[cfg(any(feature = "toml", feature = "json"))]
+    fn checked(self) -> Result<Self, CatalogError> {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if self.entries[..i].iter().any(|e| e.name == entry.name) {
+                return Err(CatalogError::DuplicateWeapon(entry.name.clone()));
+            }
+            entry.build()?;
+        }
+        Ok(self)
+    }
+}
+
+/// Errors from loading or building catalog weapons
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogError {
+    UnknownWeapon(String),
+    DuplicateWeapon(String),
+    UnknownTag {
+        weapon: String,
+        tag: String,
+    },
+    /// Weapons take one hand or two
+    Hands {
+        weapon: String,
+        hands: u8,
+    },
+    /// A bonus giving impossible damage, or a reach past `MAX_REACH`
+    OutOfRange {
+        weapon: String,
+        field: String,
+        value: i32,
+    },
+    /// The same content tagged twice
+    DuplicateContent {
+        kind: ContentKind,
+        name: String,
+    },
+    Parse(String),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CatalogError::UnknownWeapon(name) => write!(f, "No weapon named {}", name),
+            CatalogError::DuplicateWeapon(name) => {
+                write!(f, "More than one weapon named {}", name)
+            }
+            CatalogError::UnknownTag { weapon, tag } => {
+                write!(f, "{} has unknown tag {}", weapon, tag)
+            }
+            CatalogError::Hands { weapon, hands } => {
+                write!(f, "{} takes {} hands; weapons take 1 or 2", weapon, hands)
+            }
+            CatalogError::OutOfRange {
+                weapon,
+                field,
+                value,
+            } => write!(
+                f,
+                "{} has {} {}, which is out of range",
+                weapon, field, value
+            ),
+            CatalogError::DuplicateContent { kind, name } => {
+                write!(f, "{} {} is tagged more than once", kind, name)
+            }
+            CatalogError::Parse(e) => write!(f, "Invalid catalog data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// The sort of game content a catalog entry tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContentKind {
+    /// Melee or ranged
+    Weapon,
+    Armor,
+    Spell,
+    Creature,
+}
+
+impl fmt::Display for ContentKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContentKind::Weapon => write!(f, "Weapon"),
+            ContentKind::Armor => write!(f, "Armor"),
+            ContentKind::Spell => write!(f, "Spell"),
+            ContentKind::Creature => write!(f, "Creature"),
+        }
+    }
+}
+
+/// Setting tags and power level for one weapon, armor, spell or creature
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TaggedContent {
+    pub kind: ContentKind,
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// Power tier, 1 for starting gear and foes
+    #[cfg_attr(feature = "serde", serde(default = "default_level"))]
+    pub level: i32,
+}
+
+#[cfg(feature = "serde")]
+fn default_level() -> i32 {
+    1
+}
+
+impl TaggedContent {
+    pub fn new(kind: ContentKind, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            tags: Vec::new(),
+            level: 1,
+        }
+    }
+
+    pub fn with_tags(mut self, tags: &[&str]) -> Self {
+        self.tags.extend(tags.iter().map(|t| t.to_string()));
+        self
+    }
+
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+/// Tags for a game's content, queried to restrict it to a setting
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContentCatalog {
+    #[cfg_attr(feature = "serde", serde(rename = "content", default))]
+    pub entries: Vec<TaggedContent>,
+}
+
+impl ContentCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_entry(mut self, entry: TaggedContent) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Parse a list of `[[content]]` tables from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self, CatalogError> {
+        toml::from_str::<Self>(source)
+            .map_err(|e| CatalogError::Parse(e.to_string()))?
+            .checked()
+    }
+
+    /// Parse `{"content": [...]}` from JSON
+    #[cfg(feature = "json")]
+    pub fn from_json(source: &str) -> Result<Self, CatalogError> {
+        serde_json::from_str::<Self>(source)
+            .map_err(|e| CatalogError::Parse(e.to_string()))?
+            .checked()
+    }
+
+    pub fn entry(&self, kind: ContentKind, name: &str) -> Option<&TaggedContent> {
+        self.entries
+            .iter()
+            .find(|e| e.kind == kind && e.name == name)
+    }
+
+    pub fn contains(&self, kind: ContentKind, name: &str) -> bool {
+        self.entry(kind, name).is_some()
+    }
+
+    /// Content carrying every one of `tags` with a level in `levels`
+    ///
+    /// No tags matches everything in the level range.
+    pub fn filter(&self, tags: &[&str], levels: RangeInclusive<i32>) -> ContentCatalog {
+        ContentCatalog {
+            entries: self
+                .entries
+                .iter()
+                .filter(|e| levels.contains(&e.level) && tags.iter().all(|t| e.has_tag(t)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Names of the `kind` entries, in catalog order
+    pub fn names(&self, kind: ContentKind) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .filter(move |e| e.kind == kind)
+            .map(|e| e.name.as_str())
+    }
+
+    /// Reject content tagged twice
+    #[cfg(any(feature = "toml", feature = "json"))]
+    fn checked(self) -> Result<Self, CatalogError> {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if self.entries[..i]
+                .iter()
+                .any(|e| e.kind == entry.kind && e.name == entry.name)
+            {
+                return Err(CatalogError::DuplicateContent {
+                    kind: entry.kind,
+                    name: entry.name.clone(),
+                });
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// A carried weapon: catalog handle plus per-item state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeaponInstance {
+    pub handle: WeaponHandle,
+    pub durability: u8,
+    pub enchantment: i8,
+}
+
+impl WeaponInstance {
+    pub fn new(handle: WeaponHandle) -> Self {
+        Self {
+            handle,
+            durability: MAX_DURABILITY,
+            enchantment: 0,
+        }
+    }
+
+    pub fn with_enchantment(mut self, enchantment: i8) -> Self {
+        self.enchantment = enchantment;
+        self
+    }
+
+    /// Effective damage: catalog damage plus enchantment
+    pub fn damage(&self, catalog: &EquipmentCatalog) -> Option<i32> {
+        catalog
+            .weapon(self.handle)
+            .map(|w| w.damage.saturating_add(self.enchantment as i32).get())
+    }
+
+    /// Reduce durability, returning true if the weapon broke
+    pub fn wear(&mut self, amount: u8) -> bool {
+        self.durability = self.durability.saturating_sub(amount);
+        self.is_broken()
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.durability == 0
+    }
+
+    /// Build an owned `Weapon` (with enchantment applied) for the core combat API
+    pub fn resolve(&self, catalog: &EquipmentCatalog) -> Option<Weapon> {
+        let mut weapon = catalog.weapon(self.handle)?.clone();
+        weapon.damage = weapon.damage.saturating_add(self.enchantment as i32);
+        Some(weapon)
+    }
+}
+
+/// Worn armor: catalog handle plus per-item state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArmorInstance {
+    pub handle: ArmorHandle,
+    pub durability: u8,
+    pub enchantment: i8,
+}
+
+impl ArmorInstance {
+    pub fn new(handle: ArmorHandle) -> Self {
+        Self {
+            handle,
+            durability: MAX_DURABILITY,
+            enchantment: 0,
+        }
+    }
+
+    pub fn with_enchantment(mut self, enchantment: i8) -> Self {
+        self.enchantment = enchantment;
+        self
+    }
+
+    /// Effective protection: catalog protection plus enchantment (never negative)
+    pub fn protection(&self, catalog: &EquipmentCatalog) -> Option<i32> {
+        catalog
+            .armor(self.handle)
+            .map(|a| a.protection.saturating_add(self.enchantment as i32).get())
+    }
+
+    /// Reduce durability, returning true if the armor is destroyed
+    pub fn wear(&mut self, amount: u8) -> bool {
+        self.durability = self.durability.saturating_sub(amount);
+        self.is_broken()
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.durability == 0
+    }
+
+    /// Build an owned `Armor` (with enchantment applied) for the core combat API
+    pub fn resolve(&self, catalog: &EquipmentCatalog) -> Option<Armor> {
+        let mut armor = catalog.armor(self.handle)?.clone();
+        armor.protection = armor.protection.saturating_add(self.enchantment as i32);
+        Some(armor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning_deduplicates_by_name() {
+        let mut catalog = EquipmentCatalog::new();
+        let a = catalog.intern_weapon(Weapon::long_sword());
+        let b = catalog.intern_weapon(Weapon::long_sword());
+        let c = catalog.intern_weapon(Weapon::dagger());
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(catalog.weapon_count(), 2);
+        assert_eq!(catalog.weapon_handle("Dagger"), Some(c));
+        assert_eq!(catalog.weapon(a).unwrap().name, "Long Sword");
+    }
+
+    #[test]
+    fn test_standard_catalog() {
+        let catalog = EquipmentCatalog::standard();
+        assert_eq!(catalog.weapon_count(), 3);
+        assert_eq!(catalog.armor_count(), 4);
+
+        let chain = catalog.armor_handle("Chain Mail").unwrap();
+        assert_eq!(catalog.armor(chain).unwrap().protection, 3);
+        assert_eq!(catalog.armors().count(), 4);
+    }
+
+    #[test]
+    fn test_weapon_instance_enchantment_and_wear() {
+        let catalog = EquipmentCatalog::standard();
+        let sword = catalog.weapon_handle("Long Sword").unwrap();
+        let mut blade = WeaponInstance::new(sword).with_enchantment(2);
+
+        assert_eq!(blade.damage(&catalog), Some(7)); // 5 + 2
+        assert_eq!(blade.resolve(&catalog).unwrap().damage, 7);
+
+        assert!(!blade.wear(60));
+        assert!(blade.wear(60));
+        assert_eq!(blade.durability, 0);
+    }
+
+    #[test]
+    fn test_armor_instance_protection_floor() {
+        let catalog = EquipmentCatalog::standard();
+        let leather = catalog.armor_handle("Leather Armor").unwrap();
+        let cursed = ArmorInstance::new(leather).with_enchantment(-5);
+
+        assert_eq!(cursed.protection(&catalog), Some(0));
+        assert_eq!(cursed.resolve(&catalog).unwrap().protection, 0);
+    }
+
+    #[test]
+    fn test_weapon_entries_build_and_intern() {
+        let mut pike = WeaponEntry::new("Pike", WeaponImpact::Large);
+        pike.reach = 3;
+        pike.hands = 2;
+        pike.tags.push("thrusting".to_string());
+        let catalog = WeaponCatalog::new().with_entry(pike.clone());
+        let built = catalog.weapon("Pike").unwrap();
+        assert_eq!(built.damage, 7);
+        assert_eq!(built.reach, 3);
+        assert_eq!(
+            built.tags,
+            WeaponTags::TWO_HANDED.with(WeaponTags::THRUSTING)
+        );
+        assert_eq!(
+            catalog.weapon("Lance").unwrap_err(),
+            CatalogError::UnknownWeapon("Lance".to_string())
+        );
+
+        let mut equipment = EquipmentCatalog::standard();
+        let handles = equipment.intern_catalog(&catalog).unwrap();
+        assert_eq!(equipment.weapon_handle("Pike"), Some(handles[0]));
+
+        pike.hands = 3;
+        assert!(matches!(
+            pike.build(),
+            Err(CatalogError::Hands { hands: 3, .. })
+        ));
+        pike.hands = 2;
+        pike.tags.push("Serrated".to_string());
+        assert_eq!(
+            pike.build().unwrap_err(),
+            CatalogError::UnknownTag {
+                weapon: "Pike".to_string(),
+                tag: "Serrated".to_string()
+            }
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_weapon_catalog_loads_from_toml() {
+        let catalog = WeaponCatalog::from_toml(include_str!("../../data/weapons.toml")).unwrap();
+        assert_eq!(
+            catalog.names().collect::<Vec<_>>(),
+            ["Falchion", "Rapier", "Quarterstaff", "Halberd", "Flail"]
+        );
+        let staff = catalog.weapon("Quarterstaff").unwrap();
+        assert_eq!((staff.damage.get(), staff.reach), (4, 2));
+        assert!(staff.tags.contains(WeaponTags::TWO_HANDED));
+        let rapier = catalog.weapon("Rapier").unwrap();
+        assert_eq!(rapier.damage, 4);
+        assert_eq!(rapier.reach, DEFAULT_REACH);
+        assert_eq!(catalog.weapons().unwrap().len(), 5);
+
+        let doubled = "[[weapon]]\nname = \"Axe\"\nimpact = \"Medium\"\n".repeat(2);
+        assert_eq!(
+            WeaponCatalog::from_toml(&doubled).unwrap_err(),
+            CatalogError::DuplicateWeapon("Axe".to_string())
+        );
+
+        // Absurd numbers are rejected, not overflowed
+        let huge = "[[weapon]]\nname = \"X\"\nimpact = \"Large\"\nbonus = 2147483647\n";
+        assert_eq!(
+            WeaponCatalog::from_toml(huge).unwrap_err(),
+            CatalogError::OutOfRange {
+                weapon: "X".to_string(),
+                field: "bonus".to_string(),
+                value: i32::MAX
+            }
+        );
+        let long = "[[weapon]]\nname = \"Pike\"\nimpact = \"Medium\"\nreach = 9\n";
+        assert!(matches!(
+            WeaponCatalog::from_toml(long),
+            Err(CatalogError::OutOfRange { value: 9, .. })
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_weapon_catalog_loads_from_json() {
+        let catalog = WeaponCatalog::from_json(
+            r#"{"weapon": [{"name": "Morning Star", "impact": "Large", "sharp": false}]}"#,
+        )
+        .unwrap();
+        assert_eq!(catalog.weapon("Morning Star").unwrap().damage, 6);
+        assert!(matches!(
+            WeaponCatalog::from_json(r#"{"weapon": [{"name": "Axe"}]}"#),
+            Err(CatalogError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_content_filters_by_tags_and_level() {
+        let content = ContentCatalog::new()
+            .with_entry(
+                TaggedContent::new(ContentKind::Weapon, "Long Sword").with_tags(&["medieval"]),
+            )
+            .with_entry(
+                TaggedContent::new(ContentKind::Weapon, "Rifle")
+                    .with_tags(&["modern", "firearm"])
+                    .with_level(3),
+            )
+            .with_entry(
+                TaggedContent::new(ContentKind::Armor, "Full Plate")
+                    .with_tags(&["medieval"])
+                    .with_level(3),
+            )
+            .with_entry(TaggedContent::new(ContentKind::Creature, "Zombie").with_tags(&["undead"]));
+
+        let early = content.filter(&["medieval"], 1..=2);
+        assert_eq!(
+            early.names(ContentKind::Weapon).collect::<Vec<_>>(),
+            ["Long Sword"]
+        );
+        assert!(!early.contains(ContentKind::Armor, "Full Plate"));
+        assert!(content
+            .filter(&["modern", "firearm"], 1..=3)
+            .contains(ContentKind::Weapon, "Rifle"));
+        assert!(content
+            .filter(&["modern", "undead"], 1..=3)
+            .entries
+            .is_empty());
+        // No tags: just the level range
+        assert_eq!(content.filter(&[], 3..=3).entries.len(), 2);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_content_tags_load_from_toml() {
+        let content = ContentCatalog::from_toml(include_str!("../../data/content.toml")).unwrap();
+        let firearms = content.filter(&["firearm"], 1..=10);
+        assert_eq!(
+            firearms.names(ContentKind::Weapon).collect::<Vec<_>>(),
+            ["Pistol", "Rifle"]
+        );
+        assert_eq!(
+            content
+                .entry(ContentKind::Creature, "Zombie")
+                .unwrap()
+                .level,
+            1
+        );
+
+        let doubled = "[[content]]\nkind = \"Spell\"\nname = \"Spark\"\n".repeat(2);
+        assert_eq!(
+            ContentCatalog::from_toml(&doubled).unwrap_err(),
+            CatalogError::DuplicateContent {
+                kind: ContentKind::Spell,
+                name: "Spark".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_instances_are_small() {
+        assert!(std::mem::size_of::<WeaponInstance>() <= 8);
+        assert!(std::mem::size_of::<ArmorInstance>() <= 8);
+    }
+}
@@ -0,0 +1,170 @@
+//! Companion animals and pets
+//!
+//! A `Companion` is the bond between a character and an animal such as a war
+//! dog or hawk: its loyalty, how well trained it is, and the command it is
+//! following. The animal itself is an ordinary (simplified) `Character`.
+//! Commanding takes the owner's action and a handling check; in an
+//! `Encounter` companions act right after their owner and may bolt once the
+//! owner falls.
+
+use crate::modules::followers::{Morale, MORALE_DESERT, MORALE_STEADY};
+use crate::{Armor, ArmorType, Attributes, Character, Weapon, WeaponImpact};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const MAX_TRAINING: i32 = 10;
+/// Handling check total (skill + training + d10) needed to give a command
+pub const COMMAND_DIFFICULTY: i32 = 12;
+/// Handling check total needed for a day of training to raise training by one
+pub const TRAINING_DIFFICULTY: i32 = 14;
+
+/// What a companion has been told to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CompanionCommand {
+    /// Fight the most pressing foe, moving as needed
+    #[default]
+    Attack,
+    /// Stay put and only strike foes already in reach
+    Guard,
+    /// Stay out of the fight
+    Heel,
+}
+
+impl fmt::Display for CompanionCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompanionCommand::Attack => write!(f, "Attack"),
+            CompanionCommand::Guard => write!(f, "Guard"),
+            CompanionCommand::Heel => write!(f, "Heel"),
+        }
+    }
+}
+
+/// A character's bond with an animal companion
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Companion {
+    /// Name of the owning character
+    pub owner: String,
+    /// 0-10, as for followers
+    pub loyalty: i32,
+    /// 0-10, added to handling checks
+    pub training: i32,
+    pub command: CompanionCommand,
+    /// Set when the animal has bolted from a fight
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fled: bool,
+}
+
+impl Companion {
+    pub fn new(owner: &str) -> Self {
+        Self {
+            owner: owner.to_string(),
+            loyalty: 6,
+            training: 3,
+            command: CompanionCommand::default(),
+            fled: false,
+        }
+    }
+
+    pub fn with_loyalty(mut self, loyalty: i32) -> Self {
+        self.loyalty = loyalty.clamp(0, 10);
+        self
+    }
+
+    pub fn with_training(mut self, training: i32) -> Self {
+        self.training = training.clamp(0, MAX_TRAINING);
+        self
+    }
+
+    /// Give a command: handler skill + training + `roll` vs `COMMAND_DIFFICULTY`
+    ///
+    /// On failure the animal keeps its previous command.
+    pub fn command(&mut self, handler_skill: i32, command: CompanionCommand, roll: i32) -> bool {
+        let success = handler_skill + self.training + roll >= COMMAND_DIFFICULTY;
+        if success {
+            self.command = command;
+        }
+        success
+    }
+
+    /// A day of training: handler skill + training + `roll` vs `TRAINING_DIFFICULTY`
+    pub fn train(&mut self, handler_skill: i32, roll: i32) -> bool {
+        if self.training >= MAX_TRAINING {
+            return false;
+        }
+        let success = handler_skill + self.training + roll >= TRAINING_DIFFICULTY;
+        if success {
+            self.training += 1;
+        }
+        success
+    }
+
+    /// Morale check for the animal, as for followers: d10 + loyalty + wound penalty
+    pub fn morale_check(&self, animal: &Character, roll: i32) -> Morale {
+        let total = roll + self.loyalty + animal.wounds.movement_penalty();
+        if total >= MORALE_STEADY {
+            Morale::Steady
+        } else if total >= MORALE_DESERT {
+            Morale::Wavering
+        } else {
+            Morale::Deserts
+        }
+    }
+}
+
+/// A trained war dog
+pub fn war_dog(name: &str) -> Character {
+    Character::new(
+        name,
+        Attributes::new(5, 7, 6, 2, 7, 5, 3, 8, 4),
+        6,
+        6,
+        Weapon::new("Bite", WeaponImpact::Small),
+        Armor::of_type("Hide", ArmorType::HeavyCloth),
+    )
+}
+
+/// A hunting hawk: weak in a fight but hard to hit
+pub fn hawk(name: &str) -> Character {
+    Character::new(
+        name,
+        Attributes::new(1, 9, 3, 2, 8, 4, 3, 10, 4),
+        5,
+        8,
+        Weapon::new("Talons", WeaponImpact::Small),
+        Armor::none(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WoundLevel;
+
+    #[test]
+    fn test_commands_and_training() {
+        let mut bond = Companion::new("Aldric").with_training(2);
+        assert!(!bond.command(4, CompanionCommand::Heel, 5));
+        assert_eq!(bond.command, CompanionCommand::Attack);
+        assert!(bond.command(4, CompanionCommand::Heel, 6));
+        assert_eq!(bond.command, CompanionCommand::Heel);
+
+        assert!(!bond.train(4, 7));
+        assert!(bond.train(4, 8));
+        assert_eq!(bond.training, 3);
+    }
+
+    #[test]
+    fn test_morale_falls_with_wounds() {
+        let bond = Companion::new("Aldric").with_loyalty(4);
+        let mut dog = war_dog("Rex");
+        assert_eq!(bond.morale_check(&dog, 6), Morale::Steady);
+        dog.wounds.add_wound(WoundLevel::Severe);
+        assert_eq!(bond.morale_check(&dog, 6), Morale::Wavering);
+        assert_eq!(bond.morale_check(&dog, 3), Morale::Deserts);
+    }
+}
@@ -0,0 +1,486 @@
+//! Printable stat block compendium generated from runtime data
+//!
+//! A `Compendium` collects the same `Weapon`, `Armor`, `RangedWeapon`, `Spell`
+//! and `Character` values used during play and renders them as Markdown or
+//! HTML reference tables, so printed handouts never drift from the rules code.
+//! `restricted_to` cuts it down to a setting's `ContentCatalog`.
+
+use crate::modules::catalog::{ContentCatalog, ContentKind};
+use crate::modules::magic::Spell;
+use crate::modules::ranged_combat::RangedWeapon;
+use crate::{Armor, Character, Weapon};
+use std::fmt;
+
+/// Output format for an exported compendium
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompendiumFormat {
+    Markdown,
+    Html,
+}
+
+impl fmt::Display for CompendiumFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompendiumFormat::Markdown => write!(f, "Markdown"),
+            CompendiumFormat::Html => write!(f, "HTML"),
+        }
+    }
+}
+
+/// A collection of game content that can be exported as a reference document
+#[derive(Debug, Clone)]
+pub struct Compendium {
+    pub title: String,
+    pub weapons: Vec<Weapon>,
+    pub ranged_weapons: Vec<RangedWeapon>,
+    pub armor: Vec<Armor>,
+    pub spells: Vec<Spell>,
+    pub creatures: Vec<Character>,
+}
+
+impl Compendium {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            weapons: Vec::new(),
+            ranged_weapons: Vec::new(),
+            armor: Vec::new(),
+            spells: Vec::new(),
+            creatures: Vec::new(),
+        }
+    }
+
+    /// Compendium pre-filled with every built-in weapon, ranged weapon and armor preset
+    pub fn standard() -> Self {
+        Self::new("Steelkilt Compendium")
+            .with_weapon(Weapon::dagger())
+            .with_weapon(Weapon::long_sword())
+            .with_weapon(Weapon::two_handed_sword())
+            .with_ranged_weapon(RangedWeapon::short_bow())
+            .with_ranged_weapon(RangedWeapon::long_bow())
+            .with_ranged_weapon(RangedWeapon::crossbow())
+            .with_ranged_weapon(RangedWeapon::javelin())
+            .with_ranged_weapon(RangedWeapon::pistol())
+            .with_ranged_weapon(RangedWeapon::rifle())
+            .with_armor(Armor::heavy_cloth())
+            .with_armor(Armor::leather())
+            .with_armor(Armor::chain_mail())
+            .with_armor(Armor::plate())
+            .with_armor(Armor::full_plate())
+    }
+
+    pub fn with_weapon(mut self, weapon: Weapon) -> Self {
+        self.weapons.push(weapon);
+        self
+    }
+
+    pub fn with_ranged_weapon(mut self, weapon: RangedWeapon) -> Self {
+        self.ranged_weapons.push(weapon);
+        self
+    }
+
+    pub fn with_armor(mut self, armor: Armor) -> Self {
+        self.armor.push(armor);
+        self
+    }
+
+    pub fn with_spell(mut self, spell: Spell) -> Self {
+        self.spells.push(spell);
+        self
+    }
+
+    pub fn with_creature(mut self, creature: Character) -> Self {
+        self.creatures.push(creature);
+        self
+    }
+
+    /// Only the content `catalog` lists, e.g. after `ContentCatalog::filter`
+    pub fn restricted_to(&self, catalog: &ContentCatalog) -> Self {
+        let allowed = |kind, name: &str| catalog.contains(kind, name);
+        Self {
+            title: self.title.clone(),
+            weapons: self
+                .weapons
+                .iter()
+                .filter(|w| allowed(ContentKind::Weapon, &w.name))
+                .cloned()
+                .collect(),
+            ranged_weapons: self
+                .ranged_weapons
+                .iter()
+                .filter(|w| allowed(ContentKind::Weapon, &w.name))
+                .cloned()
+                .collect(),
+            armor: self
+                .armor
+                .iter()
+                .filter(|a| allowed(ContentKind::Armor, &a.name))
+                .cloned()
+                .collect(),
+            spells: self
+                .spells
+                .iter()
+                .filter(|s| allowed(ContentKind::Spell, &s.name))
+                .cloned()
+                .collect(),
+            creatures: self
+                .creatures
+                .iter()
+                .filter(|c| allowed(ContentKind::Creature, &c.name))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Check if the compendium has no entries at all
+    pub fn is_empty(&self) -> bool {
+        self.weapons.is_empty()
+            && self.ranged_weapons.is_empty()
+            && self.armor.is_empty()
+            && self.spells.is_empty()
+            && self.creatures.is_empty()
+    }
+
+    /// Render the compendium in the requested format
+    pub fn export(&self, format: CompendiumFormat) -> String {
+        match format {
+            CompendiumFormat::Markdown => self.to_markdown(),
+            CompendiumFormat::Html => self.to_html(),
+        }
+    }
+
+    /// Render the compendium as a Markdown document with one table per section
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.title);
+        for table in self.tables() {
+            out.push('\n');
+            out.push_str(&table.to_markdown());
+        }
+        out
+    }
+
+    /// Render the compendium as a standalone HTML document
+    pub fn to_html(&self) -> String {
+        let title = escape_html(&self.title);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n",
+            title, title
+        );
+        for table in self.tables() {
+            out.push_str(&table.to_html());
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Build the non-empty section tables in document order
+    fn tables(&self) -> Vec<Table> {
+        let mut tables = Vec::new();
+
+        if !self.weapons.is_empty() {
+            tables.push(Table {
+                heading: "Melee Weapons",
+                headers: &["Name", "Impact", "Damage"],
+                rows: self
+                    .weapons
+                    .iter()
+                    .map(|w| vec![w.name.clone(), w.impact.to_string(), w.damage.to_string()])
+                    .collect(),
+            });
+        }
+
+        if !self.ranged_weapons.is_empty() {
+            tables.push(Table {
+                heading: "Ranged Weapons",
+                headers: &[
+                    "Name",
+                    "Damage",
+                    "Penetration",
+                    "Point Blank (m)",
+                    "Max Range (m)",
+                    "Preparation",
+                    "Rate of Fire",
+                ],
+                rows: self
+                    .ranged_weapons
+                    .iter()
+                    .map(|w| {
+                        vec![
+                            w.name.clone(),
+                            w.damage.to_string(),
+                            w.penetration.to_string(),
+                            w.point_blank_range.to_string(),
+                            w.max_range.to_string(),
+                            w.preparation_time.to_string(),
+                            w.rate_of_fire.to_string(),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+
+        if !self.armor.is_empty() {
+            tables.push(Table {
+                heading: "Armor",
+                headers: &["Name", "Type", "Protection", "Movement Penalty"],
+                rows: self
+                    .armor
+                    .iter()
+                    .map(|a| {
+                        vec![
+                            a.name.clone(),
+                            a.armor_type.to_string(),
+                            a.protection.to_string(),
+                            a.movement_penalty.to_string(),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+
+        if !self.spells.is_empty() {
+            tables.push(Table {
+                heading: "Spells",
+                headers: &[
+                    "Name",
+                    "Branch",
+                    "Difficulty",
+                    "Preparation (min)",
+                    "Casting (segments)",
+                    "Range",
+                    "Duration",
+                ],
+                rows: self
+                    .spells
+                    .iter()
+                    .map(|s| {
+                        vec![
+                            s.name.clone(),
+                            s.branch.to_string(),
+                            s.difficulty.to_string(),
+                            s.preparation_time.to_string(),
+                            s.casting_time.to_string(),
+                            s.range.to_string(),
+                            s.duration.to_string(),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+
+        if !self.creatures.is_empty() {
+            tables.push(Table {
+                heading: "Creatures",
+                headers: &[
+                    "Name",
+                    "STR",
+                    "DEX",
+                    "CON",
+                    "REA",
+                    "INT",
+                    "WIL",
+                    "CHA",
+                    "PER",
+                    "EMP",
+                    "Weapon Skill",
+                    "Dodge",
+                    "Weapon",
+                    "Armor",
+                ],
+                rows: self
+                    .creatures
+                    .iter()
+                    .map(|c| {
+                        let a = &c.attributes;
+                        vec![
+                            c.name.clone(),
+                            a.strength.to_string(),
+                            a.dexterity.to_string(),
+                            a.constitution.to_string(),
+                            a.reason.to_string(),
+                            a.intuition.to_string(),
+                            a.willpower.to_string(),
+                            a.charisma.to_string(),
+                            a.perception.to_string(),
+                            a.empathy.to_string(),
+                            c.weapon_skill().to_string(),
+                            c.dodge_skill().to_string(),
+                            c.weapon.name.clone(),
+                            c.armor.name.clone(),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+
+        tables
+    }
+}
+
+/// A single rendered section of the compendium
+struct Table {
+    heading: &'static str,
+    headers: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn to_markdown(&self) -> String {
+        let mut out = format!("## {}\n\n", self.heading);
+        out.push_str(&format!("| {} |\n", self.headers.join(" | ")));
+        out.push_str(&format!(
+            "|{}\n",
+            self.headers.iter().map(|_| " --- |").collect::<String>()
+        ));
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = format!("<h2>{}</h2>\n<table>\n<thead>\n<tr>", self.heading);
+        for header in self.headers {
+            out.push_str(&format!("<th>{}</th>", header));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+        for row in &self.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</tbody>\n</table>\n");
+        out
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::catalog::TaggedContent;
+    use crate::modules::magic::{
+        MagicBranch, SpellComponents, SpellDifficulty, SpellDuration, SpellRange,
+    };
+    use crate::Attributes;
+
+    fn detect_magic() -> Spell {
+        Spell {
+            name: "Detect Magic".to_string(),
+            branch: MagicBranch::Divination,
+            difficulty: SpellDifficulty::Easy,
+            preparation_time: 5,
+            casting_time: 1,
+            range: SpellRange::Short(10),
+            duration: SpellDuration::Minutes(10),
+            components: SpellComponents::default(),
+        }
+    }
+
+    #[test]
+    fn test_standard_compendium_markdown() {
+        let md = Compendium::standard().to_markdown();
+
+        assert!(md.starts_with("# Steelkilt Compendium\n"));
+        assert!(md.contains("## Melee Weapons"));
+        assert!(md.contains("| Long Sword | Medium | 5 |"));
+        assert!(md.contains("## Ranged Weapons"));
+        assert!(md.contains("| Long Bow | 6 | 0 | 30 | 120 | 3 | 1 |"));
+        assert!(md.contains("| Chain Mail | Chain | 3 | -1 |"));
+
+        // Sections without entries are omitted
+        assert!(!md.contains("## Spells"));
+        assert!(!md.contains("## Creatures"));
+    }
+
+    #[test]
+    fn test_spell_and_creature_rows() {
+        let goblin = Character::new(
+            "Goblin",
+            Attributes::new(4, 7, 4, 3, 5, 3, 2, 6, 2),
+            4,
+            6,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+        let md = Compendium::new("Bestiary")
+            .with_spell(detect_magic())
+            .with_creature(goblin)
+            .to_markdown();
+
+        assert!(
+            md.contains("| Detect Magic | Divination | Easy | 5 | 1 | Short (10m) | 10 minutes |")
+        );
+        assert!(
+            md.contains("| Goblin | 4 | 7 | 4 | 3 | 5 | 3 | 2 | 6 | 2 | 4 | 6 | Dagger | None |")
+        );
+    }
+
+    #[test]
+    fn test_html_export_escapes_content() {
+        let html = Compendium::new("Arms & Armor")
+            .with_weapon(Weapon::new("<Cursed> Blade", crate::WeaponImpact::Small))
+            .export(CompendiumFormat::Html);
+
+        assert!(html.contains("<title>Arms &amp; Armor</title>"));
+        assert!(html.contains("<td>&lt;Cursed&gt; Blade</td>"));
+        assert!(html.contains("<th>Impact</th>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_restricted_to_tagged_content() {
+        let medieval = ContentCatalog::new()
+            .with_entry(
+                TaggedContent::new(ContentKind::Weapon, "Long Sword").with_tags(&["medieval"]),
+            )
+            .with_entry(
+                TaggedContent::new(ContentKind::Weapon, "Long Bow").with_tags(&["medieval"]),
+            )
+            .with_entry(TaggedContent::new(ContentKind::Weapon, "Rifle").with_tags(&["firearm"]))
+            .with_entry(
+                TaggedContent::new(ContentKind::Armor, "Chain Mail").with_tags(&["medieval"]),
+            )
+            .filter(&["medieval"], 1..=3);
+        let restricted = Compendium::standard().restricted_to(&medieval);
+
+        assert_eq!(restricted.title, "Steelkilt Compendium");
+        assert_eq!(
+            restricted
+                .weapons
+                .iter()
+                .map(|w| w.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Long Sword"]
+        );
+        assert_eq!(
+            restricted
+                .ranged_weapons
+                .iter()
+                .map(|w| w.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Long Bow"]
+        );
+        assert_eq!(restricted.armor.len(), 1);
+        assert!(Compendium::standard()
+            .restricted_to(&ContentCatalog::new())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_empty_compendium() {
+        let compendium = Compendium::new("Empty");
+        assert!(compendium.is_empty());
+        assert_eq!(compendium.to_markdown(), "# Empty\n");
+        assert!(!Compendium::standard().is_empty());
+    }
+}
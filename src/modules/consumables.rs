@@ -0,0 +1,246 @@
+//! Scroll and potion consumables carrying spells
+//!
+//! A `Consumable` holds a spell cast at a fixed quality when it was made.
+//! Anyone can drink a potion; reading a scroll needs at least
+//! `SCROLL_MIN_LORE` in the spell's branch. Both are used up on use.
+//! Potions are brewed by alchemists and scrolls scribed by anyone who can
+//! cast the spell; either way the maker pays the casting cost once.
+
+use crate::modules::magic::{CastingResult, MagicBranch, MagicError, MagicUser, Spell};
+use crate::Character;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Lore level in the spell's branch needed to read a scroll
+pub const SCROLL_MIN_LORE: i32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConsumableKind {
+    Potion,
+    Scroll,
+}
+
+impl fmt::Display for ConsumableKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConsumableKind::Potion => write!(f, "Potion"),
+            ConsumableKind::Scroll => write!(f, "Scroll"),
+        }
+    }
+}
+
+/// A single-use item with a spell locked in at a fixed quality
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Consumable {
+    pub kind: ConsumableKind,
+    pub spell: Spell,
+    /// Casting quality (margin over the spell's target) when it was made
+    pub quality: i32,
+}
+
+impl fmt::Display for Consumable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} ({:+})",
+            self.kind, self.spell.name, self.quality
+        )
+    }
+}
+
+impl Consumable {
+    pub fn potion(spell: Spell, quality: i32) -> Self {
+        Self {
+            kind: ConsumableKind::Potion,
+            spell,
+            quality,
+        }
+    }
+
+    pub fn scroll(spell: Spell, quality: i32) -> Self {
+        Self {
+            kind: ConsumableKind::Scroll,
+            spell,
+            quality,
+        }
+    }
+
+    /// Check whether `user` is able to use this item
+    pub fn can_use(&self, user: &Character) -> Result<(), MagicError> {
+        if self.kind == ConsumableKind::Potion {
+            return Ok(());
+        }
+        let branch = self.spell.branch;
+        let lore = user
+            .magic
+            .as_ref()
+            .and_then(|m| m.lores.get(&branch))
+            .ok_or(MagicError::LoreNotKnown(branch))?;
+        if lore.level < SCROLL_MIN_LORE {
+            return Err(MagicError::InsufficientLore {
+                required: SCROLL_MIN_LORE,
+                available: lore.level,
+            });
+        }
+        Ok(())
+    }
+
+    /// Drink or read the item, releasing its spell at the stored quality
+    ///
+    /// The item is consumed; no roll is made and the user gains no
+    /// exhaustion. If `user` can't use it, it is handed back with the error.
+    #[allow(clippy::result_large_err)]
+    pub fn consume(self, user: &Character) -> Result<CastingResult, (Self, MagicError)> {
+        if let Err(e) = self.can_use(user) {
+            return Err((self, e));
+        }
+        let target = self.spell.difficulty.base_target();
+        Ok(CastingResult {
+            spell_name: self.spell.name,
+            success: true,
+            quality: self.quality,
+            total: target + self.quality,
+            target,
+            aimed_at: None,
+        })
+    }
+}
+
+/// Brew a potion of a known spell; needs the Alchemy lore
+///
+/// The brew is a normal casting of the spell (with its exhaustion). A failed
+/// casting ruins the ingredients and yields `None`.
+pub fn brew_potion(
+    alchemist: &mut MagicUser,
+    spell_name: &str,
+    roll: i32,
+) -> Result<Option<Consumable>, MagicError> {
+    if !alchemist.lores.contains_key(&MagicBranch::Alchemy) {
+        return Err(MagicError::LoreNotKnown(MagicBranch::Alchemy));
+    }
+    craft(alchemist, spell_name, roll, Consumable::potion)
+}
+
+/// Scribe a scroll of a known spell; a failed casting spoils the parchment
+pub fn scribe_scroll(
+    caster: &mut MagicUser,
+    spell_name: &str,
+    roll: i32,
+) -> Result<Option<Consumable>, MagicError> {
+    craft(caster, spell_name, roll, Consumable::scroll)
+}
+
+fn craft(
+    maker: &mut MagicUser,
+    spell_name: &str,
+    roll: i32,
+    make: fn(Spell, i32) -> Consumable,
+) -> Result<Option<Consumable>, MagicError> {
+    let result = maker.cast_spell(spell_name, roll)?;
+    if !result.success {
+        return Ok(None);
+    }
+    let spell = maker.spells[spell_name].spell.clone();
+    Ok(Some(make(spell, result.quality)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::magic::{SpellComponents, SpellDifficulty, SpellDuration, SpellRange};
+    use crate::{Armor, Attributes, Weapon};
+
+    fn healing() -> Spell {
+        Spell {
+            name: "Mend Flesh".to_string(),
+            branch: MagicBranch::Alchemy,
+            difficulty: SpellDifficulty::Normal,
+            preparation_time: 10,
+            casting_time: 1,
+            range: SpellRange::Touch,
+            duration: SpellDuration::Instant,
+            components: SpellComponents::default(),
+        }
+    }
+
+    fn commoner() -> Character {
+        Character::new(
+            "Tam",
+            Attributes::new(6, 6, 6, 5, 5, 5, 5, 5, 5),
+            4,
+            4,
+            Weapon::dagger(),
+            Armor::none(),
+        )
+    }
+
+    #[test]
+    fn test_brewed_potion_keeps_quality_and_anyone_can_drink() {
+        let mut alchemist = MagicUser::new(6);
+        alchemist.add_lore(MagicBranch::Alchemy, 4);
+        alchemist.learn_spell(healing(), 3).unwrap();
+
+        // 3 skill + 6 empathy + 4 roll = 13 vs Normal 10
+        let potion = brew_potion(&mut alchemist, "Mend Flesh", 4)
+            .unwrap()
+            .unwrap();
+        assert_eq!(potion.kind, ConsumableKind::Potion);
+        assert_eq!(potion.quality, 3);
+        assert_eq!(alchemist.exhaustion_points, 2);
+
+        assert!(brew_potion(&mut alchemist, "Mend Flesh", -10)
+            .unwrap()
+            .is_none());
+
+        let result = potion.consume(&commoner()).unwrap();
+        assert!(result.success);
+        assert_eq!(result.quality, 3);
+        assert_eq!(result.total, 13);
+    }
+
+    #[test]
+    fn test_scroll_requires_lore() {
+        let mut scribe = MagicUser::new(6);
+        scribe.add_lore(MagicBranch::Alchemy, 4);
+        scribe.learn_spell(healing(), 3).unwrap();
+        let scroll = scribe_scroll(&mut scribe, "Mend Flesh", 5)
+            .unwrap()
+            .unwrap();
+
+        let (scroll, err) = scroll.consume(&commoner()).unwrap_err();
+        assert_eq!(err, MagicError::LoreNotKnown(MagicBranch::Alchemy));
+
+        let mut reader = commoner();
+        let mut magic = MagicUser::new(5);
+        magic.add_lore(MagicBranch::Alchemy, 0);
+        reader.magic = Some(magic);
+        let (scroll, err) = scroll.consume(&reader).unwrap_err();
+        assert_eq!(
+            err,
+            MagicError::InsufficientLore {
+                required: 1,
+                available: 0
+            }
+        );
+
+        reader
+            .magic
+            .as_mut()
+            .unwrap()
+            .add_lore(MagicBranch::Alchemy, 1);
+        assert_eq!(scroll.consume(&reader).unwrap().quality, 4);
+    }
+
+    #[test]
+    fn test_potion_brewing_needs_alchemy() {
+        let mut mage = MagicUser::new(6);
+        assert_eq!(
+            brew_potion(&mut mage, "Mend Flesh", 5).unwrap_err(),
+            MagicError::LoreNotKnown(MagicBranch::Alchemy)
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Dice sources for combat resolution
+//!
+//! Every roll in `combat_round_with_dice` comes from a `Dice`. `RandomDice`
+//! is the normal thread RNG; `AverageDice` always rolls the (rounded down)
+//! expected value and `ScriptedDice` replays a fixed sequence, so
+//! golden-master tests and tutorials give the same outcome every run.
+//! `Ruleset::dice` selects random, average or seeded rolls for
+//! `combat_round_with_rules`; a seeded stream records its position, so a saved
+//! encounter resumes with the same rolls it would have made. Hit locations,
+//! injury tables and encounter checks take a `Dice` too (the `_with`
+//! variants), so a whole run can be replayed from one seed.
+
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Expected value of a d10 (5.5), rounded down
+pub const AVERAGE_D10: i32 = 5;
+
+/// A source of d10 rolls
+pub trait Dice {
+    fn d10(&mut self) -> i32;
+
+    /// A number in `0..bound` for weighted tables
+    fn below(&mut self, bound: u32) -> u32 {
+        digits_below(self, bound)
+    }
+}
+
+/// Read d10s as decimal digits (two more than `bound` needs, keeping the
+/// skew small) and reduce into `0..bound`
+fn digits_below<D: Dice + ?Sized>(dice: &mut D, bound: u32) -> u32 {
+    if bound <= 1 {
+        return 0;
+    }
+    let mut value: u64 = 0;
+    let mut span: u64 = 1;
+    while span < bound as u64 * 100 {
+        value = value * 10 + (dice.d10() - 1) as u64;
+        span *= 10;
+    }
+    (value % bound as u64) as u32
+}
+
+/// Real random rolls (see `crate::d10`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomDice;
+
+impl Dice for RandomDice {
+    fn d10(&mut self) -> i32 {
+        crate::d10()
+    }
+
+    fn below(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            return 0;
+        }
+        rand::thread_rng().gen_range(0..bound)
+    }
+}
+
+/// Every die shows `AVERAGE_D10`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AverageDice;
+
+impl Dice for AverageDice {
+    fn d10(&mut self) -> i32 {
+        AVERAGE_D10
+    }
+}
+
+/// Replays `rolls` in order, starting over when they run out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptedDice {
+    rolls: Vec<i32>,
+    next: usize,
+}
+
+impl ScriptedDice {
+    /// Script of d10 results; an empty script rolls `AVERAGE_D10`
+    pub fn new(rolls: impl IntoIterator<Item = i32>) -> Self {
+        Self {
+            rolls: rolls.into_iter().map(|r| r.clamp(1, 10)).collect(),
+            next: 0,
+        }
+    }
+
+    /// Number of dice rolled so far
+    pub fn rolled(&self) -> usize {
+        self.next
+    }
+}
+
+impl Dice for ScriptedDice {
+    fn d10(&mut self) -> i32 {
+        if self.rolls.is_empty() {
+            return AVERAGE_D10;
+        }
+        let roll = self.rolls[self.next % self.rolls.len()];
+        self.next += 1;
+        roll
+    }
+}
+
+/// How `combat_round_with_rules` rolls its dice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiceMode {
+    #[default]
+    Random,
+    Average,
+    /// A repeatable stream; `rolled` is how far into it play has got
+    Seeded {
+        seed: u64,
+        rolled: u64,
+    },
+}
+
+impl DiceMode {
+    /// Start a repeatable stream of rolls
+    pub fn seeded(seed: u64) -> Self {
+        DiceMode::Seeded { seed, rolled: 0 }
+    }
+}
+
+impl Dice for DiceMode {
+    fn d10(&mut self) -> i32 {
+        match self {
+            DiceMode::Random => RandomDice.d10(),
+            DiceMode::Average => AverageDice.d10(),
+            DiceMode::Seeded { seed, rolled } => {
+                let roll = stream_roll(*seed, *rolled);
+                *rolled += 1;
+                roll
+            }
+        }
+    }
+
+    fn below(&mut self, bound: u32) -> u32 {
+        match self {
+            DiceMode::Random => RandomDice.below(bound),
+            DiceMode::Average | DiceMode::Seeded { .. } => digits_below(self, bound),
+        }
+    }
+}
+
+/// The d10 at position `index` of the stream for `seed` (SplitMix64), so a
+/// saved position picks up exactly where it left off (and batches can roll
+/// many positions at once)
+pub(crate) fn stream_roll(seed: u64, index: u64) -> i32 {
+    let mut z = seed.wrapping_add((index + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z % 10) as i32 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripted_dice_cycle() {
+        let mut dice = ScriptedDice::new([3, 12, 7]);
+        let rolls: Vec<i32> = (0..4).map(|_| dice.d10()).collect();
+        assert_eq!(rolls, vec![3, 10, 7, 3]);
+        assert_eq!(dice.rolled(), 4);
+        assert_eq!(ScriptedDice::new([]).d10(), AVERAGE_D10);
+    }
+
+    #[test]
+    fn test_dice_modes() {
+        assert_eq!(DiceMode::Average.d10(), AVERAGE_D10);
+        let roll = DiceMode::default().d10();
+        assert!((1..=10).contains(&roll));
+
+        let mut seeded = DiceMode::seeded(99);
+        let first: Vec<i32> = (0..20).map(|_| seeded.d10()).collect();
+        assert!(first.iter().all(|r| (1..=10).contains(r)));
+        assert_eq!(
+            seeded,
+            DiceMode::Seeded {
+                seed: 99,
+                rolled: 20
+            }
+        );
+        let mut resumed = DiceMode::Seeded {
+            seed: 99,
+            rolled: 10,
+        };
+        let rest: Vec<i32> = (0..10).map(|_| resumed.d10()).collect();
+        assert_eq!(rest, first[10..]);
+    }
+
+    #[test]
+    fn test_below_is_in_range_and_replays() {
+        for bound in [0, 1, 7, 10, 36, 1_000] {
+            let mut seeded = DiceMode::seeded(5);
+            let rolls: Vec<u32> = (0..50).map(|_| seeded.below(bound)).collect();
+            assert!(rolls.iter().all(|&r| r < bound.max(1)));
+            let mut replay = DiceMode::seeded(5);
+            assert!(rolls.iter().all(|&r| r == replay.below(bound)));
+            assert!(RandomDice.below(bound) < bound.max(1));
+        }
+        // d10s 3, 7, 2, 9 read as 2618, and 2618 % 36 = 26
+        assert_eq!(ScriptedDice::new([3, 7, 2, 9]).below(36), 26);
+        assert_eq!(AverageDice.below(36), 4444 % 36);
+    }
+}
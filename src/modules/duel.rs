@@ -0,0 +1,365 @@
+//! Formal duels between two characters
+//!
+//! A `Duel` runs one-on-one exchanges until its `StopCondition` is met —
+//! first blood, the first severe wound, a yield or death — and records a
+//! `DuelVerdict`. `HonorRules` forbid ranged weapons or magic; a duelist who
+//! declares a forbidden action forfeits.
+
+use crate::modules::ai::recommend_defense;
+use crate::modules::ruleset::Ruleset;
+use crate::{combat_round_with_rules, Character, CombatResult, WoundLevel};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Wound penalty at which a duelist yields under `StopCondition::Yield`
+pub const DEFAULT_YIELD_PENALTY: i32 = -4;
+
+/// When a duel ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StopCondition {
+    /// Any wound
+    FirstBlood,
+    /// A Severe or Critical wound
+    FirstSevere,
+    /// A duelist's wound penalty reaches `penalty` (or they can no longer fight)
+    Yield { penalty: i32 },
+    /// One duelist dies
+    Death,
+}
+
+impl StopCondition {
+    /// Check whether the duel is decided after `result` left `defender` as it is
+    pub fn is_met(&self, result: &CombatResult, defender: &Character) -> bool {
+        if !defender.is_alive() {
+            return true;
+        }
+        match self {
+            StopCondition::FirstBlood => result.wound_level.is_some(),
+            StopCondition::FirstSevere => result.wound_level >= Some(WoundLevel::Severe),
+            StopCondition::Yield { penalty } => {
+                !defender.can_act() || defender.wounds.movement_penalty() <= *penalty
+            }
+            StopCondition::Death => false,
+        }
+    }
+}
+
+impl fmt::Display for StopCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StopCondition::FirstBlood => write!(f, "first blood"),
+            StopCondition::FirstSevere => write!(f, "first severe wound"),
+            StopCondition::Yield { .. } => write!(f, "yield"),
+            StopCondition::Death => write!(f, "death"),
+        }
+    }
+}
+
+/// What a duelist intends to do on their turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DuelAction {
+    Melee,
+    Ranged,
+    Magic,
+}
+
+impl fmt::Display for DuelAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DuelAction::Melee => write!(f, "melee"),
+            DuelAction::Ranged => write!(f, "ranged attack"),
+            DuelAction::Magic => write!(f, "magic"),
+        }
+    }
+}
+
+/// Actions the duel's code of honor forbids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HonorRules {
+    pub no_ranged: bool,
+    pub no_magic: bool,
+}
+
+impl Default for HonorRules {
+    fn default() -> Self {
+        Self {
+            no_ranged: true,
+            no_magic: true,
+        }
+    }
+}
+
+impl HonorRules {
+    /// Anything goes
+    pub fn none() -> Self {
+        Self {
+            no_ranged: false,
+            no_magic: false,
+        }
+    }
+
+    pub fn permits(&self, action: DuelAction) -> bool {
+        match action {
+            DuelAction::Melee => true,
+            DuelAction::Ranged => !self.no_ranged,
+            DuelAction::Magic => !self.no_magic,
+        }
+    }
+}
+
+/// How a duel was decided
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DuelVerdict {
+    Victory {
+        winner: String,
+        loser: String,
+        condition: StopCondition,
+    },
+    /// A duelist broke the honor rules
+    Forfeit {
+        winner: String,
+        offender: String,
+        breach: DuelAction,
+    },
+    /// The round limit passed without a decision
+    Draw,
+}
+
+impl fmt::Display for DuelVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DuelVerdict::Victory {
+                winner,
+                loser,
+                condition,
+            } => write!(f, "{} defeats {} ({})", winner, loser, condition),
+            DuelVerdict::Forfeit {
+                winner,
+                offender,
+                breach,
+            } => write!(f, "{} forfeits to {} ({})", offender, winner, breach),
+            DuelVerdict::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
+/// Errors from declaring a duel action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuelError {
+    /// The duel already has a verdict
+    Decided,
+    /// The action breaks the honor rules; the duelist has forfeited
+    Dishonorable(DuelAction),
+}
+
+impl fmt::Display for DuelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DuelError::Decided => write!(f, "The duel is already decided"),
+            DuelError::Dishonorable(action) => {
+                write!(f, "Dishonorable action: {} is forbidden", action)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DuelError {}
+
+/// A one-on-one fight under formal rules
+#[derive(Debug, Clone)]
+pub struct Duel {
+    pub duelists: [Character; 2],
+    pub stop: StopCondition,
+    pub honor: HonorRules,
+    pub rules: Ruleset,
+    /// The exchange about to be fought (starts at 1)
+    pub round: u32,
+    pub verdict: Option<DuelVerdict>,
+}
+
+impl Duel {
+    pub fn new(first: Character, second: Character, stop: StopCondition) -> Self {
+        Self {
+            duelists: [first, second],
+            stop,
+            honor: HonorRules::default(),
+            rules: Ruleset::default(),
+            round: 1,
+            verdict: None,
+        }
+    }
+
+    pub fn with_honor(mut self, honor: HonorRules) -> Self {
+        self.honor = honor;
+        self
+    }
+
+    pub fn with_rules(mut self, rules: Ruleset) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn is_decided(&self) -> bool {
+        self.verdict.is_some()
+    }
+
+    /// Declare an action for duelist 0 or 1; a forbidden one forfeits the duel
+    pub fn declare(&mut self, duelist: usize, action: DuelAction) -> Result<(), DuelError> {
+        if self.is_decided() {
+            return Err(DuelError::Decided);
+        }
+        if self.honor.permits(action) {
+            return Ok(());
+        }
+        self.verdict = Some(DuelVerdict::Forfeit {
+            winner: self.duelists[1 - duelist].name.clone(),
+            offender: self.duelists[duelist].name.clone(),
+            breach: action,
+        });
+        Err(DuelError::Dishonorable(action))
+    }
+
+    /// One exchange: each duelist attacks in DEX order until the stop condition is met
+    pub fn exchange(&mut self) -> Vec<CombatResult> {
+        let mut results = Vec::new();
+        if self.is_decided() {
+            return results;
+        }
+        let first = usize::from(
+            self.duelists[1].attributes.dexterity > self.duelists[0].attributes.dexterity,
+        );
+        for attacker in [first, 1 - first] {
+            let (a, d) = self.duelists.split_at_mut(1);
+            let (attacker_c, defender_c) = if attacker == 0 {
+                (&mut a[0], &mut d[0])
+            } else {
+                (&mut d[0], &mut a[0])
+            };
+            if !attacker_c.can_act() {
+                continue;
+            }
+            let defense = recommend_defense(defender_c, attacker_c);
+            let result = combat_round_with_rules(attacker_c, defender_c, defense, &self.rules);
+            let decided = self.stop.is_met(&result, defender_c);
+            results.push(result);
+            if decided {
+                self.verdict = Some(DuelVerdict::Victory {
+                    winner: self.duelists[attacker].name.clone(),
+                    loser: self.duelists[1 - attacker].name.clone(),
+                    condition: self.stop,
+                });
+                break;
+            }
+        }
+        self.round += 1;
+        results
+    }
+
+    /// Fight exchanges until a verdict, or a `Draw` after `max_rounds`
+    pub fn run(&mut self, max_rounds: u32) -> &DuelVerdict {
+        for _ in 0..max_rounds {
+            if self.is_decided() {
+                break;
+            }
+            self.exchange();
+        }
+        self.verdict.get_or_insert(DuelVerdict::Draw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::modifiers::{Modifier, ModifierTarget};
+    use crate::{Armor, Attributes, Damage, Weapon};
+
+    fn duelist(name: &str) -> Character {
+        Character::new(
+            name,
+            Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+            6,
+            4,
+            Weapon::long_sword(),
+            Armor::leather(),
+        )
+    }
+
+    #[test]
+    fn test_first_blood_verdict() {
+        let mut champion = duelist("Aldric");
+        champion.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        champion.add_modifier(Modifier::new("Fury", ModifierTarget::Attack, 100));
+        let mut duel = Duel::new(champion, duelist("Grimwald"), StopCondition::FirstBlood);
+
+        let verdict = duel.run(5).clone();
+        assert_eq!(
+            verdict,
+            DuelVerdict::Victory {
+                winner: "Aldric".to_string(),
+                loser: "Grimwald".to_string(),
+                condition: StopCondition::FirstBlood,
+            }
+        );
+        assert_eq!(duel.round, 2);
+        assert!(duel.exchange().is_empty());
+    }
+
+    #[test]
+    fn test_honor_rules_and_draw() {
+        let mut duel = Duel::new(duelist("Aldric"), duelist("Grimwald"), StopCondition::Death);
+        assert_eq!(duel.declare(0, DuelAction::Melee), Ok(()));
+        assert_eq!(
+            duel.declare(1, DuelAction::Magic),
+            Err(DuelError::Dishonorable(DuelAction::Magic))
+        );
+        assert_eq!(
+            duel.verdict.as_ref().unwrap().to_string(),
+            "Grimwald forfeits to Aldric (magic)"
+        );
+        assert_eq!(duel.declare(0, DuelAction::Melee), Err(DuelError::Decided));
+
+        let mut a = duelist("Aldric");
+        let mut b = duelist("Grimwald");
+        for c in [&mut a, &mut b] {
+            c.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        }
+        let mut duel = Duel::new(a, b, StopCondition::FirstBlood).with_honor(HonorRules::none());
+        assert!(duel.declare(0, DuelAction::Ranged).is_ok());
+        assert_eq!(duel.run(3), &DuelVerdict::Draw);
+    }
+
+    #[test]
+    fn test_yield_on_wound_penalty() {
+        let mut loser = duelist("Grimwald");
+        loser.wounds.add_wound(WoundLevel::Severe);
+        let stop = StopCondition::Yield {
+            penalty: DEFAULT_YIELD_PENALTY,
+        };
+        let miss = CombatResult {
+            attacker: "Aldric".to_string(),
+            defender: "Grimwald".to_string(),
+            attack_roll: 0,
+            defense_roll: 0,
+            hit: false,
+            damage: Damage::clamped(0),
+            wound_level: None,
+            defender_died: false,
+            defender_dying: false,
+            location: None,
+            #[cfg(feature = "hit-location")]
+            armor_events: Vec::new(),
+            attack_breakdown: crate::RollBreakdown::default(),
+            defense_breakdown: crate::RollBreakdown::default(),
+        };
+        assert!(!stop.is_met(&miss, &loser));
+        loser.wounds.add_wound(WoundLevel::Severe);
+        assert!(stop.is_met(&miss, &loser));
+        assert!(!StopCondition::FirstSevere.is_met(&miss, &loser));
+    }
+}
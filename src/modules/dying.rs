@@ -0,0 +1,51 @@
+//! Dying state and death checks
+//!
+//! With `Ruleset::dying_state` enabled, a lethal wound leaves the character
+//! dying rather than dead. Each round the character makes a CON check
+//! (d10 ≤ CON); too many failures and they die, unless an ally stabilizes them
+//! first with first aid or healing magic.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Target for a first-aid check (healer skill + d10)
+pub const FIRST_AID_DIFFICULTY: i32 = 12;
+
+/// Progress of a dying character toward death
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DyingState {
+    pub failed_checks: u32,
+    pub rounds: u32,
+}
+
+impl DyingState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Outcome of a single death check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathCheck {
+    /// The check succeeded; the character clings to life
+    Holding,
+    /// The check failed; `remaining` more failures are survivable
+    Failed { remaining: u32 },
+    /// The final check failed
+    Died,
+}
+
+impl fmt::Display for DeathCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeathCheck::Holding => write!(f, "Holding on"),
+            DeathCheck::Failed { remaining } => {
+                write!(f, "Failed ({} more failure(s) survivable)", remaining)
+            }
+            DeathCheck::Died => write!(f, "Died"),
+        }
+    }
+}
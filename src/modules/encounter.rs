@@ -0,0 +1,3252 @@
+//! Multi-combatant encounters
+//!
+//! An `Encounter` holds any number of combatants split into teams and runs
+//! them round by round: each combatant that can act picks the enemy with the
+//! highest `ai::target_priority`, closes in on a battlefield if one is set,
+//! and strikes with `combat_round_in_context`, so its `set_maneuver` stance
+//! and the target's count in every exchange.
+//!
+//! ## Who acts
+//!
+//! Bosses change phase as they are wounded and spend legendary actions after
+//! other combatants' turns. Minions go down to any wound and act as one batch
+//! per team, sharing a single target choice, so large mobs stay cheap to
+//! resolve. Companion animals act right after their owner, follow the owner's
+//! last command and may bolt once wounded or left without a handler.
+//! Combatants caught with a sheathed blade or an unloaded bow (`Readiness`)
+//! act after everyone who is set, at a penalty, in their first round.
+//!
+//! ## Guarding and retreat
+//!
+//! A combatant guarding an adjacent ally gives up attacking to parry blows
+//! aimed at that ally. Combatants can `retreat`: a fighting withdrawal backs
+//! away slowly with a defense bonus, while flight runs for the edge of the
+//! field undefended. Either way, engaged enemies get a parting blow, and a
+//! fugitive who leaves the field is reported with the enemies fast enough to
+//! give chase.
+//!
+//! ## The battlefield
+//!
+//! Attacks from higher ground get `HIGH_GROUND_BONUS`, and closing in from
+//! above adds `DOWNHILL_CHARGE_BONUS` damage to that turn's blow. A defender
+//! holding a chokepoint can only be engaged by as many attackers per round as
+//! the chokepoint allows, and shield bearers standing beside a shielded
+//! teammate parry at `SHIELD_WALL_BONUS`.
+//!
+//! ## Missiles
+//!
+//! A combatant wielding a ranged weapon (`Wielding::Ranged`) holds position
+//! and shoots instead of closing in; `switch_weapon` changes to the melee
+//! weapon and back, either spending a round or dropping the bow. Shooting
+//! with an enemy in reach costs `ENGAGED_SHOT_PENALTY`, and with
+//! `Ruleset::shots_provoke` each such enemy gets a free blow first. A shooter
+//! on `overwatch` holds their action instead, and interrupts the first enemy
+//! to step into the watched cells with a snap shot at `SNAP_SHOT_PENALTY`.
+//! Under `Ruleset::over_penetration` a strong hit from a penetrating weapon
+//! carries on into whoever stands directly behind the target, friend or foe.
+//! All of this needs the `ranged` feature; without it every combatant fights
+//! hand to hand.
+//!
+//! ## Optional rules
+//!
+//! With `Ruleset::blood_loss`, bleeding wounds drain every combatant at the
+//! end of each round; with `Ruleset::hit_locations`, every blow lands on a
+//! rolled location.
+//!
+//! ## Magic
+//!
+//! At the start of each round, casters standing in an anti-magic zone are
+//! dampened or nulled, and summoned combatants inside a null field wink out.
+//! Transportation casters can `blink` across the field, `teleport` out of the
+//! fight, or `ready_blink` to vanish from the path of the next blow, and
+//! necromancers can `raise_dead` the fallen onto their own side.
+
+use crate::modules::ai::{recommend_defense, target_priority};
+use crate::modules::boss::Boss;
+use crate::modules::companions::{Companion, CompanionCommand};
+use crate::modules::dice::Dice;
+use crate::modules::followers::Morale;
+use crate::modules::hit_location::HitLocation;
+#[cfg(feature = "magic")]
+use crate::modules::magic::{SpellTarget, TargetInfo};
+use crate::modules::maneuvers::{
+    available_maneuvers, CombatManeuver, CombatStance, ManeuverError, GUARD_PENALTY,
+};
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+#[cfg(feature = "magic")]
+use crate::modules::necromancy::{self, control_limit, necromancy_lore, NecromancyError};
+use crate::modules::pathfinding::{plan_move, MoveGoal, Path};
+#[cfg(feature = "ranged")]
+use crate::modules::positioning::CELL_METERS;
+use crate::modules::positioning::{
+    movement_allowance, AntiMagic, Battlefield, GridPos, DOWNHILL_CHARGE_BONUS, SHIELD_WALL_BONUS,
+};
+#[cfg(feature = "ranged")]
+use crate::modules::ranged_combat::{
+    over_penetrate, ranged_attack_with_dice, Cover, RangedAttackState,
+};
+use crate::modules::ruleset::Ruleset;
+use crate::modules::timing::rounds_to_seconds;
+#[cfg(feature = "magic")]
+use crate::modules::transportation::{check_spell, jump, Displacement, TransportError};
+use crate::modules::turn_timer::{decide_defense, SharedDefenseProvider, TurnTimer};
+use crate::{
+    combat_round_in_context, Character, CombatContext, CombatResult, DefenseAction, WeaponTags,
+    WoundLevel,
+};
+#[cfg(feature = "magic")]
+use crate::{Damage, RollBreakdown};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Index of a combatant within an encounter
+pub type CombatantId = usize;
+
+/// Parry and dodge bonus while making a fighting withdrawal
+pub const WITHDRAW_DEFENSE: i32 = 2;
+/// Extra movement points for a combatant in full flight
+pub const FLIGHT_SPRINT: i32 = 2;
+/// Ranged penalty for shooting with an enemy in melee reach
+pub const ENGAGED_SHOT_PENALTY: i32 = -3;
+/// Ranged penalty for a snap shot taken from overwatch
+pub const SNAP_SHOT_PENALTY: i32 = -2;
+
+/// How prepared a combatant is when the fighting starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Readiness {
+    /// Weapon drawn and set
+    #[default]
+    Set,
+    /// Weapon still in its sheath
+    Sheathed,
+    /// Holding a ranged weapon that still needs loading
+    Unloaded,
+}
+
+impl Readiness {
+    /// Attack and defense penalty for the combatant's first round
+    pub fn first_round_penalty(&self) -> i32 {
+        match self {
+            Readiness::Set => 0,
+            Readiness::Sheathed => -2,
+            Readiness::Unloaded => -3,
+        }
+    }
+
+    /// Set if the ranged weapon is ready with a shot left, otherwise Unloaded
+    #[cfg(feature = "ranged")]
+    pub fn of_ranged(state: &RangedAttackState) -> Self {
+        if state.weapon_ready && state.shots_remaining > 0 {
+            Readiness::Set
+        } else {
+            Readiness::Unloaded
+        }
+    }
+}
+
+/// Which of a combatant's weapons is in hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Wielding {
+    #[default]
+    Melee,
+    Ranged,
+}
+
+/// How a combatant changes weapons mid-fight (see `Encounter::switch_weapon`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WeaponSwitch {
+    /// Put the held weapon away, then draw: costs the round's attack
+    SheatheAndDraw,
+    /// Let the ranged weapon fall and draw at once: the combatant still
+    /// attacks this round, as if unsheathing, but the dropped weapon is gone
+    DropAndDraw,
+}
+
+impl WeaponSwitch {
+    /// Readiness for the round of the switch
+    pub fn readiness(&self) -> Readiness {
+        match self {
+            WeaponSwitch::SheatheAndDraw => Readiness::Set,
+            WeaponSwitch::DropAndDraw => Readiness::Sheathed,
+        }
+    }
+
+    /// Whether the switch takes the combatant's action for the round
+    pub fn costs_action(&self) -> bool {
+        *self == WeaponSwitch::SheatheAndDraw
+    }
+}
+
+/// Something a combatant could be told to do (see `Encounter::legal_actions`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ActionKind {
+    /// Close in and strike with the melee weapon
+    Attack,
+    Shoot,
+    SwitchWeapon,
+    Guard,
+    Overwatch,
+    Withdraw,
+    Flee,
+    CommandCompanion,
+    /// Fight with this maneuver (see `Encounter::set_maneuver`)
+    Maneuver(CombatManeuver),
+}
+
+/// How a combatant leaves melee
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Retreat {
+    /// Back away at half movement, defending at `WITHDRAW_DEFENSE`
+    Withdraw,
+    /// Run for the field edge at full movement plus `FLIGHT_SPRINT`, without defending
+    Flee,
+}
+
+/// Who raised a zombie, and from whose corpse (see `Encounter::raise_dead`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Raising {
+    pub necromancer: CombatantId,
+    pub corpse: CombatantId,
+}
+
+/// A character taking part in an encounter
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Combatant {
+    pub character: Character,
+    pub team: u32,
+    pub boss: Option<Boss>,
+    /// Dropped by any wound; acts in a batch with its team's other minions
+    pub minion: bool,
+    /// Set for animals fighting alongside an owner
+    pub companion: Option<Companion>,
+    /// The combatant a companion fights for (see `Encounter::add_companion`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub owner: Option<CombatantId>,
+    /// Ally this combatant is guarding (see `Encounter::guard`)
+    pub guarding: Option<CombatantId>,
+    /// Set while falling back instead of attacking (see `Encounter::retreat`)
+    pub retreating: Option<Retreat>,
+    /// Left the battlefield by fleeing or, if summoned, winking out
+    pub escaped: bool,
+    /// Becomes `Set` after the combatant's first round
+    pub readiness: Readiness,
+    pub wielding: Wielding,
+    /// Cells watched while holding fire (see `Encounter::overwatch`)
+    pub overwatch: Option<Vec<GridPos>>,
+    /// Missiles left for the ranged weapon; `None` if not counted
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ammo: Option<u32>,
+    /// Chosen maneuver, aiming and recovery (see `Encounter::set_maneuver`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stance: CombatStance,
+    /// Conjured by magic, so it winks out in a null field
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub summoned: bool,
+    /// Transportation spell readied to blink away from the next blow this round
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blink_reaction: Option<String>,
+    /// Set for a corpse raised as a zombie
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub raised: Option<Raising>,
+}
+
+impl Combatant {
+    /// Still able to act and hasn't fled the fight
+    pub fn is_fighting(&self) -> bool {
+        self.character.can_act()
+            && !self.escaped
+            && !self.companion.as_ref().is_some_and(|c| c.fled)
+    }
+
+    /// Make this combatant a copy of `template`, reusing its allocations
+    pub fn reset_to(&mut self, template: &Combatant) {
+        let Combatant {
+            character,
+            team,
+            boss,
+            minion,
+            companion,
+            owner,
+            guarding,
+            retreating,
+            escaped,
+            readiness,
+            wielding,
+            overwatch,
+            ammo,
+            stance,
+            summoned,
+            blink_reaction,
+            raised,
+        } = template;
+        self.character.reset_to(character);
+        self.team = *team;
+        self.boss.clone_from(boss);
+        self.minion = *minion;
+        self.companion.clone_from(companion);
+        self.owner = *owner;
+        self.guarding = *guarding;
+        self.retreating = *retreating;
+        self.escaped = *escaped;
+        self.readiness = *readiness;
+        self.wielding = *wielding;
+        self.overwatch.clone_from(overwatch);
+        self.ammo = *ammo;
+        self.stance.clone_from(stance);
+        self.summoned = *summoned;
+        self.blink_reaction.clone_from(blink_reaction);
+        self.raised = *raised;
+    }
+}
+
+/// Something notable that happened outside the attack results
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EncounterEvent {
+    PhaseChanged {
+        combatant: CombatantId,
+        phase: String,
+    },
+    LegendaryAction {
+        combatant: CombatantId,
+    },
+    CompanionFled {
+        combatant: CombatantId,
+    },
+    /// A guard stepped in to take an attack aimed at their ward
+    Intercepted {
+        guardian: CombatantId,
+        ward: CombatantId,
+    },
+    /// A fleeing combatant left the field; `pursuers` are fast enough to chase
+    Escaped {
+        combatant: CombatantId,
+        pursuers: Vec<CombatantId>,
+    },
+    /// A weapon was dropped to draw another
+    WeaponDropped {
+        combatant: CombatantId,
+        weapon: String,
+    },
+    /// A watcher interrupted an enemy's move with a snap shot
+    OverwatchFired {
+        shooter: CombatantId,
+        target: CombatantId,
+    },
+    /// The defense provider missed its deadline; `action` was used instead
+    TimedOut {
+        combatant: CombatantId,
+        action: DefenseAction,
+    },
+    /// A summoned combatant winked out in a null field
+    Dispelled {
+        combatant: CombatantId,
+    },
+    /// A caster blinked across the field
+    Blinked {
+        combatant: CombatantId,
+        from: GridPos,
+        to: GridPos,
+    },
+    /// A readied blink took the caster out of the way of `attacker`'s blow
+    BlinkedAway {
+        combatant: CombatantId,
+        attacker: CombatantId,
+    },
+    /// A caster teleported out of the fight
+    Teleported {
+        combatant: CombatantId,
+    },
+    /// A botched blink or teleport wounded the caster
+    Mishap {
+        combatant: CombatantId,
+        wound: WoundLevel,
+    },
+    /// A necromancer raised a corpse as a zombie on their side
+    Raised {
+        necromancer: CombatantId,
+        corpse: CombatantId,
+        zombie: CombatantId,
+    },
+}
+
+/// A fight between two or more teams
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Encounter {
+    pub combatants: Vec<Combatant>,
+    /// Positions for combatants; without one everybody can reach everybody
+    pub battlefield: Option<Battlefield>,
+    pub rules: Ruleset,
+    /// The round about to be fought (starts at 1)
+    pub round: u32,
+    pub events: Vec<EncounterEvent>,
+    /// Owners who spent this round's action commanding a companion
+    pub commanding: Vec<CombatantId>,
+    /// (attacker, defender) pairs that have fought this round, for chokepoint limits
+    pub engaged: Vec<(CombatantId, CombatantId)>,
+    /// Combatants spending this round's action changing weapons
+    pub switching: Vec<CombatantId>,
+    /// Watchers who spent this round's action on an overwatch shot
+    pub fired: Vec<CombatantId>,
+    /// Casters who spent this round's action blinking or teleporting
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub casting: Vec<CombatantId>,
+    /// Combatants still to act in the round under way, in order
+    pub initiative: Vec<CombatantId>,
+    /// Teams whose minions have acted this round
+    pub batched: Vec<u32>,
+    /// Picks defenses in place of the AI (see `turn_timer`); not saved
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub defense_provider: Option<SharedDefenseProvider>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub turn_timer: Option<TurnTimer>,
+}
+
+impl Encounter {
+    pub fn new() -> Self {
+        Self {
+            round: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_battlefield(mut self, battlefield: Battlefield) -> Self {
+        self.battlefield = Some(battlefield);
+        self
+    }
+
+    pub fn with_rules(mut self, rules: Ruleset) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Put the encounter back to `template`, keeping every buffer it has grown
+    ///
+    /// Same result as `*self = template.clone()`; see `EncounterPool`.
+    pub fn reset_to(&mut self, template: &Encounter) {
+        let Encounter {
+            combatants,
+            battlefield,
+            rules,
+            round,
+            events,
+            commanding,
+            engaged,
+            switching,
+            fired,
+            casting,
+            initiative,
+            batched,
+            defense_provider,
+            turn_timer,
+        } = template;
+        self.combatants.truncate(combatants.len());
+        for (combatant, template) in self.combatants.iter_mut().zip(combatants) {
+            combatant.reset_to(template);
+        }
+        let kept = self.combatants.len();
+        self.combatants.extend(combatants[kept..].iter().cloned());
+        self.battlefield.clone_from(battlefield);
+        self.rules.clone_from(rules);
+        self.round = *round;
+        self.events.clone_from(events);
+        self.commanding.clone_from(commanding);
+        self.engaged.clone_from(engaged);
+        self.switching.clone_from(switching);
+        self.fired.clone_from(fired);
+        self.casting.clone_from(casting);
+        self.initiative.clone_from(initiative);
+        self.batched.clone_from(batched);
+        self.defense_provider.clone_from(defense_provider);
+        self.turn_timer = *turn_timer;
+    }
+
+    /// Ask `provider` how each defender reacts instead of the AI
+    pub fn with_defense_provider(mut self, provider: SharedDefenseProvider) -> Self {
+        self.defense_provider = Some(provider);
+        self
+    }
+
+    /// Give the defense provider a deadline for every answer
+    pub fn with_turn_timer(mut self, timer: TurnTimer) -> Self {
+        self.turn_timer = Some(timer);
+        self
+    }
+
+    pub fn add(&mut self, character: Character, team: u32) -> CombatantId {
+        self.combatants.push(Combatant {
+            character,
+            team,
+            boss: None,
+            minion: false,
+            companion: None,
+            owner: None,
+            guarding: None,
+            retreating: None,
+            escaped: false,
+            readiness: Readiness::Set,
+            wielding: Wielding::Melee,
+            overwatch: None,
+            ammo: None,
+            stance: CombatStance::new(),
+            summoned: false,
+            blink_reaction: None,
+            raised: None,
+        });
+        self.combatants.len() - 1
+    }
+
+    /// Add `count` minions copied from `template`, named "Goblin 1", "Goblin 2", ...
+    pub fn add_minions(
+        &mut self,
+        template: &Character,
+        count: usize,
+        team: u32,
+    ) -> Vec<CombatantId> {
+        (1..=count)
+            .map(|n| {
+                let mut character = template.clone();
+                character.name = format!("{} {}", template.name, n);
+                let id = self.add(character, team);
+                self.combatants[id].minion = true;
+                id
+            })
+            .collect()
+    }
+
+    /// Add a boss; its opening phase takes effect immediately
+    pub fn add_boss(&mut self, character: Character, team: u32, boss: Boss) -> CombatantId {
+        let id = self.add(character, team);
+        self.combatants[id].boss = Some(boss);
+        self.update_phase(id);
+        id
+    }
+
+    /// Add a creature conjured by magic; it vanishes if it stands in a null field
+    pub fn add_summon(&mut self, character: Character, team: u32) -> CombatantId {
+        let id = self.add(character, team);
+        self.combatants[id].summoned = true;
+        id
+    }
+
+    /// Add an animal fighting for `owner`, on the owner's team
+    ///
+    /// Returns `None`, adding nothing, if `owner` is not in the encounter.
+    pub fn add_companion(
+        &mut self,
+        owner: CombatantId,
+        animal: Character,
+        mut companion: Companion,
+    ) -> Option<CombatantId> {
+        let master = self.combatants.get(owner)?;
+        companion.owner = master.character.name.clone();
+        let id = self.add(animal, master.team);
+        self.combatants[id].companion = Some(companion);
+        self.combatants[id].owner = Some(owner);
+        Some(id)
+    }
+
+    /// Spend the owner's action this round commanding a companion
+    ///
+    /// `handler_skill` is the owner's animal handling skill; see
+    /// `Companion::command`. Returns false if the command wasn't obeyed or
+    /// the owner can't act.
+    pub fn command_companion(
+        &mut self,
+        companion: CombatantId,
+        handler_skill: i32,
+        command: CompanionCommand,
+        roll: i32,
+    ) -> bool {
+        let Some(owner) = self.owner_of(companion) else {
+            return false;
+        };
+        if !self.combatants[owner].is_fighting() || self.commanding.contains(&owner) {
+            return false;
+        }
+        self.commanding.push(owner);
+        self.combatants[companion]
+            .companion
+            .as_mut()
+            .is_some_and(|c| c.command(handler_skill, command, roll))
+    }
+
+    /// Spend `id`'s action blinking to `to` with a Transportation spell
+    ///
+    /// The cell must be free, and within the spell's range and sight. A failed
+    /// casting leaves the caster where they stood, wounded if badly failed
+    /// (see `transportation::mishap_wound`). An `id` not in the encounter is
+    /// `TransportError::UnknownCombatant`.
+    #[cfg(feature = "magic")]
+    pub fn blink(
+        &mut self,
+        id: CombatantId,
+        spell: &str,
+        to: GridPos,
+        roll: i32,
+    ) -> Result<Displacement, TransportError> {
+        let combatant = self
+            .combatants
+            .get(id)
+            .ok_or(TransportError::UnknownCombatant(id))?;
+        if !combatant.is_fighting() || self.action_spent(id) {
+            return Err(TransportError::CannotAct);
+        }
+        let name = combatant.character.name.clone();
+        let field = self.battlefield.as_mut().ok_or(TransportError::NotPlaced)?;
+        let from = field.placement(&name).ok_or(TransportError::NotPlaced)?;
+        field
+            .check_enterable(&name, to)
+            .map_err(TransportError::Blocked)?;
+        let target = SpellTarget::Point(to);
+        let info =
+            TargetInfo::on_battlefield(field, &name, &target).ok_or(TransportError::NotPlaced)?;
+        let displacement = jump(
+            &mut self.combatants[id].character,
+            spell,
+            target,
+            &info,
+            roll,
+        )?;
+        self.casting.push(id);
+        if displacement.arrived() {
+            field
+                .place(&name, to, from.facing)
+                .map_err(TransportError::Blocked)?;
+            self.events.push(EncounterEvent::Blinked {
+                combatant: id,
+                from: from.pos,
+                to,
+            });
+        }
+        self.record_mishap(id, &displacement);
+        Ok(displacement)
+    }
+
+    /// Spend `id`'s action teleporting out of the encounter
+    ///
+    /// A successful casting takes the caster off the field for good, beyond
+    /// any pursuit; a failed one leaves them in the fight. An `id` not in the
+    /// encounter is `TransportError::UnknownCombatant`.
+    #[cfg(feature = "magic")]
+    pub fn teleport(
+        &mut self,
+        id: CombatantId,
+        spell: &str,
+        roll: i32,
+    ) -> Result<Displacement, TransportError> {
+        let spent = self.action_spent(id);
+        let combatant = self
+            .combatants
+            .get_mut(id)
+            .ok_or(TransportError::UnknownCombatant(id))?;
+        if !combatant.is_fighting() || spent {
+            return Err(TransportError::CannotAct);
+        }
+        let displacement = jump(
+            &mut combatant.character,
+            spell,
+            SpellTarget::Caster,
+            &TargetInfo::caster(),
+            roll,
+        )?;
+        self.casting.push(id);
+        if displacement.arrived() {
+            combatant.escaped = true;
+            if let Some(field) = self.battlefield.as_mut() {
+                field.remove(&combatant.character.name);
+            }
+            self.events
+                .push(EncounterEvent::Teleported { combatant: id });
+        }
+        self.record_mishap(id, &displacement);
+        Ok(displacement)
+    }
+
+    /// Ready a Transportation spell to blink away from the next blow this round
+    ///
+    /// Readying costs no action. When a melee attack comes, the caster rolls
+    /// the casting first: success makes the blow miss outright, failure lets
+    /// it land as normal after any mishap. An `id` not in the encounter is
+    /// `TransportError::UnknownCombatant`.
+    #[cfg(feature = "magic")]
+    pub fn ready_blink(&mut self, id: CombatantId, spell: &str) -> Result<(), TransportError> {
+        let combatant = self
+            .combatants
+            .get_mut(id)
+            .ok_or(TransportError::UnknownCombatant(id))?;
+        check_spell(&combatant.character, spell)?;
+        combatant.blink_reaction = Some(spell.to_string());
+        Ok(())
+    }
+
+    /// Spend `necromancer`'s action raising `corpse` as a zombie on their team
+    ///
+    /// The corpse must be dead, not yet raised and, on a battlefield, still
+    /// lying there within the spell's range and sight; the zombie takes its
+    /// place (see `necromancy::zombie`). A necromancer controls at most
+    /// `necromancy::control_limit` zombies at once. Returns the new zombie, or
+    /// `None` if the casting failed.
+    #[cfg(feature = "magic")]
+    pub fn raise_dead(
+        &mut self,
+        necromancer: CombatantId,
+        corpse: CombatantId,
+        spell: &str,
+        roll: i32,
+    ) -> Result<Option<CombatantId>, NecromancyError> {
+        let Some(caster) = self.combatants.get(necromancer) else {
+            return Err(NecromancyError::UnknownCombatant(necromancer));
+        };
+        if !caster.is_fighting() || self.action_spent(necromancer) {
+            return Err(NecromancyError::CannotAct);
+        }
+        let caster = &caster.character;
+        necromancy::check_spell(caster, spell)?;
+        let body = self
+            .combatants
+            .get(corpse)
+            .ok_or(NecromancyError::UnknownCombatant(corpse))?;
+        let name = body.character.name.clone();
+        if body.character.is_alive() {
+            return Err(NecromancyError::NotACorpse(name));
+        }
+        let raised = self
+            .combatants
+            .iter()
+            .any(|c| c.raised.is_some_and(|r| r.corpse == corpse));
+        let lying = self
+            .battlefield
+            .as_ref()
+            .map(|field| field.placement(&name));
+        if raised || body.escaped || lying == Some(None) {
+            return Err(NecromancyError::NoCorpse(name));
+        }
+        let limit = control_limit(necromancy_lore(caster));
+        let controlled = self
+            .combatants
+            .iter()
+            .filter(|c| c.is_fighting())
+            .filter(|c| c.raised.is_some_and(|r| r.necromancer == necromancer))
+            .count();
+        if controlled >= limit {
+            return Err(NecromancyError::ControlLimit(limit));
+        }
+
+        let target = SpellTarget::Character(name.clone());
+        let info = match &self.battlefield {
+            Some(field) => TargetInfo::on_battlefield(
+                field,
+                &self.combatants[necromancer].character.name,
+                &target,
+            )
+            .ok_or_else(|| NecromancyError::NoCorpse(name.clone()))?,
+            None => TargetInfo::new(0, true),
+        };
+        let casting = self.combatants[necromancer]
+            .character
+            .cast(spell, target, &info, roll)?;
+        self.casting.push(necromancer);
+        if !casting.success {
+            return Ok(None);
+        }
+        let team = self.combatants[necromancer].team;
+        let zombie = self.add_summon(necromancy::zombie(&self.combatants[corpse].character), team);
+        self.combatants[zombie].raised = Some(Raising {
+            necromancer,
+            corpse,
+        });
+        if let Some(field) = self.battlefield.as_mut() {
+            if let Some(place) = field.remove(&name) {
+                // The corpse's cell was just vacated, so this can't fail
+                let _ = field.place(
+                    &self.combatants[zombie].character.name,
+                    place.pos,
+                    place.facing,
+                );
+            }
+        }
+        self.events.push(EncounterEvent::Raised {
+            necromancer,
+            corpse,
+            zombie,
+        });
+        Ok(Some(zombie))
+    }
+
+    #[cfg(feature = "magic")]
+    fn record_mishap(&mut self, id: CombatantId, displacement: &Displacement) {
+        if let Some(wound) = displacement.mishap {
+            self.events.push(EncounterEvent::Mishap {
+                combatant: id,
+                wound,
+            });
+        }
+    }
+
+    /// Have `guardian` take the Guard maneuver over an ally
+    ///
+    /// The guardian stops attacking; while adjacent to the ward it parries
+    /// attacks aimed at them at `GUARD_PENALTY`. Returns false unless both
+    /// are distinct teammates.
+    pub fn guard(&mut self, guardian: CombatantId, ward: CombatantId) -> bool {
+        let valid = guardian != ward
+            && ward < self.combatants.len()
+            && self
+                .combatants
+                .get(guardian)
+                .is_some_and(|g| g.team == self.combatants[ward].team);
+        if valid {
+            self.combatants[guardian].guarding = Some(ward);
+        }
+        valid
+    }
+
+    /// Hold fire to snap-shoot the first enemy to step into one of `zone`'s cells
+    ///
+    /// The shooter skips their turns until the shot is taken or they
+    /// `stand_down`. Returns false unless they are wielding a ranged weapon.
+    #[cfg(feature = "ranged")]
+    pub fn overwatch(&mut self, id: CombatantId, zone: Vec<GridPos>) -> bool {
+        let Some(combatant) = self.combatants.get_mut(id) else {
+            return false;
+        };
+        let valid = combatant.wielding == Wielding::Ranged
+            && combatant.character.ranged_weapon.is_some()
+            && !zone.is_empty();
+        if valid {
+            combatant.overwatch = Some(zone);
+        }
+        valid
+    }
+
+    /// Stop guarding, retreating or watching and return to attacking
+    pub fn stand_down(&mut self, id: CombatantId) {
+        if let Some(combatant) = self.combatants.get_mut(id) {
+            combatant.guarding = None;
+            combatant.retreating = None;
+            combatant.overwatch = None;
+            combatant.character.modifiers.remove_source("Withdraw");
+        }
+    }
+
+    /// Start a combatant unready (see `Readiness::first_round_penalty`)
+    pub fn set_readiness(&mut self, id: CombatantId, readiness: Readiness) {
+        if let Some(combatant) = self.combatants.get_mut(id) {
+            combatant.readiness = readiness;
+        }
+    }
+
+    /// Change between the melee and ranged weapon for the coming round
+    ///
+    /// Returns false if there is no ranged weapon to change to or from, or if
+    /// asked to drop a melee weapon (there would be nothing left to fight with).
+    #[cfg(feature = "ranged")]
+    pub fn switch_weapon(&mut self, id: CombatantId, switch: WeaponSwitch) -> bool {
+        let Some(combatant) = self.combatants.get_mut(id) else {
+            return false;
+        };
+        if combatant.character.ranged_weapon.is_none()
+            || (switch == WeaponSwitch::DropAndDraw && combatant.wielding == Wielding::Melee)
+        {
+            return false;
+        }
+        combatant.wielding = match combatant.wielding {
+            Wielding::Melee => Wielding::Ranged,
+            Wielding::Ranged => Wielding::Melee,
+        };
+        combatant.readiness = switch.readiness();
+        if switch == WeaponSwitch::DropAndDraw {
+            if let Some(weapon) = combatant.character.ranged_weapon.take() {
+                self.events.push(EncounterEvent::WeaponDropped {
+                    combatant: id,
+                    weapon: weapon.name,
+                });
+            }
+        }
+        if switch.costs_action() && !self.switching.contains(&id) {
+            self.switching.push(id);
+        }
+        true
+    }
+
+    /// Fall back on each of the combatant's turns until `stand_down`
+    pub fn retreat(&mut self, id: CombatantId, retreat: Retreat) {
+        self.stand_down(id);
+        let Some(combatant) = self.combatants.get_mut(id) else {
+            return;
+        };
+        combatant.retreating = Some(retreat);
+        if retreat == Retreat::Withdraw {
+            for target in [ModifierTarget::Parry, ModifierTarget::Dodge] {
+                combatant.character.add_modifier(Modifier::new(
+                    "Withdraw",
+                    target,
+                    WITHDRAW_DEFENSE,
+                ));
+            }
+        }
+    }
+
+    /// Fight with `maneuver` from the combatant's next turn
+    ///
+    /// Its attack, defense and damage modifiers apply to every exchange, and
+    /// one that can't attack (Defensive Position) holds the combatant back.
+    /// An `id` not in the encounter is `ManeuverError::UnknownCombatant`.
+    pub fn set_maneuver(
+        &mut self,
+        id: CombatantId,
+        maneuver: CombatManeuver,
+    ) -> Result<(), ManeuverError> {
+        let Some(combatant) = self.combatants.get_mut(id) else {
+            return Err(ManeuverError::UnknownCombatant(id));
+        };
+        combatant
+            .stance
+            .set_maneuver(maneuver, &combatant.character)
+    }
+
+    /// What the combatant can do right now, for greying out UI buttons
+    ///
+    /// Accounts for the action already being spent this round, wounds and
+    /// disabled limbs (shooting needs both arms, fleeing both legs), which
+    /// weapon is in hand, ammunition, engagement (overwatch needs nobody in
+    /// reach), exhaustion (too spent to flee or charge) and maneuvers that
+    /// need aiming or are still being recovered from.
+    pub fn legal_actions(&self, id: CombatantId) -> Vec<ActionKind> {
+        let Some(me) = self.combatants.get(id) else {
+            return Vec::new();
+        };
+        if !me.is_fighting() || self.action_spent(id) {
+            return Vec::new();
+        }
+        let character = &me.character;
+        let working = |limbs: [HitLocation; 2]| {
+            limbs
+                .iter()
+                .filter(|&&l| !character.injuries.is_disabled(l))
+                .count()
+        };
+        let arms = working([HitLocation::LeftArm, HitLocation::RightArm]);
+        let legs = working([HitLocation::LeftLeg, HitLocation::RightLeg]);
+        let rested = character.exhaustion().can_perform_exhaustive_actions();
+        let enemies: Vec<CombatantId> = (0..self.combatants.len())
+            .filter(|&e| self.combatants[e].team != me.team && self.combatants[e].is_fighting())
+            .collect();
+        let engaged = enemies.iter().any(|&e| self.in_reach(e, id));
+        let mobile = legs > 0 && movement_allowance(character) > 0;
+        #[cfg(feature = "ranged")]
+        let has_ranged = character.ranged_weapon.is_some();
+        #[cfg(not(feature = "ranged"))]
+        let has_ranged = false;
+        let can_shoot =
+            me.wielding == Wielding::Ranged && has_ranged && arms == 2 && me.ammo != Some(0);
+
+        let mut actions = Vec::new();
+        if me.wielding == Wielding::Melee
+            && arms > 0
+            && me.stance.current_maneuver.can_attack()
+            && (engaged || (mobile && !enemies.is_empty()))
+        {
+            actions.push(ActionKind::Attack);
+        }
+        if can_shoot && !enemies.is_empty() {
+            actions.push(ActionKind::Shoot);
+        }
+        if has_ranged && arms > 0 {
+            actions.push(ActionKind::SwitchWeapon);
+        }
+        let allies = (0..self.combatants.len()).any(|a| {
+            a != id && self.combatants[a].team == me.team && self.combatants[a].is_fighting()
+        });
+        if allies && me.wielding == Wielding::Melee && arms > 0 {
+            actions.push(ActionKind::Guard);
+        }
+        if can_shoot && self.battlefield.is_some() && !engaged {
+            actions.push(ActionKind::Overwatch);
+        }
+        if self.battlefield.is_some() && mobile {
+            actions.push(ActionKind::Withdraw);
+            if legs == 2 && rested {
+                actions.push(ActionKind::Flee);
+            }
+        }
+        let has_companion = self.combatants.iter().enumerate().any(|(c, combatant)| {
+            combatant.companion.as_ref().is_some_and(|pet| !pet.fled)
+                && self.owner_of(c) == Some(id)
+        });
+        if has_companion {
+            actions.push(ActionKind::CommandCompanion);
+        }
+        if me.wielding == Wielding::Melee && arms > 0 {
+            let stance = &me.stance;
+            actions.extend(
+                available_maneuvers(character)
+                    .into_iter()
+                    .filter(|&m| m != CombatManeuver::Guard)
+                    .filter(|&m| stance.recovering_from != Some(m))
+                    .filter(|&m| m != CombatManeuver::AimedAttack || stance.aiming)
+                    .filter(|&m| m != CombatManeuver::Charge || (rested && legs == 2))
+                    .map(ActionKind::Maneuver),
+            );
+        }
+        actions
+    }
+
+    /// A fighting guard adjacent to `ward`, if any
+    fn guard_for(&self, ward: CombatantId) -> Option<CombatantId> {
+        (0..self.combatants.len()).find(|&id| {
+            self.combatants[id].guarding == Some(ward)
+                && self.combatants[id].is_fighting()
+                && self.in_reach(id, ward)
+        })
+    }
+
+    fn owner_of(&self, companion: CombatantId) -> Option<CombatantId> {
+        let combatant = self.combatants.get(companion)?;
+        combatant.companion.as_ref().and(combatant.owner)
+    }
+
+    /// Find a combatant by character name
+    pub fn find(&self, name: &str) -> Option<CombatantId> {
+        self.combatants
+            .iter()
+            .position(|c| c.character.name == name)
+    }
+
+    pub fn get(&self, id: CombatantId) -> Option<&Combatant> {
+        self.combatants.get(id)
+    }
+
+    pub fn get_mut(&mut self, id: CombatantId) -> Option<&mut Combatant> {
+        self.combatants.get_mut(id)
+    }
+
+    /// Teams with at least one combatant still able to act, in ascending order
+    pub fn teams_standing(&self) -> Vec<u32> {
+        let mut teams: Vec<u32> = self
+            .combatants
+            .iter()
+            .filter(|c| c.is_fighting())
+            .map(|c| c.team)
+            .collect();
+        teams.sort_unstable();
+        teams.dedup();
+        teams
+    }
+
+    pub fn is_over(&self) -> bool {
+        // Checked after every turn, so without collecting the teams
+        let mut fighting = self.combatants.iter().filter(|c| c.is_fighting());
+        match fighting.next() {
+            Some(first) => fighting.all(|c| c.team == first.team),
+            None => true,
+        }
+    }
+
+    /// The last team standing, once the fight is over
+    pub fn winner(&self) -> Option<u32> {
+        match self.teams_standing().as_slice() {
+            [team] => Some(*team),
+            _ => None,
+        }
+    }
+
+    /// Acting order for a round: highest DEX first, ties by entry order
+    ///
+    /// Companions act immediately after their owner.
+    pub fn turn_order(&self) -> Vec<CombatantId> {
+        let mut by_dex: Vec<CombatantId> = (0..self.combatants.len()).collect();
+        by_dex.sort_by_key(|&id| {
+            let combatant = &self.combatants[id];
+            (
+                combatant.readiness != Readiness::Set,
+                -combatant.character.attributes.dexterity.get(),
+            )
+        });
+        let owners: Vec<Option<CombatantId>> = (0..self.combatants.len())
+            .map(|id| self.owner_of(id))
+            .collect();
+        let mut order = Vec::with_capacity(by_dex.len());
+        for id in by_dex {
+            if owners[id].is_some() {
+                continue;
+            }
+            order.push(id);
+            order.extend((0..owners.len()).filter(|&c| owners[c] == Some(id)));
+        }
+        order
+    }
+
+    /// Resolve one round; every combatant able to act attacks once if it can reach a foe
+    ///
+    /// After each combatant's turn, bosses with legendary actions left may
+    /// strike a foe already in reach. Finishes a round already under way.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(round = self.round))
+    )]
+    pub fn run_round(&mut self) -> Vec<CombatResult> {
+        let mut results = self.step();
+        while !self.initiative.is_empty() {
+            results.extend(self.step());
+        }
+        results
+    }
+
+    /// Resolve the next turn in initiative order, starting a round if needed
+    ///
+    /// Between steps the encounter can be saved and resumed mid-round.
+    pub fn step(&mut self) -> Vec<CombatResult> {
+        if self.initiative.is_empty() {
+            self.begin_round();
+        }
+        let mut results = Vec::new();
+        if !self.initiative.is_empty() && !self.is_over() {
+            let id = self.initiative.remove(0);
+            #[cfg(feature = "tracing")]
+            let _turn =
+                tracing::debug_span!("turn", combatant = %self.combatants[id].character.name)
+                    .entered();
+            let team = self.combatants[id].team;
+            if self.combatants[id].minion {
+                if !self.batched.contains(&team) {
+                    self.batched.push(team);
+                    results.extend(self.minion_batch(team));
+                }
+            } else if let Some(retreat) = self.combatants[id].retreating {
+                if self.combatants[id].is_fighting() {
+                    results.extend(self.fall_back(id, retreat));
+                }
+            } else {
+                results.extend(self.take_turn(id));
+            }
+            results.extend(self.legendary_actions(id));
+        }
+        if self.initiative.is_empty() || self.is_over() {
+            self.end_round();
+        }
+        results
+    }
+
+    fn begin_round(&mut self) {
+        for combatant in &mut self.combatants {
+            if let Some(boss) = combatant.boss.as_mut() {
+                boss.new_round();
+            }
+        }
+        self.companion_morale();
+        self.apply_anti_magic();
+        for combatant in &mut self.combatants {
+            let penalty = combatant.readiness.first_round_penalty();
+            if penalty != 0 {
+                for target in [
+                    ModifierTarget::Attack,
+                    ModifierTarget::Parry,
+                    ModifierTarget::Dodge,
+                ] {
+                    combatant
+                        .character
+                        .add_modifier(Modifier::new("Unready", target, penalty));
+                }
+            }
+        }
+        self.initiative = self.turn_order();
+    }
+
+    /// Dampen or null magic for combatants standing in anti-magic zones
+    fn apply_anti_magic(&mut self) {
+        let Some(field) = self.battlefield.as_mut() else {
+            return;
+        };
+        for (id, combatant) in self.combatants.iter_mut().enumerate() {
+            let zone = field.anti_magic_for(&combatant.character.name);
+            #[cfg(feature = "magic")]
+            combatant.character.apply_anti_magic(zone);
+            if combatant.summoned && zone == Some(AntiMagic::Null) && combatant.is_fighting() {
+                combatant.escaped = true;
+                field.remove(&combatant.character.name);
+                self.events
+                    .push(EncounterEvent::Dispelled { combatant: id });
+            }
+        }
+    }
+
+    fn end_round(&mut self) {
+        if self.rules.blood_loss {
+            for combatant in &mut self.combatants {
+                if combatant.character.is_alive() {
+                    combatant.character.bleed();
+                }
+            }
+        }
+        for combatant in &mut self.combatants {
+            if combatant.readiness != Readiness::Set {
+                combatant.character.modifiers.remove_source("Unready");
+                combatant.readiness = Readiness::Set;
+            }
+            combatant.stance.end_round();
+            combatant.blink_reaction = None;
+        }
+        self.initiative.clear();
+        self.batched.clear();
+        self.commanding.clear();
+        self.engaged.clear();
+        self.switching.clear();
+        self.fired.clear();
+        self.casting.clear();
+        self.round += 1;
+    }
+
+    /// Already used this round's action on something other than attacking
+    fn action_spent(&self, id: CombatantId) -> bool {
+        self.commanding.contains(&id)
+            || self.switching.contains(&id)
+            || self.fired.contains(&id)
+            || self.casting.contains(&id)
+    }
+
+    fn take_turn(&mut self, id: CombatantId) -> Vec<CombatResult> {
+        if !self.combatants[id].is_fighting()
+            || self.action_spent(id)
+            || self.combatants[id].guarding.is_some()
+            || self.combatants[id].overwatch.is_some()
+            || !self.combatants[id].stance.current_maneuver.can_attack()
+        {
+            return Vec::new();
+        }
+        let Some(target) = self.choose_target(id) else {
+            return Vec::new();
+        };
+        #[cfg(feature = "ranged")]
+        if self.combatants[id].wielding == Wielding::Ranged {
+            return self.shoot(id, target);
+        }
+        let command = self.combatants[id].companion.as_ref().map(|c| c.command);
+        let start = self.position(id);
+        let mut results = Vec::new();
+        let ready = match command {
+            Some(CompanionCommand::Heel) => false,
+            Some(CompanionCommand::Guard) => self.in_reach(id, target),
+            _ => self.close_in(id, target, &mut results),
+        };
+        if !ready {
+            return results;
+        }
+        if self.position(id) != start
+            && self.elevation(start) > self.elevation(self.position(target))
+        {
+            self.combatants[id].character.add_modifier(Modifier::new(
+                "Downhill Charge",
+                ModifierTarget::Damage,
+                DOWNHILL_CHARGE_BONUS,
+            ));
+        }
+        let result = self.attack(id, target);
+        self.combatants[id]
+            .character
+            .modifiers
+            .remove_source("Downhill Charge");
+        results.extend(result);
+        results
+    }
+
+    fn position(&self, id: CombatantId) -> Option<GridPos> {
+        let field = self.battlefield.as_ref()?;
+        Some(field.placement(&self.combatants[id].character.name)?.pos)
+    }
+
+    fn elevation(&self, pos: Option<GridPos>) -> i32 {
+        match (&self.battlefield, pos) {
+            (Some(field), Some(pos)) => field.elevation_at(pos),
+            _ => 0,
+        }
+    }
+
+    /// Take parting blows from engaged enemies, then move away
+    fn fall_back(&mut self, id: CombatantId, retreat: Retreat) -> Vec<CombatResult> {
+        let team = self.combatants[id].team;
+        let engaged: Vec<CombatantId> = (0..self.combatants.len())
+            .filter(|&e| {
+                self.combatants[e].team != team
+                    && self.combatants[e].is_fighting()
+                    && self.in_reach(e, id)
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for &enemy in &engaged {
+            if !self.combatants[id].is_fighting() {
+                break;
+            }
+            if !self.can_strike(enemy) {
+                continue;
+            }
+            if retreat == Retreat::Flee {
+                // Back turned: the defense roll can't rise above zero
+                let character = &mut self.combatants[id].character;
+                let parry = -character.parry_roll_with(10);
+                let dodge = -character.dodge_roll_with(10);
+                character.add_modifier(Modifier::new("Flight", ModifierTarget::Parry, parry));
+                character.add_modifier(Modifier::new("Flight", ModifierTarget::Dodge, dodge));
+            }
+            results.extend(self.attack(enemy, id));
+            self.combatants[id]
+                .character
+                .modifiers
+                .remove_source("Flight");
+        }
+        if !self.combatants[id].is_fighting() {
+            return results;
+        }
+
+        match retreat {
+            Retreat::Withdraw => {
+                if let Some(&enemy) = engaged.first() {
+                    let goal = MoveGoal::Kite {
+                        from: self.combatants[enemy].character.name.clone(),
+                        range: 2,
+                    };
+                    let allowance = (movement_allowance(&self.combatants[id].character) / 2).max(1);
+                    results.extend(self.move_toward(id, &goal, allowance));
+                }
+            }
+            Retreat::Flee => {
+                let allowance = movement_allowance(&self.combatants[id].character) + FLIGHT_SPRINT;
+                if let Some(exit) = self.escape_route(id) {
+                    results.extend(self.move_toward(id, &MoveGoal::Cell(exit), allowance));
+                }
+                if !self.combatants[id].is_fighting() {
+                    return results;
+                }
+                let at_edge = match &self.battlefield {
+                    Some(field) => field
+                        .placement(&self.combatants[id].character.name)
+                        .is_none_or(|p| field.is_edge(p.pos)),
+                    None => true,
+                };
+                if at_edge {
+                    self.escape(id, allowance);
+                }
+            }
+        }
+        results
+    }
+
+    /// The edge cell farthest from any enemy (nearest to the fugitive on ties)
+    fn escape_route(&self, id: CombatantId) -> Option<GridPos> {
+        let field = self.battlefield.as_ref()?;
+        let me = &self.combatants[id];
+        let from = field.placement(&me.character.name)?.pos;
+        let enemies: Vec<GridPos> = self
+            .combatants
+            .iter()
+            .filter(|c| c.team != me.team && c.is_fighting())
+            .filter_map(|c| field.placement(&c.character.name))
+            .map(|p| p.pos)
+            .collect();
+        field
+            .cells()
+            .filter(|&pos| field.is_edge(pos) && field.move_cost(pos).is_some())
+            .filter(|&pos| {
+                field
+                    .occupant(pos)
+                    .is_none_or(|name| name == me.character.name)
+            })
+            .max_by_key(|&pos| {
+                let threat = enemies.iter().map(|&e| field.distance(pos, e)).min();
+                (threat.unwrap_or(0), -field.distance(from, pos))
+            })
+    }
+
+    fn move_toward(
+        &mut self,
+        id: CombatantId,
+        goal: &MoveGoal,
+        allowance: i32,
+    ) -> Vec<CombatResult> {
+        let Some(field) = self.battlefield.as_ref() else {
+            return Vec::new();
+        };
+        match plan_move(field, &self.combatants[id].character.name, goal, allowance) {
+            Some(path) => self.advance(id, &path),
+            None => Vec::new(),
+        }
+    }
+
+    /// Walk a path a cell at a time, drawing overwatch fire on the way
+    fn advance(&mut self, id: CombatantId, path: &Path) -> Vec<CombatResult> {
+        let mut results = Vec::new();
+        for &pos in &path.steps {
+            let Some(field) = self.battlefield.as_mut() else {
+                break;
+            };
+            if field
+                .step(&self.combatants[id].character.name, pos)
+                .is_err()
+            {
+                break;
+            }
+            results.extend(self.overwatch_fire(id, pos));
+            if !self.combatants[id].is_fighting() {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Enemies watching `pos` snap-shoot the combatant who just entered it
+    #[cfg(feature = "ranged")]
+    fn overwatch_fire(&mut self, mover: CombatantId, pos: GridPos) -> Vec<CombatResult> {
+        let team = self.combatants[mover].team;
+        let watchers: Vec<CombatantId> = (0..self.combatants.len())
+            .filter(|&w| {
+                let watcher = &self.combatants[w];
+                watcher.team != team
+                    && watcher.is_fighting()
+                    && watcher
+                        .overwatch
+                        .as_ref()
+                        .is_some_and(|zone| zone.contains(&pos))
+                    && self
+                        .battlefield
+                        .as_ref()
+                        .zip(self.position(w))
+                        .is_some_and(|(field, at)| field.line_of_sight(at, pos))
+            })
+            .collect();
+        let mut results = Vec::new();
+        for w in watchers {
+            if !self.combatants[mover].is_fighting() {
+                break;
+            }
+            self.combatants[w].overwatch = None;
+            self.fired.push(w);
+            self.events.push(EncounterEvent::OverwatchFired {
+                shooter: w,
+                target: mover,
+            });
+            self.combatants[w].character.add_modifier(Modifier::new(
+                "Snap Shot",
+                ModifierTarget::Ranged,
+                SNAP_SHOT_PENALTY,
+            ));
+            results.extend(self.shoot(w, mover));
+            self.combatants[w]
+                .character
+                .modifiers
+                .remove_source("Snap Shot");
+        }
+        results
+    }
+
+    /// Nobody can be on overwatch without missile weapons
+    #[cfg(not(feature = "ranged"))]
+    fn overwatch_fire(&mut self, _mover: CombatantId, _pos: GridPos) -> Vec<CombatResult> {
+        Vec::new()
+    }
+
+    /// Leave the field; enemies who move at least as fast can keep up the chase
+    fn escape(&mut self, id: CombatantId, speed: i32) {
+        let team = self.combatants[id].team;
+        let pursuers = (0..self.combatants.len())
+            .filter(|&e| {
+                let enemy = &self.combatants[e];
+                enemy.team != team
+                    && enemy.is_fighting()
+                    && movement_allowance(&enemy.character) + FLIGHT_SPRINT >= speed
+            })
+            .collect();
+        let combatant = &mut self.combatants[id];
+        combatant.escaped = true;
+        if let Some(field) = self.battlefield.as_mut() {
+            field.remove(&combatant.character.name);
+        }
+        self.events.push(EncounterEvent::Escaped {
+            combatant: id,
+            pursuers,
+        });
+    }
+
+    /// Wounded companions, and those whose owner is down, check morale and may flee
+    fn companion_morale(&mut self) {
+        for id in 0..self.combatants.len() {
+            let combatant = &self.combatants[id];
+            let Some(companion) = &combatant.companion else {
+                continue;
+            };
+            if companion.fled || !combatant.character.can_act() {
+                continue;
+            }
+            let owner_down = self
+                .owner_of(id)
+                .is_none_or(|owner| !self.combatants[owner].is_fighting());
+            if !owner_down && combatant.character.wounds.movement_penalty() == 0 {
+                continue;
+            }
+            let roll = self.rules.dice.d10();
+            if companion.morale_check(&combatant.character, roll) == Morale::Deserts {
+                self.flee(id);
+            }
+        }
+    }
+
+    fn flee(&mut self, id: CombatantId) {
+        let combatant = &mut self.combatants[id];
+        if let Some(companion) = combatant.companion.as_mut() {
+            companion.fled = true;
+        }
+        if let Some(field) = self.battlefield.as_mut() {
+            field.remove(&combatant.character.name);
+        }
+        self.events
+            .push(EncounterEvent::CompanionFled { combatant: id });
+    }
+
+    /// All of a team's minions attack, sharing one target until it falls
+    fn minion_batch(&mut self, team: u32) -> Vec<CombatResult> {
+        let minions: Vec<CombatantId> = self
+            .turn_order()
+            .into_iter()
+            .filter(|&id| self.combatants[id].minion && self.combatants[id].team == team)
+            .collect();
+        let mut results = Vec::new();
+        let mut target = None;
+        for id in minions {
+            if !self.combatants[id].character.can_act() || !self.can_strike(id) {
+                continue;
+            }
+            if !target.is_some_and(|t: CombatantId| self.combatants[t].is_fighting()) {
+                target = self.choose_target(id);
+            }
+            let Some(t) = target else {
+                break;
+            };
+            if self.close_in(id, t, &mut results) {
+                results.extend(self.attack(id, t));
+            }
+        }
+        results
+    }
+
+    /// Bosses other than `after` spend legendary actions on foes in reach
+    fn legendary_actions(&mut self, after: CombatantId) -> Vec<CombatResult> {
+        let mut results = Vec::new();
+        for id in 0..self.combatants.len() {
+            let combatant = &self.combatants[id];
+            if id == after
+                || combatant.boss.is_none()
+                || !combatant.is_fighting()
+                || !self.can_strike(id)
+                || self.is_over()
+            {
+                continue;
+            }
+            let Some(target) = self.choose_target(id) else {
+                continue;
+            };
+            if !self.in_reach(id, target) {
+                continue;
+            }
+            let spent = self.combatants[id]
+                .boss
+                .as_mut()
+                .is_some_and(|boss| boss.use_action());
+            if spent {
+                self.events
+                    .push(EncounterEvent::LegendaryAction { combatant: id });
+                results.extend(self.attack(id, target));
+            }
+        }
+        results
+    }
+
+    /// Fighting with a maneuver that allows attacking (not Defensive Position or Guard)
+    fn can_strike(&self, id: CombatantId) -> bool {
+        self.combatants[id].stance.current_maneuver.can_attack()
+    }
+
+    /// One blow at `target`, or `None` if the attacker's maneuver can't attack
+    fn attack(&mut self, id: CombatantId, target: CombatantId) -> Option<CombatResult> {
+        if !self.engaged.contains(&(id, target)) {
+            self.engaged.push((id, target));
+        }
+        let guardian = self.guard_for(target).filter(|&g| g != id);
+        let high_ground = self.battlefield.as_ref().map_or(0, |field| {
+            let defender = guardian.unwrap_or(target);
+            field.high_ground(
+                &self.combatants[id].character.name,
+                &self.combatants[defender].character.name,
+            )
+        });
+        if high_ground != 0 {
+            self.combatants[id].character.add_modifier(Modifier::new(
+                "High Ground",
+                ModifierTarget::Attack,
+                high_ground,
+            ));
+        }
+        let defender = guardian.unwrap_or(target);
+        if self.in_shield_wall(defender) {
+            self.combatants[defender]
+                .character
+                .add_modifier(Modifier::new(
+                    "Shield Wall",
+                    ModifierTarget::Parry,
+                    SHIELD_WALL_BONUS,
+                ));
+        }
+        let result = match guardian {
+            Some(guardian) => {
+                let result = self.intercept(id, guardian);
+                if result.is_some() {
+                    self.events.push(EncounterEvent::Intercepted {
+                        guardian,
+                        ward: target,
+                    });
+                }
+                result
+            }
+            None => self.strike(id, target),
+        };
+        self.combatants[id]
+            .character
+            .modifiers
+            .remove_source("High Ground");
+        self.combatants[defender]
+            .character
+            .modifiers
+            .remove_source("Shield Wall");
+        result
+    }
+
+    /// Carrying a shield next to a fighting teammate who also carries one
+    fn in_shield_wall(&self, id: CombatantId) -> bool {
+        let (Some(field), Some(pos)) = (&self.battlefield, self.position(id)) else {
+            return false;
+        };
+        let me = &self.combatants[id];
+        me.character.weapon.has_tag(WeaponTags::SHIELD)
+            && (0..self.combatants.len()).any(|ally| {
+                let other = &self.combatants[ally];
+                ally != id
+                    && other.team == me.team
+                    && other.is_fighting()
+                    && other.character.weapon.has_tag(WeaponTags::SHIELD)
+                    && self
+                        .position(ally)
+                        .is_some_and(|at| field.distance(pos, at) == 1)
+            })
+    }
+
+    /// A defender in a chokepoint takes on only so many attackers a round
+    fn can_engage(&self, id: CombatantId, target: CombatantId) -> bool {
+        let width = self
+            .battlefield
+            .as_ref()
+            .zip(self.position(target))
+            .and_then(|(field, pos)| field.chokepoint_at(pos));
+        let Some(width) = width else {
+            return true;
+        };
+        let attackers = self.engaged.iter().filter(|(_, d)| *d == target);
+        self.engaged.contains(&(id, target)) || attackers.count() < width
+    }
+
+    fn strike(&mut self, id: CombatantId, target: CombatantId) -> Option<CombatResult> {
+        if !self.can_strike(id) {
+            return None;
+        }
+        #[cfg(feature = "magic")]
+        if let Some(evaded) = self.blink_away(id, target) {
+            return Some(evaded);
+        }
+        let mut dice = self.rules.dice;
+        let context = CombatContext::new(
+            self.combatants[id].stance.clone(),
+            self.combatants[target].stance.clone(),
+        );
+        let (attacker, defender) = pair_mut(&mut self.combatants, id, target);
+        let (defense, timed_out) = match &self.defense_provider {
+            Some(provider) => {
+                decide_defense(provider, self.turn_timer.as_ref(), defender, attacker)
+            }
+            None => (recommend_defense(defender, attacker), false),
+        };
+        let result = combat_round_in_context(
+            attacker,
+            defender,
+            defense,
+            &context,
+            &self.rules,
+            &mut dice,
+        )?;
+        self.rules.dice = dice;
+        if timed_out {
+            self.events.push(EncounterEvent::TimedOut {
+                combatant: target,
+                action: defense,
+            });
+        }
+        Some(self.land(target, result))
+    }
+
+    /// The target spends a readied blink; on success the blow misses
+    #[cfg(feature = "magic")]
+    fn blink_away(&mut self, id: CombatantId, target: CombatantId) -> Option<CombatResult> {
+        let spell = self.combatants[target].blink_reaction.take()?;
+        let mut dice = self.rules.dice;
+        let roll = dice.d10();
+        let blink = jump(
+            &mut self.combatants[target].character,
+            &spell,
+            SpellTarget::Caster,
+            &TargetInfo::caster(),
+            roll,
+        );
+        let result = match blink {
+            Ok(blink) if blink.arrived() => {
+                let attacker = &self.combatants[id].character;
+                let attack_breakdown = attacker.attack_breakdown(dice.d10());
+                Some(CombatResult {
+                    attacker: attacker.name.clone(),
+                    defender: self.combatants[target].character.name.clone(),
+                    attack_roll: attack_breakdown.total(),
+                    defense_roll: blink.casting.total,
+                    hit: false,
+                    damage: Damage::clamped(0),
+                    wound_level: None,
+                    defender_died: false,
+                    defender_dying: false,
+                    location: None,
+                    #[cfg(feature = "hit-location")]
+                    armor_events: Vec::new(),
+                    attack_breakdown,
+                    defense_breakdown: RollBreakdown::default(),
+                })
+            }
+            Ok(blink) => {
+                self.record_mishap(target, &blink);
+                None
+            }
+            Err(_) => None,
+        };
+        self.rules.dice = dice;
+        if result.is_some() {
+            self.events.push(EncounterEvent::BlinkedAway {
+                combatant: target,
+                attacker: id,
+            });
+        }
+        result
+    }
+
+    /// Loose a missile from where the shooter stands, if the target is in range
+    ///
+    /// Enemies in melee reach spoil the aim and, if the rules say so, get a
+    /// free blow before the shot.
+    #[cfg(feature = "ranged")]
+    fn shoot(&mut self, id: CombatantId, target: CombatantId) -> Vec<CombatResult> {
+        let team = self.combatants[id].team;
+        let engaged: Vec<CombatantId> = (0..self.combatants.len())
+            .filter(|&e| {
+                self.combatants[e].team != team
+                    && self.combatants[e].is_fighting()
+                    && self.in_reach(e, id)
+            })
+            .collect();
+        let mut results = Vec::new();
+        if self.rules.shots_provoke {
+            for &enemy in &engaged {
+                if !self.combatants[id].is_fighting() {
+                    return results;
+                }
+                if self.can_strike(enemy) {
+                    results.extend(self.attack(enemy, id));
+                }
+            }
+        }
+        if !self.combatants[id].is_fighting()
+            || !self.combatants[target].is_fighting()
+            || self.combatants[id].ammo == Some(0)
+        {
+            return results;
+        }
+        if let Some(ammo) = &mut self.combatants[id].ammo {
+            *ammo -= 1;
+        }
+        if !engaged.is_empty() {
+            self.combatants[id].character.add_modifier(Modifier::new(
+                "Engaged",
+                ModifierTarget::Ranged,
+                ENGAGED_SHOT_PENALTY,
+            ));
+        }
+        let distance = self.battlefield.as_ref().map_or(CELL_METERS, |field| {
+            field
+                .ranged_distance(
+                    &self.combatants[id].character.name,
+                    &self.combatants[target].character.name,
+                )
+                .unwrap_or(CELL_METERS)
+        });
+        let mut dice = self.rules.dice;
+        let (shooter, defender) = pair_mut(&mut self.combatants, id, target);
+        let result = ranged_attack_with_dice(
+            shooter,
+            defender,
+            distance,
+            Cover::None,
+            &self.rules,
+            &mut dice,
+        );
+        self.rules.dice = dice;
+        self.combatants[id]
+            .character
+            .modifiers
+            .remove_source("Engaged");
+        let Some(result) = result else {
+            return results;
+        };
+        let first = self.land(target, result);
+        let through = match self.behind(id, target) {
+            Some(behind) if self.rules.over_penetration => {
+                let (shooter, victim) = pair_mut(&mut self.combatants, id, behind);
+                over_penetrate(shooter, &first, victim, &self.rules).map(|result| (behind, result))
+            }
+            _ => None,
+        };
+        results.push(first);
+        if let Some((behind, result)) = through {
+            results.push(self.land(behind, result));
+        }
+        results
+    }
+
+    /// Whoever stands in the cell just beyond `target`, seen from `id`
+    #[cfg(feature = "ranged")]
+    fn behind(&self, id: CombatantId, target: CombatantId) -> Option<CombatantId> {
+        let field = self.battlefield.as_ref()?;
+        let (from, at) = (self.position(id)?, self.position(target)?);
+        // Extend the line of fire past the target and take the next cell on it
+        let beyond = GridPos::new(2 * at.x - from.x, 2 * at.y - from.y);
+        let line = field.kind.line(from, beyond);
+        let next = line.iter().skip_while(|&&pos| pos != at).nth(1)?;
+        self.find(field.occupant(*next)?)
+            .filter(|&b| b != id && self.combatants[b].is_fighting())
+    }
+
+    /// Finish off a wounded minion and let a boss change phase
+    fn land(&mut self, target: CombatantId, mut result: CombatResult) -> CombatResult {
+        if self.combatants[target].minion && result.wound_level.is_some() {
+            let minion = &mut self.combatants[target].character;
+            minion.wounds.critical = minion.wounds.critical.max(2);
+            minion.dying = None;
+            result.defender_died = true;
+            result.defender_dying = false;
+        }
+        self.update_phase(target);
+        result
+    }
+
+    /// The guard parries in the ward's place, at a penalty
+    ///
+    /// `None` if the attacker's maneuver can't attack, as in `strike`.
+    fn intercept(&mut self, id: CombatantId, guardian: CombatantId) -> Option<CombatResult> {
+        if !self.can_strike(id) {
+            return None;
+        }
+        let mut dice = self.rules.dice;
+        let context = CombatContext::new(
+            self.combatants[id].stance.clone(),
+            self.combatants[guardian].stance.clone(),
+        );
+        let (attacker, defender) = pair_mut(&mut self.combatants, id, guardian);
+        defender.add_modifier(Modifier::new("Guard", ModifierTarget::Parry, GUARD_PENALTY));
+        let result = combat_round_in_context(
+            attacker,
+            defender,
+            DefenseAction::Parry,
+            &context,
+            &self.rules,
+            &mut dice,
+        );
+        self.rules.dice = dice;
+        defender.modifiers.remove_source("Guard");
+        Some(self.land(guardian, result?))
+    }
+
+    fn update_phase(&mut self, id: CombatantId) {
+        let combatant = &mut self.combatants[id];
+        let Some(boss) = combatant.boss.as_mut() else {
+            return;
+        };
+        if let Some(phase) = boss.update_phase(&mut combatant.character) {
+            self.events.push(EncounterEvent::PhaseChanged {
+                combatant: id,
+                phase: phase.name.clone(),
+            });
+        }
+    }
+
+    /// Run rounds until one team is left or `max_rounds` have been fought
+    pub fn run(&mut self, max_rounds: u32) -> Option<u32> {
+        self.rounds(max_rounds).for_each(drop);
+        self.winner()
+    }
+
+    /// Fight like `run`, yielding each round's attacks as it is resolved
+    pub fn rounds(&mut self, max_rounds: u32) -> Rounds<'_> {
+        Rounds {
+            encounter: self,
+            left: max_rounds,
+        }
+    }
+
+    /// Seconds of fighting in the rounds resolved so far
+    pub fn elapsed_seconds(&self) -> i32 {
+        rounds_to_seconds(self.round.saturating_sub(1) as i32)
+    }
+
+    fn choose_target(&self, id: CombatantId) -> Option<CombatantId> {
+        let me = &self.combatants[id];
+        self.combatants
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| c.team != me.team && c.is_fighting() && self.can_engage(id, *i))
+            .map(|(i, c)| (i, target_priority(&me.character, &c.character)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    fn in_reach(&self, id: CombatantId, target: CombatantId) -> bool {
+        let Some(field) = &self.battlefield else {
+            return true;
+        };
+        let me = field.placement(&self.combatants[id].character.name);
+        let them = field.placement(&self.combatants[target].character.name);
+        match (me, them) {
+            (Some(a), Some(b)) => field.distance(a.pos, b.pos) <= 1,
+            _ => true,
+        }
+    }
+
+    /// Move toward the target on the battlefield; true if now adjacent
+    ///
+    /// Overwatch shots drawn on the way are added to `results`.
+    fn close_in(
+        &mut self,
+        id: CombatantId,
+        target: CombatantId,
+        results: &mut Vec<CombatResult>,
+    ) -> bool {
+        let Some(field) = self.battlefield.as_ref() else {
+            return true;
+        };
+        let me = &self.combatants[id].character;
+        let them = &self.combatants[target].character.name;
+        let (Some(from), Some(to)) = (field.placement(&me.name), field.placement(them)) else {
+            return true;
+        };
+        if field.distance(from.pos, to.pos) <= 1 {
+            return true;
+        }
+        let goal = MoveGoal::Engage(them.clone());
+        if let Some(path) = plan_move(field, &me.name, &goal, movement_allowance(me)) {
+            results.extend(self.advance(id, &path));
+        }
+        self.combatants[id].is_fighting() && self.in_reach(id, target)
+    }
+}
+
+/// Round-by-round results of a fight (see `Encounter::rounds`)
+#[derive(Debug)]
+pub struct Rounds<'a> {
+    encounter: &'a mut Encounter,
+    left: u32,
+}
+
+impl Iterator for Rounds<'_> {
+    type Item = Vec<CombatResult>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 || self.encounter.is_over() {
+            return None;
+        }
+        self.left -= 1;
+        Some(self.encounter.run_round())
+    }
+}
+
+/// Borrow two different combatants' characters mutably
+fn pair_mut(
+    combatants: &mut [Combatant],
+    a: CombatantId,
+    b: CombatantId,
+) -> (&mut Character, &mut Character) {
+    if a < b {
+        let (left, right) = combatants.split_at_mut(b);
+        (&mut left[a].character, &mut right[0].character)
+    } else {
+        let (left, right) = combatants.split_at_mut(a);
+        (&mut right[0].character, &mut left[b].character)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::boss::BossPhase;
+    use crate::modules::companions::war_dog;
+    use crate::modules::dice::DiceMode;
+    use crate::modules::exhaustion::{ATTACK_EXHAUSTION, CHARGE_EXHAUSTION};
+    #[cfg(feature = "ranged")]
+    use crate::modules::injuries::WoundDescription;
+    use crate::modules::modifiers::{Modifier, ModifierTarget};
+    use crate::modules::positioning::{Facing, GridKind, HIGH_GROUND_BONUS};
+    #[cfg(feature = "ranged")]
+    use crate::modules::ranged_combat::{calculate_ranged_modifiers, RangedWeapon, TargetSize};
+    use crate::modules::timing::SECONDS_PER_ROUND;
+    use crate::{Armor, Attributes, Weapon};
+
+    fn fighter(name: &str, dex: i32) -> Character {
+        Character::new(
+            name,
+            Attributes::new(6, dex, 6, 5, 5, 5, 5, 5, 5),
+            6,
+            4,
+            Weapon::long_sword(),
+            Armor::leather(),
+        )
+    }
+
+    #[test]
+    fn test_turn_order_and_winner() {
+        let mut encounter = Encounter::new();
+        encounter.add(fighter("Slow", 3), 1);
+        encounter.add(fighter("Quick", 9), 2);
+        encounter.add(fighter("Middling", 6), 1);
+        assert_eq!(encounter.turn_order(), vec![1, 2, 0]);
+        assert_eq!(encounter.teams_standing(), vec![1, 2]);
+        assert!(!encounter.is_over());
+        assert_eq!(encounter.elapsed_seconds(), 0);
+
+        let mut champion = fighter("Champion", 6);
+        champion.add_modifier(Modifier::new("Blessed", ModifierTarget::Attack, 30));
+        champion.add_modifier(Modifier::new("Blessed", ModifierTarget::Damage, 30));
+        encounter.add(champion, 1);
+        assert_eq!(encounter.run(10), Some(1));
+        assert!(!encounter.combatants[1].character.can_act());
+        assert_eq!(
+            encounter.elapsed_seconds(),
+            (encounter.round as i32 - 1) * SECONDS_PER_ROUND
+        );
+    }
+
+    #[test]
+    fn test_skirmishes_and_free_for_alls_report_each_round() {
+        let mut skirmish = Encounter::new();
+        for (i, dex) in [4, 5, 6, 7, 8, 9].into_iter().enumerate() {
+            skirmish.add(fighter(&format!("F{}", i), dex), i as u32 % 2 + 1);
+        }
+        skirmish.rules.dice = DiceMode::seeded(3);
+        let mut replay = skirmish.clone();
+
+        let rounds: Vec<_> = skirmish.rounds(40).collect();
+        assert!(skirmish.is_over());
+        assert_eq!(rounds.len() as u32, skirmish.round - 1);
+        assert!(rounds.iter().all(|round| round.len() <= 6));
+        assert_eq!(replay.run(40), skirmish.winner());
+        assert!(skirmish.winner().is_some());
+
+        // Everyone on their own team: one survivor at most
+        let mut brawl = Encounter::new();
+        for (i, dex) in [5, 6, 7, 8].into_iter().enumerate() {
+            brawl.add(fighter(&format!("B{}", i), dex), i as u32);
+        }
+        brawl.rules.dice = DiceMode::seeded(8);
+        let attacks: usize = brawl.rounds(60).map(|round| round.len()).sum();
+        assert!(attacks > 0);
+        assert!(brawl.teams_standing().len() <= 1);
+        assert_eq!(brawl.rounds(10).count(), 0);
+    }
+
+    #[test]
+    fn test_null_fields_dispel_summons() {
+        let field = Battlefield::new(GridKind::Square, 10, 3)
+            .with_anti_magic(GridPos::new(7, 1), 1, AntiMagic::Null)
+            .with_anti_magic(GridPos::new(1, 1), 1, AntiMagic::Dampened(-3));
+        assert_eq!(
+            field.anti_magic_at(GridPos::new(8, 2)),
+            Some(AntiMagic::Null)
+        );
+        assert_eq!(field.anti_magic_at(GridPos::new(4, 1)), None);
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let field = encounter.battlefield.as_mut().unwrap();
+        for (name, x) in [("Elara", 0), ("Imp", 6), ("Wisp", 3)] {
+            field
+                .place(name, GridPos::new(x, 1), Facing::NORTH)
+                .unwrap();
+        }
+        encounter.add(fighter("Elara", 6), 1);
+        let imp = encounter.add_summon(fighter("Imp", 6), 1);
+        let wisp = encounter.add_summon(fighter("Wisp", 6), 1);
+
+        encounter.step();
+        assert_eq!(
+            encounter.events,
+            vec![EncounterEvent::Dispelled { combatant: imp }]
+        );
+        assert!(!encounter.combatants[imp].is_fighting());
+        assert!(encounter.combatants[wisp].is_fighting());
+        let field = encounter.battlefield.as_ref().unwrap();
+        assert!(field.placement("Imp").is_none());
+        assert_eq!(field.anti_magic_for("Elara"), Some(AntiMagic::Dampened(-3)));
+        #[cfg(feature = "magic")]
+        assert_eq!(
+            encounter.combatants[0]
+                .character
+                .modifiers
+                .total(ModifierTarget::Casting),
+            -3
+        );
+    }
+
+    #[test]
+    fn test_combatants_close_in_on_the_battlefield() {
+        let mut field = Battlefield::new(GridKind::Square, 14, 3);
+        field
+            .place("Aldric", GridPos::new(0, 1), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Grimwald", GridPos::new(13, 1), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        encounter.add(fighter("Aldric", 6), 1);
+        encounter.add(fighter("Grimwald", 5), 2);
+
+        // 13 cells apart with 5 movement each: nobody reaches a foe in round 1
+        let results = encounter.run_round();
+        assert!(results.is_empty());
+        let field = encounter.battlefield.as_ref().unwrap();
+        let a = field.placement("Aldric").unwrap().pos;
+        let g = field.placement("Grimwald").unwrap().pos;
+        assert_eq!(field.distance(a, g), 3);
+        assert_eq!(encounter.round, 2);
+        assert!(!encounter.run_round().is_empty());
+    }
+
+    #[test]
+    fn test_minions_drop_to_any_wound_and_share_a_target() {
+        let mut encounter = Encounter::new();
+        let mut hero = fighter("Aldric", 8);
+        hero.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        hero.add_modifier(Modifier::new("Fury", ModifierTarget::Attack, 100));
+        let hero = encounter.add(hero, 1);
+        let mut goblin = fighter("Goblin", 4);
+        goblin.weapon = Weapon::dagger();
+        goblin.armor = Armor::none();
+        let ids = encounter.add_minions(&goblin, 20, 2);
+        assert_eq!(encounter.combatants[ids[19]].character.name, "Goblin 20");
+
+        // Every goblin attacks the hero; the hero's hit drops one goblin outright
+        let results = encounter.run_round();
+        assert_eq!(results.len(), 20);
+        assert!(results.iter().skip(1).all(|r| r.defender == "Aldric"));
+        assert!(results[0].defender_died);
+        assert_eq!(
+            encounter
+                .combatants
+                .iter()
+                .filter(|c| c.minion && !c.character.is_alive())
+                .count(),
+            1
+        );
+        assert!(encounter.combatants[hero].character.can_act());
+    }
+
+    #[test]
+    fn test_boss_phases_and_legendary_actions() {
+        // Everyone is warded so nobody falls and every turn produces an attack
+        let warded = |name, dex| {
+            let mut c = fighter(name, dex);
+            c.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+            c
+        };
+        let mut encounter = Encounter::new();
+        for name in ["Aldric", "Brenna", "Cedric"] {
+            encounter.add(warded(name, 5), 1);
+        }
+        let boss = Boss::new(BossPhase::new("Calm", 0.0).with_legendary_actions(1)).with_phase(
+            BossPhase::new("Frenzy", 0.5)
+                .with_modifier(Modifier::new("", ModifierTarget::Attack, 2))
+                .with_legendary_actions(2),
+        );
+        let id = encounter.add_boss(warded("Troll", 4), 2, boss);
+        assert_eq!(
+            encounter.events,
+            vec![EncounterEvent::PhaseChanged {
+                combatant: id,
+                phase: "Calm".to_string()
+            }]
+        );
+
+        // Three heroes and one boss turn, plus one legendary action
+        assert_eq!(encounter.run_round().len(), 5);
+
+        encounter.combatants[id].character.wounds.severe = 3;
+        encounter.update_phase(id);
+        assert_eq!(
+            encounter.combatants[id]
+                .character
+                .modifiers
+                .total(ModifierTarget::Attack),
+            2
+        );
+        encounter.events.clear();
+        encounter.run_round();
+        let legendary = encounter
+            .events
+            .iter()
+            .filter(|e| matches!(e, EncounterEvent::LegendaryAction { .. }))
+            .count();
+        assert_eq!(legendary, 2);
+    }
+
+    #[test]
+    fn test_escaped_boss_takes_no_legendary_actions() {
+        let mut encounter = Encounter::new();
+        encounter.add(fighter("Aldric", 5), 1);
+        encounter.add(fighter("Brenna", 5), 1);
+        let boss = Boss::new(BossPhase::new("Calm", 0.0).with_legendary_actions(2));
+        let troll = encounter.add_boss(fighter("Troll", 4), 2, boss);
+        encounter.add(fighter("Goblin", 3), 2);
+        encounter.combatants[troll].escaped = true;
+        encounter.events.clear();
+
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.attacker != "Troll"));
+        assert!(!encounter
+            .events
+            .iter()
+            .any(|e| matches!(e, EncounterEvent::LegendaryAction { .. })));
+    }
+
+    #[test]
+    fn test_companions_follow_owner_and_commands() {
+        let warded = |mut c: Character| {
+            c.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+            c
+        };
+        let mut encounter = Encounter::new();
+        let owner = encounter.add(warded(fighter("Aldric", 3)), 1);
+        encounter.add(warded(fighter("Grimwald", 6)), 2);
+        let dog = encounter
+            .add_companion(owner, warded(war_dog("Rex")), Companion::new(""))
+            .unwrap();
+        assert_eq!(encounter.combatants[dog].team, 1);
+        assert_eq!(encounter.turn_order(), vec![1, owner, dog]);
+
+        // Commanding costs the owner's attack this round
+        assert!(encounter.command_companion(dog, 5, CompanionCommand::Heel, 10));
+        assert!(!encounter.command_companion(dog, 5, CompanionCommand::Attack, 10));
+        assert!(encounter
+            .run_round()
+            .iter()
+            .all(|r| r.attacker == "Grimwald"));
+
+        encounter.combatants[dog]
+            .companion
+            .as_mut()
+            .unwrap()
+            .command = CompanionCommand::Attack;
+        let results = encounter.run_round();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[2].attacker, "Rex");
+
+        // The bond is to the combatant, not whoever shares the owner's name
+        let mut encounter = Encounter::new();
+        encounter.add(fighter("Aldric", 3), 2);
+        let owner = encounter.add(fighter("Aldric", 3), 1);
+        let dog = encounter
+            .add_companion(owner, war_dog("Rex"), Companion::new(""))
+            .unwrap();
+        assert_eq!(encounter.turn_order(), vec![0, owner, dog]);
+        assert!(encounter.command_companion(dog, 5, CompanionCommand::Heel, 10));
+        assert!(encounter.commanding.contains(&owner));
+
+        // An unknown owner adds nothing
+        assert_eq!(
+            encounter.add_companion(9, war_dog("Fang"), Companion::new("")),
+            None
+        );
+        assert_eq!(encounter.combatants.len(), 3);
+    }
+
+    #[test]
+    fn test_companion_flees_without_its_owner() {
+        let mut encounter = Encounter::new();
+        let owner = encounter.add(fighter("Aldric", 3), 1);
+        encounter.add(fighter("Grimwald", 6), 2);
+        let dog = encounter
+            .add_companion(owner, war_dog("Rex"), Companion::new("").with_loyalty(0))
+            .unwrap();
+        encounter.combatants[owner]
+            .character
+            .wounds
+            .add_wound(crate::WoundLevel::Critical);
+        let hurt = &mut encounter.combatants[dog].character.wounds;
+        hurt.severe = 2;
+        hurt.light = 3;
+
+        // d10 + 0 loyalty - 7 wound penalty never reaches the desertion threshold
+        encounter.run_round();
+        assert!(encounter.combatants[dog].companion.as_ref().unwrap().fled);
+        assert!(encounter
+            .events
+            .contains(&EncounterEvent::CompanionFled { combatant: dog }));
+        assert_eq!(encounter.winner(), Some(2));
+    }
+
+    #[test]
+    fn test_guard_intercepts_attacks_on_adjacent_ally() {
+        let mut field = Battlefield::new(GridKind::Square, 6, 3);
+        for (name, x) in [("Squire", 0), ("Mage", 1), ("Ogre", 2)] {
+            field
+                .place(name, GridPos::new(x, 1), Facing::NORTH)
+                .unwrap();
+        }
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let mut squire = fighter("Squire", 3);
+        squire.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        let squire = encounter.add(squire, 1);
+        let mage = encounter.add(fighter("Mage", 2), 1);
+        let ogre = encounter.add(fighter("Ogre", 9), 2);
+
+        assert!(!encounter.guard(squire, ogre));
+        assert!(encounter.guard(squire, mage));
+
+        // The ogre swings at the mage, but the warded squire parries instead;
+        // the mage's own attack goes through unguarded
+        let results = encounter.run_round();
+        assert_eq!(results[0].defender, "Squire");
+        assert!(!results[0].hit);
+        assert_eq!(
+            encounter.events,
+            vec![EncounterEvent::Intercepted {
+                guardian: squire,
+                ward: mage
+            }]
+        );
+        assert_eq!(results.len(), 2);
+        assert_eq!(encounter.combatants[squire].character.modifiers.len(), 1);
+
+        encounter.stand_down(squire);
+        assert_eq!(encounter.guard_for(mage), None);
+
+        // The guard parries from its own stance, and a guarding minion still
+        // drops to any wound
+        let mut field = Battlefield::new(GridKind::Square, 6, 3);
+        for (name, x) in [("Squire", 0), ("Mage", 1), ("Ogre", 2)] {
+            field
+                .place(name, GridPos::new(x, 1), Facing::NORTH)
+                .unwrap();
+        }
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let squire = encounter.add(fighter("Squire", 3), 1);
+        let mage = encounter.add(fighter("Mage", 2), 1);
+        let mut ogre = fighter("Ogre", 9);
+        ogre.add_modifier(Modifier::new("Rage", ModifierTarget::Attack, 100));
+        encounter.add(ogre, 2);
+        encounter.combatants[squire].minion = true;
+        encounter
+            .set_maneuver(squire, CombatManeuver::DefensivePosition)
+            .unwrap();
+        assert!(encounter.guard(squire, mage));
+        let results = encounter.run_round();
+        assert_eq!(results[0].defender, "Squire");
+        assert_eq!(
+            results[0].defense_breakdown.stance_modifier,
+            CombatManeuver::DefensivePosition.defense_modifier()
+        );
+        assert!(results[0].defender_died);
+
+        // An attacker that can't strike is not intercepted
+        encounter.events.clear();
+        let ogre = encounter.find("Ogre").unwrap();
+        encounter
+            .set_maneuver(ogre, CombatManeuver::DefensivePosition)
+            .unwrap();
+        let acolyte = encounter.add(fighter("Acolyte", 2), 1);
+        encounter
+            .battlefield
+            .as_mut()
+            .unwrap()
+            .place("Acolyte", GridPos::new(3, 1), Facing::NORTH)
+            .unwrap();
+        let bodyguard = encounter.add(fighter("Bodyguard", 2), 1);
+        encounter
+            .battlefield
+            .as_mut()
+            .unwrap()
+            .place("Bodyguard", GridPos::new(3, 2), Facing::NORTH)
+            .unwrap();
+        encounter
+            .set_maneuver(bodyguard, CombatManeuver::Guard)
+            .unwrap();
+        assert!(encounter.guard(bodyguard, acolyte));
+        assert!(encounter.attack(ogre, acolyte).is_none());
+        assert!(encounter.events.is_empty());
+    }
+
+    #[test]
+    fn test_withdrawal_backs_away_under_parting_blows() {
+        let mut field = Battlefield::new(GridKind::Square, 8, 1);
+        field
+            .place("Aldric", GridPos::new(3, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Grimwald", GridPos::new(4, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let mut aldric = fighter("Aldric", 9);
+        aldric.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        aldric.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        let aldric = encounter.add(aldric, 1);
+        let grimwald = encounter.add(fighter("Grimwald", 3), 2);
+
+        encounter.retreat(aldric, Retreat::Withdraw);
+        assert_eq!(
+            encounter.combatants[aldric]
+                .character
+                .modifiers
+                .total(ModifierTarget::Parry),
+            100 + WITHDRAW_DEFENSE
+        );
+
+        // Aldric takes a parting blow and steps back; Grimwald follows up
+        let results = encounter.run_round();
+        assert_eq!(results[0].defender, "Aldric");
+        assert!(!results[0].hit);
+        let field = encounter.battlefield.as_ref().unwrap();
+        assert_eq!(field.placement("Aldric").unwrap().pos, GridPos::new(2, 0));
+        assert!(encounter.in_reach(grimwald, aldric));
+
+        encounter.stand_down(aldric);
+        assert_eq!(
+            encounter.combatants[aldric]
+                .character
+                .modifiers
+                .total(ModifierTarget::Parry),
+            100
+        );
+    }
+
+    #[test]
+    fn test_flight_is_undefended_and_hands_off_pursuit() {
+        let mut field = Battlefield::new(GridKind::Square, 6, 3);
+        field
+            .place("Aldric", GridPos::new(1, 1), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Grimwald", GridPos::new(2, 1), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let mut aldric = fighter("Aldric", 9);
+        aldric.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        aldric.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        let aldric = encounter.add(aldric, 1);
+        let mut grimwald = fighter("Grimwald", 3);
+        grimwald.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -100));
+        let grimwald = encounter.add(grimwald, 2);
+
+        encounter.retreat(aldric, Retreat::Flee);
+        let results = encounter.run_round();
+
+        // Even a warded fugitive is hit from behind, then runs off the field
+        assert_eq!(results.len(), 1);
+        assert!(results[0].hit);
+        assert!(encounter.combatants[aldric]
+            .character
+            .modifiers
+            .iter()
+            .all(|m| m.source != "Flight"));
+        assert!(encounter.combatants[aldric].escaped);
+        assert!(encounter
+            .battlefield
+            .as_ref()
+            .unwrap()
+            .placement("Aldric")
+            .is_none());
+        assert_eq!(
+            encounter.events,
+            vec![EncounterEvent::Escaped {
+                combatant: aldric,
+                pursuers: vec![grimwald]
+            }]
+        );
+        assert_eq!(encounter.winner(), Some(2));
+    }
+
+    #[test]
+    fn test_elevation_applies_high_ground_and_downhill_charge() {
+        let opening_blow = |field: Battlefield| {
+            let mut encounter = Encounter::new().with_battlefield(field);
+            encounter.rules.dice = DiceMode::Average;
+            let mut aldric = fighter("Aldric", 9);
+            aldric.add_modifier(Modifier::new("Fury", ModifierTarget::Attack, 3));
+            let aldric = encounter.add(aldric, 1);
+            encounter.add(fighter("Grimwald", 3), 2);
+            let field = encounter.battlefield.as_mut().unwrap();
+            field
+                .place("Aldric", GridPos::new(0, 0), Facing::NORTH)
+                .unwrap();
+            field
+                .place("Grimwald", GridPos::new(3, 0), Facing::NORTH)
+                .unwrap();
+            let results = encounter.run_round();
+            assert_eq!(
+                encounter.combatants[aldric]
+                    .character
+                    .modifiers
+                    .total(ModifierTarget::Attack),
+                3
+            );
+            results[0].damage.get()
+        };
+
+        let flat = opening_blow(Battlefield::new(GridKind::Square, 6, 1));
+        // Charging down a slope and striking from a ledge still above the foe
+        let hill = opening_blow(
+            Battlefield::new(GridKind::Square, 6, 1)
+                .with_elevation(GridPos::new(0, 0), 2)
+                .with_elevation(GridPos::new(2, 0), 1),
+        );
+        assert!(flat > 0);
+        assert_eq!(hill, flat + HIGH_GROUND_BONUS + DOWNHILL_CHARGE_BONUS);
+    }
+
+    #[test]
+    fn test_chokepoint_limits_attackers_per_round() {
+        let field = Battlefield::new(GridKind::Square, 5, 3).with_chokepoint(GridPos::new(2, 1), 1);
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let mut warden = fighter("Warden", 1);
+        warden.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        warden.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        let warden = encounter.add(warden, 1);
+        let first = encounter.add(fighter("Orc", 9), 2);
+        encounter.add(fighter("Goblin", 8), 2);
+        let field = encounter.battlefield.as_mut().unwrap();
+        for (name, pos) in [("Warden", (2, 1)), ("Orc", (1, 1)), ("Goblin", (1, 0))] {
+            field
+                .place(name, GridPos::new(pos.0, pos.1), Facing::NORTH)
+                .unwrap();
+        }
+
+        // Only the first foe can press the doorway; the goblin waits
+        let results = encounter.run_round();
+        let attackers: Vec<&str> = results
+            .iter()
+            .filter(|r| r.defender == "Warden")
+            .map(|r| r.attacker.as_str())
+            .collect();
+        assert_eq!(attackers, vec!["Orc"]);
+        assert!(encounter.engaged.is_empty());
+        assert!(encounter.can_engage(first, warden));
+    }
+
+    #[test]
+    fn test_shield_wall_needs_an_adjacent_shielded_ally() {
+        let mut encounter =
+            Encounter::new().with_battlefield(Battlefield::new(GridKind::Square, 5, 3));
+        let shielded = |name: &str| {
+            let mut character = fighter(name, 5);
+            character.weapon = Weapon::shield();
+            character
+        };
+        let left = encounter.add(shielded("Left"), 1);
+        let right = encounter.add(shielded("Right"), 1);
+        let field = encounter.battlefield.as_mut().unwrap();
+        field
+            .place("Left", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Right", GridPos::new(1, 0), Facing::NORTH)
+            .unwrap();
+        assert!(encounter.in_shield_wall(left));
+        assert!(encounter.in_shield_wall(right));
+
+        let field = encounter.battlefield.as_mut().unwrap();
+        field.step("Right", GridPos::new(2, 0)).unwrap();
+        assert!(!encounter.in_shield_wall(left));
+
+        encounter.combatants[right].character.weapon = Weapon::long_sword();
+        encounter
+            .battlefield
+            .as_mut()
+            .unwrap()
+            .step("Right", GridPos::new(1, 0))
+            .unwrap();
+        assert!(!encounter.in_shield_wall(left));
+    }
+
+    #[test]
+    fn test_unready_combatants_act_last_at_a_penalty() {
+        let mut encounter = Encounter::new();
+        encounter.rules.dice = DiceMode::Average;
+        let scout = encounter.add(fighter("Scout", 9), 1);
+        let mut guard = fighter("Guard", 3);
+        guard.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -100));
+        let guard = encounter.add(guard, 2);
+        encounter.set_readiness(scout, Readiness::Sheathed);
+        assert_eq!(encounter.turn_order(), vec![guard, scout]);
+
+        #[cfg(feature = "ranged")]
+        {
+            let mut bow = RangedAttackState::new();
+            assert_eq!(Readiness::of_ranged(&bow), Readiness::Unloaded);
+            bow.prepare_weapon(&RangedWeapon::short_bow());
+            assert_eq!(Readiness::of_ranged(&bow), Readiness::Set);
+        }
+
+        // Drawing costs the scout on both attack and defense, for one round only
+        let results = encounter.run_round();
+        let penalty = Readiness::Sheathed.first_round_penalty();
+        assert_eq!(results[0].attacker, "Guard");
+        assert_eq!(results[0].defense_roll, results[0].attack_roll + penalty);
+        assert_eq!(results[1].attack_roll, results[0].attack_roll + penalty);
+        assert_eq!(encounter.combatants[scout].readiness, Readiness::Set);
+        assert!(encounter.combatants[scout].character.modifiers.is_empty());
+        assert_eq!(encounter.turn_order(), vec![scout, guard]);
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_archer_shoots_then_switches_weapons() {
+        let mut field = Battlefield::new(GridKind::Square, 8, 1);
+        field
+            .place("Archer", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Knight", GridPos::new(7, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        encounter.rules.dice = DiceMode::Average;
+        let archer = fighter("Archer", 6).with_ranged(RangedWeapon::short_bow(), 6);
+        let archer = encounter.add(archer, 1);
+        encounter.combatants[archer].wielding = Wielding::Ranged;
+        let mut knight = fighter("Knight", 5);
+        knight.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -100));
+        knight.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        let knight = encounter.add(knight, 2);
+
+        // The archer looses from where they stand
+        let results = encounter.run_round();
+        assert!(results.iter().any(|r| r.attacker == "Archer"));
+        assert_eq!(encounter.position(archer), Some(GridPos::new(0, 0)));
+
+        // Slinging the bow to draw a blade takes the whole round
+        assert!(!encounter.switch_weapon(knight, WeaponSwitch::SheatheAndDraw));
+        assert!(encounter.switch_weapon(archer, WeaponSwitch::SheatheAndDraw));
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.attacker != "Archer"));
+        assert!(!encounter.switch_weapon(archer, WeaponSwitch::DropAndDraw));
+
+        // Dropping the bow is quicker: the archer still attacks, unready
+        encounter.switch_weapon(archer, WeaponSwitch::SheatheAndDraw);
+        encounter.run_round();
+        assert!(encounter.switch_weapon(archer, WeaponSwitch::DropAndDraw));
+        assert!(encounter.combatants[archer]
+            .character
+            .ranged_weapon
+            .is_none());
+        assert!(encounter.events.contains(&EncounterEvent::WeaponDropped {
+            combatant: archer,
+            weapon: "Short Bow".to_string(),
+        }));
+        let expected = encounter.combatants[archer].character.attack_roll_with(5)
+            + Readiness::Sheathed.first_round_penalty();
+        let results = encounter.run_round();
+        let blow = results.iter().find(|r| r.attacker == "Archer").unwrap();
+        assert_eq!(blow.attack_roll, expected);
+        assert_eq!(encounter.combatants[archer].wielding, Wielding::Melee);
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_shooting_in_melee_is_penalized_and_provokes() {
+        let mut field = Battlefield::new(GridKind::Square, 4, 1);
+        field
+            .place("Archer", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Knight", GridPos::new(1, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        encounter.rules = Ruleset::default()
+            .with_dice(DiceMode::Average)
+            .with_shots_provoke(true);
+        let archer = fighter("Archer", 6).with_ranged(RangedWeapon::short_bow(), 6);
+        let archer = encounter.add(archer, 1);
+        encounter.combatants[archer].wielding = Wielding::Ranged;
+        let mut knight = fighter("Knight", 5);
+        knight.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -100));
+        knight.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        encounter.add(knight, 2);
+
+        let shooter = &encounter.combatants[archer].character;
+        let expected = shooter.ranged_roll_with(5)
+            + calculate_ranged_modifiers(
+                CELL_METERS,
+                TargetSize::Medium,
+                Cover::None,
+                shooter.ranged_weapon.as_ref().unwrap(),
+                &RangedAttackState::new(),
+            )
+            + ENGAGED_SHOT_PENALTY;
+        let results = encounter.run_round();
+        let shot = results.iter().find(|r| r.attacker == "Archer").unwrap();
+        assert_eq!(shot.attack_roll, expected);
+        // The knight's free blow comes on top of their own turn
+        assert_eq!(results.iter().filter(|r| r.attacker == "Knight").count(), 2);
+        let shooter = &encounter.combatants[archer].character;
+        assert_eq!(shooter.modifiers.total(ModifierTarget::Ranged), 0);
+    }
+
+    #[test]
+    fn test_minions_in_defensive_position_hold_back() {
+        let mut encounter = Encounter::new();
+        encounter.add(fighter("Aldric", 8), 1);
+        let ids = encounter.add_minions(&fighter("Goblin", 4), 2, 2);
+        for &id in &ids {
+            encounter
+                .set_maneuver(id, CombatManeuver::DefensivePosition)
+                .unwrap();
+        }
+
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.attacker == "Aldric"));
+    }
+
+    #[test]
+    fn test_defensive_enemy_takes_no_parting_blow() {
+        let mut field = Battlefield::new(GridKind::Square, 8, 1);
+        field
+            .place("Aldric", GridPos::new(3, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Grimwald", GridPos::new(4, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let aldric = encounter.add(fighter("Aldric", 9), 1);
+        let grimwald = encounter.add(fighter("Grimwald", 3), 2);
+        encounter
+            .set_maneuver(grimwald, CombatManeuver::DefensivePosition)
+            .unwrap();
+
+        encounter.retreat(aldric, Retreat::Withdraw);
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.attacker != "Grimwald"));
+        let field = encounter.battlefield.as_ref().unwrap();
+        assert_eq!(field.placement("Aldric").unwrap().pos, GridPos::new(2, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_defensive_enemy_takes_no_free_blow_at_a_shooter() {
+        let mut field = Battlefield::new(GridKind::Square, 4, 1);
+        field
+            .place("Archer", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Knight", GridPos::new(1, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        encounter.rules = Ruleset::default().with_shots_provoke(true);
+        let archer = fighter("Archer", 6).with_ranged(RangedWeapon::short_bow(), 6);
+        let archer = encounter.add(archer, 1);
+        encounter.combatants[archer].wielding = Wielding::Ranged;
+        let knight = encounter.add(fighter("Knight", 5), 2);
+        encounter
+            .set_maneuver(knight, CombatManeuver::DefensivePosition)
+            .unwrap();
+
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.attacker == "Archer"));
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_defensive_boss_spends_no_legendary_actions() {
+        let mut encounter = Encounter::new();
+        encounter.add(fighter("Aldric", 5), 1);
+        encounter.add(fighter("Brenna", 5), 1);
+        let boss = Boss::new(BossPhase::new("Calm", 0.0).with_legendary_actions(2));
+        let troll = encounter.add_boss(fighter("Troll", 4), 2, boss);
+        encounter
+            .set_maneuver(troll, CombatManeuver::DefensivePosition)
+            .unwrap();
+        encounter.events.clear();
+
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.attacker != "Troll"));
+        assert!(!encounter
+            .events
+            .iter()
+            .any(|e| matches!(e, EncounterEvent::LegendaryAction { .. })));
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_overwatch_snap_shoots_first_enemy_into_zone() {
+        let mut field = Battlefield::new(GridKind::Square, 8, 1);
+        field
+            .place("Archer", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Knight", GridPos::new(7, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        encounter.rules.dice = DiceMode::Average;
+        let archer = fighter("Archer", 6).with_ranged(RangedWeapon::short_bow(), 6);
+        let archer = encounter.add(archer, 1);
+        let mut knight = fighter("Knight", 5);
+        knight.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        let knight = encounter.add(knight, 2);
+
+        let zone = vec![GridPos::new(4, 0), GridPos::new(3, 0)];
+        assert!(!encounter.overwatch(archer, zone.clone()));
+        encounter.combatants[archer].wielding = Wielding::Ranged;
+        assert!(encounter.overwatch(archer, zone));
+
+        let shooter = &encounter.combatants[archer].character;
+        let expected = shooter.ranged_roll_with(5)
+            + calculate_ranged_modifiers(
+                4 * CELL_METERS,
+                TargetSize::Medium,
+                Cover::None,
+                shooter.ranged_weapon.as_ref().unwrap(),
+                &RangedAttackState::new(),
+            )
+            + SNAP_SHOT_PENALTY;
+        let results = encounter.run_round();
+        let shots: Vec<_> = results.iter().filter(|r| r.attacker == "Archer").collect();
+        assert_eq!(shots.len(), 1);
+        assert_eq!(shots[0].attack_roll, expected);
+        assert!(encounter.events.contains(&EncounterEvent::OverwatchFired {
+            shooter: archer,
+            target: knight,
+        }));
+        assert!(encounter.combatants[archer].overwatch.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_over_penetration_hits_the_combatant_behind() {
+        let mut field = Battlefield::new(GridKind::Square, 6, 1);
+        for (name, x) in [("Archer", 0), ("Squire", 2), ("Knight", 3)] {
+            field
+                .place(name, GridPos::new(x, 0), Facing::NORTH)
+                .unwrap();
+        }
+        let mut encounter = Encounter::new().with_battlefield(field).with_rules(
+            Ruleset::default()
+                .with_dice(DiceMode::Average)
+                .with_over_penetration(true),
+        );
+        let archer = fighter("Archer", 6).with_ranged(RangedWeapon::crossbow(), 10);
+        let archer = encounter.add(archer, 1);
+        encounter.combatants[archer].wielding = Wielding::Ranged;
+        encounter.add(fighter("Knight", 5), 2);
+        encounter.add(fighter("Squire", 4), 2);
+
+        let results = encounter.run_round();
+        let shots: Vec<_> = results.iter().filter(|r| r.attacker == "Archer").collect();
+        assert_eq!(shots.len(), 2);
+        assert_eq!(shots[0].defender, "Squire");
+        assert_eq!(shots[1].defender, "Knight");
+    }
+
+    #[test]
+    fn test_exhaustion_rule_tires_attackers_and_chargers() {
+        let duel = |exhaustion: bool| {
+            let mut encounter = Encounter::new();
+            encounter.rules = Ruleset::new()
+                .with_dice(DiceMode::Average)
+                .with_exhaustion(exhaustion);
+            let ids: Vec<CombatantId> = [("Raider", 1), ("Warden", 2)]
+                .into_iter()
+                .map(|(name, team)| {
+                    let mut c = fighter(name, 6);
+                    c.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+                    c.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+                    encounter.add(c, team)
+                })
+                .collect();
+            encounter
+                .set_maneuver(ids[0], CombatManeuver::Charge)
+                .unwrap();
+            encounter.run_round();
+            let fatigue = |id: CombatantId| encounter.combatants[id].character.fatigue.get();
+            (fatigue(ids[0]), fatigue(ids[1]))
+        };
+
+        assert_eq!(duel(false), (0, 0));
+        let (raider, warden) = duel(true);
+        assert!(warden >= ATTACK_EXHAUSTION);
+        assert_eq!(raider - warden, CHARGE_EXHAUSTION);
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_legal_actions_follow_weapon_ammo_wounds_and_maneuvers() {
+        let mut field = Battlefield::new(GridKind::Square, 8, 1);
+        field
+            .place("Archer", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Knight", GridPos::new(7, 0), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        encounter.rules.dice = DiceMode::Average;
+        let archer = fighter("Archer", 6).with_ranged(RangedWeapon::short_bow(), 6);
+        let archer = encounter.add(archer, 1);
+        encounter.combatants[archer].wielding = Wielding::Ranged;
+        encounter.combatants[archer].ammo = Some(1);
+        let mut knight = fighter("Knight", 5);
+        knight.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -100));
+        knight.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+        let knight = encounter.add(knight, 2);
+
+        assert_eq!(
+            encounter.legal_actions(archer),
+            vec![
+                ActionKind::Shoot,
+                ActionKind::SwitchWeapon,
+                ActionKind::Overwatch,
+                ActionKind::Withdraw,
+                ActionKind::Flee,
+            ]
+        );
+        let options = encounter.legal_actions(knight);
+        assert!(options.contains(&ActionKind::Attack));
+        assert!(options.contains(&ActionKind::Maneuver(CombatManeuver::Charge)));
+        assert!(!options.contains(&ActionKind::Maneuver(CombatManeuver::AimedAttack)));
+
+        // The last arrow is loosed; the bow is now useless
+        encounter.run_round();
+        assert_eq!(encounter.combatants[archer].ammo, Some(0));
+        assert!(!encounter.legal_actions(archer).contains(&ActionKind::Shoot));
+        let shots = encounter.run_round();
+        assert!(shots.iter().all(|r| r.attacker != "Archer"));
+
+        // A maimed leg rules out fleeing and charging
+        let wounded = &mut encounter.combatants[knight].character;
+        wounded.inflict_wound(
+            WoundDescription::new(WoundLevel::Severe, "Arrow").at(HitLocation::LeftLeg),
+        );
+        let options = encounter.legal_actions(knight);
+        assert!(options.contains(&ActionKind::Withdraw));
+        assert!(!options.contains(&ActionKind::Flee));
+        assert!(!options.contains(&ActionKind::Maneuver(CombatManeuver::Charge)));
+
+        // Holding a defensive position means no attacks
+        encounter
+            .set_maneuver(knight, CombatManeuver::DefensivePosition)
+            .unwrap();
+        assert!(encounter.run_round().iter().all(|r| r.attacker != "Knight"));
+        assert!(!encounter
+            .legal_actions(knight)
+            .contains(&ActionKind::Attack));
+        assert_eq!(
+            encounter.set_maneuver(knight, CombatManeuver::AimedAttack),
+            Err(ManeuverError::NotPrepared)
+        );
+        assert_eq!(
+            encounter.set_maneuver(99, CombatManeuver::Charge),
+            Err(ManeuverError::UnknownCombatant(99))
+        );
+    }
+
+    #[cfg(feature = "magic")]
+    fn blinker() -> Character {
+        use crate::modules::magic::{
+            MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration,
+            SpellRange,
+        };
+        let mut magic = MagicUser::new(4);
+        magic.add_lore(MagicBranch::Transportation, 6);
+        for (name, difficulty) in [
+            ("Blink", SpellDifficulty::Easy),
+            ("Teleport", SpellDifficulty::Hard),
+        ] {
+            magic
+                .learn_spell(
+                    Spell {
+                        name: name.to_string(),
+                        branch: MagicBranch::Transportation,
+                        difficulty,
+                        preparation_time: 0,
+                        casting_time: 1,
+                        range: SpellRange::Short(20),
+                        duration: SpellDuration::Instant,
+                        components: SpellComponents::default(),
+                    },
+                    3,
+                )
+                .unwrap();
+        }
+        let mut mage = fighter("Elara", 6);
+        mage.magic = Some(magic);
+        mage
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_blink_repositions_and_spends_the_action() {
+        use crate::modules::positioning::MoveError;
+        let mut field = Battlefield::new(GridKind::Square, 12, 3);
+        field
+            .place("Elara", GridPos::new(0, 1), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Brute", GridPos::new(11, 1), Facing::NORTH)
+            .unwrap();
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let mage = encounter.add(blinker(), 1);
+        encounter.add(fighter("Brute", 5), 2);
+
+        let occupied = GridPos::new(11, 1);
+        assert_eq!(
+            encounter.blink(mage, "Blink", occupied, 10).unwrap_err(),
+            TransportError::Blocked(MoveError::Occupied(occupied))
+        );
+        let to = GridPos::new(8, 1);
+        let blink = encounter.blink(mage, "Blink", to, 10).unwrap();
+        assert!(blink.arrived());
+        let field = encounter.battlefield.as_ref().unwrap();
+        assert_eq!(field.placement("Elara").unwrap().pos, to);
+        assert_eq!(
+            encounter.events,
+            vec![EncounterEvent::Blinked {
+                combatant: mage,
+                from: GridPos::new(0, 1),
+                to,
+            }]
+        );
+        assert!(encounter.legal_actions(mage).is_empty());
+        assert_eq!(
+            encounter
+                .blink(mage, "Blink", GridPos::new(7, 1), 10)
+                .unwrap_err(),
+            TransportError::CannotAct
+        );
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_readied_blink_evades_and_teleport_ends_the_fight() {
+        let mut encounter = Encounter::new().with_rules(Ruleset {
+            dice: DiceMode::Average,
+            ..Ruleset::new()
+        });
+        let mage = encounter.add(blinker(), 1);
+        let brute = encounter.add(fighter("Brute", 5), 2);
+        encounter.combatants[brute]
+            .character
+            .add_modifier(Modifier::new("Fury", ModifierTarget::Attack, 100));
+
+        // The readied blink turns aside a blow that could not otherwise miss
+        encounter.ready_blink(mage, "Blink").unwrap();
+        let results = encounter.run_round();
+        let evaded = results.iter().find(|r| r.defender == "Elara").unwrap();
+        assert!(!evaded.hit);
+        assert!(encounter.events.contains(&EncounterEvent::BlinkedAway {
+            combatant: mage,
+            attacker: brute,
+        }));
+        assert!(encounter.combatants[mage].blink_reaction.is_none());
+
+        encounter.combatants[brute]
+            .character
+            .modifiers
+            .remove_source("Fury");
+
+        // A badly failed teleport leaves the caster hurt and still in the fight
+        let botched = encounter.teleport(mage, "Teleport", 1).unwrap();
+        assert_eq!(botched.mishap, Some(WoundLevel::Light));
+        assert!(encounter.combatants[mage].is_fighting());
+        encounter.run_round();
+
+        let teleport = encounter.teleport(mage, "Teleport", 10).unwrap();
+        assert!(teleport.arrived());
+        assert!(encounter.combatants[mage].escaped);
+        assert!(encounter
+            .events
+            .contains(&EncounterEvent::Teleported { combatant: mage }));
+        assert_eq!(encounter.winner(), Some(2));
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_transport_rejects_unknown_combatants() {
+        let mut encounter = Encounter::new();
+        assert_eq!(
+            encounter.teleport(7, "Teleport", 10).unwrap_err(),
+            TransportError::UnknownCombatant(7)
+        );
+        assert_eq!(
+            encounter
+                .blink(7, "Blink", GridPos::new(0, 0), 10)
+                .unwrap_err(),
+            TransportError::UnknownCombatant(7)
+        );
+        assert_eq!(
+            encounter.ready_blink(7, "Blink"),
+            Err(TransportError::UnknownCombatant(7))
+        );
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_necromancer_raises_corpses_up_to_their_control_limit() {
+        use crate::modules::magic::{
+            MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration,
+            SpellRange,
+        };
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Necromancy, 1);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Raise Dead".to_string(),
+                    branch: MagicBranch::Necromancy,
+                    difficulty: SpellDifficulty::Easy,
+                    preparation_time: 0,
+                    casting_time: 1,
+                    range: SpellRange::Short(10),
+                    duration: SpellDuration::Instant,
+                    components: SpellComponents::default(),
+                },
+                1,
+            )
+            .unwrap();
+        let mut morwen = fighter("Morwen", 6);
+        morwen.magic = Some(magic);
+        let mut field = Battlefield::new(GridKind::Square, 30, 3);
+        for (name, x) in [("Morwen", 0), ("Grimwald", 3), ("Orm", 4), ("Brute", 29)] {
+            field
+                .place(name, GridPos::new(x, 1), Facing::NORTH)
+                .unwrap();
+        }
+        let mut encounter = Encounter::new().with_battlefield(field);
+        let necromancer = encounter.add(morwen, 1);
+        let mut corpses = Vec::new();
+        for name in ["Grimwald", "Orm"] {
+            let mut body = fighter(name, 5);
+            body.wounds.critical = 2;
+            corpses.push(encounter.add(body, 2));
+        }
+        let brute = encounter.add(fighter("Brute", 5), 2);
+
+        assert_eq!(
+            encounter.raise_dead(necromancer, brute, "Raise Dead", 10),
+            Err(NecromancyError::NotACorpse("Brute".to_string()))
+        );
+        assert_eq!(
+            encounter.raise_dead(necromancer, 99, "Raise Dead", 10),
+            Err(NecromancyError::UnknownCombatant(99))
+        );
+        assert_eq!(
+            encounter.raise_dead(99, corpses[0], "Raise Dead", 10),
+            Err(NecromancyError::UnknownCombatant(99))
+        );
+        let zombie = encounter
+            .raise_dead(necromancer, corpses[0], "Raise Dead", 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(encounter.combatants[zombie].team, 1);
+        assert!(encounter.combatants[zombie].character.traits.is_undead());
+        let field = encounter.battlefield.as_ref().unwrap();
+        assert_eq!(
+            field.placement("Zombie Grimwald").unwrap().pos,
+            GridPos::new(3, 1)
+        );
+        assert!(field.placement("Grimwald").is_none());
+        assert_eq!(
+            encounter.events,
+            vec![EncounterEvent::Raised {
+                necromancer,
+                corpse: corpses[0],
+                zombie,
+            }]
+        );
+
+        // One zombie is all a first-level necromancer can hold
+        encounter.run_round();
+        assert_eq!(
+            encounter.raise_dead(necromancer, corpses[0], "Raise Dead", 10),
+            Err(NecromancyError::NoCorpse("Grimwald".to_string()))
+        );
+        assert_eq!(
+            encounter.raise_dead(necromancer, corpses[1], "Raise Dead", 10),
+            Err(NecromancyError::ControlLimit(1))
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_rounds_emit_tracing_spans_and_events() {
+        use std::sync::{Arc, Mutex};
+        use tracing::span;
+
+        /// Records span names and event messages
+        #[derive(Default)]
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        impl tracing::Subscriber for Recorder {
+            fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+                let mut log = self.0.lock().unwrap();
+                log.push(span.metadata().name().to_string());
+                span::Id::from_u64(log.len() as u64)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &tracing::Event<'_>) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(event.metadata().name().to_string());
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let recorder = Recorder::default();
+        let log = recorder.0.clone();
+        let mut encounter = Encounter::new();
+        encounter.rules.dice = DiceMode::Average;
+        encounter.add(fighter("Ash", 6), 1);
+        encounter.add(fighter("Birch", 5), 2);
+        tracing::subscriber::with_default(recorder, || encounter.run_round());
+
+        let log = log.lock().unwrap();
+        let count = |name: &str| log.iter().filter(|n| n.as_str() == name).count();
+        assert_eq!(count("run_round"), 1);
+        assert_eq!(count("turn"), 2);
+        assert_eq!(count("combat_round_in_context"), 2);
+        assert_eq!(log.iter().filter(|n| n.starts_with("event ")).count(), 2);
+    }
+}
@@ -0,0 +1,100 @@
+//! Random encounter tables
+//!
+//! An `EncounterTable` holds weighted entries, optionally limited to certain
+//! terrain, plus the daily chance (on d10) that an encounter happens at all.
+
+use crate::modules::dice::{Dice, RandomDice};
+use crate::modules::travel::Terrain;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One possible encounter
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EncounterEntry {
+    pub name: String,
+    pub weight: u32,
+    /// Terrain where the encounter can happen (empty = anywhere)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub terrains: Vec<Terrain>,
+}
+
+/// Weighted random encounter table
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EncounterTable {
+    /// An encounter happens when d10 ≤ this value (0 = never, 10 = always)
+    pub daily_chance: i32,
+    pub entries: Vec<EncounterEntry>,
+}
+
+impl EncounterTable {
+    pub fn new(daily_chance: i32) -> Self {
+        Self {
+            daily_chance: daily_chance.clamp(0, 10),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn with_entry(mut self, name: &str, weight: u32, terrains: &[Terrain]) -> Self {
+        self.entries.push(EncounterEntry {
+            name: name.to_string(),
+            weight,
+            terrains: terrains.to_vec(),
+        });
+        self
+    }
+
+    fn candidates(&self, terrain: Terrain) -> impl Iterator<Item = &EncounterEntry> {
+        self.entries.iter().filter(move |e| {
+            e.weight > 0 && (e.terrains.is_empty() || e.terrains.contains(&terrain))
+        })
+    }
+
+    /// Pick the entry at position `roll` (0-based) within the total weight
+    pub fn pick(&self, terrain: Terrain, roll: u32) -> Option<&EncounterEntry> {
+        let mut remaining = roll;
+        for entry in self.candidates(terrain) {
+            if remaining < entry.weight {
+                return Some(entry);
+            }
+            remaining -= entry.weight;
+        }
+        None
+    }
+
+    /// Make a daily encounter check in `terrain`
+    pub fn check(&self, terrain: Terrain) -> Option<&EncounterEntry> {
+        self.check_with(terrain, &mut RandomDice)
+    }
+
+    /// Make a daily encounter check in `terrain`, rolling on `dice`
+    pub fn check_with(&self, terrain: Terrain, dice: &mut dyn Dice) -> Option<&EncounterEntry> {
+        if dice.d10() > self.daily_chance {
+            return None;
+        }
+        let total: u32 = self.candidates(terrain).map(|e| e.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        self.pick(terrain, dice.below(total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_respects_weights_and_terrain() {
+        let table = EncounterTable::new(3)
+            .with_entry("Bandits", 2, &[])
+            .with_entry("Wolves", 1, &[Terrain::Forest]);
+
+        assert_eq!(table.pick(Terrain::Forest, 1).unwrap().name, "Bandits");
+        assert_eq!(table.pick(Terrain::Forest, 2).unwrap().name, "Wolves");
+        assert_eq!(table.pick(Terrain::Road, 2), None);
+        assert!(EncounterTable::new(0).check(Terrain::Road).is_none());
+    }
+}
@@ -1,9 +1,32 @@
 //! Exhaustion system based on Draft RPG Section 4.24.1
-
+//!
+//! Casting fatigue lives on `MagicUser` with empathy as its threshold. For a
+//! character who both fights and casts, `Exhaustion::combined` scales the
+//! magical points onto stamina so both pools fill the same levels and give
+//! one penalty, and `rest_with_magic` recovers both at the physical rate.
+//!
+//! A character's own tracker is `Character::fatigue`, whose penalty every
+//! roll already carries. Under `Ruleset::exhaustion` combat fills it too:
+//! each attack costs `ATTACK_EXHAUSTION`, each dodge `DODGE_EXHAUSTION`, and
+//! a charge (see `combat_round_in_context`) a further `CHARGE_EXHAUSTION`.
+
+#[cfg(feature = "magic")]
+use crate::modules::magic::MagicUser;
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Exhaustion from making a melee attack (see `Ruleset::exhaustion`)
+pub const ATTACK_EXHAUSTION: i32 = 1;
+/// Exhaustion from dodging a blow or missile
+pub const DODGE_EXHAUSTION: i32 = 1;
+/// Exhaustion from a charge, on top of the attack's own
+pub const CHARGE_EXHAUSTION: i32 = 2;
+
 /// Tracks character exhaustion from combat and physical exertion
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Exhaustion {
     pub points: i32,
     pub stamina_threshold: i32,
@@ -72,7 +95,56 @@ impl Exhaustion {
     }
 }
 
+/// Casting fatigue stacked onto physical exhaustion
+#[cfg(feature = "magic")]
+impl Exhaustion {
+    /// A caster's magical exhaustion converted to this tracker's points
+    pub fn magical_points(&self, magic: &MagicUser) -> i32 {
+        magic.exhaustion_points * self.stamina_threshold / magic.empathy.max(1)
+    }
+
+    /// Physical and magical exhaustion stacked into one tracker
+    pub fn combined(&self, magic: &MagicUser) -> Exhaustion {
+        Exhaustion {
+            points: self.points + self.magical_points(magic),
+            stamina_threshold: self.stamina_threshold,
+        }
+    }
+
+    /// Move all of a caster's magical exhaustion into this tracker
+    ///
+    /// Converted as in `magical_points`, but rounding up so no fatigue is lost.
+    pub fn absorb_magic(&mut self, magic: &mut MagicUser) {
+        let empathy = magic.empathy.max(1);
+        self.points += (magic.exhaustion_points * self.stamina_threshold + empathy - 1) / empathy;
+        magic.exhaustion_points = 0;
+    }
+
+    /// Penalty from physical and magical exhaustion together
+    pub fn combined_penalty(&self, magic: &MagicUser) -> i32 {
+        self.combined(magic).penalty()
+    }
+
+    /// Rest, recovering physical points first and then magical ones
+    ///
+    /// Recovery uses the physical rate (1 point per 2 rounds) in this
+    /// tracker's points; what is left after physical fatigue is gone is
+    /// converted back to the caster's scale, rounding in the caster's favor.
+    pub fn rest_with_magic(&mut self, magic: &mut MagicUser, rounds: i32) {
+        let recovery = (rounds / 2).max(0);
+        let physical = recovery.min(self.points);
+        self.points -= physical;
+        let left = recovery - physical;
+        if left > 0 {
+            let threshold = self.stamina_threshold.max(1);
+            let magical = (left * magic.empathy.max(1) + threshold - 1) / threshold;
+            magic.exhaustion_points = (magic.exhaustion_points - magical).max(0);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExhaustionLevel {
     None,
     Light,
@@ -158,6 +230,30 @@ mod tests {
         assert_eq!(exhaustion.status(), "Completely Drained");
     }
 
+    #[cfg(feature = "magic")]
+    #[test]
+    fn test_magic_and_physical_exhaustion_stack() {
+        let mut body = Exhaustion::new(8);
+        let mut magic = MagicUser::new(4);
+        body.add_points(6);
+        magic.exhaustion_points = 3;
+
+        // Neither pool alone is past its threshold...
+        assert_eq!(body.penalty(), 0);
+        assert_eq!(magic.exhaustion_penalty(), 0);
+        // ...but 3 of 4 empathy is 6 of 8 stamina, so together they are
+        assert_eq!(body.magical_points(&magic), 6);
+        assert_eq!(body.combined(&magic).level(), ExhaustionLevel::Light);
+        assert_eq!(body.combined_penalty(&magic), -1);
+
+        // 8 rounds recovers 4 points: all physical first...
+        body.rest_with_magic(&mut magic, 8);
+        assert_eq!((body.points, magic.exhaustion_points), (2, 3));
+        // ...then 2 physical and 4 stamina-points of magic (2 empathy-points)
+        body.rest_with_magic(&mut magic, 12);
+        assert_eq!((body.points, magic.exhaustion_points), (0, 1));
+    }
+
     #[test]
     fn test_combat_exhaustion() {
         // Simulate 10 rounds of combat
@@ -0,0 +1,348 @@
+//! Familiars bonded to spellcasters
+//!
+//! A `Familiar` is a small creature (a cat, a raven) bonded to a caster's
+//! `MagicUser`. While the bond holds, its senses add to the caster's
+//! perception and it can carry a Touch spell to a target the caster can't
+//! reach. The bond runs both ways: every wound the familiar takes comes back
+//! to the caster as exhaustion, a critical one as a wound, and its death as a
+//! severe wound that breaks the bond.
+
+use crate::modules::injuries::WoundDescription;
+use crate::modules::magic::{
+    validate_target, CastingResult, MagicError, SpellRange, SpellTarget, TargetInfo,
+};
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use crate::{Character, WoundLevel, Wounds};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Perception bonus a familiar lends unless set otherwise
+pub const FAMILIAR_PERCEPTION_BONUS: i32 = 2;
+/// Source of the perception modifier a bonded familiar grants
+pub const FAMILIAR_SOURCE: &str = "Familiar";
+/// Cause recorded on wounds the caster takes through the bond
+pub const BOND_FEEDBACK_CAUSE: &str = "Bond feedback";
+
+/// A small creature bonded to a caster
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Familiar {
+    pub name: String,
+    pub species: String,
+    /// Added to the caster's perception while the bond holds
+    pub perception_bonus: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wounds: Wounds,
+}
+
+impl Familiar {
+    pub fn new(name: &str, species: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            species: species.to_string(),
+            perception_bonus: FAMILIAR_PERCEPTION_BONUS,
+            wounds: Wounds::new(),
+        }
+    }
+
+    /// A cat: keen ears and eyes in the dark
+    pub fn cat(name: &str) -> Self {
+        Self::new(name, "Cat")
+    }
+
+    /// A raven: sharp-eyed, and sees from above
+    pub fn raven(name: &str) -> Self {
+        Self::new(name, "Raven").with_perception_bonus(3)
+    }
+
+    pub fn with_perception_bonus(mut self, bonus: i32) -> Self {
+        self.perception_bonus = bonus;
+        self
+    }
+
+    /// Able to carry a spell: alive and not incapacitated
+    pub fn can_act(&self) -> bool {
+        !self.wounds.is_incapacitated()
+    }
+}
+
+/// Exhaustion the caster takes when the familiar suffers a wound of `level`
+pub const fn feedback_exhaustion(level: WoundLevel) -> i32 {
+    match level {
+        WoundLevel::Light => 1,
+        WoundLevel::Severe => 2,
+        WoundLevel::Critical => 3,
+    }
+}
+
+/// What a hurt familiar passed back to its caster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BondFeedback {
+    pub exhaustion: i32,
+    /// Wound inflicted on the caster, if any
+    pub wound: Option<WoundLevel>,
+    /// The familiar died and the bond is broken
+    pub familiar_died: bool,
+}
+
+/// Errors from bonding with or acting through a familiar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FamiliarError {
+    /// The caster has no magic
+    NoMagic,
+    /// The caster has no bonded familiar
+    NoFamiliar,
+    /// The familiar is too badly hurt to act
+    Incapacitated,
+    /// Only Touch spells can be carried by a familiar
+    NotTouch(String),
+    Magic(MagicError),
+}
+
+impl fmt::Display for FamiliarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FamiliarError::NoMagic => write!(f, "Caster has no magic"),
+            FamiliarError::NoFamiliar => write!(f, "Caster has no familiar"),
+            FamiliarError::Incapacitated => write!(f, "Familiar is too badly hurt to act"),
+            FamiliarError::NotTouch(spell) => {
+                write!(
+                    f,
+                    "{} is not a Touch spell; a familiar can't carry it",
+                    spell
+                )
+            }
+            FamiliarError::Magic(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FamiliarError {}
+
+impl From<MagicError> for FamiliarError {
+    fn from(e: MagicError) -> Self {
+        FamiliarError::Magic(e)
+    }
+}
+
+/// Bond `familiar` to `caster`, returning any familiar it replaces
+///
+/// The familiar's perception bonus is added as a `FAMILIAR_SOURCE` modifier.
+pub fn bond(caster: &mut Character, familiar: Familiar) -> Result<Option<Familiar>, FamiliarError> {
+    let magic = caster.magic.as_mut().ok_or(FamiliarError::NoMagic)?;
+    let bonus = familiar.perception_bonus;
+    let previous = magic.familiar.replace(familiar);
+    caster.modifiers.remove_source(FAMILIAR_SOURCE);
+    caster.add_modifier(Modifier::new(
+        FAMILIAR_SOURCE,
+        ModifierTarget::Perception,
+        bonus,
+    ));
+    Ok(previous)
+}
+
+/// Break the bond, returning the familiar
+pub fn release(caster: &mut Character) -> Option<Familiar> {
+    let familiar = caster.magic.as_mut()?.familiar.take();
+    caster.modifiers.remove_source(FAMILIAR_SOURCE);
+    familiar
+}
+
+/// Wound the caster's familiar, passing the feedback back through the bond
+///
+/// The caster takes `feedback_exhaustion` and, for a Critical wound, a Light
+/// wound of their own. If the familiar dies the caster takes a Severe wound
+/// instead and the bond is broken.
+pub fn hurt(caster: &mut Character, level: WoundLevel) -> Result<BondFeedback, FamiliarError> {
+    let familiar = caster
+        .magic
+        .as_mut()
+        .ok_or(FamiliarError::NoMagic)?
+        .familiar
+        .as_mut()
+        .ok_or(FamiliarError::NoFamiliar)?;
+    familiar.wounds.add_wound(level);
+    let familiar_died = familiar.wounds.is_dead();
+
+    let exhaustion = feedback_exhaustion(level);
+    caster.exert(exhaustion);
+    let wound = if familiar_died {
+        release(caster);
+        Some(WoundLevel::Severe)
+    } else {
+        (level == WoundLevel::Critical).then_some(WoundLevel::Light)
+    };
+    if let Some(wound) = wound {
+        caster.inflict_wound(WoundDescription::new(wound, BOND_FEEDBACK_CAUSE));
+    }
+    Ok(BondFeedback {
+        exhaustion,
+        wound,
+        familiar_died,
+    })
+}
+
+/// Cast a Touch spell carried by the familiar to `target`
+///
+/// `from_familiar` measures the target from the familiar, not the caster.
+/// The familiar must be able to act; the casting is otherwise the caster's
+/// own, with its usual adjustments and exhaustion.
+pub fn deliver_touch(
+    caster: &mut Character,
+    spell_name: &str,
+    target: SpellTarget,
+    from_familiar: &TargetInfo,
+    roll: i32,
+) -> Result<CastingResult, FamiliarError> {
+    let magic = caster.magic.as_ref().ok_or(FamiliarError::NoMagic)?;
+    let familiar = magic.familiar.as_ref().ok_or(FamiliarError::NoFamiliar)?;
+    if !familiar.can_act() {
+        return Err(FamiliarError::Incapacitated);
+    }
+    let learned = magic
+        .spells
+        .get(spell_name)
+        .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
+    if !matches!(learned.spell.range, SpellRange::Touch) {
+        return Err(FamiliarError::NotTouch(spell_name.to_string()));
+    }
+    validate_target(&learned.spell, &target, from_familiar)?;
+    let mut result = caster.cast_spell(spell_name, roll)?;
+    result.aimed_at = Some(target);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::magic::{
+        MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration,
+    };
+    use crate::{Armor, Attributes, Weapon};
+
+    fn witch() -> Character {
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Necromancy, 6);
+        for (name, range) in [
+            ("Chill Touch", SpellRange::Touch),
+            ("Dread", SpellRange::Short(20)),
+        ] {
+            magic
+                .learn_spell(
+                    Spell {
+                        name: name.to_string(),
+                        branch: MagicBranch::Necromancy,
+                        difficulty: SpellDifficulty::Easy,
+                        preparation_time: 0,
+                        casting_time: 1,
+                        range,
+                        duration: SpellDuration::Instant,
+                        components: SpellComponents::default(),
+                    },
+                    5,
+                )
+                .unwrap();
+        }
+        Character::new_with_magic(
+            "Morwen",
+            Attributes::new(5, 6, 6, 7, 7, 7, 6, 6, 7),
+            3,
+            4,
+            Weapon::dagger(),
+            Armor::none(),
+            magic,
+        )
+    }
+
+    #[test]
+    fn test_bonded_familiar_sharpens_perception_and_carries_touch_spells() {
+        let mut morwen = witch();
+        let unaided = morwen.perception_roll_with(5);
+        assert!(bond(&mut morwen, Familiar::cat("Soot")).unwrap().is_none());
+        assert_eq!(morwen.perception_roll_with(5), unaided + 2);
+
+        // A raven replaces the cat, and its bonus replaces the cat's
+        let cat = bond(&mut morwen, Familiar::raven("Quill"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(cat.name, "Soot");
+        assert_eq!(morwen.perception_roll_with(5), unaided + 3);
+
+        // The raven reaches a foe 30m away from the witch
+        let foe = SpellTarget::Character("Ghoul".to_string());
+        let result = deliver_touch(
+            &mut morwen,
+            "Chill Touch",
+            foe.clone(),
+            &TargetInfo::new(2, true),
+            9,
+        )
+        .unwrap();
+        assert_eq!(result.aimed_at, Some(foe.clone()));
+        assert_eq!(
+            deliver_touch(
+                &mut morwen,
+                "Dread",
+                foe.clone(),
+                &TargetInfo::new(2, true),
+                9
+            )
+            .unwrap_err(),
+            FamiliarError::NotTouch("Dread".to_string())
+        );
+        assert!(matches!(
+            deliver_touch(
+                &mut morwen,
+                "Chill Touch",
+                foe,
+                &TargetInfo::new(8, true),
+                9
+            ),
+            Err(FamiliarError::Magic(MagicError::OutOfRange { .. }))
+        ));
+
+        release(&mut morwen);
+        assert_eq!(morwen.perception_roll_with(5), unaided);
+    }
+
+    #[test]
+    fn test_hurt_familiar_feeds_back_to_caster() {
+        let mut morwen = witch();
+        assert_eq!(
+            hurt(&mut morwen, WoundLevel::Light).unwrap_err(),
+            FamiliarError::NoFamiliar
+        );
+        bond(&mut morwen, Familiar::cat("Soot")).unwrap();
+
+        let light = hurt(&mut morwen, WoundLevel::Light).unwrap();
+        assert_eq!((light.exhaustion, light.wound), (1, None));
+        assert_eq!(morwen.fatigue, 1);
+
+        // A critical wound cuts the caster too, and leaves the cat unable to act
+        let critical = hurt(&mut morwen, WoundLevel::Critical).unwrap();
+        assert_eq!(critical.wound, Some(WoundLevel::Light));
+        assert_eq!(morwen.wounds.light, 1);
+        assert_eq!(
+            deliver_touch(
+                &mut morwen,
+                "Chill Touch",
+                SpellTarget::Caster,
+                &TargetInfo::caster(),
+                9
+            )
+            .unwrap_err(),
+            FamiliarError::Incapacitated
+        );
+
+        // Its death wounds the witch badly and breaks the bond
+        let death = hurt(&mut morwen, WoundLevel::Critical).unwrap();
+        assert!(death.familiar_died);
+        assert_eq!(death.wound, Some(WoundLevel::Severe));
+        assert_eq!(morwen.wounds.severe, 1);
+        assert_eq!(morwen.injuries.active[1].cause, BOND_FEEDBACK_CAUSE);
+        assert!(morwen.magic.as_ref().unwrap().familiar.is_none());
+        assert_eq!(morwen.modifiers.total(ModifierTarget::Perception), 0);
+    }
+}
@@ -0,0 +1,354 @@
+//! Divine favor: miracles for priests
+//!
+//! A parallel to the magic system for campaigns whose casters pray rather
+//! than study. A `Devotee` serves one `Deity` and keeps a pool of favor,
+//! earned by the deeds the deity approves of and lost to those it forbids.
+//! Miracles are invoked with a Willpower check in place of a lore-based
+//! casting roll and are paid for in favor: the full cost on success,
+//! `FAILED_INVOCATION_COST` when the deity doesn't answer.
+
+use crate::Character;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Most favor a devotee can bank unless set otherwise
+pub const MAX_FAVOR: i32 = 20;
+/// Favor lost to an invocation the deity doesn't answer
+pub const FAILED_INVOCATION_COST: i32 = 1;
+
+/// How much a miracle asks of the deity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MiracleDifficulty {
+    Minor,
+    Major,
+    Wondrous,
+}
+
+impl MiracleDifficulty {
+    /// Willpower check total needed to invoke
+    pub fn base_target(&self) -> i32 {
+        match self {
+            MiracleDifficulty::Minor => 10,
+            MiracleDifficulty::Major => 13,
+            MiracleDifficulty::Wondrous => 16,
+        }
+    }
+}
+
+impl fmt::Display for MiracleDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MiracleDifficulty::Minor => write!(f, "Minor"),
+            MiracleDifficulty::Major => write!(f, "Major"),
+            MiracleDifficulty::Wondrous => write!(f, "Wondrous"),
+        }
+    }
+}
+
+/// A miracle a deity grants its faithful
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Miracle {
+    pub name: String,
+    pub difficulty: MiracleDifficulty,
+    /// Favor spent when the miracle is granted
+    pub cost: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub description: String,
+}
+
+impl Miracle {
+    pub fn new(name: &str, difficulty: MiracleDifficulty, cost: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            difficulty,
+            cost,
+            description: String::new(),
+        }
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+}
+
+/// Something a deity rewards (positive favor) or punishes (negative)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deed {
+    pub name: String,
+    pub favor: i32,
+}
+
+/// A god or spirit, with the deeds it cares about and the miracles it grants
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deity {
+    pub name: String,
+    pub domain: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deeds: Vec<Deed>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub miracles: Vec<Miracle>,
+}
+
+impl Deity {
+    pub fn new(name: &str, domain: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            domain: domain.to_string(),
+            deeds: Vec::new(),
+            miracles: Vec::new(),
+        }
+    }
+
+    pub fn with_deed(mut self, name: &str, favor: i32) -> Self {
+        self.deeds.push(Deed {
+            name: name.to_string(),
+            favor,
+        });
+        self
+    }
+
+    pub fn with_miracle(mut self, miracle: Miracle) -> Self {
+        self.miracles.push(miracle);
+        self
+    }
+
+    /// A sun goddess of healing and vigilance against the dead
+    pub fn dawn() -> Self {
+        Self::new("Solanna", "Dawn")
+            .with_deed("Tend the wounded", 1)
+            .with_deed("Destroy the undead", 3)
+            .with_deed("Shelter a pilgrim", 2)
+            .with_deed("Desecrate a shrine", -6)
+            .with_deed("Raise the dead", -10)
+            .with_miracle(
+                Miracle::new("Blessing", MiracleDifficulty::Minor, 2)
+                    .with_description("Steadies an ally's hand for the fight"),
+            )
+            .with_miracle(
+                Miracle::new("Mend Flesh", MiracleDifficulty::Major, 4)
+                    .with_description("Closes a wound as if a week had passed"),
+            )
+            .with_miracle(
+                Miracle::new("Sunburst", MiracleDifficulty::Wondrous, 8)
+                    .with_description("Blinding light that sears the undead"),
+            )
+    }
+
+    pub fn deed(&self, name: &str) -> Option<&Deed> {
+        self.deeds.iter().find(|d| d.name == name)
+    }
+
+    pub fn miracle(&self, name: &str) -> Option<&Miracle> {
+        self.miracles.iter().find(|m| m.name == name)
+    }
+}
+
+/// Outcome of invoking a miracle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub miracle: String,
+    pub success: bool,
+    pub quality: i32,
+    pub total: i32,
+    pub target: i32,
+    pub favor_spent: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FavorError {
+    /// The deity doesn't grant that miracle
+    UnknownMiracle(String),
+    /// The deity doesn't care about that deed
+    UnknownDeed(String),
+    InsufficientFavor {
+        required: i32,
+        available: i32,
+    },
+}
+
+impl fmt::Display for FavorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FavorError::UnknownMiracle(name) => write!(f, "Miracle not granted: {}", name),
+            FavorError::UnknownDeed(name) => write!(f, "Deed means nothing here: {}", name),
+            FavorError::InsufficientFavor {
+                required,
+                available,
+            } => write!(f, "Not enough favor: need {}, have {}", required, available),
+        }
+    }
+}
+
+impl std::error::Error for FavorError {}
+
+/// A character's standing with their deity
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Devotee {
+    pub deity: Deity,
+    pub favor: i32,
+    pub max_favor: i32,
+}
+
+impl Devotee {
+    pub fn new(deity: Deity) -> Self {
+        Self {
+            deity,
+            favor: 0,
+            max_favor: MAX_FAVOR,
+        }
+    }
+
+    pub fn with_favor(mut self, favor: i32) -> Self {
+        self.favor = favor.clamp(0, self.max_favor);
+        self
+    }
+
+    pub fn with_max_favor(mut self, max_favor: i32) -> Self {
+        self.max_favor = max_favor.max(0);
+        self.favor = self.favor.min(self.max_favor);
+        self
+    }
+
+    /// Record a deed the deity judges, returning the change in favor
+    ///
+    /// Favor stays between 0 and `max_favor`.
+    pub fn perform(&mut self, deed: &str) -> Result<i32, FavorError> {
+        let change = self
+            .deity
+            .deed(deed)
+            .ok_or_else(|| FavorError::UnknownDeed(deed.to_string()))?
+            .favor;
+        let before = self.favor;
+        self.favor = (self.favor + change).clamp(0, self.max_favor);
+        Ok(self.favor - before)
+    }
+
+    /// Invoke a miracle: the invoker's Willpower check against its difficulty
+    ///
+    /// The devotee must hold at least the miracle's cost in favor. Success
+    /// spends the cost, failure `FAILED_INVOCATION_COST`.
+    pub fn invoke(
+        &mut self,
+        invoker: &Character,
+        miracle: &str,
+        roll: i32,
+    ) -> Result<Invocation, FavorError> {
+        let granted = self
+            .deity
+            .miracle(miracle)
+            .ok_or_else(|| FavorError::UnknownMiracle(miracle.to_string()))?;
+        if self.favor < granted.cost {
+            return Err(FavorError::InsufficientFavor {
+                required: granted.cost,
+                available: self.favor,
+            });
+        }
+        let total = invoker.willpower_roll_with(roll);
+        let target = granted.difficulty.base_target();
+        let success = total >= target;
+        let favor_spent = if success {
+            granted.cost
+        } else {
+            FAILED_INVOCATION_COST.min(self.favor)
+        };
+        self.favor -= favor_spent;
+        Ok(Invocation {
+            miracle: granted.name.clone(),
+            success,
+            quality: total - target,
+            total,
+            target,
+            favor_spent,
+        })
+    }
+
+    /// Miracles the devotee has the favor to ask for
+    pub fn available_miracles(&self) -> impl Iterator<Item = &Miracle> {
+        self.deity.miracles.iter().filter(|m| m.cost <= self.favor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::traits::Trait;
+    use crate::{Armor, Attributes, Weapon};
+
+    fn priest() -> Character {
+        Character::new(
+            "Brother Anselm",
+            Attributes::new(5, 5, 6, 6, 6, 7, 6, 5, 4),
+            4,
+            4,
+            Weapon::club(),
+            Armor::none(),
+        )
+    }
+
+    #[test]
+    fn test_deeds_earn_and_lose_favor_within_bounds() {
+        let mut anselm = Devotee::new(Deity::dawn()).with_max_favor(5);
+        assert_eq!(anselm.perform("Destroy the undead"), Ok(3));
+        assert_eq!(anselm.perform("Destroy the undead"), Ok(2));
+        assert_eq!(anselm.favor, 5);
+        assert_eq!(anselm.perform("Raise the dead"), Ok(-5));
+        assert_eq!(anselm.favor, 0);
+        assert_eq!(
+            anselm.perform("Hoard gold"),
+            Err(FavorError::UnknownDeed("Hoard gold".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_miracles_use_willpower_and_cost_favor() {
+        let mut anselm = Devotee::new(Deity::dawn()).with_favor(5);
+        let priest = priest();
+        assert_eq!(
+            anselm
+                .available_miracles()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Blessing", "Mend Flesh"]
+        );
+        assert_eq!(
+            anselm.invoke(&priest, "Sunburst", 10).unwrap_err(),
+            FavorError::InsufficientFavor {
+                required: 8,
+                available: 5
+            }
+        );
+
+        // Willpower 7 + 6 meets the Major target of 13
+        let mended = anselm.invoke(&priest, "Mend Flesh", 6).unwrap();
+        assert!(mended.success);
+        assert_eq!((mended.total, mended.favor_spent), (13, 4));
+        assert_eq!(anselm.favor, 1);
+
+        assert_eq!(
+            anselm.invoke(&priest, "Blessing", 1).unwrap_err(),
+            FavorError::InsufficientFavor {
+                required: 2,
+                available: 1
+            }
+        );
+
+        // An unanswered prayer still costs a little
+        anselm.favor = 2;
+        let unanswered = anselm.invoke(&priest, "Blessing", 1).unwrap();
+        assert!(!unanswered.success);
+        assert_eq!(anselm.favor, 2 - FAILED_INVOCATION_COST);
+
+        // Iron Will helps the devout as much as anyone
+        anselm.favor = 2;
+        let steadfast = priest.with_trait(Trait::iron_will());
+        assert!(anselm.invoke(&steadfast, "Blessing", 1).unwrap().success);
+    }
+}
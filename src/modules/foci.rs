@@ -0,0 +1,333 @@
+//! Staffs, wands and other charged magical foci
+//!
+//! A `Focus` adds a casting bonus for its branch, stores spells its owner has
+//! prepared ahead of time, and holds charges. A caster must attune to a focus
+//! before using it; casting through one either releases a stored spell or
+//! spends a charge, so the spell's preparation time is skipped.
+
+use crate::modules::magic::{CastingResult, MagicBranch, MagicError};
+use crate::Character;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Hours of quiet study needed to attune to a focus
+pub const ATTUNEMENT_HOURS: i32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FocusKind {
+    Staff,
+    Wand,
+    Orb,
+}
+
+impl fmt::Display for FocusKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FocusKind::Staff => write!(f, "Staff"),
+            FocusKind::Wand => write!(f, "Wand"),
+            FocusKind::Orb => write!(f, "Orb"),
+        }
+    }
+}
+
+/// A magical focus item
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Focus {
+    pub name: String,
+    pub kind: FocusKind,
+    /// Branch the focus is tuned to; `None` helps every branch
+    pub branch: Option<MagicBranch>,
+    /// Added to casting rolls for spells of the focus's branch
+    pub casting_bonus: i32,
+    /// Lore level in the branch needed to attune
+    pub required_lore: i32,
+    pub charges: i32,
+    pub max_charges: i32,
+    /// How many prepared spells the focus can hold
+    pub capacity: usize,
+    pub stored: Vec<String>,
+    /// Name of the attuned caster
+    pub attuned_to: Option<String>,
+}
+
+impl Focus {
+    pub fn new(name: &str, kind: FocusKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            branch: None,
+            casting_bonus: 0,
+            required_lore: 0,
+            charges: 0,
+            max_charges: 0,
+            capacity: 0,
+            stored: Vec::new(),
+            attuned_to: None,
+        }
+    }
+
+    /// A staff: a modest bonus and room for two prepared spells
+    pub fn staff(name: &str, branch: MagicBranch) -> Self {
+        Self::new(name, FocusKind::Staff)
+            .with_branch(branch, 1)
+            .with_capacity(2)
+    }
+
+    /// A wand: a handful of charges and no stored spells
+    pub fn wand(name: &str, branch: MagicBranch, charges: i32) -> Self {
+        Self::new(name, FocusKind::Wand)
+            .with_branch(branch, 2)
+            .with_charges(charges)
+    }
+
+    pub fn with_branch(mut self, branch: MagicBranch, casting_bonus: i32) -> Self {
+        self.branch = Some(branch);
+        self.casting_bonus = casting_bonus;
+        self
+    }
+
+    pub fn with_charges(mut self, charges: i32) -> Self {
+        self.max_charges = charges.max(0);
+        self.charges = self.max_charges;
+        self
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn with_required_lore(mut self, level: i32) -> Self {
+        self.required_lore = level;
+        self
+    }
+
+    pub fn is_attuned_to(&self, caster: &str) -> bool {
+        self.attuned_to.as_deref() == Some(caster)
+    }
+
+    /// Store a prepared spell for later release
+    pub fn store(&mut self, spell_name: &str) -> Result<(), FocusError> {
+        if self.stored.len() >= self.capacity {
+            return Err(FocusError::Full);
+        }
+        self.stored.push(spell_name.to_string());
+        Ok(())
+    }
+
+    /// Restore up to `charges`, never past the maximum
+    pub fn recharge(&mut self, charges: i32) {
+        self.charges = (self.charges + charges.max(0)).min(self.max_charges);
+    }
+
+    /// Casting bonus for a spell of `branch`
+    pub fn bonus_for(&self, branch: MagicBranch) -> i32 {
+        match self.branch {
+            Some(b) if b != branch => 0,
+            _ => self.casting_bonus,
+        }
+    }
+}
+
+/// Errors from attuning to or casting through a focus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FocusError {
+    /// The caster has no magic
+    NoMagic,
+    /// Already attuned to another caster
+    AttunedElsewhere(String),
+    InsufficientLore {
+        required: i32,
+        available: i32,
+    },
+    NotAttuned,
+    /// No stored spell of that name and no charges left
+    NoCharges,
+    /// No room for another stored spell
+    Full,
+    Magic(MagicError),
+}
+
+impl fmt::Display for FocusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FocusError::NoMagic => write!(f, "Caster has no magic"),
+            FocusError::AttunedElsewhere(owner) => write!(f, "Focus is attuned to {}", owner),
+            FocusError::InsufficientLore {
+                required,
+                available,
+            } => write!(
+                f,
+                "Insufficient lore to attune: need {}, have {}",
+                required, available
+            ),
+            FocusError::NotAttuned => write!(f, "Caster is not attuned to this focus"),
+            FocusError::NoCharges => write!(f, "Focus has no charges left"),
+            FocusError::Full => write!(f, "Focus cannot hold another spell"),
+            FocusError::Magic(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FocusError {}
+
+impl From<MagicError> for FocusError {
+    fn from(e: MagicError) -> Self {
+        FocusError::Magic(e)
+    }
+}
+
+/// Attune `caster` to `focus` (after `ATTUNEMENT_HOURS` of study)
+///
+/// The caster needs `required_lore` in the focus's branch, and a focus
+/// serves one caster at a time.
+pub fn attune(caster: &Character, focus: &mut Focus) -> Result<(), FocusError> {
+    let magic = caster.magic.as_ref().ok_or(FocusError::NoMagic)?;
+    if let Some(owner) = &focus.attuned_to {
+        if owner != &caster.name {
+            return Err(FocusError::AttunedElsewhere(owner.clone()));
+        }
+    }
+    let available = match focus.branch {
+        Some(branch) => magic.lores.get(&branch).map_or(0, |l| l.level),
+        None => magic.lores.values().map(|l| l.level).max().unwrap_or(0),
+    };
+    if available < focus.required_lore {
+        return Err(FocusError::InsufficientLore {
+            required: focus.required_lore,
+            available,
+        });
+    }
+    focus.attuned_to = Some(caster.name.clone());
+    Ok(())
+}
+
+/// Release the focus so another caster can attune to it
+pub fn unattune(focus: &mut Focus) {
+    focus.attuned_to = None;
+}
+
+/// Cast a known spell through an attuned focus, with no preparation
+///
+/// A stored copy of the spell is used up first; otherwise one charge is
+/// spent. The focus's bonus is added to the roll.
+pub fn cast_from_focus(
+    caster: &mut Character,
+    focus: &mut Focus,
+    spell_name: &str,
+    roll: i32,
+) -> Result<CastingResult, FocusError> {
+    if !focus.is_attuned_to(&caster.name) {
+        return Err(FocusError::NotAttuned);
+    }
+    let magic = caster.magic.as_mut().ok_or(FocusError::NoMagic)?;
+    let branch = magic
+        .spells
+        .get(spell_name)
+        .map(|s| s.spell.branch)
+        .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
+
+    if let Some(i) = focus.stored.iter().position(|s| s == spell_name) {
+        focus.stored.remove(i);
+    } else if focus.charges > 0 {
+        focus.charges -= 1;
+    } else {
+        return Err(FocusError::NoCharges);
+    }
+    Ok(magic.cast_spell(spell_name, roll + focus.bonus_for(branch))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::magic::{
+        MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration, SpellRange,
+    };
+    use crate::{Armor, Attributes, Weapon};
+
+    fn mage(name: &str, lore: i32) -> Character {
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Elementalism, lore);
+        magic
+            .learn_spell(
+                Spell {
+                    name: "Fire Bolt".to_string(),
+                    branch: MagicBranch::Elementalism,
+                    difficulty: SpellDifficulty::Hard,
+                    preparation_time: 10,
+                    casting_time: 1,
+                    range: SpellRange::Short(30),
+                    duration: SpellDuration::Instant,
+                    components: SpellComponents::default(),
+                },
+                lore.min(3),
+            )
+            .unwrap();
+        let mut c = Character::new(
+            name,
+            Attributes::new(5, 6, 5, 7, 6, 6, 5, 6, 6),
+            3,
+            4,
+            Weapon::dagger(),
+            Armor::none(),
+        );
+        c.magic = Some(magic);
+        c
+    }
+
+    #[test]
+    fn test_attunement_rules() {
+        let mut staff =
+            Focus::staff("Ashwood Staff", MagicBranch::Elementalism).with_required_lore(4);
+        let novice = mage("Pip", 2);
+        let elara = mage("Elara", 5);
+
+        assert_eq!(
+            attune(&novice, &mut staff),
+            Err(FocusError::InsufficientLore {
+                required: 4,
+                available: 2
+            })
+        );
+        assert_eq!(attune(&elara, &mut staff), Ok(()));
+        assert_eq!(
+            attune(&mage("Malachar", 6), &mut staff),
+            Err(FocusError::AttunedElsewhere("Elara".to_string()))
+        );
+        unattune(&mut staff);
+        assert!(staff.attuned_to.is_none());
+    }
+
+    #[test]
+    fn test_casting_uses_stored_spells_then_charges() {
+        let mut elara = mage("Elara", 5);
+        let mut wand = Focus::wand("Wand of Sparks", MagicBranch::Elementalism, 1);
+        wand.capacity = 1;
+        assert_eq!(
+            cast_from_focus(&mut elara, &mut wand, "Fire Bolt", 5).unwrap_err(),
+            FocusError::NotAttuned
+        );
+        attune(&elara, &mut wand).unwrap();
+        wand.store("Fire Bolt").unwrap();
+        assert_eq!(wand.store("Fire Bolt"), Err(FocusError::Full));
+
+        // 3 skill + 6 empathy + 1 roll + 2 wand bonus = 12, the Hard target
+        let result = cast_from_focus(&mut elara, &mut wand, "Fire Bolt", 1).unwrap();
+        assert!(result.success);
+        assert!(wand.stored.is_empty());
+        assert_eq!(wand.charges, 1);
+
+        cast_from_focus(&mut elara, &mut wand, "Fire Bolt", 5).unwrap();
+        assert_eq!(wand.charges, 0);
+        assert_eq!(
+            cast_from_focus(&mut elara, &mut wand, "Fire Bolt", 5).unwrap_err(),
+            FocusError::NoCharges
+        );
+        wand.recharge(5);
+        assert_eq!(wand.charges, 1);
+    }
+}
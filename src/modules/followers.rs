@@ -0,0 +1,274 @@
+//! Hirelings and follower loyalty
+//!
+//! A `Retinue` holds the NPC followers attached to a party. Each `Follower`
+//! has a loyalty score (0-10) moved by pay, treatment and casualties. Loyalty
+//! feeds morale checks in combat; a failed check means the follower deserts.
+
+use crate::Character;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const MAX_LOYALTY: i32 = 10;
+/// Morale total (d10 + loyalty + wound penalty) needed to hold steady
+pub const MORALE_STEADY: i32 = 10;
+/// Morale total below which a follower deserts
+pub const MORALE_DESERT: i32 = 6;
+
+/// Things that change how a follower feels about the party
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LoyaltyEvent {
+    /// Wages paid in full and on time
+    Paid,
+    /// Payday missed
+    Unpaid,
+    /// Extra share of loot or a bonus
+    Bonus,
+    /// Treated well: praised, healed, given rest
+    FairTreatment,
+    /// Abused, blamed or put in needless danger
+    Mistreated,
+    /// A fellow follower was killed
+    CompanionKilled,
+    /// The party won a fight together
+    Victory,
+}
+
+impl LoyaltyEvent {
+    pub fn loyalty_change(&self) -> i32 {
+        match self {
+            LoyaltyEvent::Paid | LoyaltyEvent::FairTreatment | LoyaltyEvent::Victory => 1,
+            LoyaltyEvent::Bonus => 2,
+            LoyaltyEvent::Unpaid | LoyaltyEvent::CompanionKilled => -2,
+            LoyaltyEvent::Mistreated => -3,
+        }
+    }
+}
+
+/// Result of a follower's morale check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Morale {
+    Steady,
+    /// Stays, but at -2 until the next check
+    Wavering,
+    Deserts,
+}
+
+impl Morale {
+    /// Roll penalty while in this state
+    pub fn penalty(&self) -> i32 {
+        match self {
+            Morale::Steady | Morale::Deserts => 0,
+            Morale::Wavering => -2,
+        }
+    }
+}
+
+impl fmt::Display for Morale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Morale::Steady => write!(f, "Steady"),
+            Morale::Wavering => write!(f, "Wavering"),
+            Morale::Deserts => write!(f, "Deserts"),
+        }
+    }
+}
+
+/// A hired or sworn NPC travelling with the party
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Follower {
+    pub character: Character,
+    pub loyalty: i32,
+    /// Wage per payday, in coins
+    pub wage: u32,
+}
+
+impl Follower {
+    /// Newly hired follower with middling loyalty
+    pub fn hire(character: Character, wage: u32) -> Self {
+        Self {
+            character,
+            loyalty: MAX_LOYALTY / 2,
+            wage,
+        }
+    }
+
+    pub fn with_loyalty(mut self, loyalty: i32) -> Self {
+        self.loyalty = loyalty.clamp(0, MAX_LOYALTY);
+        self
+    }
+
+    pub fn apply(&mut self, event: LoyaltyEvent) {
+        self.loyalty = (self.loyalty + event.loyalty_change()).clamp(0, MAX_LOYALTY);
+    }
+
+    /// Morale check with `roll` from `d10()`: d10 + loyalty + wound penalty
+    pub fn morale_check(&self, roll: i32) -> Morale {
+        let total = roll + self.loyalty + self.character.wounds.movement_penalty();
+        if total >= MORALE_STEADY {
+            Morale::Steady
+        } else if total >= MORALE_DESERT {
+            Morale::Wavering
+        } else {
+            Morale::Deserts
+        }
+    }
+}
+
+/// The followers attached to a party
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Retinue {
+    pub followers: Vec<Follower>,
+}
+
+impl Retinue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn hire(&mut self, follower: Follower) {
+        self.followers.push(follower);
+    }
+
+    pub fn len(&self) -> usize {
+        self.followers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.followers.is_empty()
+    }
+
+    /// Total wages due on payday
+    pub fn wages_due(&self) -> u32 {
+        self.followers.iter().map(|f| f.wage).sum()
+    }
+
+    /// Pay followers in order from `purse`; those who can't be paid lose loyalty
+    pub fn payday(&mut self, purse: &mut u32) {
+        for follower in &mut self.followers {
+            if *purse >= follower.wage {
+                *purse -= follower.wage;
+                follower.apply(LoyaltyEvent::Paid);
+            } else {
+                follower.apply(LoyaltyEvent::Unpaid);
+            }
+        }
+    }
+
+    /// Apply an event to every follower
+    pub fn apply_all(&mut self, event: LoyaltyEvent) {
+        for follower in &mut self.followers {
+            follower.apply(event);
+        }
+    }
+
+    /// Remove dead followers, lowering the loyalty of those who remain
+    pub fn bury_dead(&mut self) -> Vec<Follower> {
+        let (dead, alive): (Vec<_>, Vec<_>) = self
+            .followers
+            .drain(..)
+            .partition(|f| !f.character.is_alive());
+        self.followers = alive;
+        for _ in &dead {
+            self.apply_all(LoyaltyEvent::CompanionKilled);
+        }
+        dead
+    }
+
+    /// Morale check for every follower during combat; deserters leave the retinue
+    ///
+    /// `roll` supplies a d10 result per check (e.g. `|| d10()`). Returns the
+    /// deserters; everyone else's result is in the second element, by index.
+    pub fn combat_morale<F>(&mut self, mut roll: F) -> (Vec<Follower>, Vec<Morale>)
+    where
+        F: FnMut() -> i32,
+    {
+        let results: Vec<Morale> = self
+            .followers
+            .iter()
+            .map(|f| f.morale_check(roll()))
+            .collect();
+        let mut deserters = Vec::new();
+        let mut kept = Vec::new();
+        let mut remaining = Vec::new();
+        for (follower, morale) in self.followers.drain(..).zip(results) {
+            if morale == Morale::Deserts {
+                deserters.push(follower);
+            } else {
+                kept.push(morale);
+                remaining.push(follower);
+            }
+        }
+        self.followers = remaining;
+        (deserters, kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+
+    fn mercenary(name: &str) -> Follower {
+        Follower::hire(
+            Character::new(
+                name,
+                Attributes::new(6, 6, 6, 5, 5, 5, 5, 5, 5),
+                5,
+                4,
+                Weapon::long_sword(),
+                Armor::leather(),
+            ),
+            10,
+        )
+    }
+
+    #[test]
+    fn test_pay_and_treatment_move_loyalty() {
+        let mut retinue = Retinue::new();
+        retinue.hire(mercenary("Bren"));
+        retinue.hire(mercenary("Odo"));
+        assert_eq!(retinue.wages_due(), 20);
+
+        let mut purse = 15;
+        retinue.payday(&mut purse);
+        assert_eq!(purse, 5);
+        assert_eq!(retinue.followers[0].loyalty, 6);
+        assert_eq!(retinue.followers[1].loyalty, 3);
+
+        retinue.followers[1].apply(LoyaltyEvent::Mistreated);
+        retinue.followers[1].apply(LoyaltyEvent::Mistreated);
+        assert_eq!(retinue.followers[1].loyalty, 0);
+    }
+
+    #[test]
+    fn test_casualties_and_desertion() {
+        let mut retinue = Retinue::new();
+        retinue.hire(mercenary("Bren").with_loyalty(8));
+        retinue.hire(mercenary("Odo").with_loyalty(2));
+        retinue.hire(mercenary("Wat"));
+        retinue.followers[2]
+            .character
+            .wounds
+            .add_wound(WoundLevel::Critical);
+        retinue.followers[2]
+            .character
+            .wounds
+            .add_wound(WoundLevel::Critical);
+
+        let dead = retinue.bury_dead();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(retinue.followers[0].loyalty, 6);
+        assert_eq!(retinue.followers[1].loyalty, 0);
+
+        let (deserters, kept) = retinue.combat_morale(|| 4);
+        assert_eq!(deserters.len(), 1);
+        assert_eq!(deserters[0].character.name, "Odo");
+        assert_eq!(kept, vec![Morale::Steady]);
+        assert_eq!(retinue.len(), 1);
+    }
+}
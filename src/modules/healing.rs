@@ -0,0 +1,328 @@
+//! Recovery and triage between encounters
+//!
+//! Open wounds close after a number of days set by their severity
+//! (`healing_days`); `Character::recovery_date` puts that on the campaign
+//! calendar. After a fight, `triage` lets a healer work through the party's
+//! wounded, worst first: the dying get first aid, then every fresh wound gets a
+//! treatment check (healer skill + d10, plus `BANDAGE_BONUS` while bandages
+//! last). A treated wound is stabilized: it stops bleeding and heals in half
+//! the time.
+//!
+//! Where the wounded recuperate matters: a `CareQuality` (field camp, town
+//! healer, temple) adds to treatment checks, shortens recovery and lowers the
+//! chance that an untreated wound festers (`check_infections`).
+
+use crate::modules::dice::Dice;
+use crate::modules::injuries::WoundDescription;
+use crate::modules::supplies::Supplies;
+use crate::{Character, WoundLevel};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bonus to a treatment check when a clean bandage is used
+pub const BANDAGE_BONUS: i32 = 2;
+
+/// Days an untreated wound takes to close
+pub fn healing_days(severity: WoundLevel) -> i64 {
+    match severity {
+        WoundLevel::Light => 7,
+        WoundLevel::Severe => 21,
+        WoundLevel::Critical => 42,
+    }
+}
+
+/// Target for a treatment check (healer skill + d10)
+pub fn treatment_difficulty(severity: WoundLevel) -> i32 {
+    match severity {
+        WoundLevel::Light => 8,
+        WoundLevel::Severe => 10,
+        WoundLevel::Critical => 12,
+    }
+}
+
+/// Where the wounded are looked after
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CareQuality {
+    /// Bedrolls, boiled water and whatever is in the pack
+    #[default]
+    FieldCamp,
+    /// A bed, clean linen and a paid healer
+    TownHealer,
+    /// Priests, herbs and quiet
+    Temple,
+}
+
+impl CareQuality {
+    /// Bonus to treatment checks made here
+    pub fn treatment_bonus(&self) -> i32 {
+        match self {
+            CareQuality::FieldCamp => 0,
+            CareQuality::TownHealer => 2,
+            CareQuality::Temple => 3,
+        }
+    }
+
+    /// Percentage of the usual healing time a wound takes here
+    pub fn recovery_percent(&self) -> i64 {
+        match self {
+            CareQuality::FieldCamp => 100,
+            CareQuality::TownHealer => 75,
+            CareQuality::Temple => 50,
+        }
+    }
+
+    /// An untreated wound festers on a d10 at or under this
+    pub fn infection_risk(&self) -> i32 {
+        match self {
+            CareQuality::FieldCamp => 3,
+            CareQuality::TownHealer => 1,
+            CareQuality::Temple => 0,
+        }
+    }
+
+    /// `days` of healing scaled by the quality of care, rounded up
+    pub fn recovery_days(&self, days: i64) -> i64 {
+        (days * self.recovery_percent() + 99) / 100
+    }
+}
+
+impl fmt::Display for CareQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CareQuality::FieldCamp => write!(f, "Field Camp"),
+            CareQuality::TownHealer => write!(f, "Town Healer"),
+            CareQuality::Temple => write!(f, "Temple"),
+        }
+    }
+}
+
+/// One wound a healer worked on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Treatment {
+    pub patient: String,
+    pub wound: WoundDescription,
+    pub bandaged: bool,
+    pub stabilized: bool,
+}
+
+/// What a round of triage achieved
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TriageReport {
+    /// Dying characters pulled back by first aid
+    pub saved: Vec<String>,
+    pub treatments: Vec<Treatment>,
+}
+
+impl TriageReport {
+    pub fn bandages_used(&self) -> usize {
+        self.treatments.iter().filter(|t| t.bandaged).count()
+    }
+}
+
+/// Treat a party's wounded after a fight
+///
+/// Patients are seen dying first, then by their worst open wound; each
+/// untreated wound gets one check. The dead are skipped.
+pub fn triage(
+    party: &mut [Character],
+    healer_skill: i32,
+    supplies: &mut Supplies,
+    dice: &mut dyn Dice,
+) -> TriageReport {
+    triage_with_care(party, healer_skill, supplies, CareQuality::FieldCamp, dice)
+}
+
+/// Treat a party's wounded under `care`, which adds to every treatment check
+pub fn triage_with_care(
+    party: &mut [Character],
+    healer_skill: i32,
+    supplies: &mut Supplies,
+    care: CareQuality,
+    dice: &mut dyn Dice,
+) -> TriageReport {
+    let mut report = TriageReport::default();
+    let mut order: Vec<usize> = (0..party.len()).filter(|&i| party[i].is_alive()).collect();
+    order.sort_by_key(|&i| {
+        let patient = &party[i];
+        let worst = patient.injuries.active.iter().map(|w| w.severity).max();
+        std::cmp::Reverse((patient.is_dying(), worst))
+    });
+
+    for i in order {
+        let patient = &mut party[i];
+        if patient.is_dying() && patient.first_aid(healer_skill, dice.d10()) {
+            report.saved.push(patient.name.clone());
+        }
+
+        let mut wounds: Vec<usize> = (0..patient.injuries.active.len())
+            .filter(|&w| !patient.injuries.active[w].stabilized)
+            .collect();
+        wounds.sort_by_key(|&w| std::cmp::Reverse(patient.injuries.active[w].severity));
+        for w in wounds {
+            let wound = &mut patient.injuries.active[w];
+            let bandaged = supplies.take_bandage();
+            let bonus = care.treatment_bonus() + if bandaged { BANDAGE_BONUS } else { 0 };
+            let stabilized =
+                healer_skill + dice.d10() + bonus >= treatment_difficulty(wound.severity);
+            if stabilized {
+                wound.stabilize();
+            }
+            report.treatments.push(Treatment {
+                patient: patient.name.clone(),
+                wound: wound.clone(),
+                bandaged,
+                stabilized,
+            });
+        }
+    }
+    report
+}
+
+/// Roll for infection in each of a patient's untreated wounds
+///
+/// Stabilized wounds were cleaned and cannot fester. Returns the wounds that
+/// became infected.
+pub fn check_infections(
+    patient: &mut Character,
+    care: CareQuality,
+    dice: &mut dyn Dice,
+) -> Vec<WoundDescription> {
+    let mut infected = Vec::new();
+    for wound in &mut patient.injuries.active {
+        if wound.stabilized || wound.infected {
+            continue;
+        }
+        if dice.d10() <= care.infection_risk() {
+            wound.infected = true;
+            infected.push(wound.clone());
+        }
+    }
+    infected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::calendar::CampaignDate;
+    use crate::modules::dice::ScriptedDice;
+    use crate::modules::dying::DyingState;
+    use crate::{Armor, Attributes, Weapon};
+
+    fn patient(name: &str) -> Character {
+        Character::new(
+            name,
+            Attributes::new(5, 5, 5, 5, 5, 5, 5, 5, 5),
+            5,
+            5,
+            Weapon::long_sword(),
+            Armor::none(),
+        )
+    }
+
+    #[test]
+    fn test_triage_stabilizes_worst_wounds_first() {
+        let mut scratched = patient("Pell");
+        scratched.inflict_wound(WoundDescription::new(WoundLevel::Light, "Dagger"));
+        let mut gored = patient("Odo");
+        gored.inflict_wound(WoundDescription::new(WoundLevel::Severe, "Spear"));
+        gored.inflict_wound(WoundDescription::new(WoundLevel::Light, "Spear"));
+        gored.dying = Some(DyingState::new());
+        let mut party = vec![scratched, gored];
+
+        let today = CampaignDate::new(1, 3, 10);
+        assert_eq!(party[1].recovery_date(today), today.add_days(21));
+
+        // One bandage: first aid succeeds, the Severe wound is bandaged and
+        // held, the next two checks are made bare-handed
+        let mut supplies = Supplies::new(0, 0).with_bandages(1);
+        let mut dice = ScriptedDice::new(vec![8, 4, 6, 1]);
+        let report = triage(&mut party, 4, &mut supplies, &mut dice);
+
+        assert_eq!(report.saved, vec!["Odo".to_string()]);
+        assert_eq!(report.bandages_used(), 1);
+        assert_eq!(supplies.bandages, 0);
+        let outcomes: Vec<_> = report
+            .treatments
+            .iter()
+            .map(|t| (t.patient.as_str(), t.wound.severity, t.stabilized))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("Odo", WoundLevel::Severe, true),
+                ("Odo", WoundLevel::Light, true),
+                ("Pell", WoundLevel::Light, false),
+            ]
+        );
+
+        let odo = &party[1];
+        assert!(!odo.is_dying());
+        assert!(!odo.injuries.is_bleeding());
+        assert_eq!(odo.recovery_date(today), today.add_days(11));
+        assert_eq!(party[0].recovery_date(today), today.add_days(7));
+
+        // Stabilized wounds are not treated again
+        let again = triage(&mut party, 4, &mut supplies, &mut dice);
+        assert_eq!(again.treatments.len(), 1);
+    }
+
+    #[test]
+    fn test_care_quality_speeds_recovery_and_limits_infection() {
+        let mut wounded = patient("Ysolde");
+        wounded.inflict_wound(WoundDescription::new(WoundLevel::Severe, "Axe"));
+        let today = CampaignDate::new(2, 6, 1);
+        assert_eq!(
+            wounded.recovery_date_with_care(today, CareQuality::Temple),
+            today.add_days(11)
+        );
+        assert_eq!(
+            wounded.recovery_date_with_care(today, CareQuality::TownHealer),
+            today.add_days(16)
+        );
+
+        // A roll of 2 is safe in town but festers in the field
+        let mut in_town = wounded.clone();
+        assert!(check_infections(
+            &mut in_town,
+            CareQuality::TownHealer,
+            &mut ScriptedDice::new(vec![2])
+        )
+        .is_empty());
+        let infected = check_infections(
+            &mut wounded,
+            CareQuality::FieldCamp,
+            &mut ScriptedDice::new(vec![2]),
+        );
+        assert_eq!(infected.len(), 1);
+        assert_eq!(wounded.recovery_date(today), today.add_days(42));
+
+        // Temple care turns a failed field treatment into a success
+        let mut party = vec![patient("Bran")];
+        party[0].inflict_wound(WoundDescription::new(WoundLevel::Critical, "Maul"));
+        let mut supplies = Supplies::new(0, 0);
+        let field = triage(
+            &mut party.clone(),
+            5,
+            &mut supplies,
+            &mut ScriptedDice::new(vec![5]),
+        );
+        assert!(!field.treatments[0].stabilized);
+        let temple = triage_with_care(
+            &mut party,
+            5,
+            &mut supplies,
+            CareQuality::Temple,
+            &mut ScriptedDice::new(vec![5]),
+        );
+        assert!(temple.treatments[0].stabilized);
+        assert!(check_infections(
+            &mut party[0],
+            CareQuality::FieldCamp,
+            &mut ScriptedDice::new(vec![1])
+        )
+        .is_empty());
+    }
+}
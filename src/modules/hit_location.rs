@@ -1,10 +1,31 @@
 //! Hit location tracking system based on Draft RPG Section 4.24.3
-
-use crate::d10;
+//!
+//! Where a blow lands is rolled on a `HitLocationTable` of weighted entries
+//! per `AttackDirection`. Each `BodyPlan` (humanoid, quadruped, serpent,
+//! flyer) has its own table; the built-in ones are also shipped as
+//! `data/hit_locations.toml`.
+//!
+//! Helmets, shields and other `ArmorPiece`s are worn in a character's
+//! `armor_pieces` on top of their body armor and only count against hits to
+//! the locations (and from the directions) they cover; see
+//! `Character::damage_at`.
+
+#[cfg(feature = "hit-location")]
+use crate::modules::dice::{Dice, RandomDice};
+#[cfg(feature = "hit-location")]
+use crate::modules::prosthetics::{Prosthetic, ProstheticError};
+#[cfg(feature = "hit-location")]
+use crate::WoundLevel;
+#[cfg(feature = "hit-location")]
+use std::collections::HashMap;
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Body locations that can be hit
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum HitLocation {
     Head,
     Torso,
@@ -12,51 +33,14 @@ pub enum HitLocation {
     RightArm,
     LeftLeg,
     RightLeg,
+    LeftForeleg,
+    RightForeleg,
+    LeftWing,
+    RightWing,
+    Tail,
 }
 
 impl HitLocation {
-    /// Determine hit location based on attack direction
-    pub fn determine(direction: AttackDirection) -> Self {
-        let roll = d10();
-        match direction {
-            AttackDirection::Front | AttackDirection::Back => match roll {
-                1..=2 => HitLocation::LeftLeg,
-                3..=4 => HitLocation::RightLeg,
-                5..=6 => HitLocation::Torso,
-                7 => HitLocation::LeftArm,
-                8 => HitLocation::RightArm,
-                9..=10 => HitLocation::Head,
-                _ => HitLocation::Torso,
-            },
-            AttackDirection::Left | AttackDirection::Right => match roll {
-                1..=2 => HitLocation::LeftLeg,
-                3..=4 => HitLocation::Torso,
-                5..=7 => HitLocation::LeftArm,
-                8 => HitLocation::RightArm,
-                9..=10 => HitLocation::Head,
-                _ => HitLocation::Torso,
-            },
-            AttackDirection::Above => match roll {
-                1 => HitLocation::LeftLeg,
-                2 => HitLocation::RightLeg,
-                3 => HitLocation::Torso,
-                4..=5 => HitLocation::LeftArm,
-                6..=7 => HitLocation::RightArm,
-                8..=10 => HitLocation::Head,
-                _ => HitLocation::Torso,
-            },
-            AttackDirection::Below => match roll {
-                1..=2 => HitLocation::LeftLeg,
-                3..=4 => HitLocation::RightLeg,
-                5..=7 => HitLocation::Torso,
-                8 => HitLocation::LeftArm,
-                9 => HitLocation::RightArm,
-                10 => HitLocation::Head,
-                _ => HitLocation::Torso,
-            },
-        }
-    }
-
     /// Get damage multiplier for this location (critical hits)
     pub fn damage_multiplier(&self) -> f32 {
         match self {
@@ -64,9 +48,17 @@ impl HitLocation {
             HitLocation::Torso => 1.0,
             HitLocation::LeftArm | HitLocation::RightArm => 0.75,
             HitLocation::LeftLeg | HitLocation::RightLeg => 0.75,
+            HitLocation::LeftForeleg | HitLocation::RightForeleg => 0.75,
+            HitLocation::LeftWing | HitLocation::RightWing => 0.75,
+            HitLocation::Tail => 0.5,
         }
     }
 
+    /// `damage` scaled by the location's multiplier, rounded to nearest
+    pub fn scale(&self, damage: i32) -> i32 {
+        (damage as f32 * self.damage_multiplier()).round() as i32
+    }
+
     /// Check if hit to this location causes weapon drop
     pub fn causes_weapon_drop(&self) -> bool {
         matches!(self, HitLocation::LeftArm | HitLocation::RightArm)
@@ -74,13 +66,27 @@ impl HitLocation {
 
     /// Check if hit to this location can sever limb (for severe/critical wounds)
     pub fn can_sever(&self) -> bool {
-        matches!(
-            self,
-            HitLocation::LeftArm
-                | HitLocation::RightArm
-                | HitLocation::LeftLeg
-                | HitLocation::RightLeg
-        )
+        !matches!(self, HitLocation::Head | HitLocation::Torso)
+    }
+}
+
+#[cfg(feature = "hit-location")]
+impl HitLocation {
+    /// Determine a humanoid's hit location based on attack direction
+    pub fn determine(direction: AttackDirection) -> Self {
+        Self::determine_for(BodyPlan::Humanoid, direction)
+    }
+
+    /// Determine hit location on the d10 table for a body plan
+    pub fn determine_for(plan: BodyPlan, direction: AttackDirection) -> Self {
+        Self::determine_with(plan, direction, &mut RandomDice)
+    }
+
+    /// Determine hit location for a body plan, rolling the d10 on `dice`
+    pub fn determine_with(plan: BodyPlan, direction: AttackDirection, dice: &mut dyn Dice) -> Self {
+        plan.table()
+            .pick(direction, (dice.d10() - 1) as u32)
+            .unwrap_or(HitLocation::Torso)
     }
 }
 
@@ -93,12 +99,18 @@ impl fmt::Display for HitLocation {
             HitLocation::RightArm => write!(f, "Right Arm"),
             HitLocation::LeftLeg => write!(f, "Left Leg"),
             HitLocation::RightLeg => write!(f, "Right Leg"),
+            HitLocation::LeftForeleg => write!(f, "Left Foreleg"),
+            HitLocation::RightForeleg => write!(f, "Right Foreleg"),
+            HitLocation::LeftWing => write!(f, "Left Wing"),
+            HitLocation::RightWing => write!(f, "Right Wing"),
+            HitLocation::Tail => write!(f, "Tail"),
         }
     }
 }
 
 /// Direction of attack for hit location determination
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AttackDirection {
     Front,
     Back,
@@ -121,7 +133,292 @@ impl fmt::Display for AttackDirection {
     }
 }
 
+/// Overall body shape, which decides the hit-location table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BodyPlan {
+    /// Two arms, two legs
+    #[default]
+    Humanoid,
+    /// Four legs and a tail: wolves, horses, bears
+    Quadruped,
+    /// Legless: snakes, giant worms
+    Serpent,
+    /// Winged: birds, bats, wyverns
+    Flyer,
+}
+
+#[cfg(feature = "hit-location")]
+impl BodyPlan {
+    /// The built-in hit-location table for this body plan
+    pub fn table(&self) -> HitLocationTable {
+        use AttackDirection::*;
+        use HitLocation::*;
+        let table = HitLocationTable::new(*self);
+        match self {
+            BodyPlan::Humanoid => table
+                .with_direction(
+                    Front,
+                    &[
+                        (LeftLeg, 2),
+                        (RightLeg, 2),
+                        (Torso, 2),
+                        (LeftArm, 1),
+                        (RightArm, 1),
+                        (Head, 2),
+                    ],
+                )
+                .with_direction(
+                    Back,
+                    &[
+                        (LeftLeg, 2),
+                        (RightLeg, 2),
+                        (Torso, 2),
+                        (LeftArm, 1),
+                        (RightArm, 1),
+                        (Head, 2),
+                    ],
+                )
+                .with_direction(
+                    Left,
+                    &[
+                        (LeftLeg, 2),
+                        (Torso, 2),
+                        (LeftArm, 3),
+                        (RightArm, 1),
+                        (Head, 2),
+                    ],
+                )
+                .with_direction(
+                    Right,
+                    &[
+                        (LeftLeg, 2),
+                        (Torso, 2),
+                        (LeftArm, 3),
+                        (RightArm, 1),
+                        (Head, 2),
+                    ],
+                )
+                .with_direction(
+                    Above,
+                    &[
+                        (LeftLeg, 1),
+                        (RightLeg, 1),
+                        (Torso, 1),
+                        (LeftArm, 2),
+                        (RightArm, 2),
+                        (Head, 3),
+                    ],
+                )
+                .with_direction(
+                    Below,
+                    &[
+                        (LeftLeg, 2),
+                        (RightLeg, 2),
+                        (Torso, 3),
+                        (LeftArm, 1),
+                        (RightArm, 1),
+                        (Head, 1),
+                    ],
+                ),
+            BodyPlan::Quadruped => table
+                .with_direction(
+                    Front,
+                    &[(Head, 3), (LeftForeleg, 2), (RightForeleg, 2), (Torso, 3)],
+                )
+                .with_direction(Back, &[(LeftLeg, 3), (RightLeg, 3), (Torso, 2), (Tail, 2)])
+                .with_direction(
+                    Left,
+                    &[(Head, 1), (LeftForeleg, 2), (Torso, 5), (LeftLeg, 2)],
+                )
+                .with_direction(
+                    Right,
+                    &[(Head, 1), (RightForeleg, 2), (Torso, 5), (RightLeg, 2)],
+                )
+                .with_direction(Above, &[(Head, 2), (Torso, 7), (Tail, 1)])
+                .with_direction(
+                    Below,
+                    &[
+                        (LeftForeleg, 2),
+                        (RightForeleg, 2),
+                        (Torso, 2),
+                        (LeftLeg, 2),
+                        (RightLeg, 2),
+                    ],
+                ),
+            BodyPlan::Serpent => table
+                .with_direction(Front, &[(Head, 4), (Torso, 6)])
+                .with_direction(Back, &[(Torso, 4), (Tail, 6)])
+                .with_direction(Left, &[(Head, 2), (Torso, 5), (Tail, 3)])
+                .with_direction(Right, &[(Head, 2), (Torso, 5), (Tail, 3)])
+                .with_direction(Above, &[(Head, 2), (Torso, 5), (Tail, 3)])
+                .with_direction(Below, &[(Torso, 7), (Tail, 3)]),
+            BodyPlan::Flyer => table
+                .with_direction(
+                    Front,
+                    &[(Head, 3), (Torso, 3), (LeftWing, 2), (RightWing, 2)],
+                )
+                .with_direction(
+                    Back,
+                    &[(Torso, 2), (LeftWing, 3), (RightWing, 3), (Tail, 2)],
+                )
+                .with_direction(Left, &[(Head, 1), (Torso, 3), (LeftWing, 4), (LeftLeg, 2)])
+                .with_direction(
+                    Right,
+                    &[(Head, 1), (Torso, 3), (RightWing, 4), (RightLeg, 2)],
+                )
+                .with_direction(
+                    Above,
+                    &[(Head, 1), (Torso, 3), (LeftWing, 3), (RightWing, 3)],
+                )
+                .with_direction(Below, &[(Torso, 4), (LeftLeg, 3), (RightLeg, 3)]),
+        }
+    }
+
+    /// Built-in tables for every body plan
+    pub fn standard_tables() -> Vec<HitLocationTable> {
+        [
+            BodyPlan::Humanoid,
+            BodyPlan::Quadruped,
+            BodyPlan::Serpent,
+            BodyPlan::Flyer,
+        ]
+        .iter()
+        .map(BodyPlan::table)
+        .collect()
+    }
+}
+
+impl fmt::Display for BodyPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BodyPlan::Humanoid => write!(f, "Humanoid"),
+            BodyPlan::Quadruped => write!(f, "Quadruped"),
+            BodyPlan::Serpent => write!(f, "Serpent"),
+            BodyPlan::Flyer => write!(f, "Flyer"),
+        }
+    }
+}
+
+/// A location's share of a hit-location table
+#[cfg(feature = "hit-location")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocationWeight {
+    pub location: HitLocation,
+    pub weight: u32,
+}
+
+/// Weighted hit locations for a body plan, by attack direction
+#[cfg(feature = "hit-location")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HitLocationTable {
+    pub body_plan: BodyPlan,
+    /// Entries per direction; directions without any use the `Front` entries
+    pub directions: HashMap<AttackDirection, Vec<LocationWeight>>,
+}
+
+#[cfg(feature = "hit-location")]
+impl HitLocationTable {
+    pub fn new(body_plan: BodyPlan) -> Self {
+        Self {
+            body_plan,
+            directions: HashMap::new(),
+        }
+    }
+
+    pub fn with_direction(
+        mut self,
+        direction: AttackDirection,
+        entries: &[(HitLocation, u32)],
+    ) -> Self {
+        self.directions.insert(
+            direction,
+            entries
+                .iter()
+                .map(|&(location, weight)| LocationWeight { location, weight })
+                .collect(),
+        );
+        self
+    }
+
+    /// Parse a list of `[[table]]` entries from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Vec<Self>, HitLocationError> {
+        #[derive(Deserialize)]
+        struct File {
+            table: Vec<HitLocationTable>,
+        }
+        toml::from_str::<File>(source)
+            .map(|file| file.table)
+            .map_err(|e| HitLocationError::Parse(e.to_string()))
+    }
+
+    /// Entries for attacks from `direction`
+    pub fn entries(&self, direction: AttackDirection) -> &[LocationWeight] {
+        self.directions
+            .get(&direction)
+            .or_else(|| self.directions.get(&AttackDirection::Front))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn total_weight(&self, direction: AttackDirection) -> u32 {
+        self.entries(direction).iter().map(|e| e.weight).sum()
+    }
+
+    /// Pick the location at position `roll` (0-based) within the total weight
+    pub fn pick(&self, direction: AttackDirection, roll: u32) -> Option<HitLocation> {
+        let mut remaining = roll;
+        for entry in self.entries(direction) {
+            if remaining < entry.weight {
+                return Some(entry.location);
+            }
+            remaining -= entry.weight;
+        }
+        None
+    }
+
+    /// Roll a location for an attack from `direction`
+    pub fn roll(&self, direction: AttackDirection) -> Option<HitLocation> {
+        self.roll_with(direction, &mut RandomDice)
+    }
+
+    /// Roll a location for an attack from `direction` on `dice`
+    pub fn roll_with(
+        &self,
+        direction: AttackDirection,
+        dice: &mut dyn Dice,
+    ) -> Option<HitLocation> {
+        let total = self.total_weight(direction);
+        if total == 0 {
+            return None;
+        }
+        self.pick(direction, dice.below(total))
+    }
+}
+
+/// Errors from loading hit-location tables
+#[cfg(feature = "hit-location")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HitLocationError {
+    Parse(String),
+}
+
+#[cfg(feature = "hit-location")]
+impl fmt::Display for HitLocationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HitLocationError::Parse(e) => write!(f, "Invalid hit location data: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "hit-location")]
+impl std::error::Error for HitLocationError {}
+
 /// Tracks injuries to specific body locations
+#[cfg(feature = "hit-location")]
 #[derive(Debug, Clone)]
 pub struct LocationalDamage {
     pub location: HitLocation,
@@ -130,8 +427,11 @@ pub struct LocationalDamage {
     pub critical_wounds: i32,
     pub severed: bool,
     pub disabled: bool,
+    /// Prosthetic or aid compensating for a severed/disabled location
+    pub prosthetic: Option<Prosthetic>,
 }
 
+#[cfg(feature = "hit-location")]
 impl LocationalDamage {
     pub fn new(location: HitLocation) -> Self {
         Self {
@@ -141,6 +441,7 @@ impl LocationalDamage {
             critical_wounds: 0,
             severed: false,
             disabled: false,
+            prosthetic: None,
         }
     }
 
@@ -166,13 +467,23 @@ impl LocationalDamage {
         }
     }
 
-    /// Check if this location is functional
+    /// Fit a prosthetic or aid to this location
+    pub fn fit_prosthetic(&mut self, prosthetic: Prosthetic) -> Result<(), ProstheticError> {
+        prosthetic.check_fit(self)?;
+        self.prosthetic = Some(prosthetic);
+        Ok(())
+    }
+
+    /// Check if this location is functional (a fitted prosthetic restores partial function)
     pub fn is_functional(&self) -> bool {
-        !self.disabled && !self.severed
+        self.prosthetic.is_some() || (!self.disabled && !self.severed)
     }
 
     /// Get penalty from wounds to this location
     pub fn penalty(&self) -> i32 {
+        if let Some(prosthetic) = &self.prosthetic {
+            return prosthetic.location_penalty;
+        }
         if self.severed {
             return -999; // Completely unusable
         }
@@ -183,6 +494,7 @@ impl LocationalDamage {
     }
 }
 
+#[cfg(feature = "hit-location")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WoundSeverity {
     Light,
@@ -190,9 +502,235 @@ pub enum WoundSeverity {
     Critical,
 }
 
-#[cfg(test)]
+#[cfg(feature = "hit-location")]
+impl From<WoundLevel> for WoundSeverity {
+    fn from(level: WoundLevel) -> Self {
+        match level {
+            WoundLevel::Light => WoundSeverity::Light,
+            WoundLevel::Severe => WoundSeverity::Severe,
+            WoundLevel::Critical => WoundSeverity::Critical,
+        }
+    }
+}
+
+/// Hard hits an armor piece takes before it is ruined, unless set otherwise
+#[cfg(feature = "hit-location")]
+pub const DEFAULT_PIECE_DURABILITY: i32 = 4;
+
+/// How far a hit must beat a parry to count as a critical parry, which knocks
+/// a loose helmet off
+#[cfg(feature = "hit-location")]
+pub const CRITICAL_PARRY_MARGIN: i32 = 5;
+
+/// Armor worn on specific body locations, such as a helmet or shield
+#[cfg(feature = "hit-location")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArmorPiece {
+    pub name: String,
+    pub protection: i32,
+    /// Locations this piece protects
+    pub covers: Vec<HitLocation>,
+    /// Attack directions the piece guards against (empty = every direction)
+    pub arcs: Vec<AttackDirection>,
+    /// Hard hits left before the piece is ruined
+    pub durability: i32,
+    pub max_durability: i32,
+    /// Not strapped on: a critical hit to a covered location knocks it off
+    pub loose: bool,
+}
+
+#[cfg(feature = "hit-location")]
+impl ArmorPiece {
+    pub fn new(name: &str, protection: i32, covers: &[HitLocation]) -> Self {
+        Self {
+            name: name.to_string(),
+            protection,
+            covers: covers.to_vec(),
+            arcs: Vec::new(),
+            durability: DEFAULT_PIECE_DURABILITY,
+            max_durability: DEFAULT_PIECE_DURABILITY,
+            loose: false,
+        }
+    }
+
+    /// Restrict the piece to attacks from the given directions
+    pub fn with_arcs(mut self, arcs: &[AttackDirection]) -> Self {
+        self.arcs = arcs.to_vec();
+        self
+    }
+
+    pub fn with_durability(mut self, durability: i32) -> Self {
+        self.durability = durability;
+        self.max_durability = durability;
+        self
+    }
+
+    pub fn loose(mut self) -> Self {
+        self.loose = true;
+        self
+    }
+
+    /// Open-faced helmet protecting the head; can be knocked off
+    pub fn helmet() -> Self {
+        Self::new("Helmet", 2, &[HitLocation::Head]).loose()
+    }
+
+    /// Closed great helm
+    pub fn great_helm() -> Self {
+        Self::new("Great Helm", 3, &[HitLocation::Head]).with_durability(6)
+    }
+
+    /// Shield carried on the given arm; also guards the torso against attacks
+    /// from the front and the shield side
+    pub fn shield(arm: HitLocation) -> Self {
+        let side = if arm == HitLocation::RightArm {
+            AttackDirection::Right
+        } else {
+            AttackDirection::Left
+        };
+        Self::new("Shield", 2, &[arm, HitLocation::Torso])
+            .with_arcs(&[AttackDirection::Front, side])
+    }
+
+    /// Check if the piece stops a hit to `location` coming from `direction`
+    pub fn protects(&self, location: HitLocation, direction: AttackDirection) -> bool {
+        self.covers.contains(&location) && (self.arcs.is_empty() || self.arcs.contains(&direction))
+    }
+
+    /// Protection left, falling in step with durability (rounded up)
+    pub fn current_protection(&self) -> i32 {
+        if self.durability <= 0 || self.max_durability <= 0 {
+            return 0;
+        }
+        let max = i64::from(self.max_durability);
+        let remaining = i64::from(self.protection) * i64::from(self.durability).min(max);
+        ((remaining + max - 1) / max) as i32
+    }
+}
+
+/// What a hard hit did to a piece of armor
+#[cfg(feature = "hit-location")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ArmorEvent {
+    /// Dented or split; `protection` is what the piece still gives
+    Damaged { piece: String, protection: i32 },
+    /// Ruined and discarded
+    Destroyed { piece: String },
+    /// Sent flying; it can be picked up again with `PiecemealArmor::recover`
+    KnockedOff { piece: String },
+}
+
+#[cfg(feature = "hit-location")]
+impl fmt::Display for ArmorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArmorEvent::Damaged { piece, protection } => {
+                write!(f, "{} dented (protection {})", piece, protection)
+            }
+            ArmorEvent::Destroyed { piece } => write!(f, "{} ruined", piece),
+            ArmorEvent::KnockedOff { piece } => write!(f, "{} knocked off", piece),
+        }
+    }
+}
+
+/// Pieces worn over body armor that cover individual locations
+#[cfg(feature = "hit-location")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PiecemealArmor {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pieces: Vec<ArmorPiece>,
+    /// Pieces knocked off in the fight, waiting to be picked up
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dropped: Vec<ArmorPiece>,
+}
+
+#[cfg(feature = "hit-location")]
+impl PiecemealArmor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Nothing worn or dropped
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty() && self.dropped.is_empty()
+    }
+
+    pub fn with_piece(mut self, piece: ArmorPiece) -> Self {
+        self.pieces.push(piece);
+        self
+    }
+
+    /// Protection the pieces give against a hit to `location` from `direction`
+    pub fn protection_at(&self, location: HitLocation, direction: AttackDirection) -> i32 {
+        self.pieces
+            .iter()
+            .filter(|p| p.protects(location, direction))
+            .map(ArmorPiece::current_protection)
+            .fold(0, i32::saturating_add)
+    }
+
+    /// Wear down the pieces in the way of a located hit
+    ///
+    /// `damage` is the hit's damage at `location`, after the location's
+    /// multiplier and before armor. A piece it beats loses a point of
+    /// durability; a `critical_parry` to the head knocks loose pieces off.
+    pub fn take_hit(
+        &mut self,
+        damage: i32,
+        location: HitLocation,
+        direction: AttackDirection,
+        critical_parry: bool,
+    ) -> Vec<ArmorEvent> {
+        let knock_off = critical_parry && location == HitLocation::Head;
+        let mut events = Vec::new();
+        let mut kept = Vec::with_capacity(self.pieces.len());
+        for mut piece in self.pieces.drain(..) {
+            if !piece.protects(location, direction) {
+                kept.push(piece);
+            } else if piece.loose && knock_off {
+                events.push(ArmorEvent::KnockedOff {
+                    piece: piece.name.clone(),
+                });
+                self.dropped.push(piece);
+            } else if damage > piece.current_protection() {
+                piece.durability -= 1;
+                if piece.durability <= 0 {
+                    events.push(ArmorEvent::Destroyed { piece: piece.name });
+                } else {
+                    events.push(ArmorEvent::Damaged {
+                        piece: piece.name.clone(),
+                        protection: piece.current_protection(),
+                    });
+                    kept.push(piece);
+                }
+            } else {
+                kept.push(piece);
+            }
+        }
+        self.pieces = kept;
+        events
+    }
+
+    /// Put a knocked-off piece back on; false if none by that name was dropped
+    pub fn recover(&mut self, name: &str) -> bool {
+        match self.dropped.iter().position(|p| p.name == name) {
+            Some(i) => {
+                let piece = self.dropped.remove(i);
+                self.pieces.push(piece);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "hit-location"))]
 mod tests {
     use super::*;
+    use crate::Armor;
 
     #[test]
     fn test_hit_location_determination() {
@@ -206,6 +744,7 @@ mod tests {
                 | HitLocation::RightArm
                 | HitLocation::LeftLeg
                 | HitLocation::RightLeg => {}
+                _ => panic!("{} is not a humanoid location", loc),
             }
         }
     }
@@ -297,6 +836,7 @@ mod tests {
                     | HitLocation::RightArm
                     | HitLocation::LeftLeg
                     | HitLocation::RightLeg => {}
+                    _ => panic!("{} is not a humanoid location", loc),
                 }
             }
         }
@@ -510,4 +1050,175 @@ mod tests {
         assert!(head.is_functional());
         assert_eq!(head.penalty(), 0);
     }
+
+    fn wearing(armor: Armor, pieces: &[ArmorPiece]) -> crate::Character {
+        pieces.iter().fold(
+            crate::Character::builder().armor(armor).build(),
+            |character, piece| character.with_armor_piece(piece.clone()),
+        )
+    }
+
+    #[test]
+    fn test_helmet_protects_head_only() {
+        let soldier = wearing(Armor::leather(), &[ArmorPiece::helmet()]);
+
+        assert_eq!(
+            soldier.protection_at(HitLocation::Head, AttackDirection::Back),
+            4
+        );
+        assert_eq!(
+            soldier.protection_at(HitLocation::Torso, AttackDirection::Front),
+            2
+        );
+    }
+
+    #[test]
+    fn test_shield_guards_front_arc() {
+        let armor = PiecemealArmor::new().with_piece(ArmorPiece::shield(HitLocation::LeftArm));
+
+        assert_eq!(
+            armor.protection_at(HitLocation::LeftArm, AttackDirection::Front),
+            2
+        );
+        assert_eq!(
+            armor.protection_at(HitLocation::Torso, AttackDirection::Left),
+            2
+        );
+        assert_eq!(
+            armor.protection_at(HitLocation::Torso, AttackDirection::Back),
+            0
+        );
+        assert_eq!(
+            armor.protection_at(HitLocation::RightArm, AttackDirection::Front),
+            0
+        );
+    }
+
+    #[test]
+    fn test_locational_damage_path() {
+        let knight = wearing(Armor::chain_mail(), &[ArmorPiece::great_helm()]);
+
+        // Head: 8 × 1.5 = 12, minus chain (3) and helm (3)
+        assert_eq!(
+            knight.damage_at(8, HitLocation::Head, AttackDirection::Front),
+            6
+        );
+        // Leg: 4 × 0.75 = 3, fully absorbed by chain
+        assert_eq!(
+            knight.damage_at(4, HitLocation::LeftLeg, AttackDirection::Front),
+            0
+        );
+    }
+
+    #[test]
+    fn test_body_plan_tables() {
+        let wolf = BodyPlan::Quadruped.table();
+        assert_eq!(wolf.total_weight(AttackDirection::Front), 10);
+        assert_eq!(
+            wolf.pick(AttackDirection::Front, 0),
+            Some(HitLocation::Head)
+        );
+        assert_eq!(wolf.pick(AttackDirection::Back, 9), Some(HitLocation::Tail));
+        assert_eq!(wolf.pick(AttackDirection::Front, 10), None);
+
+        let snake = BodyPlan::Serpent.table();
+        for _ in 0..20 {
+            let loc = HitLocation::determine_for(BodyPlan::Serpent, AttackDirection::Left);
+            assert!(snake
+                .entries(AttackDirection::Left)
+                .iter()
+                .any(|e| e.location == loc));
+        }
+
+        // Directions left out of a table fall back to the front
+        let blob = HitLocationTable::new(BodyPlan::Serpent)
+            .with_direction(AttackDirection::Front, &[(HitLocation::Torso, 1)]);
+        assert_eq!(blob.roll(AttackDirection::Below), Some(HitLocation::Torso));
+        assert!(HitLocation::Tail.can_sever());
+
+        let wyvern = crate::modules::Ancestry::new("Wyvern").with_body_plan(BodyPlan::Flyer);
+        let character = crate::Character::builder()
+            .ancestry(&wyvern)
+            .unwrap()
+            .build();
+        assert_eq!(character.body_plan(), BodyPlan::Flyer);
+    }
+
+    #[test]
+    fn test_hit_locations_replay_from_injected_dice() {
+        use crate::modules::dice::{DiceMode, ScriptedDice};
+
+        let mut dice = ScriptedDice::new([1, 10]);
+        let wolf = BodyPlan::Quadruped;
+        assert_eq!(
+            HitLocation::determine_with(wolf, AttackDirection::Front, &mut dice),
+            HitLocation::Head
+        );
+        assert_eq!(
+            HitLocation::determine_with(wolf, AttackDirection::Back, &mut dice),
+            HitLocation::Tail
+        );
+
+        let table = BodyPlan::Humanoid.table();
+        let run = |seed| {
+            let mut dice = DiceMode::seeded(seed);
+            (0..30)
+                .map(|_| table.roll_with(AttackDirection::Right, &mut dice))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(8), run(8));
+        assert!(run(8).iter().all(Option::is_some));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_data_file_matches_presets() {
+        let loaded =
+            HitLocationTable::from_toml(include_str!("../../data/hit_locations.toml")).unwrap();
+        assert_eq!(loaded, BodyPlan::standard_tables());
+    }
+
+    #[test]
+    fn test_armor_pieces_wear_and_fly_off() {
+        let mut armor = PiecemealArmor::new()
+            .with_piece(ArmorPiece::helmet())
+            .with_piece(ArmorPiece::shield(HitLocation::LeftArm));
+
+        // A glancing blow doesn't mark the shield
+        let events = armor.take_hit(2, HitLocation::LeftArm, AttackDirection::Front, false);
+        assert!(events.is_empty());
+
+        let events = armor.take_hit(6, HitLocation::LeftArm, AttackDirection::Front, false);
+        assert_eq!(
+            events,
+            vec![ArmorEvent::Damaged {
+                piece: "Shield".to_string(),
+                protection: 2
+            }]
+        );
+        for _ in 0..3 {
+            armor.take_hit(6, HitLocation::Torso, AttackDirection::Front, false);
+        }
+        assert_eq!(armor.pieces.len(), 1);
+
+        // Only a critical parry to the head sends the helmet flying
+        let events = armor.take_hit(9, HitLocation::Head, AttackDirection::Back, false);
+        assert!(matches!(events[..], [ArmorEvent::Damaged { .. }]));
+        let events = armor.take_hit(9, HitLocation::Head, AttackDirection::Back, true);
+        assert_eq!(
+            events,
+            vec![ArmorEvent::KnockedOff {
+                piece: "Helmet".to_string()
+            }]
+        );
+        assert_eq!(
+            armor.protection_at(HitLocation::Head, AttackDirection::Front),
+            0
+        );
+        assert!(armor.recover("Helmet"));
+        assert_eq!(
+            armor.protection_at(HitLocation::Head, AttackDirection::Front),
+            2
+        );
+    }
 }
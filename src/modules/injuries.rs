@@ -0,0 +1,240 @@
+//! Narrative wound descriptions and scars
+//!
+//! Each wound a character takes can carry a `WoundDescription` (where it
+//! landed, what caused it, how bad it is, whether it bleeds). When the wounds
+//! heal, the serious ones leave a `Scar` behind for storytelling tools, and
+//! critical ones may leave a `PermanentInjury` (see `permanent_injury`).
+
+use crate::modules::healing::healing_days;
+use crate::modules::hit_location::HitLocation;
+#[cfg(feature = "hit-location")]
+use crate::modules::hit_location::LocationalDamage;
+use crate::modules::permanent_injury::PermanentInjury;
+use crate::WoundLevel;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Structured account of a single wound
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WoundDescription {
+    pub location: Option<HitLocation>,
+    /// What inflicted the wound (usually a weapon name)
+    pub cause: String,
+    pub severity: WoundLevel,
+    pub bleeding: bool,
+    /// Treated after the fight; heals in half the time (see `healing`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stabilized: bool,
+    /// Festering; takes twice as long to close
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub infected: bool,
+}
+
+impl WoundDescription {
+    /// Describe a wound; Severe and Critical wounds bleed by default
+    pub fn new(severity: WoundLevel, cause: &str) -> Self {
+        Self {
+            location: None,
+            cause: cause.to_string(),
+            severity,
+            bleeding: severity >= WoundLevel::Severe,
+            stabilized: false,
+            infected: false,
+        }
+    }
+
+    pub fn at(mut self, location: HitLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    pub fn with_bleeding(mut self, bleeding: bool) -> Self {
+        self.bleeding = bleeding;
+        self
+    }
+
+    /// Mark the wound as treated; a stabilized wound stops bleeding
+    pub fn stabilize(&mut self) {
+        self.stabilized = true;
+        self.bleeding = false;
+    }
+
+    /// Days until this wound closes
+    pub fn days_to_heal(&self) -> i64 {
+        let days = healing_days(self.severity);
+        let days = if self.stabilized {
+            (days + 1) / 2
+        } else {
+            days
+        };
+        if self.infected {
+            days * 2
+        } else {
+            days
+        }
+    }
+
+    /// Scar left once this wound heals (Light wounds heal cleanly)
+    pub fn scar(&self) -> Option<Scar> {
+        let depth = match self.severity {
+            WoundLevel::Light => return None,
+            WoundLevel::Severe => "Thin",
+            WoundLevel::Critical => "Deep",
+        };
+        let description = match self.location {
+            Some(location) => format!(
+                "{} scar on the {} from a {}",
+                depth,
+                location.to_string().to_lowercase(),
+                self.cause
+            ),
+            None => format!("{} scar from a {}", depth, self.cause),
+        };
+        Some(Scar {
+            location: self.location,
+            description,
+        })
+    }
+}
+
+impl fmt::Display for WoundDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} wound", self.severity)?;
+        if let Some(location) = self.location {
+            write!(f, " to the {}", location)?;
+        }
+        write!(f, " from {}", self.cause)?;
+        if self.bleeding {
+            write!(f, " (bleeding)")?;
+        }
+        if self.stabilized {
+            write!(f, " (stabilized)")?;
+        }
+        if self.infected {
+            write!(f, " (infected)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Permanent mark left by a healed wound
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scar {
+    pub location: Option<HitLocation>,
+    pub description: String,
+}
+
+impl fmt::Display for Scar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+/// Open wound descriptions and accumulated scars for one character
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Injuries {
+    pub active: Vec<WoundDescription>,
+    pub scars: Vec<Scar>,
+    /// Lasting injuries from healed Critical wounds
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub permanent: Vec<PermanentInjury>,
+}
+
+impl Injuries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, wound: WoundDescription) {
+        self.active.push(wound);
+    }
+
+    /// Check if any open wound is bleeding
+    pub fn is_bleeding(&self) -> bool {
+        self.active.iter().any(|w| w.bleeding)
+    }
+
+    /// Check if open wounds have put `location` out of action
+    ///
+    /// A Severe wound disables a limb, a Critical wound any location.
+    pub fn is_disabled(&self, location: HitLocation) -> bool {
+        self.active.iter().any(|w| {
+            w.location == Some(location)
+                && match w.severity {
+                    WoundLevel::Light => false,
+                    WoundLevel::Severe => location.can_sever(),
+                    WoundLevel::Critical => true,
+                }
+        })
+    }
+
+    /// Open wounds tallied per location, in the order each was first hit
+    #[cfg(feature = "hit-location")]
+    pub fn damaged_locations(&self) -> Vec<LocationalDamage> {
+        let mut locations: Vec<LocationalDamage> = Vec::new();
+        for wound in &self.active {
+            let Some(location) = wound.location else {
+                continue;
+            };
+            let index = match locations.iter().position(|l| l.location == location) {
+                Some(index) => index,
+                None => {
+                    locations.push(LocationalDamage::new(location));
+                    locations.len() - 1
+                }
+            };
+            locations[index].add_wound(wound.severity.into());
+        }
+        locations
+    }
+
+    /// Close every open wound, returning the scars it left
+    pub fn heal_all(&mut self) -> Vec<Scar> {
+        let new_scars: Vec<Scar> = self.active.drain(..).filter_map(|w| w.scar()).collect();
+        self.scars.extend(new_scars.iter().cloned());
+        new_scars
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wound_description_display() {
+        let wound =
+            WoundDescription::new(WoundLevel::Severe, "Long Sword").at(HitLocation::LeftArm);
+        assert!(wound.bleeding);
+        assert_eq!(
+            wound.to_string(),
+            "Severe wound to the Left Arm from Long Sword (bleeding)"
+        );
+
+        let graze = WoundDescription::new(WoundLevel::Light, "Dagger");
+        assert_eq!(graze.to_string(), "Light wound from Dagger");
+    }
+
+    #[test]
+    fn test_healing_leaves_scars_for_serious_wounds() {
+        let mut injuries = Injuries::new();
+        injuries.record(WoundDescription::new(WoundLevel::Light, "Dagger"));
+        injuries.record(
+            WoundDescription::new(WoundLevel::Critical, "War Hammer").at(HitLocation::Head),
+        );
+        assert!(injuries.is_bleeding());
+
+        let scars = injuries.heal_all();
+        assert_eq!(scars.len(), 1);
+        assert_eq!(
+            scars[0].description,
+            "Deep scar on the head from a War Hammer"
+        );
+        assert!(injuries.active.is_empty());
+        assert_eq!(injuries.scars, scars);
+    }
+}
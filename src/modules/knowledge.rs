@@ -0,0 +1,249 @@
+//! Knowledge and lore checks with downtime research
+//!
+//! Knowledge skills (`SkillCategory::Knowledge`) cover history, monsters and
+//! heraldry. Recalling a fact is a check of skill level + REA + d10 against
+//! how obscure the fact is. Downtime research into a creature type can turn
+//! up its weakness, a temporary bonus applied through the modifier pipeline.
+
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use crate::modules::skills::SkillSet;
+use crate::Character;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Base target for a research roll
+pub const RESEARCH_TARGET: i32 = 14;
+/// Extra research days beyond this add nothing
+pub const MAX_RESEARCH_DAYS: i32 = 5;
+/// Days a discovered weakness stays fresh in memory
+pub const WEAKNESS_DURATION_DAYS: u32 = 30;
+
+/// Fields of knowledge with their skill names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnowledgeField {
+    History,
+    Monsters,
+    Heraldry,
+}
+
+impl KnowledgeField {
+    pub fn skill_name(&self) -> &'static str {
+        match self {
+            KnowledgeField::History => "History",
+            KnowledgeField::Monsters => "Monster Lore",
+            KnowledgeField::Heraldry => "Heraldry",
+        }
+    }
+
+    /// Recall check using the matching skill from `skills`
+    pub fn recall(
+        &self,
+        skills: &SkillSet,
+        reason: i32,
+        obscurity: Obscurity,
+        roll: i32,
+    ) -> Recall {
+        recall_check(
+            skills.get_skill_level(self.skill_name()),
+            reason,
+            obscurity,
+            roll,
+        )
+    }
+}
+
+/// How widely known a fact is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Obscurity {
+    Common,
+    Uncommon,
+    Obscure,
+}
+
+impl Obscurity {
+    pub fn target(&self) -> i32 {
+        match self {
+            Obscurity::Common => 10,
+            Obscurity::Uncommon => 14,
+            Obscurity::Obscure => 18,
+        }
+    }
+}
+
+/// Outcome of a recall check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recall {
+    Known,
+    /// Missed by 4 or less: half-remembered
+    Vague,
+    Unknown,
+}
+
+/// Recall check: level + REA + `roll` (from `d10()`) against the obscurity
+pub fn recall_check(level: i32, reason: i32, obscurity: Obscurity, roll: i32) -> Recall {
+    if level <= 0 {
+        return Recall::Unknown;
+    }
+    let total = level + reason + roll;
+    let target = obscurity.target();
+    if total >= target {
+        Recall::Known
+    } else if total >= target - 4 {
+        Recall::Vague
+    } else {
+        Recall::Unknown
+    }
+}
+
+/// A researched weakness of a creature type ("knows the troll's weakness")
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreatureWeakness {
+    pub creature: String,
+    pub attack_bonus: i32,
+    pub damage_bonus: i32,
+    pub days_remaining: u32,
+}
+
+impl CreatureWeakness {
+    /// Modifier source name, e.g. "Knows the Troll's weakness"
+    pub fn source(&self) -> String {
+        format!("Knows the {}'s weakness", self.creature)
+    }
+
+    pub fn modifiers(&self) -> Vec<Modifier> {
+        let source = self.source();
+        vec![
+            Modifier::new(&source, ModifierTarget::Attack, self.attack_bonus),
+            Modifier::new(&source, ModifierTarget::Damage, self.damage_bonus),
+        ]
+    }
+}
+
+/// Spend `days` of downtime researching a creature type
+///
+/// Rolls Monster Lore level + REA + `roll` + days spent (up to 5) against
+/// `RESEARCH_TARGET`. Success grants +1 attack and damage against that
+/// creature; beating the target by 5 or more grants +2.
+pub fn research(
+    creature: &str,
+    level: i32,
+    reason: i32,
+    days: i32,
+    roll: i32,
+) -> Option<CreatureWeakness> {
+    let total = level + reason + roll + days.clamp(0, MAX_RESEARCH_DAYS);
+    let bonus = match total - RESEARCH_TARGET {
+        i32::MIN..=-1 => return None,
+        0..=4 => 1,
+        _ => 2,
+    };
+    Some(CreatureWeakness {
+        creature: creature.to_string(),
+        attack_bonus: bonus,
+        damage_bonus: bonus,
+        days_remaining: WEAKNESS_DURATION_DAYS,
+    })
+}
+
+/// Researched weaknesses a character currently remembers
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lore {
+    pub weaknesses: Vec<CreatureWeakness>,
+}
+
+impl Lore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Learn a weakness, replacing any older entry for the same creature
+    pub fn learn(&mut self, weakness: CreatureWeakness) {
+        self.weaknesses.retain(|w| w.creature != weakness.creature);
+        self.weaknesses.push(weakness);
+    }
+
+    pub fn weakness(&self, creature: &str) -> Option<&CreatureWeakness> {
+        self.weaknesses.iter().find(|w| w.creature == creature)
+    }
+
+    /// Let time pass; weaknesses fade once their days run out
+    pub fn pass_days(&mut self, days: u32) {
+        for w in &mut self.weaknesses {
+            w.days_remaining = w.days_remaining.saturating_sub(days);
+        }
+        self.weaknesses.retain(|w| w.days_remaining > 0);
+    }
+
+    /// Register the bonus against `creature` on a character before a fight
+    ///
+    /// Returns the modifier source, for `Modifiers::remove_source` afterwards.
+    pub fn prepare(&self, character: &mut Character, creature: &str) -> Option<String> {
+        let weakness = self.weakness(creature)?;
+        for modifier in weakness.modifiers() {
+            character.add_modifier(modifier);
+        }
+        Some(weakness.source())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::skills::{Skill, SkillCategory, SkillDifficulty};
+    use crate::{Armor, Attributes, Weapon};
+
+    #[test]
+    fn test_recall_uses_reason_and_obscurity() {
+        let mut skills = SkillSet::new(0);
+        skills.add_skill(
+            Skill::new("Heraldry", 6, SkillDifficulty::Normal)
+                .with_category(SkillCategory::Knowledge)
+                .with_level(4),
+        );
+
+        let field = KnowledgeField::Heraldry;
+        assert_eq!(
+            field.recall(&skills, 6, Obscurity::Common, 1),
+            Recall::Known
+        );
+        assert_eq!(
+            field.recall(&skills, 6, Obscurity::Obscure, 4),
+            Recall::Vague
+        );
+        assert_eq!(
+            KnowledgeField::History.recall(&skills, 10, Obscurity::Common, 10),
+            Recall::Unknown
+        );
+    }
+
+    #[test]
+    fn test_research_grants_fading_bonus() {
+        assert!(research("Troll", 2, 5, 1, 3).is_none());
+        assert_eq!(research("Troll", 4, 6, 2, 3).unwrap().attack_bonus, 1);
+        // Days beyond the cap don't help: 4 + 6 + 4 + 5 = 19
+        let weakness = research("Troll", 4, 6, 20, 4).unwrap();
+        assert_eq!(weakness.damage_bonus, 2);
+
+        let mut lore = Lore::new();
+        lore.learn(weakness);
+        let mut hunter = Character::new(
+            "Aldric",
+            Attributes::new(7, 6, 7, 6, 5, 5, 5, 5, 5),
+            6,
+            4,
+            Weapon::long_sword(),
+            Armor::leather(),
+        );
+        assert!(lore.prepare(&mut hunter, "Goblin").is_none());
+        let source = lore.prepare(&mut hunter, "Troll").unwrap();
+        assert_eq!(source, "Knows the Troll's weakness");
+        assert_eq!(hunter.modifiers.total(ModifierTarget::Attack), 2);
+        assert_eq!(hunter.modifiers.remove_source(&source), 2);
+
+        lore.pass_days(WEAKNESS_DURATION_DAYS);
+        assert!(lore.weakness("Troll").is_none());
+    }
+}
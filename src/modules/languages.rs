@@ -0,0 +1,243 @@
+//! Languages and comprehension checks
+//!
+//! Languages are skills in the `SkillCategory::Language` category. Skill level
+//! maps to a `Fluency`; fluent speakers always understand, while those with
+//! partial fluency make a comprehension check (level + INT + d10). Fluency is
+//! always read from a character's `SkillSet` (see `Languages::from_skills`),
+//! so nothing is stored on `Character`.
+
+use crate::modules::skills::{SkillCategory, SkillSet};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How well a language is known
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Fluency {
+    None,
+    Broken,
+    Conversational,
+    Fluent,
+    Native,
+}
+
+impl Fluency {
+    pub fn from_level(level: i32) -> Self {
+        match level {
+            i32::MIN..=0 => Fluency::None,
+            1..=3 => Fluency::Broken,
+            4..=6 => Fluency::Conversational,
+            7..=9 => Fluency::Fluent,
+            _ => Fluency::Native,
+        }
+    }
+
+    /// Modifier to social checks (negotiation, surrender) held in this language
+    pub fn social_modifier(&self) -> i32 {
+        match self {
+            Fluency::None => -10,
+            Fluency::Broken => -3,
+            Fluency::Conversational => -1,
+            Fluency::Fluent | Fluency::Native => 0,
+        }
+    }
+}
+
+impl fmt::Display for Fluency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fluency::None => write!(f, "None"),
+            Fluency::Broken => write!(f, "Broken"),
+            Fluency::Conversational => write!(f, "Conversational"),
+            Fluency::Fluent => write!(f, "Fluent"),
+            Fluency::Native => write!(f, "Native"),
+        }
+    }
+}
+
+/// How involved the speech or text being understood is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Simple,
+    Everyday,
+    Technical,
+}
+
+impl Complexity {
+    pub fn target(&self) -> i32 {
+        match self {
+            Complexity::Simple => 10,
+            Complexity::Everyday => 14,
+            Complexity::Technical => 18,
+        }
+    }
+}
+
+/// Outcome of a comprehension check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comprehension {
+    Understood,
+    /// Missed by 4 or less: the general meaning gets through
+    Gist,
+    Lost,
+}
+
+/// Language levels known by a character
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Languages {
+    pub known: BTreeMap<String, i32>,
+}
+
+/// A language two speakers share, at the weaker speaker's fluency
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedLanguage {
+    pub language: String,
+    pub fluency: Fluency,
+}
+
+impl Languages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_language(mut self, language: impl Into<String>, level: i32) -> Self {
+        self.known.insert(language.into(), level.clamp(0, 10));
+        self
+    }
+
+    /// Collect the language-category skills from a skill set
+    pub fn from_skills(skills: &SkillSet) -> Self {
+        Self {
+            known: skills
+                .in_category(SkillCategory::Language)
+                .map(|s| (s.name.clone(), s.level.clamp(0, 10)))
+                .collect(),
+        }
+    }
+
+    pub fn level(&self, language: &str) -> i32 {
+        self.known.get(language).copied().unwrap_or(0)
+    }
+
+    pub fn fluency(&self, language: &str) -> Fluency {
+        Fluency::from_level(self.level(language))
+    }
+
+    /// Check understanding of `language` with `roll` from `d10()`
+    pub fn comprehension_check(
+        &self,
+        language: &str,
+        intuition: i32,
+        complexity: Complexity,
+        roll: i32,
+    ) -> Comprehension {
+        match self.fluency(language) {
+            Fluency::None => Comprehension::Lost,
+            Fluency::Fluent | Fluency::Native => Comprehension::Understood,
+            Fluency::Broken | Fluency::Conversational => {
+                let total = self.level(language) + intuition + roll;
+                let target = complexity.target();
+                if total >= target {
+                    Comprehension::Understood
+                } else if total >= target - 4 {
+                    Comprehension::Gist
+                } else {
+                    Comprehension::Lost
+                }
+            }
+        }
+    }
+
+    /// The shared language both speakers handle best, if any
+    pub fn common_language(&self, other: &Languages) -> Option<SharedLanguage> {
+        self.known
+            .keys()
+            .map(|language| SharedLanguage {
+                fluency: self.fluency(language).min(other.fluency(language)),
+                language: language.clone(),
+            })
+            .filter(|shared| shared.fluency > Fluency::None)
+            .max_by_key(|shared| shared.fluency)
+    }
+}
+
+/// Best shared language between two speakers' skills, if they can talk at all
+pub fn can_communicate(skills: &SkillSet, other: &SkillSet) -> Option<SharedLanguage> {
+    Languages::from_skills(skills).common_language(&Languages::from_skills(other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::skills::{Skill, SkillDifficulty};
+
+    fn speaker(languages: &[(&str, i32)]) -> SkillSet {
+        let mut skills = SkillSet::new(0);
+        for &(language, level) in languages {
+            skills.add_skill(
+                Skill::new(language, 6, SkillDifficulty::Hard)
+                    .with_category(SkillCategory::Language)
+                    .with_level(level),
+            );
+        }
+        skills
+    }
+
+    #[test]
+    fn test_languages_from_skill_category() {
+        let mut skills = SkillSet::new(0);
+        skills.add_skill(
+            Skill::new("Elvish", 6, SkillDifficulty::Hard)
+                .with_category(SkillCategory::Language)
+                .with_level(3),
+        );
+        skills.add_skill(Skill::new("Climbing", 6, SkillDifficulty::Normal).with_level(4));
+
+        let langs = Languages::from_skills(&skills);
+        assert_eq!(langs.known.len(), 1);
+        assert_eq!(langs.fluency("Elvish"), Fluency::Broken);
+        assert_eq!(langs.fluency("Dwarvish"), Fluency::None);
+    }
+
+    #[test]
+    fn test_comprehension_check() {
+        let langs = Languages::new()
+            .with_language("Common", 10)
+            .with_language("Orcish", 2);
+
+        assert_eq!(
+            langs.comprehension_check("Common", 5, Complexity::Technical, 1),
+            Comprehension::Understood
+        );
+        // 2 + 6 + 4 = 12 vs 14
+        assert_eq!(
+            langs.comprehension_check("Orcish", 6, Complexity::Everyday, 4),
+            Comprehension::Gist
+        );
+        assert_eq!(
+            langs.comprehension_check("Orcish", 6, Complexity::Technical, 4),
+            Comprehension::Lost
+        );
+        assert_eq!(
+            langs.comprehension_check("Elvish", 6, Complexity::Simple, 10),
+            Comprehension::Lost
+        );
+    }
+
+    #[test]
+    fn test_can_communicate_uses_weaker_speaker() {
+        let knight = speaker(&[("Common", 10), ("Orcish", 2)]);
+        let orc = speaker(&[("Orcish", 10), ("Common", 5)]);
+        let elf = speaker(&[("Elvish", 10)]);
+
+        let shared = can_communicate(&knight, &orc).unwrap();
+        assert_eq!(shared.language, "Common");
+        assert_eq!(shared.fluency, Fluency::Conversational);
+        assert_eq!(shared.fluency.social_modifier(), -1);
+        assert!(can_communicate(&knight, &elf).is_none());
+    }
+}
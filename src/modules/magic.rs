@@ -1,13 +1,24 @@
 //! Magic system based on Draft RPG Chapter 5
 
-use std::collections::HashMap;
+use crate::modules::exhaustion::Exhaustion;
+pub use crate::modules::exhaustion::ExhaustionLevel;
+use crate::modules::familiars::Familiar;
+use crate::modules::modifiers::Modifier;
+use crate::modules::positioning::{Battlefield, GridPos, CELL_METERS};
+use crate::modules::supplies::Supplies;
+use crate::modules::timing::{hours_to_rounds, minutes_to_rounds, segments_to_rounds};
+use crate::ArmorType;
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// Modifier source for an anti-magic zone's casting penalty
+pub const ANTI_MAGIC: &str = "Anti-magic";
+
 /// Branches of magic as defined in Draft RPG
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MagicBranch {
     Alchemy,        // Constitution and alteration of matter
@@ -22,8 +33,20 @@ pub enum MagicBranch {
 }
 
 impl MagicBranch {
+    pub const ALL: [MagicBranch; 9] = [
+        MagicBranch::Alchemy,
+        MagicBranch::Animation,
+        MagicBranch::Conjuration,
+        MagicBranch::Divination,
+        MagicBranch::Elementalism,
+        MagicBranch::Mentalism,
+        MagicBranch::Necromancy,
+        MagicBranch::Thaumaturgy,
+        MagicBranch::Transportation,
+    ];
+
     /// Get the difficulty of learning this branch's lore
-    pub fn lore_difficulty(&self) -> LoreDifficulty {
+    pub const fn lore_difficulty(&self) -> LoreDifficulty {
         match self {
             MagicBranch::Alchemy => LoreDifficulty::Hard,
             MagicBranch::Animation => LoreDifficulty::Hard,
@@ -64,7 +87,13 @@ pub enum LoreDifficulty {
 }
 
 impl LoreDifficulty {
-    pub fn cost_multiplier(&self) -> i32 {
+    pub const ALL: [LoreDifficulty; 3] = [
+        LoreDifficulty::Normal,
+        LoreDifficulty::Hard,
+        LoreDifficulty::VeryHard,
+    ];
+
+    pub const fn cost_multiplier(&self) -> i32 {
         match self {
             LoreDifficulty::Normal => 1,
             LoreDifficulty::Hard => 2,
@@ -73,6 +102,22 @@ impl LoreDifficulty {
     }
 }
 
+// Harder lore costs more, and every branch costs at least the base
+const _: () = {
+    let mut i = 1;
+    while i < LoreDifficulty::ALL.len() {
+        assert!(
+            LoreDifficulty::ALL[i].cost_multiplier() > LoreDifficulty::ALL[i - 1].cost_multiplier()
+        );
+        i += 1;
+    }
+    let mut i = 0;
+    while i < MagicBranch::ALL.len() {
+        assert!(MagicBranch::ALL[i].lore_difficulty().cost_multiplier() >= 1);
+        i += 1;
+    }
+};
+
 /// A spell within a magic branch
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -84,6 +129,127 @@ pub struct Spell {
     pub casting_time: i32,     // segments
     pub range: SpellRange,
     pub duration: SpellDuration,
+    /// Words, gestures and materials needed to cast (none by default)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub components: SpellComponents,
+}
+
+impl Spell {
+    pub fn with_components(mut self, components: SpellComponents) -> Self {
+        self.components = components;
+        self
+    }
+
+    /// Combat rounds spent preparing the spell
+    pub fn preparation_rounds(&self) -> i32 {
+        minutes_to_rounds(self.preparation_time)
+    }
+
+    /// Combat rounds spent casting (a started round counts)
+    pub fn casting_rounds(&self) -> i32 {
+        segments_to_rounds(self.casting_time)
+    }
+}
+
+/// A material a spell needs at hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MaterialComponent {
+    /// Name as carried in `Supplies::materials`
+    pub name: String,
+    pub count: u32,
+    /// Used up by a successful casting, rather than only held
+    pub consumed: bool,
+}
+
+/// What casting takes besides skill: words, gestures and materials
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpellComponents {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub verbal: bool,
+    /// Needs a free hand to gesture with
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub somatic: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub materials: Vec<MaterialComponent>,
+}
+
+impl SpellComponents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn verbal(mut self) -> Self {
+        self.verbal = true;
+        self
+    }
+
+    pub fn somatic(mut self) -> Self {
+        self.somatic = true;
+        self
+    }
+
+    /// Require `count` of a material, used up on success
+    pub fn with_material(mut self, name: &str, count: u32) -> Self {
+        self.materials.push(MaterialComponent {
+            name: name.to_string(),
+            count,
+            consumed: true,
+        });
+        self
+    }
+
+    /// Require a material that is held while casting but not used up
+    pub fn with_focus(mut self, name: &str) -> Self {
+        self.materials.push(MaterialComponent {
+            name: name.to_string(),
+            count: 1,
+            consumed: false,
+        });
+        self
+    }
+
+    /// Check the caster can speak and gesture as needed and carries the materials
+    pub fn check(
+        &self,
+        can_speak: bool,
+        can_gesture: bool,
+        supplies: &Supplies,
+    ) -> Result<(), MagicError> {
+        if self.verbal && !can_speak {
+            return Err(MagicError::CannotSpeak);
+        }
+        if self.somatic && !can_gesture {
+            return Err(MagicError::CannotGesture);
+        }
+        for material in &self.materials {
+            let available = supplies.material_count(&material.name);
+            if available < material.count {
+                return Err(MagicError::MissingMaterial {
+                    name: material.name.clone(),
+                    needed: material.count,
+                    available,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Take the consumed materials out of `supplies`
+    pub fn consume(&self, supplies: &mut Supplies) {
+        for material in self.materials.iter().filter(|m| m.consumed) {
+            supplies.take_material(&material.name, material.count);
+        }
+    }
+}
+
+/// What keeps a caster from speaking or gesturing, as the host tracks it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CasterRestraints {
+    pub gagged: bool,
+    /// Held fast, so unable to gesture whatever the state of the arms
+    pub grappled: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -104,6 +270,16 @@ impl SpellDifficulty {
     }
 }
 
+impl fmt::Display for SpellDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpellDifficulty::Easy => write!(f, "Easy"),
+            SpellDifficulty::Normal => write!(f, "Normal"),
+            SpellDifficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SpellRange {
@@ -115,6 +291,137 @@ pub enum SpellRange {
     Unlimited,
 }
 
+impl fmt::Display for SpellRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpellRange::Personal => write!(f, "Personal"),
+            SpellRange::Touch => write!(f, "Touch"),
+            SpellRange::Short(m) => write!(f, "Short ({}m)", m),
+            SpellRange::Medium(m) => write!(f, "Medium ({}m)", m),
+            SpellRange::Long(m) => write!(f, "Long ({}m)", m),
+            SpellRange::Unlimited => write!(f, "Unlimited"),
+        }
+    }
+}
+
+impl SpellRange {
+    /// Farthest a target may be, in meters (`None` for unlimited)
+    pub fn max_distance(&self) -> Option<i32> {
+        match self {
+            SpellRange::Personal => Some(0),
+            SpellRange::Touch => Some(TOUCH_METERS),
+            SpellRange::Short(m) | SpellRange::Medium(m) | SpellRange::Long(m) => Some(*m),
+            SpellRange::Unlimited => None,
+        }
+    }
+}
+
+/// Exhaustion per round for each maintained concentration spell
+pub const CONCENTRATION_EXHAUSTION: i32 = 1;
+/// Casting penalty for each concentration effect beyond the first
+pub const CONCENTRATION_PENALTY: i32 = -2;
+
+/// Reach of a Touch spell: the adjacent cell
+pub const TOUCH_METERS: i32 = CELL_METERS;
+
+/// What a spell is aimed at
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SpellTarget {
+    Caster,
+    /// Another character, by name
+    Character(String),
+    Point(GridPos),
+    /// Everything within `radius` cells of `center`
+    Area {
+        center: GridPos,
+        radius: i32,
+    },
+}
+
+impl fmt::Display for SpellTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpellTarget::Caster => write!(f, "self"),
+            SpellTarget::Character(name) => write!(f, "{}", name),
+            SpellTarget::Point(pos) => write!(f, "{}", pos),
+            SpellTarget::Area { center, radius } => {
+                write!(f, "area of radius {} around {}", radius, center)
+            }
+        }
+    }
+}
+
+/// Where a target lies relative to the caster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetInfo {
+    /// Meters from the caster
+    pub distance: i32,
+    pub line_of_sight: bool,
+}
+
+impl TargetInfo {
+    pub fn new(distance: i32, line_of_sight: bool) -> Self {
+        Self {
+            distance,
+            line_of_sight,
+        }
+    }
+
+    /// The caster themselves
+    pub fn caster() -> Self {
+        Self::new(0, true)
+    }
+
+    /// Measure a target on a battlefield; `None` if the caster or target isn't placed
+    pub fn on_battlefield(field: &Battlefield, caster: &str, target: &SpellTarget) -> Option<Self> {
+        let from = field.placement(caster)?.pos;
+        let to = match target {
+            SpellTarget::Caster => return Some(Self::caster()),
+            SpellTarget::Character(name) => field.placement(name)?.pos,
+            SpellTarget::Point(pos) | SpellTarget::Area { center: pos, .. } => *pos,
+        };
+        Some(Self::new(
+            field.distance(from, to) * CELL_METERS,
+            field.line_of_sight(from, to),
+        ))
+    }
+}
+
+/// Check that `target` is legal for `spell`
+///
+/// Personal spells only affect the caster and Touch spells can't take an
+/// area. Anything but the caster must be within range and, unless the range
+/// is Unlimited, in line of sight.
+pub fn validate_target(
+    spell: &Spell,
+    target: &SpellTarget,
+    info: &TargetInfo,
+) -> Result<(), MagicError> {
+    match (&spell.range, target) {
+        (_, SpellTarget::Caster) => return Ok(()),
+        (SpellRange::Personal, _) | (SpellRange::Touch, SpellTarget::Area { .. }) => {
+            return Err(MagicError::InvalidTarget {
+                spell: spell.name.clone(),
+                target: target.to_string(),
+            })
+        }
+        _ => {}
+    }
+    if let Some(range) = spell.range.max_distance() {
+        if info.distance > range {
+            return Err(MagicError::OutOfRange {
+                range,
+                distance: info.distance,
+            });
+        }
+    }
+    if !info.line_of_sight && !matches!(spell.range, SpellRange::Unlimited) {
+        return Err(MagicError::NoLineOfSight);
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SpellDuration {
@@ -123,6 +430,34 @@ pub enum SpellDuration {
     Minutes(i32),
     Hours(i32),
     Permanent,
+    /// Lasts while the caster maintains it each round (see `MagicUser::maintain_concentration`)
+    Concentration,
+}
+
+impl SpellDuration {
+    /// Length in combat rounds; `None` for effects with no fixed end
+    pub fn rounds(&self) -> Option<i32> {
+        match self {
+            SpellDuration::Instant => Some(0),
+            SpellDuration::Rounds(n) => Some(*n),
+            SpellDuration::Minutes(n) => Some(minutes_to_rounds(*n)),
+            SpellDuration::Hours(n) => Some(hours_to_rounds(*n)),
+            SpellDuration::Permanent | SpellDuration::Concentration => None,
+        }
+    }
+}
+
+impl fmt::Display for SpellDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpellDuration::Instant => write!(f, "Instant"),
+            SpellDuration::Rounds(n) => write!(f, "{} rounds", n),
+            SpellDuration::Minutes(n) => write!(f, "{} minutes", n),
+            SpellDuration::Hours(n) => write!(f, "{} hours", n),
+            SpellDuration::Permanent => write!(f, "Permanent"),
+            SpellDuration::Concentration => write!(f, "Concentration"),
+        }
+    }
 }
 
 /// Lore knowledge in a branch of magic
@@ -170,6 +505,49 @@ impl MagicLore {
     }
 }
 
+/// Default casting penalty for carrying a shield
+pub const SHIELD_CASTING_PENALTY: i32 = -1;
+
+/// How much worn armor and shields hinder casting, per branch
+///
+/// Each branch scales the armor type's `casting_penalty` by a percentage
+/// (100 unless configured); the shield penalty applies to every branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArmorInterference {
+    pub branch_percent: BTreeMap<MagicBranch, i32>,
+    pub shield: i32,
+}
+
+impl Default for ArmorInterference {
+    fn default() -> Self {
+        Self {
+            branch_percent: BTreeMap::new(),
+            shield: SHIELD_CASTING_PENALTY,
+        }
+    }
+}
+
+impl ArmorInterference {
+    /// Scale armor penalties for a branch; 0 ignores armor entirely
+    pub fn with_branch(mut self, branch: MagicBranch, percent: i32) -> Self {
+        self.branch_percent.insert(branch, percent);
+        self
+    }
+
+    pub fn with_shield(mut self, penalty: i32) -> Self {
+        self.shield = penalty;
+        self
+    }
+
+    /// Casting penalty for a spell of `branch` in the given gear
+    pub fn penalty(&self, branch: MagicBranch, armor: ArmorType, shield: bool) -> i32 {
+        let percent = self.branch_percent.get(&branch).copied().unwrap_or(100);
+        let shield = if shield { self.shield } else { 0 };
+        armor.casting_penalty() * percent / 100 + shield
+    }
+}
+
 /// A learned spell with skill level
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -182,22 +560,50 @@ pub struct LearnedSpell {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MagicUser {
-    pub lores: HashMap<MagicBranch, MagicLore>,
-    pub spells: HashMap<String, LearnedSpell>,
+    /// Lores and spells are kept sorted, so iteration and serialized order are stable
+    pub lores: BTreeMap<MagicBranch, MagicLore>,
+    pub spells: BTreeMap<String, LearnedSpell>,
     pub empathy: i32,
     pub exhaustion_points: i32, // From casting spells
+    /// Concentration spells currently being maintained
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub concentration: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub armor_interference: ArmorInterference,
+    /// Standing in a null field: every casting fails
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub nulled: bool,
+    /// Concentration effects put aside by a null field, restored on leaving it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub suspended: Vec<Modifier>,
+    /// Bonded familiar (see `familiars::bond`)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub familiar: Option<Familiar>,
 }
 
 impl MagicUser {
     pub fn new(empathy: i32) -> Self {
         Self {
-            lores: HashMap::new(),
-            spells: HashMap::new(),
+            lores: BTreeMap::new(),
+            spells: BTreeMap::new(),
             empathy,
             exhaustion_points: 0,
+            concentration: Vec::new(),
+            armor_interference: ArmorInterference::default(),
+            nulled: false,
+            suspended: Vec::new(),
+            familiar: None,
         }
     }
 
+    pub fn with_armor_interference(mut self, interference: ArmorInterference) -> Self {
+        self.armor_interference = interference;
+        self
+    }
+
     /// Add a lore to the magic user
     pub fn add_lore(&mut self, branch: MagicBranch, level: i32) {
         let mut lore = MagicLore::new(branch, self.empathy);
@@ -233,13 +639,47 @@ impl MagicUser {
 
     /// Attempt to cast a spell
     pub fn cast_spell(&mut self, spell_name: &str, roll: i32) -> Result<CastingResult, MagicError> {
+        self.cast_with_penalty(spell_name, roll, 0)
+    }
+
+    /// Attempt to cast a spell while wearing armor and possibly a shield
+    pub fn cast_spell_in_armor(
+        &mut self,
+        spell_name: &str,
+        armor: ArmorType,
+        shield: bool,
+        roll: i32,
+    ) -> Result<CastingResult, MagicError> {
+        let branch = self
+            .spells
+            .get(spell_name)
+            .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?
+            .spell
+            .branch;
+        let penalty = self.armor_interference.penalty(branch, armor, shield);
+        self.cast_with_penalty(spell_name, roll, penalty)
+    }
+
+    fn cast_with_penalty(
+        &mut self,
+        spell_name: &str,
+        roll: i32,
+        penalty: i32,
+    ) -> Result<CastingResult, MagicError> {
+        if self.nulled {
+            return Err(MagicError::Nulled);
+        }
         let learned_spell = self
             .spells
             .get(spell_name)
             .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
 
         // Calculate total: skill level + empathy + roll
-        let total = learned_spell.skill_level + self.empathy + roll;
+        let total = learned_spell.skill_level
+            + self.empathy
+            + roll
+            + penalty
+            + self.concentration_penalty();
         let target = learned_spell.spell.difficulty.base_target();
 
         let success = total >= target;
@@ -248,17 +688,79 @@ impl MagicUser {
         // Casting causes exhaustion
         if success {
             self.exhaustion_points += self.calculate_exhaustion(&learned_spell.spell, quality);
+            if matches!(learned_spell.spell.duration, SpellDuration::Concentration)
+                && !self.is_concentrating_on(spell_name)
+            {
+                self.concentration.push(spell_name.to_string());
+            }
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            spell = spell_name,
+            total,
+            target,
+            success,
+            quality,
+            "spell cast"
+        );
         Ok(CastingResult {
             spell_name: spell_name.to_string(),
             success,
             quality,
             total,
             target,
+            aimed_at: None,
         })
     }
 
+    /// Cast a spell at a target, checking it against the spell's range first
+    ///
+    /// An illegal target fails before any roll or exhaustion. The target is
+    /// passed through in `CastingResult::aimed_at` for effect resolution.
+    pub fn cast_spell_at(
+        &mut self,
+        spell_name: &str,
+        target: SpellTarget,
+        info: &TargetInfo,
+        roll: i32,
+    ) -> Result<CastingResult, MagicError> {
+        let learned_spell = self
+            .spells
+            .get(spell_name)
+            .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
+        validate_target(&learned_spell.spell, &target, info)?;
+        let mut result = self.cast_spell(spell_name, roll)?;
+        result.aimed_at = Some(target);
+        Ok(result)
+    }
+
+    pub fn is_concentrating_on(&self, spell_name: &str) -> bool {
+        self.concentration.iter().any(|s| s == spell_name)
+    }
+
+    /// Casting penalty for holding several effects: -2 for each beyond the first
+    pub fn concentration_penalty(&self) -> i32 {
+        CONCENTRATION_PENALTY * (self.concentration.len() as i32 - 1).max(0)
+    }
+
+    /// Keep every concentration effect going for another round
+    ///
+    /// Costs the caster a small action (left to the host) and
+    /// `CONCENTRATION_EXHAUSTION` per effect. Returns the exhaustion added.
+    pub fn maintain_concentration(&mut self) -> i32 {
+        let cost = CONCENTRATION_EXHAUSTION * self.concentration.len() as i32;
+        self.exhaustion_points += cost;
+        cost
+    }
+
+    /// Stop maintaining a spell, ending its effect; false if it wasn't maintained
+    pub fn drop_concentration(&mut self, spell_name: &str) -> bool {
+        let before = self.concentration.len();
+        self.concentration.retain(|s| s != spell_name);
+        self.concentration.len() < before
+    }
+
     /// Calculate exhaustion from casting a spell
     fn calculate_exhaustion(&self, spell: &Spell, quality: i32) -> i32 {
         let base_exhaustion = match spell.difficulty {
@@ -280,39 +782,25 @@ impl MagicUser {
         self.exhaustion_points = (self.exhaustion_points - hours).max(0);
     }
 
+    /// Magical exhaustion as a tracker with empathy as its threshold
+    pub fn exhaustion(&self) -> Exhaustion {
+        Exhaustion {
+            points: self.exhaustion_points,
+            stamina_threshold: self.empathy,
+        }
+    }
+
     /// Get current exhaustion level
     pub fn exhaustion_level(&self) -> ExhaustionLevel {
-        if self.exhaustion_points >= self.empathy * 3 {
-            ExhaustionLevel::Critical
-        } else if self.exhaustion_points >= self.empathy * 2 {
-            ExhaustionLevel::Severe
-        } else if self.exhaustion_points > self.empathy {
-            ExhaustionLevel::Light
-        } else {
-            ExhaustionLevel::None
-        }
+        self.exhaustion().level()
     }
 
     /// Get penalty from magical exhaustion
     pub fn exhaustion_penalty(&self) -> i32 {
-        match self.exhaustion_level() {
-            ExhaustionLevel::None => 0,
-            ExhaustionLevel::Light => -1,
-            ExhaustionLevel::Severe => -2,
-            ExhaustionLevel::Critical => -4,
-        }
+        self.exhaustion().penalty()
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum ExhaustionLevel {
-    None,
-    Light,
-    Severe,
-    Critical,
-}
-
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CastingResult {
@@ -321,13 +809,41 @@ pub struct CastingResult {
     pub quality: i32,
     pub total: i32,
     pub target: i32,
+    /// What the spell was cast at (see `MagicUser::cast_spell_at`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub aimed_at: Option<SpellTarget>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MagicError {
     LoreNotKnown(MagicBranch),
-    InsufficientLore { required: i32, available: i32 },
+    InsufficientLore {
+        required: i32,
+        available: i32,
+    },
     SpellNotKnown(String),
+    /// The spell can't be aimed at that kind of target
+    InvalidTarget {
+        spell: String,
+        target: String,
+    },
+    /// Target distance and spell range, in meters
+    OutOfRange {
+        range: i32,
+        distance: i32,
+    },
+    NoLineOfSight,
+    /// A verbal component, and the caster can't speak
+    CannotSpeak,
+    /// A somatic component, and the caster has no free hand to gesture with
+    CannotGesture,
+    MissingMaterial {
+        name: String,
+        needed: u32,
+        available: u32,
+    },
+    /// The caster stands in a null field (see `positioning::AntiMagic`)
+    Nulled,
 }
 
 impl fmt::Display for MagicError {
@@ -345,6 +861,29 @@ impl fmt::Display for MagicError {
                 )
             }
             MagicError::SpellNotKnown(name) => write!(f, "Spell not known: {}", name),
+            MagicError::InvalidTarget { spell, target } => {
+                write!(f, "{} can't target {}", spell, target)
+            }
+            MagicError::OutOfRange { range, distance } => {
+                write!(
+                    f,
+                    "Out of range: target {}m away, range {}m",
+                    distance, range
+                )
+            }
+            MagicError::NoLineOfSight => write!(f, "No line of sight to target"),
+            MagicError::CannotSpeak => write!(f, "Caster can't speak the words"),
+            MagicError::CannotGesture => write!(f, "Caster has no free hand to gesture"),
+            MagicError::MissingMaterial {
+                name,
+                needed,
+                available,
+            } => write!(
+                f,
+                "Missing material: need {} {}, have {}",
+                needed, name, available
+            ),
+            MagicError::Nulled => write!(f, "Magic fails inside a null field"),
         }
     }
 }
@@ -402,7 +941,12 @@ mod tests {
             casting_time: 1,
             range: SpellRange::Short(10),
             duration: SpellDuration::Minutes(10),
+            components: SpellComponents::default(),
         };
+        assert_eq!(spell.duration.rounds(), Some(120));
+        assert_eq!(spell.preparation_rounds(), 60);
+        assert_eq!(spell.casting_rounds(), 1);
+        assert_eq!(SpellDuration::Concentration.rounds(), None);
 
         // Learn spell at level 3
         assert!(mage.learn_spell(spell, 3).is_ok());
@@ -416,6 +960,7 @@ mod tests {
             casting_time: 2,
             range: SpellRange::Personal,
             duration: SpellDuration::Hours(1),
+            components: SpellComponents::default(),
         };
 
         assert!(matches!(
@@ -437,6 +982,7 @@ mod tests {
             casting_time: 2,
             range: SpellRange::Short(20),
             duration: SpellDuration::Minutes(5),
+            components: SpellComponents::default(),
         };
 
         mage.learn_spell(spell, 4).unwrap();
@@ -450,6 +996,46 @@ mod tests {
         assert!(mage.exhaustion_points > 0);
     }
 
+    #[test]
+    fn test_armor_interference() {
+        let interference = ArmorInterference::default()
+            .with_branch(MagicBranch::Alchemy, 0)
+            .with_branch(MagicBranch::Elementalism, 200);
+        let mut mage = MagicUser::new(7).with_armor_interference(interference);
+        mage.add_lore(MagicBranch::Elementalism, 5);
+        mage.add_lore(MagicBranch::Alchemy, 5);
+        let spell = |name: &str, branch| Spell {
+            name: name.to_string(),
+            branch,
+            difficulty: SpellDifficulty::Normal,
+            preparation_time: 10,
+            casting_time: 2,
+            range: SpellRange::Touch,
+            duration: SpellDuration::Instant,
+            components: SpellComponents::default(),
+        };
+        mage.learn_spell(spell("Spark", MagicBranch::Elementalism), 4)
+            .unwrap();
+        mage.learn_spell(spell("Transmute", MagicBranch::Alchemy), 4)
+            .unwrap();
+
+        // 4 skill + 7 empathy + 5 roll = 16; chain is -2, doubled for Elementalism
+        let chain = mage
+            .cast_spell_in_armor("Spark", ArmorType::Chain, false, 5)
+            .unwrap();
+        assert_eq!(chain.total, 12);
+        let shielded = mage
+            .cast_spell_in_armor("Spark", ArmorType::Chain, true, 5)
+            .unwrap();
+        assert_eq!(shielded.total, 11);
+
+        // Alchemy ignores armor, but not the shield
+        let plate = mage
+            .cast_spell_in_armor("Transmute", ArmorType::FullPlate, true, 5)
+            .unwrap();
+        assert_eq!(plate.total, 15);
+    }
+
     #[test]
     fn test_magical_exhaustion() {
         let mut mage = MagicUser::new(6);
@@ -486,6 +1072,7 @@ mod tests {
             casting_time: 1,
             range: SpellRange::Medium(50),
             duration: SpellDuration::Instant,
+            components: SpellComponents::default(),
         };
 
         // Don't have Elementalism lore
@@ -494,4 +1081,144 @@ mod tests {
             Err(MagicError::LoreNotKnown(MagicBranch::Elementalism))
         ));
     }
+
+    #[test]
+    fn test_lores_iterate_in_branch_order() {
+        let mut mage = MagicUser::new(7);
+        mage.add_lore(MagicBranch::Necromancy, 2);
+        mage.add_lore(MagicBranch::Alchemy, 3);
+        mage.add_lore(MagicBranch::Elementalism, 4);
+        let branches: Vec<MagicBranch> = mage.lores.keys().copied().collect();
+        assert_eq!(
+            branches,
+            vec![
+                MagicBranch::Alchemy,
+                MagicBranch::Elementalism,
+                MagicBranch::Necromancy
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spell_targeting() {
+        let spell = |name: &str, range| Spell {
+            name: name.to_string(),
+            branch: MagicBranch::Animation,
+            difficulty: SpellDifficulty::Easy,
+            preparation_time: 0,
+            casting_time: 1,
+            range,
+            duration: SpellDuration::Instant,
+            components: SpellComponents::default(),
+        };
+        let mut healer = MagicUser::new(7);
+        healer.add_lore(MagicBranch::Animation, 5);
+        healer
+            .learn_spell(spell("Healing Touch", SpellRange::Touch), 4)
+            .unwrap();
+        healer
+            .learn_spell(spell("Shield", SpellRange::Personal), 4)
+            .unwrap();
+
+        let ally = SpellTarget::Character("Brenna".to_string());
+        assert_eq!(
+            healer
+                .cast_spell_at("Healing Touch", ally.clone(), &TargetInfo::new(50, true), 9)
+                .unwrap_err(),
+            MagicError::OutOfRange {
+                range: TOUCH_METERS,
+                distance: 50
+            }
+        );
+        assert_eq!(healer.exhaustion_points, 0);
+
+        let result = healer
+            .cast_spell_at("Healing Touch", ally.clone(), &TargetInfo::new(2, true), 9)
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.aimed_at, Some(ally.clone()));
+
+        assert!(matches!(
+            healer.cast_spell_at("Shield", ally, &TargetInfo::new(0, true), 9),
+            Err(MagicError::InvalidTarget { .. })
+        ));
+        assert!(healer
+            .cast_spell_at("Shield", SpellTarget::Caster, &TargetInfo::caster(), 9)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_targets_on_the_battlefield() {
+        use crate::modules::positioning::{CellTerrain, Facing, GridKind};
+
+        let mut field = Battlefield::new(GridKind::Square, 10, 5)
+            .with_terrain(GridPos::new(4, 2), CellTerrain::Wall);
+        field
+            .place("Mage", GridPos::new(1, 2), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Troll", GridPos::new(8, 2), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Goblin", GridPos::new(8, 0), Facing::NORTH)
+            .unwrap();
+
+        let troll = SpellTarget::Character("Troll".to_string());
+        let info = TargetInfo::on_battlefield(&field, "Mage", &troll).unwrap();
+        assert_eq!(info, TargetInfo::new(7 * CELL_METERS, false));
+
+        let bolt = Spell {
+            name: "Fire Bolt".to_string(),
+            branch: MagicBranch::Elementalism,
+            difficulty: SpellDifficulty::Normal,
+            preparation_time: 0,
+            casting_time: 1,
+            range: SpellRange::Short(30),
+            duration: SpellDuration::Instant,
+            components: SpellComponents::default(),
+        };
+        assert_eq!(
+            validate_target(&bolt, &troll, &info),
+            Err(MagicError::NoLineOfSight)
+        );
+        let goblin = SpellTarget::Character("Goblin".to_string());
+        let info = TargetInfo::on_battlefield(&field, "Mage", &goblin).unwrap();
+        assert_eq!(validate_target(&bolt, &goblin, &info), Ok(()));
+    }
+
+    #[test]
+    fn test_concentration_spells() {
+        let spell = |name: &str| Spell {
+            name: name.to_string(),
+            branch: MagicBranch::Mentalism,
+            difficulty: SpellDifficulty::Easy,
+            preparation_time: 0,
+            casting_time: 1,
+            range: SpellRange::Short(10),
+            duration: SpellDuration::Concentration,
+            components: SpellComponents::default(),
+        };
+        let mut mage = MagicUser::new(6);
+        mage.add_lore(MagicBranch::Mentalism, 5);
+        for name in ["Hold Person", "Levitate", "Veil"] {
+            mage.learn_spell(spell(name), 3).unwrap();
+        }
+
+        assert!(mage.cast_spell("Hold Person", 5).unwrap().success);
+        assert!(mage.cast_spell("Levitate", 5).unwrap().success);
+        assert_eq!(mage.concentration, vec!["Hold Person", "Levitate"]);
+
+        // The third effect is cast at -2: 3 + 6 + 1 - 2 = 8, just enough
+        let third = mage.cast_spell("Veil", 1).unwrap();
+        assert_eq!(third.total, 8);
+        assert_eq!(mage.concentration_penalty(), -4);
+
+        let before = mage.exhaustion_points;
+        assert_eq!(mage.maintain_concentration(), 3);
+        assert_eq!(mage.exhaustion_points, before + 3);
+
+        assert!(mage.drop_concentration("Levitate"));
+        assert!(!mage.drop_concentration("Levitate"));
+        assert_eq!(mage.concentration_penalty(), -2);
+    }
 }
@@ -1,10 +1,25 @@
 //! Special combat maneuvers based on Draft RPG Section 4.22
 
+use crate::modules::positioning::movement_allowance;
+use crate::{Character, WeaponTags};
 use std::fmt;
 use inquire_derive::Selectable;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weapon skill needed to riposte
+pub const RIPOSTE_SKILL: i32 = 6;
+/// Movement points needed to charge
+pub const CHARGE_MOVEMENT: i32 = 3;
+/// Parry penalty when stepping in to defend a guarded ally
+pub const GUARD_PENALTY: i32 = -2;
+/// Source of the modifiers a stance adds while its holder fights
+pub const MANEUVER_SOURCE: &str = "Maneuver";
+
 /// Special combat maneuvers that characters can perform
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Selectable)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CombatManeuver {
     /// Normal attack with no special effects
     Normal,
@@ -16,9 +31,32 @@ pub enum CombatManeuver {
     AllOutAttack,
     /// Aimed attack: -2 attack, +2 damage (requires aiming previous round)
     AimedAttack,
+    /// Riposte: +1 attack, +1 defense, parrying into the counter (requires skill)
+    Riposte,
+    /// Shield bash: +1 defense, -1 damage (shield)
+    ShieldBash,
+    /// Pommel strike: +1 attack, -2 damage (hilted weapon)
+    PommelStrike,
+    /// Lunge: +1 attack, -1 defense (thrusting weapon)
+    Lunge,
+    /// Guard: parry attacks on an adjacent ally at `GUARD_PENALTY`, cannot attack
+    Guard,
 }
 
 impl CombatManeuver {
+    pub const ALL: [CombatManeuver; 10] = [
+        CombatManeuver::Normal,
+        CombatManeuver::DefensivePosition,
+        CombatManeuver::Charge,
+        CombatManeuver::AllOutAttack,
+        CombatManeuver::AimedAttack,
+        CombatManeuver::Riposte,
+        CombatManeuver::ShieldBash,
+        CombatManeuver::PommelStrike,
+        CombatManeuver::Lunge,
+        CombatManeuver::Guard,
+    ];
+
     /// Get attack modifier for this maneuver
     pub fn attack_modifier(&self) -> i32 {
         match self {
@@ -27,6 +65,11 @@ impl CombatManeuver {
             CombatManeuver::Charge => 1,
             CombatManeuver::AllOutAttack => 2,
             CombatManeuver::AimedAttack => -2,
+            CombatManeuver::Riposte => 1,
+            CombatManeuver::ShieldBash => 0,
+            CombatManeuver::PommelStrike => 1,
+            CombatManeuver::Lunge => 1,
+            CombatManeuver::Guard => 0, // Can't attack
         }
     }
 
@@ -38,6 +81,11 @@ impl CombatManeuver {
             CombatManeuver::Charge => -2,
             CombatManeuver::AllOutAttack => -4,
             CombatManeuver::AimedAttack => 0,
+            CombatManeuver::Riposte => 1,
+            CombatManeuver::ShieldBash => 1,
+            CombatManeuver::PommelStrike => 0,
+            CombatManeuver::Lunge => -1,
+            CombatManeuver::Guard => 0,
         }
     }
 
@@ -49,18 +97,55 @@ impl CombatManeuver {
             CombatManeuver::Charge => 1,
             CombatManeuver::AllOutAttack => 0,
             CombatManeuver::AimedAttack => 2,
+            CombatManeuver::Riposte => 0,
+            CombatManeuver::ShieldBash => -1,
+            CombatManeuver::PommelStrike => -2,
+            CombatManeuver::Lunge => 0,
+            CombatManeuver::Guard => 0,
         }
     }
 
     /// Check if this maneuver allows attacking
     pub fn can_attack(&self) -> bool {
-        !matches!(self, CombatManeuver::DefensivePosition)
+        !matches!(
+            self,
+            CombatManeuver::DefensivePosition | CombatManeuver::Guard
+        )
     }
 
     /// Check if this maneuver requires preparation
     pub fn requires_preparation(&self) -> bool {
         matches!(self, CombatManeuver::AimedAttack)
     }
+
+    /// What the acting character needs to attempt this maneuver
+    pub fn prerequisite(&self) -> Option<Prerequisite> {
+        match self {
+            CombatManeuver::Riposte => Some(Prerequisite::WeaponSkill(RIPOSTE_SKILL)),
+            CombatManeuver::Charge => Some(Prerequisite::Movement(CHARGE_MOVEMENT)),
+            CombatManeuver::ShieldBash => Some(Prerequisite::WeaponTag(WeaponTags::SHIELD)),
+            CombatManeuver::PommelStrike => Some(Prerequisite::WeaponTag(WeaponTags::HILTED)),
+            CombatManeuver::Lunge => Some(Prerequisite::WeaponTag(WeaponTags::THRUSTING)),
+            _ => None,
+        }
+    }
+
+    /// Attack and defense penalty for the round after this maneuver
+    ///
+    /// Maneuvers with a penalty leave the character off balance and can't be
+    /// used two rounds running.
+    pub fn recovery_penalty(&self) -> i32 {
+        match self {
+            CombatManeuver::Charge => -1,
+            CombatManeuver::AllOutAttack => -2,
+            _ => 0,
+        }
+    }
+
+    /// Check if this maneuver needs a recovery round afterwards
+    pub fn requires_recovery(&self) -> bool {
+        self.recovery_penalty() != 0
+    }
 }
 
 impl fmt::Display for CombatManeuver {
@@ -71,16 +156,66 @@ impl fmt::Display for CombatManeuver {
             CombatManeuver::Charge => write!(f, "Charge"),
             CombatManeuver::AllOutAttack => write!(f, "All-Out Attack"),
             CombatManeuver::AimedAttack => write!(f, "Aimed Attack"),
+            CombatManeuver::Riposte => write!(f, "Riposte"),
+            CombatManeuver::ShieldBash => write!(f, "Shield Bash"),
+            CombatManeuver::PommelStrike => write!(f, "Pommel Strike"),
+            CombatManeuver::Lunge => write!(f, "Lunge"),
+            CombatManeuver::Guard => write!(f, "Guard"),
         }
     }
 }
 
+/// Requirement a character must meet to attempt a maneuver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prerequisite {
+    /// Minimum weapon skill
+    WeaponSkill(i32),
+    /// Minimum movement points this round (see `positioning::movement_allowance`)
+    Movement(i32),
+    /// The equipped weapon carries these tags
+    WeaponTag(WeaponTags),
+}
+
+impl Prerequisite {
+    pub fn is_met(&self, actor: &Character) -> bool {
+        match self {
+            Prerequisite::WeaponSkill(level) => actor.weapon_skill() >= *level,
+            Prerequisite::Movement(points) => movement_allowance(actor) >= *points,
+            Prerequisite::WeaponTag(tags) => actor.weapon.has_tag(*tags),
+        }
+    }
+}
+
+impl fmt::Display for Prerequisite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Prerequisite::WeaponSkill(level) => write!(f, "weapon skill {}", level),
+            Prerequisite::Movement(points) => write!(f, "{} movement points", points),
+            Prerequisite::WeaponTag(tags) => write!(f, "a {} weapon", tags),
+        }
+    }
+}
+
+/// Maneuvers `actor` meets the prerequisites for with the equipped weapon
+///
+/// Meant for menus; stance state such as aiming or recovery is checked by
+/// `CombatStance::set_maneuver`.
+pub fn available_maneuvers(actor: &Character) -> Vec<CombatManeuver> {
+    CombatManeuver::ALL
+        .into_iter()
+        .filter(|m| m.prerequisite().is_none_or(|p| p.is_met(actor)))
+        .collect()
+}
+
 /// Tracks combat stance and preparation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CombatStance {
     pub current_maneuver: CombatManeuver,
     pub aiming: bool,
     pub charged_this_round: bool,
+    /// Maneuver from last round the character is still recovering from
+    pub recovering_from: Option<CombatManeuver>,
 }
 
 impl CombatStance {
@@ -89,15 +224,31 @@ impl CombatStance {
             current_maneuver: CombatManeuver::Normal,
             aiming: false,
             charged_this_round: false,
+            recovering_from: None,
         }
     }
 
-    /// Set the combat maneuver for next action
-    pub fn set_maneuver(&mut self, maneuver: CombatManeuver) -> Result<(), ManeuverError> {
+    /// Set the combat maneuver for next action, checking `actor` can attempt it
+    pub fn set_maneuver(
+        &mut self,
+        maneuver: CombatManeuver,
+        actor: &Character,
+    ) -> Result<(), ManeuverError> {
+        if let Some(prerequisite) = maneuver.prerequisite() {
+            if !prerequisite.is_met(actor) {
+                return Err(ManeuverError::PrerequisiteNotMet {
+                    maneuver,
+                    prerequisite,
+                });
+            }
+        }
         // Check if aimed attack without aiming
         if maneuver == CombatManeuver::AimedAttack && !self.aiming {
             return Err(ManeuverError::NotPrepared);
         }
+        if self.recovering_from == Some(maneuver) {
+            return Err(ManeuverError::Recovering(maneuver));
+        }
 
         self.current_maneuver = maneuver;
 
@@ -120,19 +271,33 @@ impl CombatStance {
     }
 
     /// Reset stance at end of round
+    ///
+    /// A maneuver that needs recovery drops back to Normal and leaves the
+    /// character off balance for the next round.
     pub fn end_round(&mut self) {
         self.charged_this_round = false;
         // Aiming persists across rounds until used
+        if self.current_maneuver.requires_recovery() {
+            self.recovering_from = Some(self.current_maneuver);
+            self.current_maneuver = CombatManeuver::Normal;
+        } else {
+            self.recovering_from = None;
+        }
     }
 
-    /// Get total attack modifier including maneuver
+    /// Off-balance penalty from last round's maneuver
+    pub fn recovery_penalty(&self) -> i32 {
+        self.recovering_from.map_or(0, |m| m.recovery_penalty())
+    }
+
+    /// Get total attack modifier including maneuver and recovery
     pub fn total_attack_modifier(&self) -> i32 {
-        self.current_maneuver.attack_modifier()
+        self.current_maneuver.attack_modifier() + self.recovery_penalty()
     }
 
-    /// Get total defense modifier including maneuver
+    /// Get total defense modifier including maneuver and recovery
     pub fn total_defense_modifier(&self) -> i32 {
-        self.current_maneuver.defense_modifier()
+        self.current_maneuver.defense_modifier() + self.recovery_penalty()
     }
 
     /// Get total damage modifier including maneuver
@@ -150,12 +315,28 @@ impl Default for CombatStance {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ManeuverError {
     NotPrepared,
+    /// The maneuver was used last round and can't be repeated yet
+    Recovering(CombatManeuver),
+    PrerequisiteNotMet {
+        maneuver: CombatManeuver,
+        prerequisite: Prerequisite,
+    },
+    /// No combatant in the encounter has this id
+    UnknownCombatant(usize),
 }
 
 impl fmt::Display for ManeuverError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ManeuverError::NotPrepared => write!(f, "Maneuver requires preparation"),
+            ManeuverError::Recovering(m) => {
+                write!(f, "Still recovering from {}; it can't be repeated yet", m)
+            }
+            ManeuverError::PrerequisiteNotMet {
+                maneuver,
+                prerequisite,
+            } => write!(f, "{} requires {}", maneuver, prerequisite),
+            ManeuverError::UnknownCombatant(id) => write!(f, "No combatant with id {}", id),
         }
     }
 }
@@ -165,6 +346,18 @@ impl std::error::Error for ManeuverError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Armor, Attributes, SkillLevel, Weapon, WoundLevel};
+
+    fn fighter() -> Character {
+        Character::new(
+            "Aldric",
+            Attributes::new(7, 7, 7, 6, 6, 6, 5, 6, 5),
+            7,
+            5,
+            Weapon::long_sword(),
+            Armor::leather(),
+        )
+    }
 
     #[test]
     fn test_maneuver_modifiers() {
@@ -186,7 +379,7 @@ mod tests {
     fn test_defensive_position() {
         let mut stance = CombatStance::new();
         stance
-            .set_maneuver(CombatManeuver::DefensivePosition)
+            .set_maneuver(CombatManeuver::DefensivePosition, &fighter())
             .unwrap();
 
         assert_eq!(stance.total_defense_modifier(), 2);
@@ -196,7 +389,9 @@ mod tests {
     #[test]
     fn test_charge_maneuver() {
         let mut stance = CombatStance::new();
-        stance.set_maneuver(CombatManeuver::Charge).unwrap();
+        stance
+            .set_maneuver(CombatManeuver::Charge, &fighter())
+            .unwrap();
         stance.record_charge();
 
         assert_eq!(stance.total_attack_modifier(), 1);
@@ -213,14 +408,18 @@ mod tests {
         let mut stance = CombatStance::new();
 
         // Can't do aimed attack without aiming
-        assert!(stance.set_maneuver(CombatManeuver::AimedAttack).is_err());
+        assert!(stance
+            .set_maneuver(CombatManeuver::AimedAttack, &fighter())
+            .is_err());
 
         // Start aiming
         stance.start_aiming();
         assert!(stance.aiming);
 
         // Now can do aimed attack
-        assert!(stance.set_maneuver(CombatManeuver::AimedAttack).is_ok());
+        assert!(stance
+            .set_maneuver(CombatManeuver::AimedAttack, &fighter())
+            .is_ok());
         assert_eq!(stance.total_attack_modifier(), -2);
         assert_eq!(stance.total_damage_modifier(), 2);
 
@@ -231,9 +430,97 @@ mod tests {
     #[test]
     fn test_all_out_attack() {
         let mut stance = CombatStance::new();
-        stance.set_maneuver(CombatManeuver::AllOutAttack).unwrap();
+        stance
+            .set_maneuver(CombatManeuver::AllOutAttack, &fighter())
+            .unwrap();
 
         assert_eq!(stance.total_attack_modifier(), 2);
         assert_eq!(stance.total_defense_modifier(), -4);
     }
+
+    #[test]
+    fn test_recovery_after_all_out_attack() {
+        let mut stance = CombatStance::new();
+        stance
+            .set_maneuver(CombatManeuver::AllOutAttack, &fighter())
+            .unwrap();
+        stance.end_round();
+
+        // Off balance: back to Normal with -2 attack and defense
+        assert_eq!(stance.current_maneuver, CombatManeuver::Normal);
+        assert_eq!(stance.total_attack_modifier(), -2);
+        assert_eq!(stance.total_defense_modifier(), -2);
+        assert_eq!(
+            stance.set_maneuver(CombatManeuver::AllOutAttack, &fighter()),
+            Err(ManeuverError::Recovering(CombatManeuver::AllOutAttack))
+        );
+
+        // A different big maneuver is allowed, and stacks its own aftermath
+        stance
+            .set_maneuver(CombatManeuver::Charge, &fighter())
+            .unwrap();
+        assert_eq!(stance.total_defense_modifier(), -4);
+        stance.end_round();
+        assert_eq!(stance.recovery_penalty(), -1);
+
+        stance.end_round();
+        assert_eq!(stance.recovery_penalty(), 0);
+        assert!(stance
+            .set_maneuver(CombatManeuver::Charge, &fighter())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_maneuver_prerequisites() {
+        let mut stance = CombatStance::new();
+        let mut novice = fighter();
+        novice.set_weapon_skill(SkillLevel::clamped(4));
+
+        assert_eq!(
+            stance.set_maneuver(CombatManeuver::Riposte, &novice),
+            Err(ManeuverError::PrerequisiteNotMet {
+                maneuver: CombatManeuver::Riposte,
+                prerequisite: Prerequisite::WeaponSkill(RIPOSTE_SKILL),
+            })
+        );
+        assert!(stance
+            .set_maneuver(CombatManeuver::Riposte, &fighter())
+            .is_ok());
+
+        // Plate (-1) and a severe wound leave too little movement to charge
+        let mut hobbled = fighter();
+        hobbled.armor = Armor::plate();
+        hobbled.wounds.add_wound(WoundLevel::Severe);
+        assert!(matches!(
+            stance.set_maneuver(CombatManeuver::Charge, &hobbled),
+            Err(ManeuverError::PrerequisiteNotMet {
+                prerequisite: Prerequisite::Movement(CHARGE_MOVEMENT),
+                ..
+            })
+        ));
+        assert_eq!(stance.current_maneuver, CombatManeuver::Riposte);
+    }
+
+    #[test]
+    fn test_maneuvers_filtered_by_weapon() {
+        let mut aldric = fighter();
+        let sword = available_maneuvers(&aldric);
+        assert!(sword.contains(&CombatManeuver::Lunge));
+        assert!(sword.contains(&CombatManeuver::PommelStrike));
+        assert!(!sword.contains(&CombatManeuver::ShieldBash));
+
+        aldric.equip_weapon(Weapon::shield());
+        let shield = available_maneuvers(&aldric);
+        assert!(shield.contains(&CombatManeuver::ShieldBash));
+        assert!(!shield.contains(&CombatManeuver::Lunge));
+
+        let mut stance = CombatStance::new();
+        assert_eq!(
+            stance.set_maneuver(CombatManeuver::PommelStrike, &aldric),
+            Err(ManeuverError::PrerequisiteNotMet {
+                maneuver: CombatManeuver::PommelStrike,
+                prerequisite: Prerequisite::WeaponTag(WeaponTags::HILTED),
+            })
+        );
+    }
 }
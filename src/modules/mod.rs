@@ -2,25 +2,248 @@
 //!
 //! This module contains optional advanced features including:
 //! - Skill development and progression
+//! - Character backgrounds and professions
+//! - Species and ancestry
 //! - Exhaustion system
+//! - Blood loss from accumulated wounds
 //! - Special combat maneuvers
 //! - Hit location tracking
+//! - Battlefield positioning on square and hex grids
+//! - Seeded procedural arenas
+//! - A* pathfinding for AI movement
+//! - Threat assessment and utility AI for NPCs
+//! - Seeded batch NPC generation
+//! - Seeded NPC names, personalities and backstories
+//! - Expected-value combat odds
+//! - Vectorized batch attack resolution
+//! - Multi-combatant encounters
+//! - Pooled encounter state for long simulations
+//! - Boss phases and legendary actions
+//! - Decision deadlines for player and bot defenses
+//! - Formal duels with stop conditions and honor rules
+//! - Scripted encounter scenarios
+//! - Wound descriptions and scars
+//! - Recovery time, post-combat triage and quality of care
+//! - Dying state and death checks
+//! - Optional rule toggles (Ruleset)
+//! - Random, average and scripted dice
+//! - Round, segment and real-time conversions
+//! - Lasting roll modifiers
+//! - Berserk rage that ignores wound penalties
+//! - Traits: advantages and disadvantages
+//! - Permanent injury tables
+//! - Prosthetics and compensating equipment
+//! - Campaign calendar
+//! - Random encounter tables
+//! - Overland travel
+//! - Food, water, ammunition and bandages
+//! - Hirelings and follower loyalty
+//! - Companion animals and pets
+//! - Mount control, training and mounted archery
+//! - Reputation and faction standing
+//! - Languages and comprehension checks
+//! - Knowledge and lore checks with research
 //! - Ranged combat
 //! - Magic system
+//! - Scroll and potion consumables
+//! - Spell research
+//! - Telekinetic shoves, disarms and hurled objects
+//! - Blinks and teleports in combat
+//! - Raising fallen combatants as zombies
+//! - Divine favor and miracles
+//! - Staffs, wands and charged magical foci
+//! - Familiars bonded to casters
+//! - Combat narration templates
+//! - Compendium export
+//! - Shared equipment catalog, data-driven weapon lists and setting content tags
+//!
+//! Cargo features decide which of these are compiled; `full` (the default)
+//! turns them all on. The `#[cfg]`s below are the dependency graph:
+//! - `core`: everything without a gate
+//! - `magic`: magic, consumables, familiars, foci, research, necromancy,
+//!   telekinesis, transportation
+//! - `ranged`: ranged combat, and shooting and overwatch in encounters
+//! - `skills`: skills, knowledge, languages, backgrounds
+//! - `hit-location`: body plan tables, locational damage, piecemeal armor
+//! - `favor`: deities, favor and miracles
+//! - `magic` and `ranged` together: compendium export, NPC generation
 
+pub mod ai;
+pub mod ancestry;
+pub mod arena;
+#[cfg(feature = "skills")]
+pub mod backgrounds;
+pub mod backstory;
+pub mod batch_rolls;
+pub mod berserk;
+pub mod blood_loss;
+pub mod boss;
+pub mod calendar;
+pub mod catalog;
+pub mod companions;
+#[cfg(all(feature = "magic", feature = "ranged"))]
+pub mod compendium;
+#[cfg(feature = "magic")]
+pub mod consumables;
+pub mod dice;
+pub mod duel;
+pub mod dying;
+pub mod encounter;
+pub mod encounter_table;
 pub mod exhaustion;
+#[cfg(feature = "magic")]
+pub mod familiars;
+#[cfg(feature = "favor")]
+pub mod favor;
+#[cfg(feature = "magic")]
+pub mod foci;
+pub mod followers;
+pub mod healing;
 pub mod hit_location;
+pub mod injuries;
+#[cfg(feature = "skills")]
+pub mod knowledge;
+#[cfg(feature = "skills")]
+pub mod languages;
+#[cfg(feature = "magic")]
 pub mod magic;
 pub mod maneuvers;
+pub mod modifiers;
+pub mod mounts;
+pub mod narration;
+#[cfg(feature = "magic")]
+pub mod necromancy;
+#[cfg(all(feature = "magic", feature = "ranged"))]
+pub mod npc;
+pub mod odds;
+pub mod pathfinding;
+pub mod permanent_injury;
+pub mod pool;
+pub mod positioning;
+pub mod prosthetics;
+#[cfg(feature = "ranged")]
 pub mod ranged_combat;
+pub mod reputation;
+#[cfg(feature = "magic")]
+pub mod research;
+pub mod ruleset;
+pub mod scenario;
+#[cfg(feature = "skills")]
 pub mod skills;
+pub mod supplies;
+#[cfg(feature = "magic")]
+pub mod telekinesis;
+pub mod timing;
+pub mod traits;
+#[cfg(feature = "magic")]
+pub mod transportation;
+pub mod travel;
+pub mod turn_timer;
 
 // Re-export commonly used types
+pub use ai::{
+    choose_target, evaluate_threat, recommend_defense, ActionScorer, AiAction, AiContext,
+    AiDecision, Archetype, Consideration,
+};
+pub use ancestry::{Ancestry, AncestryError, AttributeAdjustments, Sense, TargetSize};
+pub use arena::{ArenaDifficulty, ArenaGenerator};
+#[cfg(feature = "skills")]
+pub use backgrounds::{Background, BackgroundError, SkillGrant};
+pub use backstory::{BackstoryError, BackstoryGenerator, BackstoryTables, Culture, Persona};
+pub use batch_rolls::{resolve_batch, resolve_scalar, AttackProfile, BatchTally};
+pub use berserk::Berserk;
+pub use blood_loss::BloodLossLevel;
+pub use boss::{Boss, BossPhase};
+pub use calendar::CampaignDate;
+pub use catalog::{
+    ArmorHandle, ArmorInstance, CatalogError, ContentCatalog, ContentKind, EquipmentCatalog,
+    TaggedContent, WeaponCatalog, WeaponEntry, WeaponHandle, WeaponInstance,
+};
+pub use companions::{Companion, CompanionCommand};
+#[cfg(all(feature = "magic", feature = "ranged"))]
+pub use compendium::{Compendium, CompendiumFormat};
+#[cfg(feature = "magic")]
+pub use consumables::{Consumable, ConsumableKind};
+pub use dice::{AverageDice, Dice, DiceMode, RandomDice, ScriptedDice};
+pub use duel::{Duel, DuelAction, DuelError, DuelVerdict, HonorRules, StopCondition};
+pub use dying::{DeathCheck, DyingState};
+#[cfg(feature = "ranged")]
+pub use encounter::{
+    ActionKind, Combatant, CombatantId, Encounter, EncounterEvent, Readiness, Retreat, Rounds,
+    WeaponSwitch, Wielding,
+};
+pub use encounter_table::{EncounterEntry, EncounterTable};
 pub use exhaustion::{Exhaustion, ExhaustionLevel};
-pub use hit_location::{AttackDirection, HitLocation, LocationalDamage};
-pub use magic::{CastingResult, MagicBranch, MagicError, MagicLore, MagicUser, Spell};
-pub use maneuvers::{CombatManeuver, CombatStance, ManeuverError};
-pub use ranged_combat::{
-    calculate_ranged_modifiers, Cover, RangedAttackState, RangedWeapon, TargetSize,
+#[cfg(feature = "magic")]
+pub use familiars::{BondFeedback, Familiar, FamiliarError};
+#[cfg(feature = "favor")]
+pub use favor::{Deity, Devotee, FavorError, Invocation, Miracle, MiracleDifficulty};
+#[cfg(feature = "magic")]
+pub use foci::{Focus, FocusError, FocusKind};
+pub use followers::{Follower, LoyaltyEvent, Morale, Retinue};
+pub use healing::{
+    check_infections, triage, triage_with_care, CareQuality, Treatment, TriageReport,
+};
+#[cfg(feature = "hit-location")]
+pub use hit_location::{
+    ArmorEvent, ArmorPiece, HitLocationError, HitLocationTable, LocationWeight, LocationalDamage,
+    PiecemealArmor,
+};
+pub use hit_location::{AttackDirection, BodyPlan, HitLocation};
+pub use injuries::{Injuries, Scar, WoundDescription};
+#[cfg(feature = "skills")]
+pub use knowledge::{CreatureWeakness, KnowledgeField, Lore, Obscurity, Recall};
+#[cfg(feature = "skills")]
+pub use languages::{
+    can_communicate, Complexity, Comprehension, Fluency, Languages, SharedLanguage,
+};
+#[cfg(feature = "magic")]
+pub use magic::{
+    CasterRestraints, CastingResult, MagicBranch, MagicError, MagicLore, MagicUser,
+    MaterialComponent, Spell, SpellComponents, SpellTarget, TargetInfo,
+};
+pub use maneuvers::{
+    available_maneuvers, CombatManeuver, CombatStance, ManeuverError, Prerequisite,
+};
+pub use modifiers::{Modifier, ModifierTarget, Modifiers};
+pub use mounts::{ControlResult, ControlTrigger, Gait, Mount, MountQuality};
+pub use narration::{
+    Beat, CombatBeat, NarrationError, NarrationStyle, Narrator, Template, TemplateNarrator,
+    TemplatePack,
+};
+#[cfg(feature = "magic")]
+pub use necromancy::NecromancyError;
+#[cfg(all(feature = "magic", feature = "ranged"))]
+pub use npc::{generate_batch, threat_level, NpcConstraints, NpcError, NpcRole};
+pub use odds::{expected_rounds_to_kill, hit_probability, AttackOdds, Odds};
+pub use pathfinding::{MoveGoal, Path};
+pub use permanent_injury::{InjuryTable, PermanentInjury};
+#[cfg(feature = "ranged")]
+pub use pool::EncounterPool;
+pub use positioning::{
+    AntiMagic, Battlefield, CellTerrain, Cover, Facing, GridKind, GridPos, Hazard, Lighting,
+    MoveError, Placement,
+};
+pub use prosthetics::{Prosthetic, ProstheticError};
+#[cfg(feature = "ranged")]
+pub use ranged_combat::{calculate_ranged_modifiers, RangedAttackState, RangedWeapon};
+pub use reputation::{Reputation, ReputationEvent, Standing};
+#[cfg(feature = "magic")]
+pub use research::{ResearchError, ResearchProgress, ResearchProject};
+pub use ruleset::Ruleset;
+#[cfg(feature = "ranged")]
+pub use scenario::{
+    Environment, Participant, Scenario, ScenarioError, ScenarioOutcome, ScenarioRun, TerrainCell,
+    Trigger, TriggerCondition, TriggerEffect, VictoryCondition,
 };
-pub use skills::{Skill, SkillDifficulty, SkillError, SkillSet};
+#[cfg(feature = "skills")]
+pub use skills::{Skill, SkillCategory, SkillDifficulty, SkillError, SkillSet};
+pub use supplies::{AmmoKind, Supplies, SupplyWarning};
+#[cfg(feature = "magic")]
+pub use telekinesis::{HurledObject, Telekinesis, TelekinesisError, TelekineticEffect};
+pub use traits::{Trait, TraitRule, Traits};
+#[cfg(feature = "magic")]
+pub use transportation::{Displacement, TransportError};
+pub use travel::{Journey, Terrain, TravelParty, TravelReport, Traveller, Weather};
+#[cfg(feature = "ranged")]
+pub use turn_timer::{decide_defense, DefenseProvider, SharedDefenseProvider, TurnTimer};
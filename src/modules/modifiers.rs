@@ -0,0 +1,176 @@
+//! Lasting roll modifiers attached to a character
+//!
+//! Injuries, traits and other long-lived effects register `Modifier`s on a
+//! character's `Modifiers` list instead of patching skills directly. The
+//! character's roll methods sum the modifiers for the relevant target, and
+//! everything from one source can be removed again in a single call.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The kind of roll or value a modifier adjusts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ModifierTarget {
+    Attack,
+    Parry,
+    /// Dodge rolls (dodges are also affected by `Movement`)
+    Dodge,
+    Damage,
+    Movement,
+    Ranged,
+    Casting,
+    Perception,
+}
+
+impl fmt::Display for ModifierTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ModifierTarget::Attack => write!(f, "Attack"),
+            ModifierTarget::Parry => write!(f, "Parry"),
+            ModifierTarget::Dodge => write!(f, "Dodge"),
+            ModifierTarget::Damage => write!(f, "Damage"),
+            ModifierTarget::Movement => write!(f, "Movement"),
+            ModifierTarget::Ranged => write!(f, "Ranged"),
+            ModifierTarget::Casting => write!(f, "Casting"),
+            ModifierTarget::Perception => write!(f, "Perception"),
+        }
+    }
+}
+
+/// A single bonus or penalty from a named source
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Modifier {
+    pub source: String,
+    pub target: ModifierTarget,
+    pub value: i32,
+}
+
+impl Modifier {
+    pub fn new(source: &str, target: ModifierTarget, value: i32) -> Self {
+        Self {
+            source: source.to_string(),
+            target,
+            value,
+        }
+    }
+}
+
+impl Clone for Modifier {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source.clone(),
+            target: self.target,
+            value: self.value,
+        }
+    }
+
+    /// Reuses the source string's buffer
+    fn clone_from(&mut self, source: &Self) {
+        self.source.clone_from(&source.source);
+        self.target = source.target;
+        self.value = source.value;
+    }
+}
+
+impl fmt::Display for Modifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:+} ({})", self.target, self.value, self.source)
+    }
+}
+
+/// All modifiers currently registered on a character
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Modifiers(Vec<Modifier>);
+
+impl Clone for Modifiers {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.0.clone_from(&source.0);
+    }
+}
+
+impl Modifiers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, modifier: Modifier) {
+        self.0.push(modifier);
+    }
+
+    /// Remove every modifier from `source`, returning how many were removed
+    pub fn remove_source(&mut self, source: &str) -> usize {
+        let before = self.0.len();
+        self.0.retain(|m| m.source != source);
+        before - self.0.len()
+    }
+
+    /// Remove and return every modifier from `source`
+    pub fn take_source(&mut self, source: &str) -> Vec<Modifier> {
+        let (taken, kept) = std::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|m| m.source == source);
+        self.0 = kept;
+        taken
+    }
+
+    /// Sum of all modifiers for a target
+    pub fn total(&self, target: ModifierTarget) -> i32 {
+        self.0
+            .iter()
+            .filter(|m| m.target == target)
+            .fold(0i32, |total, m| total.saturating_add(m.value))
+    }
+
+    /// Sum of the modifiers for a target that came from `source`
+    pub fn total_from(&self, source: &str, target: ModifierTarget) -> i32 {
+        self.0
+            .iter()
+            .filter(|m| m.source == source && m.target == target)
+            .fold(0i32, |total, m| total.saturating_add(m.value))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Modifier> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_and_removal_by_source() {
+        let mut mods = Modifiers::new();
+        mods.add(Modifier::new("Lost Eye", ModifierTarget::Ranged, -2));
+        mods.add(Modifier::new("Lost Eye", ModifierTarget::Perception, -2));
+        mods.add(Modifier::new("Blessing", ModifierTarget::Ranged, 1));
+
+        assert_eq!(mods.total(ModifierTarget::Ranged), -1);
+        assert_eq!(mods.total(ModifierTarget::Attack), 0);
+
+        assert_eq!(mods.remove_source("Lost Eye"), 2);
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods.total(ModifierTarget::Ranged), 1);
+        assert_eq!(
+            mods.iter().next().unwrap().to_string(),
+            "Ranged +1 (Blessing)"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Mounts: control checks, training and mounted archery
+//!
+//! Riders roll to keep a frightened or wounded mount under control, spend
+//! downtime training it, and shoot from the saddle at a penalty that a good
+//! riding skill reduces.
+
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const MAX_MOUNT_TRAINING: i32 = 10;
+/// Days of downtime one training check represents
+pub const TRAINING_DAYS: u32 = 7;
+/// Handling check total (skill + training + d10) for a training session to succeed
+pub const TRAINING_DIFFICULTY: i32 = 16;
+/// Modifier source for the mounted archery penalty
+pub const MOUNTED_SOURCE: &str = "Mounted";
+
+/// How well schooled a mount is, derived from its training
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MountQuality {
+    /// Training 0-3
+    Green,
+    /// Training 4-7
+    Trained,
+    /// Training 8-10
+    Warhorse,
+}
+
+impl MountQuality {
+    pub fn from_training(training: i32) -> Self {
+        match training {
+            i32::MIN..=3 => MountQuality::Green,
+            4..=7 => MountQuality::Trained,
+            _ => MountQuality::Warhorse,
+        }
+    }
+}
+
+impl fmt::Display for MountQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MountQuality::Green => write!(f, "Green"),
+            MountQuality::Trained => write!(f, "Trained"),
+            MountQuality::Warhorse => write!(f, "Warhorse"),
+        }
+    }
+}
+
+/// What calls for a control check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ControlTrigger {
+    /// Fire, loud noises, monsters, the smell of blood
+    Frightened,
+    /// The mount has just been wounded
+    Wounded,
+}
+
+impl ControlTrigger {
+    /// Check total (riding + training + d10) needed to keep control
+    pub fn difficulty(&self) -> i32 {
+        match self {
+            ControlTrigger::Frightened => 14,
+            ControlTrigger::Wounded => 16,
+        }
+    }
+}
+
+/// Result of a control check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ControlResult {
+    Controlled,
+    /// Missed by up to 4: the mount refuses and the rider loses the action
+    Balks,
+    /// Missed by 5-8: the mount runs off, carrying the rider with it
+    Bolts,
+    /// Missed by more than 8: the rider is thrown
+    Thrown,
+}
+
+impl fmt::Display for ControlResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ControlResult::Controlled => write!(f, "Controlled"),
+            ControlResult::Balks => write!(f, "Balks"),
+            ControlResult::Bolts => write!(f, "Bolts"),
+            ControlResult::Thrown => write!(f, "Thrown"),
+        }
+    }
+}
+
+/// Speed of a mount while its rider acts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Gait {
+    #[default]
+    Standing,
+    Walk,
+    Gallop,
+}
+
+impl Gait {
+    /// Ranged attack penalty from the saddle before riding skill is applied
+    pub fn archery_penalty(&self) -> i32 {
+        match self {
+            Gait::Standing => -1,
+            Gait::Walk => -3,
+            Gait::Gallop => -6,
+        }
+    }
+}
+
+/// A riding animal
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mount {
+    pub name: String,
+    /// 0-10, added to control checks
+    pub training: i32,
+}
+
+impl Mount {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            training: 2,
+        }
+    }
+
+    pub fn with_training(mut self, training: i32) -> Self {
+        self.training = training.clamp(0, MAX_MOUNT_TRAINING);
+        self
+    }
+
+    pub fn quality(&self) -> MountQuality {
+        MountQuality::from_training(self.training)
+    }
+
+    /// Keep the mount in hand: riding skill + training + `roll` vs the trigger's difficulty
+    pub fn control_check(
+        &self,
+        riding_skill: i32,
+        trigger: ControlTrigger,
+        roll: i32,
+    ) -> ControlResult {
+        let margin = riding_skill + self.training + roll - trigger.difficulty();
+        match margin {
+            0.. => ControlResult::Controlled,
+            -4..=-1 => ControlResult::Balks,
+            -8..=-5 => ControlResult::Bolts,
+            _ => ControlResult::Thrown,
+        }
+    }
+
+    /// Spend `days` of downtime training, one check with `roll` per `TRAINING_DAYS`
+    ///
+    /// Each successful check raises training by one. Returns the number of
+    /// points gained.
+    pub fn train<F>(&mut self, handler_skill: i32, days: u32, mut roll: F) -> i32
+    where
+        F: FnMut() -> i32,
+    {
+        let mut gained = 0;
+        for _ in 0..days / TRAINING_DAYS {
+            if self.training >= MAX_MOUNT_TRAINING {
+                break;
+            }
+            if handler_skill + self.training + roll() >= TRAINING_DIFFICULTY {
+                self.training += 1;
+                gained += 1;
+            }
+        }
+        gained
+    }
+}
+
+/// Ranged penalty for shooting from the saddle: the gait's penalty, reduced
+/// by half the riding skill, never better than 0
+pub fn mounted_archery_penalty(riding_skill: i32, gait: Gait) -> i32 {
+    (gait.archery_penalty() + riding_skill.max(0) / 2).min(0)
+}
+
+/// The mounted archery penalty as a `Ranged` modifier for the rider
+pub fn mounted_archery_modifier(riding_skill: i32, gait: Gait) -> Modifier {
+    Modifier::new(
+        MOUNTED_SOURCE,
+        ModifierTarget::Ranged,
+        mounted_archery_penalty(riding_skill, gait),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_check_margins() {
+        let horse = Mount::new("Bess").with_training(4);
+        assert_eq!(horse.quality(), MountQuality::Trained);
+        // 5 riding + 4 training = 9 before the roll
+        assert_eq!(
+            horse.control_check(5, ControlTrigger::Frightened, 5),
+            ControlResult::Controlled
+        );
+        assert_eq!(
+            horse.control_check(5, ControlTrigger::Wounded, 5),
+            ControlResult::Balks
+        );
+        assert_eq!(
+            horse.control_check(5, ControlTrigger::Wounded, 1),
+            ControlResult::Bolts
+        );
+        assert_eq!(
+            horse.control_check(0, ControlTrigger::Wounded, 1),
+            ControlResult::Thrown
+        );
+    }
+
+    #[test]
+    fn test_training_downtime() {
+        let mut horse = Mount::new("Bess").with_training(3);
+        // Three weeks, the middle check fails
+        let mut rolls = [10, 1, 10].into_iter();
+        assert_eq!(horse.train(4, 24, || rolls.next().unwrap()), 2);
+        assert_eq!(horse.training, 5);
+
+        let mut warhorse = Mount::new("Thunder").with_training(10);
+        assert_eq!(warhorse.train(10, 70, || 10), 0);
+        assert_eq!(warhorse.quality(), MountQuality::Warhorse);
+    }
+
+    #[test]
+    fn test_riding_skill_reduces_archery_penalty() {
+        assert_eq!(mounted_archery_penalty(0, Gait::Gallop), -6);
+        assert_eq!(mounted_archery_penalty(6, Gait::Gallop), -3);
+        assert_eq!(mounted_archery_penalty(6, Gait::Walk), 0);
+        let modifier = mounted_archery_modifier(2, Gait::Gallop);
+        assert_eq!(modifier.target, ModifierTarget::Ranged);
+        assert_eq!(modifier.value, -5);
+    }
+}
@@ -0,0 +1,403 @@
+//! Combat narration
+//!
+//! A `Narrator` turns structured combat results into prose for logs and
+//! front-ends. The stock `TemplateNarrator` picks from a `TemplatePack` of
+//! templates with slots (`{attacker}`, `{defender}`, `{weapon}`,
+//! `{location}`, `{damage}`) keyed by what happened (`Beat`). Templates whose
+//! slots can't be filled are skipped, so packs may mix plain and detailed
+//! lines. Terse and Dramatic packs are built in and ship as
+//! `data/narration.toml`; load your own with `TemplatePack::from_toml`.
+
+use crate::modules::hit_location::HitLocation;
+use crate::{CombatResult, WoundLevel};
+use rand::Rng;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// What an attack came to, from the narrator's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Beat {
+    Miss,
+    /// Hit, but armor stopped it
+    Glance,
+    Light,
+    Severe,
+    Critical,
+    /// Left dying rather than dead
+    Dying,
+    Kill,
+}
+
+impl Beat {
+    pub fn of(result: &CombatResult) -> Self {
+        if !result.hit {
+            Beat::Miss
+        } else if result.defender_died {
+            Beat::Kill
+        } else if result.defender_dying {
+            Beat::Dying
+        } else {
+            match result.wound_level {
+                None => Beat::Glance,
+                Some(WoundLevel::Light) => Beat::Light,
+                Some(WoundLevel::Severe) => Beat::Severe,
+                Some(WoundLevel::Critical) => Beat::Critical,
+            }
+        }
+    }
+
+    /// Plain line used when a pack has nothing that fits
+    fn fallback(&self) -> &'static str {
+        match self {
+            Beat::Miss => "{attacker} misses {defender}.",
+            Beat::Glance => "{attacker}'s blow glances off {defender}.",
+            Beat::Light => "{attacker} wounds {defender}.",
+            Beat::Severe => "{attacker} badly wounds {defender}.",
+            Beat::Critical => "{attacker} cuts {defender} down.",
+            Beat::Dying => "{defender} falls, dying.",
+            Beat::Kill => "{attacker} kills {defender}.",
+        }
+    }
+}
+
+impl fmt::Display for Beat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Beat::Miss => write!(f, "Miss"),
+            Beat::Glance => write!(f, "Glance"),
+            Beat::Light => write!(f, "Light"),
+            Beat::Severe => write!(f, "Severe"),
+            Beat::Critical => write!(f, "Critical"),
+            Beat::Dying => write!(f, "Dying"),
+            Beat::Kill => write!(f, "Kill"),
+        }
+    }
+}
+
+/// Everything a template can mention about one attack
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombatBeat {
+    pub beat: Beat,
+    pub attacker: String,
+    pub defender: String,
+    pub damage: i32,
+    pub weapon: Option<String>,
+    pub location: Option<HitLocation>,
+}
+
+impl CombatBeat {
+    pub fn from_result(result: &CombatResult) -> Self {
+        Self {
+            beat: Beat::of(result),
+            attacker: result.attacker.clone(),
+            defender: result.defender.clone(),
+            damage: result.damage.get(),
+            weapon: None,
+            location: result.location,
+        }
+    }
+
+    pub fn with_weapon(mut self, weapon: &str) -> Self {
+        self.weapon = Some(weapon.to_string());
+        self
+    }
+
+    pub fn at(mut self, location: HitLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    fn slot(&self, name: &str) -> Option<String> {
+        match name {
+            "attacker" => Some(self.attacker.clone()),
+            "defender" => Some(self.defender.clone()),
+            "damage" => Some(self.damage.to_string()),
+            "weapon" => self.weapon.as_ref().map(|w| w.to_lowercase()),
+            "location" => self.location.map(|l| l.to_string().to_lowercase()),
+            _ => None,
+        }
+    }
+
+    /// Fill `template`'s slots, or `None` if one is unknown or empty
+    pub fn fill(&self, template: &str) -> Option<String> {
+        let mut out = String::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let close = open + rest[open..].find('}')?;
+            out.push_str(&self.slot(&rest[open + 1..close])?);
+            rest = &rest[close + 1..];
+        }
+        out.push_str(rest);
+        Some(out)
+    }
+}
+
+/// Turns combat into prose
+pub trait Narrator {
+    fn narrate(&mut self, beat: &CombatBeat) -> String;
+}
+
+/// One line of a template pack
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Template {
+    pub beat: Beat,
+    pub text: String,
+}
+
+/// A named set of templates in one voice
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TemplatePack {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default, rename = "template"))]
+    pub templates: Vec<Template>,
+}
+
+impl TemplatePack {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            templates: Vec::new(),
+        }
+    }
+
+    pub fn with_template(mut self, beat: Beat, text: &str) -> Self {
+        self.templates.push(Template {
+            beat,
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// Short, flat reporting
+    pub fn terse() -> Self {
+        Self::new("Terse")
+            .with_template(Beat::Miss, "{attacker} misses {defender}.")
+            .with_template(Beat::Glance, "{defender}'s armor turns the blow.")
+            .with_template(Beat::Light, "{attacker} nicks {defender}.")
+            .with_template(Beat::Light, "{attacker} grazes {defender}'s {location}.")
+            .with_template(Beat::Severe, "{attacker} wounds {defender} badly.")
+            .with_template(
+                Beat::Severe,
+                "{attacker} hits {defender}'s {location} hard.",
+            )
+            .with_template(Beat::Critical, "{attacker} cripples {defender}.")
+            .with_template(Beat::Dying, "{defender} goes down, dying.")
+            .with_template(Beat::Kill, "{attacker} kills {defender}.")
+    }
+
+    /// Bloody, breathless saga prose
+    pub fn dramatic() -> Self {
+        Self::new("Dramatic")
+            .with_template(Beat::Miss, "{defender} twists aside as {attacker} lunges!")
+            .with_template(
+                Beat::Miss,
+                "{attacker}'s {weapon} hisses through empty air where {defender} stood!",
+            )
+            .with_template(
+                Beat::Glance,
+                "Steel rings on steel as {defender}'s armor drinks the blow!",
+            )
+            .with_template(Beat::Light, "{attacker} draws first blood from {defender}!")
+            .with_template(
+                Beat::Light,
+                "{attacker}'s {weapon} leaves a thin red line across {defender}'s {location}!",
+            )
+            .with_template(
+                Beat::Severe,
+                "{attacker} staggers {defender} with a brutal blow!",
+            )
+            .with_template(
+                Beat::Severe,
+                "{attacker}'s {weapon} bites deep into {defender}'s {location}!",
+            )
+            .with_template(
+                Beat::Critical,
+                "{defender} reels, broken by {attacker}'s onslaught!",
+            )
+            .with_template(
+                Beat::Critical,
+                "{attacker}'s {weapon} smashes {defender}'s {location} to ruin!",
+            )
+            .with_template(
+                Beat::Dying,
+                "{defender} crumples, lifeblood pooling in the dust!",
+            )
+            .with_template(Beat::Kill, "{attacker} strikes {defender} down for good!")
+            .with_template(
+                Beat::Kill,
+                "With a final blow to the {location}, {attacker} ends {defender}!",
+            )
+    }
+
+    /// The built-in packs
+    pub fn standard() -> Vec<Self> {
+        vec![Self::terse(), Self::dramatic()]
+    }
+
+    /// Parse a list of `[[pack]]` tables from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Vec<Self>, NarrationError> {
+        #[derive(Deserialize)]
+        struct File {
+            pack: Vec<TemplatePack>,
+        }
+        toml::from_str::<File>(source)
+            .map(|file| file.pack)
+            .map_err(|e| NarrationError::Parse(e.to_string()))
+    }
+
+    /// Templates for `beat` whose slots `beat` can fill, already filled
+    fn lines(&self, beat: &CombatBeat) -> Vec<String> {
+        self.templates
+            .iter()
+            .filter(|t| t.beat == beat.beat)
+            .filter_map(|t| beat.fill(&t.text))
+            .collect()
+    }
+}
+
+/// Built-in narration voices
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NarrationStyle {
+    #[default]
+    Terse,
+    Dramatic,
+}
+
+impl NarrationStyle {
+    pub fn pack(&self) -> TemplatePack {
+        match self {
+            NarrationStyle::Terse => TemplatePack::terse(),
+            NarrationStyle::Dramatic => TemplatePack::dramatic(),
+        }
+    }
+}
+
+/// Narrates by picking a fitting template from a pack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateNarrator {
+    pub pack: TemplatePack,
+}
+
+impl TemplateNarrator {
+    pub fn new(pack: TemplatePack) -> Self {
+        Self { pack }
+    }
+
+    pub fn with_style(style: NarrationStyle) -> Self {
+        Self::new(style.pack())
+    }
+
+    /// The fitting line at position `roll`, wrapping around
+    pub fn pick(&self, beat: &CombatBeat, roll: u32) -> String {
+        let lines = self.pack.lines(beat);
+        if lines.is_empty() {
+            return beat
+                .fill(beat.beat.fallback())
+                .unwrap_or_else(|| beat.beat.to_string());
+        }
+        lines[roll as usize % lines.len()].clone()
+    }
+}
+
+impl Default for TemplateNarrator {
+    fn default() -> Self {
+        Self::with_style(NarrationStyle::default())
+    }
+}
+
+impl Narrator for TemplateNarrator {
+    fn narrate(&mut self, beat: &CombatBeat) -> String {
+        self.pick(beat, rand::thread_rng().gen())
+    }
+}
+
+/// Errors from loading template packs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NarrationError {
+    Parse(String),
+}
+
+impl fmt::Display for NarrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NarrationError::Parse(e) => write!(f, "Invalid narration data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for NarrationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(hit: bool, wound_level: Option<WoundLevel>) -> CombatResult {
+        CombatResult {
+            attacker: "Thorgar".to_string(),
+            defender: "Roland".to_string(),
+            attack_roll: 14,
+            defense_roll: 9,
+            hit,
+            damage: crate::Damage::clamped(7),
+            wound_level,
+            defender_died: false,
+            defender_dying: false,
+            location: None,
+            #[cfg(feature = "hit-location")]
+            armor_events: Vec::new(),
+            attack_breakdown: crate::RollBreakdown::default(),
+            defense_breakdown: crate::RollBreakdown::default(),
+        }
+    }
+
+    #[test]
+    fn test_templates_fill_slots_and_skip_what_they_cannot() {
+        let severe = CombatBeat::from_result(&result(true, Some(WoundLevel::Severe)));
+        let dramatic = TemplateNarrator::with_style(NarrationStyle::Dramatic);
+
+        // Without weapon or location only the plain line fits
+        assert_eq!(
+            dramatic.pick(&severe, 1),
+            "Thorgar staggers Roland with a brutal blow!"
+        );
+        let detailed = severe
+            .clone()
+            .with_weapon("Long Sword")
+            .at(HitLocation::LeftArm);
+        assert_eq!(
+            dramatic.pick(&detailed, 1),
+            "Thorgar's long sword bites deep into Roland's left arm!"
+        );
+
+        assert_eq!(
+            TemplateNarrator::default().pick(&CombatBeat::from_result(&result(false, None)), 0),
+            "Thorgar misses Roland."
+        );
+
+        // A custom pack with no matching line falls back to plain prose
+        let mut custom =
+            TemplateNarrator::new(TemplatePack::new("Saga").with_template(Beat::Kill, "{skald}"));
+        let glance = CombatBeat::from_result(&result(true, None));
+        assert_eq!(glance.beat, Beat::Glance);
+        assert_eq!(
+            custom.narrate(&glance),
+            "Thorgar's blow glances off Roland."
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_data_file_matches_presets() {
+        let loaded = TemplatePack::from_toml(include_str!("../../data/narration.toml")).unwrap();
+        assert_eq!(loaded, TemplatePack::standard());
+        assert!(TemplatePack::from_toml("pack = 3").is_err());
+    }
+}
@@ -0,0 +1,170 @@
+//! Raising the dead mid-fight
+//!
+//! A necromancer casts a Necromancy spell at a fallen combatant's corpse to
+//! raise it as a zombie fighting for their side (see `Encounter::raise_dead`).
+//! How many zombies they can hold at once grows with their Necromancy lore.
+//! Zombies carry `Trait::undead`: slow to dodge and move, but they feel no
+//! wound penalties, never bleed and drop straight to dead rather than dying.
+
+use crate::modules::magic::{MagicBranch, MagicError};
+use crate::modules::traits::Trait;
+use crate::Character;
+use std::fmt;
+
+/// Name prefix for raised corpses ("Zombie Grimwald")
+pub const ZOMBIE: &str = "Zombie";
+
+/// Zombies a necromancer with `lore` levels of Necromancy can control at once
+pub const fn control_limit(lore: i32) -> usize {
+    if lore <= 0 {
+        0
+    } else {
+        (lore / 2 + 1) as usize
+    }
+}
+
+/// Necromancy lore level of `caster`, 0 if untrained
+pub fn necromancy_lore(caster: &Character) -> i32 {
+    caster
+        .magic
+        .as_ref()
+        .and_then(|m| m.lores.get(&MagicBranch::Necromancy))
+        .map_or(0, |lore| lore.level)
+}
+
+/// The zombie `corpse` rises as
+///
+/// It keeps the corpse's attributes, skills, gear and ancestry, loses any
+/// magic, and starts unwounded with `Trait::undead`.
+pub fn zombie(corpse: &Character) -> Character {
+    let mut zombie = Character::new(
+        &format!("{} {}", ZOMBIE, corpse.name),
+        corpse.attributes,
+        corpse.weapon_skill().get(),
+        corpse.dodge_skill().get(),
+        corpse.weapon.clone(),
+        corpse.armor.clone(),
+    )
+    .with_trait(Trait::undead());
+    zombie.ancestry.clone_from(&corpse.ancestry);
+    zombie
+}
+
+/// Errors from raising the dead
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NecromancyError {
+    /// The spell isn't a Necromancy spell
+    NotNecromancy(String),
+    /// The target isn't dead
+    NotACorpse(String),
+    /// The corpse was already raised, or isn't on the battlefield
+    NoCorpse(String),
+    /// The necromancer already controls as many zombies as they can
+    ControlLimit(usize),
+    /// The necromancer can't act this round
+    CannotAct,
+    /// No combatant in the encounter has this id
+    UnknownCombatant(usize),
+    Magic(MagicError),
+}
+
+impl fmt::Display for NecromancyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NecromancyError::NotNecromancy(spell) => {
+                write!(f, "{} is not a Necromancy spell", spell)
+            }
+            NecromancyError::NotACorpse(name) => write!(f, "{} is not dead", name),
+            NecromancyError::NoCorpse(name) => {
+                write!(f, "{}'s corpse is not there to raise", name)
+            }
+            NecromancyError::ControlLimit(limit) => {
+                write!(f, "Already controlling {} zombies", limit)
+            }
+            NecromancyError::CannotAct => write!(f, "Necromancer cannot act this round"),
+            NecromancyError::UnknownCombatant(id) => write!(f, "No combatant with id {}", id),
+            NecromancyError::Magic(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for NecromancyError {}
+
+impl From<MagicError> for NecromancyError {
+    fn from(e: MagicError) -> Self {
+        NecromancyError::Magic(e)
+    }
+}
+
+/// Check `spell_name` is a Necromancy spell the caster knows
+pub fn check_spell(caster: &Character, spell_name: &str) -> Result<(), NecromancyError> {
+    let branch = caster
+        .magic
+        .as_ref()
+        .and_then(|m| m.spells.get(spell_name))
+        .map(|learned| learned.spell.branch)
+        .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
+    if branch != MagicBranch::Necromancy {
+        return Err(NecromancyError::NotNecromancy(spell_name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::ruleset::Ruleset;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+
+    #[test]
+    fn test_zombies_ignore_wounds_and_never_linger_dying() {
+        assert_eq!(control_limit(0), 0);
+        assert_eq!(control_limit(1), 1);
+        assert_eq!(control_limit(6), 4);
+
+        let mut corpse = Character::new(
+            "Grimwald",
+            Attributes::new(7, 5, 7, 4, 4, 5, 4, 5, 3),
+            6,
+            4,
+            Weapon::long_sword(),
+            Armor::none(),
+        );
+        corpse.add_wound(WoundLevel::Critical);
+        corpse.add_wound(WoundLevel::Critical);
+        let fresh_attack = corpse.weapon_skill().get() + 5;
+        let mut risen = zombie(&corpse);
+        assert_eq!(risen.name, "Zombie Grimwald");
+        assert!(risen.can_act());
+        assert!(risen.traits.is_undead());
+
+        risen.add_wound(WoundLevel::Severe);
+        assert_eq!(risen.attack_roll_with(5), fresh_attack);
+        assert_eq!(risen.attack_breakdown(5).wound_penalty, 0);
+
+        // A killing blow ends it outright, even where the living would be dying
+        let rules = Ruleset {
+            dying_state: true,
+            ..Ruleset::new()
+        };
+        let mut hero = Character::new(
+            "Aldric",
+            Attributes::new(9, 7, 6, 5, 5, 5, 5, 5, 5),
+            9,
+            5,
+            Weapon::two_handed_sword(),
+            Armor::none(),
+        );
+        let mut dice = crate::modules::dice::ScriptedDice::new([10, 1]);
+        while risen.is_alive() {
+            crate::combat_round_with_dice(
+                &mut hero,
+                &mut risen,
+                crate::DefenseAction::Parry,
+                &rules,
+                &mut dice,
+            );
+            assert!(!risen.is_dying());
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Batch NPC generation for load tests and simulations
+//!
+//! `generate_batch` rolls up any number of varied characters from a seed:
+//! attributes, an ancestry, a role (melee, ranged or caster) with gear to
+//! match, and combat skills set by a threat level from 1 to 10. The
+//! `NpcConstraints` narrow what comes out, e.g. `melee_only()`,
+//! `with_threat(3, 5)` or `no_magic()`. The same seed and constraints always
+//! give the same batch, and every character stays within the attribute caps.
+
+use crate::modules::ancestry::Ancestry;
+use crate::modules::magic::{
+    MagicBranch, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration, SpellRange,
+};
+use crate::modules::ranged_combat::RangedWeapon;
+use crate::{Armor, Attributes, Character, Weapon};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Lowest threat level
+pub const THREAT_MIN: i32 = 1;
+/// Highest threat level
+pub const THREAT_MAX: i32 = 10;
+
+/// How a generated NPC fights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NpcRole {
+    Melee,
+    /// Bow, crossbow or javelins, with a dagger for close quarters
+    Ranged,
+    /// Spells first, a dagger as a last resort
+    Caster,
+}
+
+impl NpcRole {
+    pub const ALL: [NpcRole; 3] = [NpcRole::Melee, NpcRole::Ranged, NpcRole::Caster];
+
+    /// The role a character's gear suggests
+    pub fn of(character: &Character) -> Self {
+        if character.magic.is_some() {
+            NpcRole::Caster
+        } else if character.ranged_weapon.is_some() {
+            NpcRole::Ranged
+        } else {
+            NpcRole::Melee
+        }
+    }
+}
+
+impl fmt::Display for NpcRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NpcRole::Melee => write!(f, "Warrior"),
+            NpcRole::Ranged => write!(f, "Archer"),
+            NpcRole::Caster => write!(f, "Mage"),
+        }
+    }
+}
+
+/// How dangerous a character is, from 1 to 10
+///
+/// The average of their best offensive skill (weapon, ranged or spell) and
+/// their dodge, rounded up.
+pub fn threat_level(character: &Character) -> i32 {
+    let spell = character
+        .magic
+        .as_ref()
+        .and_then(|m| m.spells.values().map(|s| s.skill_level).max())
+        .unwrap_or(0);
+    let offense = character
+        .weapon_skill()
+        .max(character.ranged_skill.unwrap_or(character.weapon_skill()))
+        .get()
+        .max(spell);
+    ((offense + character.dodge_skill().get() + 1) / 2).clamp(THREAT_MIN, THREAT_MAX)
+}
+
+/// What `generate_batch` may produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NpcConstraints {
+    pub roles: Vec<NpcRole>,
+    pub min_threat: i32,
+    pub max_threat: i32,
+}
+
+impl NpcConstraints {
+    /// Any role at any threat level
+    pub fn new() -> Self {
+        Self {
+            roles: NpcRole::ALL.to_vec(),
+            min_threat: THREAT_MIN,
+            max_threat: THREAT_MAX,
+        }
+    }
+
+    pub fn melee_only(mut self) -> Self {
+        self.roles = vec![NpcRole::Melee];
+        self
+    }
+
+    pub fn with_roles(mut self, roles: &[NpcRole]) -> Self {
+        self.roles = roles.to_vec();
+        self
+    }
+
+    pub fn with_threat(mut self, min: i32, max: i32) -> Self {
+        self.min_threat = min;
+        self.max_threat = max;
+        self
+    }
+
+    pub fn no_magic(mut self) -> Self {
+        self.roles.retain(|&r| r != NpcRole::Caster);
+        self
+    }
+
+    /// Check `character` meets these constraints
+    pub fn allows(&self, character: &Character) -> bool {
+        self.roles.contains(&NpcRole::of(character))
+            && (self.min_threat..=self.max_threat).contains(&threat_level(character))
+    }
+
+    fn validate(&self) -> Result<(), NpcError> {
+        if self.roles.is_empty() {
+            return Err(NpcError::NoRoles);
+        }
+        if self.min_threat < THREAT_MIN
+            || self.max_threat > THREAT_MAX
+            || self.min_threat > self.max_threat
+        {
+            return Err(NpcError::ThreatRange {
+                min: self.min_threat,
+                max: self.max_threat,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for NpcConstraints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generate `n` characters meeting `constraints`, reproducibly from `seed`
+pub fn generate_batch(
+    n: usize,
+    constraints: &NpcConstraints,
+    seed: u64,
+) -> Result<Vec<Character>, NpcError> {
+    constraints.validate()?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let ancestries = Ancestry::standard();
+    Ok((1..=n)
+        .map(|i| {
+            let role = constraints.roles[rng.gen_range(0..constraints.roles.len())];
+            let threat = rng.gen_range(constraints.min_threat..=constraints.max_threat);
+            let ancestry = &ancestries[rng.gen_range(0..ancestries.len())];
+            generate_one(&mut rng, i, role, threat, ancestry)
+        })
+        .collect())
+}
+
+fn generate_one(
+    rng: &mut StdRng,
+    index: usize,
+    role: NpcRole,
+    threat: i32,
+    ancestry: &Ancestry,
+) -> Character {
+    // 3-8 leaves room for any ancestry's adjustments within 1-10
+    let mut roll = || rng.gen_range(3..=8);
+    let attributes = Attributes::new(
+        roll(),
+        roll(),
+        roll(),
+        roll(),
+        roll(),
+        roll(),
+        roll(),
+        roll(),
+        roll(),
+    );
+    // Offense at the threat level, dodge at it or one below
+    let dodge = (threat - rng.gen_range(0..=1)).max(THREAT_MIN);
+    let name = format!("{} {} {}", ancestry.name, role, index);
+    let builder = Character::builder()
+        .name(&name)
+        .attributes(attributes)
+        .ancestry(ancestry)
+        .expect("attributes leave room for ancestry adjustments")
+        .dodge_skill(dodge);
+
+    let builder = match role {
+        NpcRole::Melee => {
+            let weapons = [
+                Weapon::dagger(),
+                Weapon::long_sword(),
+                Weapon::two_handed_sword(),
+            ];
+            let armors = [Armor::leather(), Armor::chain_mail(), Armor::plate()];
+            builder
+                .weapon_skill(threat)
+                .weapon(weapons[rng.gen_range(0..weapons.len())].clone())
+                .armor(armors[rng.gen_range(0..armors.len())].clone())
+        }
+        NpcRole::Ranged => {
+            let bows = [
+                RangedWeapon::short_bow(),
+                RangedWeapon::long_bow(),
+                RangedWeapon::crossbow(),
+                RangedWeapon::javelin(),
+            ];
+            builder
+                .weapon_skill((threat - 2).max(0))
+                .weapon(Weapon::dagger())
+                .armor(Armor::leather())
+                .ranged(bows[rng.gen_range(0..bows.len())].clone(), threat)
+        }
+        NpcRole::Caster => {
+            let mut magic = MagicUser::new(attributes.empathy.get());
+            magic.add_lore(MagicBranch::Elementalism, threat);
+            magic
+                .learn_spell(fire_bolt(), threat)
+                .expect("lore matches spell level");
+            builder
+                .weapon_skill((threat - 3).max(0))
+                .weapon(Weapon::dagger())
+                .armor(Armor::none())
+                .magic(magic)
+        }
+    };
+    builder.build()
+}
+
+fn fire_bolt() -> Spell {
+    Spell {
+        name: "Fire Bolt".to_string(),
+        branch: MagicBranch::Elementalism,
+        difficulty: SpellDifficulty::Normal,
+        preparation_time: 0,
+        casting_time: 1,
+        range: SpellRange::Short(30),
+        duration: SpellDuration::Instant,
+        components: SpellComponents::default(),
+    }
+}
+
+/// Constraints that no character could meet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpcError {
+    /// Every role was ruled out
+    NoRoles,
+    /// Threat bounds outside 1-10 or reversed
+    ThreatRange { min: i32, max: i32 },
+}
+
+impl fmt::Display for NpcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NpcError::NoRoles => write!(f, "No roles left to generate"),
+            NpcError::ThreatRange { min, max } => write!(
+                f,
+                "Threat {}-{} is not within {}-{}",
+                min, max, THREAT_MIN, THREAT_MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NpcError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_is_varied_valid_and_reproducible() {
+        let constraints = NpcConstraints::new().melee_only().with_threat(3, 5);
+        let batch = generate_batch(200, &constraints, 9).unwrap();
+        assert_eq!(batch.len(), 200);
+        assert!(batch.iter().all(|c| constraints.allows(c)));
+        for c in &batch {
+            let a = &c.attributes;
+            for score in [a.strength, a.dexterity, a.constitution, a.empathy] {
+                assert!((1..=10).contains(&score.get()));
+            }
+        }
+        let weapons: std::collections::HashSet<_> =
+            batch.iter().map(|c| c.weapon.name.clone()).collect();
+        assert_eq!(weapons.len(), 3);
+
+        let again = generate_batch(200, &constraints, 9).unwrap();
+        let names = |b: &[Character]| b.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&batch), names(&again));
+        let attributes = |b: &[Character]| {
+            b.iter()
+                .map(|c| format!("{:?}", c.attributes))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(attributes(&batch), attributes(&again));
+
+        let mundane = generate_batch(100, &NpcConstraints::new().no_magic(), 1).unwrap();
+        assert!(mundane.iter().all(|c| c.magic.is_none()));
+        assert!(mundane.iter().any(|c| NpcRole::of(c) == NpcRole::Ranged));
+        let anyone = generate_batch(100, &NpcConstraints::default(), 1).unwrap();
+        assert!(anyone.iter().all(|c| NpcConstraints::new().allows(c)));
+        assert!(anyone.iter().any(|c| NpcRole::of(c) == NpcRole::Caster));
+
+        let impossible = NpcConstraints::new()
+            .with_roles(&[NpcRole::Caster])
+            .no_magic();
+        assert_eq!(
+            generate_batch(1, &impossible, 0).unwrap_err(),
+            NpcError::NoRoles
+        );
+        assert_eq!(
+            generate_batch(1, &NpcConstraints::new().with_threat(6, 4), 0).unwrap_err(),
+            NpcError::ThreatRange { min: 6, max: 4 }
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! Expected-value combat odds, computed without rolling
+//!
+//! Every attack is decided by one attacker d10 against one defender d10, so
+//! the exact odds come from the 100 possible roll pairs. `AttackOdds` gives
+//! the chance of each outcome of a single attack, `expected_damage` the mean
+//! damage, and `expected_rounds_to_kill` follows the defender's wounds (and
+//! growing penalties) through to death. `Odds::between` bundles these for UI
+//! tooltips ("63% to hit") and quick balance checks.
+
+use crate::modules::ai::recommend_defense;
+use crate::modules::modifiers::ModifierTarget;
+use crate::{is_lethal_damage, Character, DefenseAction, WoundLevel, Wounds};
+use std::collections::HashMap;
+
+/// Chance of each outcome of a single attack; the fields sum to 1.0
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AttackOdds {
+    pub miss: f32,
+    /// Hits that do 1 damage or less
+    pub graze: f32,
+    pub light: f32,
+    pub severe: f32,
+    pub critical: f32,
+    /// Damage over twice CON: killed outright
+    pub kill: f32,
+    /// Mean damage over all rolls, counting only hits that wound
+    pub expected_damage: f32,
+}
+
+impl AttackOdds {
+    /// Work out the odds for `attacker` against `defender` answering with `defense`
+    pub fn new(attacker: &Character, defender: &Character, defense: DefenseAction) -> Self {
+        odds_with_wounds(attacker, defender, defense, &defender.wounds)
+    }
+
+    pub fn hit(&self) -> f32 {
+        1.0 - self.miss
+    }
+
+    /// Chance the attack leaves any wound
+    pub fn wound(&self) -> f32 {
+        self.light + self.severe + self.critical + self.kill
+    }
+}
+
+/// Chance that an attack hits (attack roll beats the defense roll)
+pub fn hit_probability(attacker: &Character, defender: &Character, defense: DefenseAction) -> f32 {
+    AttackOdds::new(attacker, defender, defense).hit()
+}
+
+/// Expected damage per attack from `attacker` when `defender` answers with `defense`
+///
+/// Averages over every pair of d10 rolls, counting only hits that wound
+/// (more than 1 damage).
+pub fn expected_damage(attacker: &Character, defender: &Character, defense: DefenseAction) -> f32 {
+    AttackOdds::new(attacker, defender, defense).expected_damage
+}
+
+/// Expected number of attacks until `defender` dies, always defending with `defense`
+///
+/// Wound penalties are re-applied after every wound. `None` if the attacker
+/// can never kill the defender.
+pub fn expected_rounds_to_kill(
+    attacker: &Character,
+    defender: &Character,
+    defense: DefenseAction,
+) -> Option<f32> {
+    let mut memo = HashMap::new();
+    let rounds = rounds_from(attacker, defender, defense, &defender.wounds, &mut memo);
+    rounds.is_finite().then_some(rounds)
+}
+
+/// Summary of one character attacking another
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Odds {
+    /// The defense the defender is expected to choose
+    pub defense: DefenseAction,
+    pub attack: AttackOdds,
+    pub rounds_to_kill: Option<f32>,
+}
+
+impl Odds {
+    /// Odds of `attacker` against `defender`, who defends as `recommend_defense` suggests
+    pub fn between(attacker: &Character, defender: &Character) -> Self {
+        let defense = recommend_defense(defender, attacker);
+        Self {
+            defense,
+            attack: AttackOdds::new(attacker, defender, defense),
+            rounds_to_kill: expected_rounds_to_kill(attacker, defender, defense),
+        }
+    }
+}
+
+/// Odds as if the defender carried `wounds`
+fn odds_with_wounds(
+    attacker: &Character,
+    defender: &Character,
+    defense: DefenseAction,
+    wounds: &Wounds,
+) -> AttackOdds {
+    let attack = attacker.weapon_skill().get()
+        + attacker.armor.movement_penalty
+        + attacker.wounds.melee_penalty()
+        + attacker.modifiers.total(ModifierTarget::Attack);
+    let wound_penalty = match defense {
+        DefenseAction::Parry => wounds.melee_penalty(),
+        DefenseAction::Dodge => wounds.movement_penalty(),
+    };
+    let defend = defense_base(defender, defense) + wound_penalty;
+    let bonus = attacker
+        .strength_bonus()
+        .saturating_add(attacker.weapon.damage.get())
+        .saturating_add(attacker.modifiers.total(ModifierTarget::Damage))
+        .saturating_sub(defender.armor.protection.get());
+    let con = defender.attributes.constitution.get();
+
+    // Roll-pair counts: miss, graze, light, severe, critical, kill
+    let mut counts = [0u32; 6];
+    let mut total_damage = 0;
+    for attack_die in 1..=10 {
+        for defense_die in 1..=10 {
+            let margin = (attack + attack_die) - (defend + defense_die);
+            let damage = margin + bonus;
+            let outcome = if margin <= 0 {
+                0
+            } else if is_lethal_damage(damage, con) {
+                5
+            } else {
+                match WoundLevel::from_damage(damage, con) {
+                    None => 1,
+                    Some(WoundLevel::Light) => 2,
+                    Some(WoundLevel::Severe) => 3,
+                    Some(WoundLevel::Critical) => 4,
+                }
+            };
+            counts[outcome] += 1;
+            if outcome > 1 {
+                total_damage += damage;
+            }
+        }
+    }
+    let chance = |n: u32| n as f32 / 100.0;
+    AttackOdds {
+        miss: chance(counts[0]),
+        graze: chance(counts[1]),
+        light: chance(counts[2]),
+        severe: chance(counts[3]),
+        critical: chance(counts[4]),
+        kill: chance(counts[5]),
+        expected_damage: total_damage as f32 / 100.0,
+    }
+}
+
+/// Defense roll before the d10 and wound penalty, as in `Character::parry_roll` / `dodge_roll`
+fn defense_base(defender: &Character, defense: DefenseAction) -> i32 {
+    let armor = defender.armor.movement_penalty;
+    match defense {
+        DefenseAction::Parry => {
+            defender.weapon_skill().get() + armor + defender.modifiers.total(ModifierTarget::Parry)
+        }
+        DefenseAction::Dodge => {
+            defender.dodge_skill().get()
+                + armor
+                + defender.modifiers.total(ModifierTarget::Dodge)
+                + defender.modifiers.total(ModifierTarget::Movement)
+        }
+    }
+}
+
+/// Expected attacks to kill from a given wound state
+///
+/// Every wound raises the light-wound load, so states are visited in order
+/// and the recursion always terminates.
+fn rounds_from(
+    attacker: &Character,
+    defender: &Character,
+    defense: DefenseAction,
+    wounds: &Wounds,
+    memo: &mut HashMap<(i32, i32, i32), f32>,
+) -> f32 {
+    if wounds.is_dead() {
+        return 0.0;
+    }
+    let key = (wounds.light, wounds.severe, wounds.critical);
+    if let Some(&rounds) = memo.get(&key) {
+        return rounds;
+    }
+
+    let odds = odds_with_wounds(attacker, defender, defense, wounds);
+    let rounds = if odds.wound() == 0.0 {
+        f32::INFINITY
+    } else {
+        let mut next = 0.0;
+        for (chance, level) in [
+            (odds.light, WoundLevel::Light),
+            (odds.severe, WoundLevel::Severe),
+            (odds.critical, WoundLevel::Critical),
+        ] {
+            if chance > 0.0 {
+                let mut after = wounds.clone();
+                after.add_wound(level);
+                next += chance * rounds_from(attacker, defender, defense, &after, memo);
+            }
+        }
+        // Attacks that miss or graze leave the state unchanged
+        (1.0 + next) / odds.wound()
+    };
+    memo.insert(key, rounds);
+    rounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::modifiers::Modifier;
+    use crate::{Armor, Attributes, Weapon};
+
+    fn fighter(name: &str, skill: i32) -> Character {
+        Character::new(
+            name,
+            Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+            skill,
+            4,
+            Weapon::long_sword(),
+            Armor::leather(),
+        )
+    }
+
+    #[test]
+    fn test_hit_probability_of_equal_fighters() {
+        let a = fighter("Aldric", 6);
+        let b = fighter("Grimwald", 6);
+        // Equal totals: the attacker must roll strictly higher, 45 of 100 pairs
+        assert!((hit_probability(&a, &b, DefenseAction::Parry) - 0.45).abs() < 1e-4);
+
+        let odds = AttackOdds::new(&a, &b, DefenseAction::Parry);
+        let sum = odds.miss + odds.graze + odds.wound();
+        assert!((sum - 1.0).abs() < 1e-4);
+        assert!(odds.expected_damage > 0.0);
+    }
+
+    #[test]
+    fn test_rounds_to_kill() {
+        let a = fighter("Aldric", 8);
+        let b = fighter("Grimwald", 4);
+        let rounds = expected_rounds_to_kill(&a, &b, DefenseAction::Parry).unwrap();
+        let weaker = expected_rounds_to_kill(&b, &a, DefenseAction::Parry).unwrap();
+        assert!(rounds >= 1.0);
+        assert!(rounds < weaker);
+
+        let mut wall = fighter("Wall", 4);
+        wall.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        assert_eq!(
+            expected_rounds_to_kill(&a, &wall, DefenseAction::Parry),
+            None
+        );
+        assert_eq!(Odds::between(&a, &wall).attack.hit(), 0.0);
+    }
+
+    #[test]
+    fn test_wounded_defender_is_easier_to_kill() {
+        let a = fighter("Aldric", 6);
+        let mut b = fighter("Grimwald", 6);
+        let fresh = Odds::between(&a, &b);
+        b.wounds.add_wound(WoundLevel::Severe);
+        let hurt = Odds::between(&a, &b);
+        assert!(hurt.attack.hit() > fresh.attack.hit());
+        assert!(hurt.rounds_to_kill.unwrap() < fresh.rounds_to_kill.unwrap());
+    }
+}
@@ -0,0 +1,272 @@
+//! A* pathfinding over a `Battlefield`
+//!
+//! Paths respect terrain movement costs, steer around hazards when a safer
+//! route exists, and never pass through cells held by other combatants. AI
+//! code picks a `MoveGoal` (close to melee, retreat to cover, kite at range)
+//! and `plan_move` trims the route to what the mover can cover this round.
+
+use crate::modules::positioning::{Battlefield, Cover, GridPos, MoveError};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// A route from a start cell, excluding the start itself
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Path {
+    pub steps: Vec<GridPos>,
+    /// Movement points needed to walk the whole route
+    pub cost: i32,
+}
+
+impl Path {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn destination(&self) -> Option<GridPos> {
+        self.steps.last().copied()
+    }
+}
+
+/// Where an AI-controlled combatant wants to end up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveGoal {
+    /// A specific cell
+    Cell(GridPos),
+    /// Any cell adjacent to the named combatant
+    Engage(String),
+    /// The nearest cell offering at least this much cover
+    TakeCover(Cover),
+    /// Any cell at least `range` away from the named combatant
+    Kite { from: String, range: i32 },
+}
+
+/// Cheapest path for `mover` from its current cell to `to`
+pub fn find_path(field: &Battlefield, mover: &str, to: GridPos) -> Option<Path> {
+    let start = field.placement(mover)?.pos;
+    search(
+        field,
+        mover,
+        start,
+        |pos| pos == to,
+        |pos| field.distance(pos, to),
+    )
+}
+
+/// Cheapest path for `mover` toward a goal, or `None` if it can't be reached
+///
+/// Returns an empty path if the mover already satisfies the goal.
+pub fn path_to_goal(field: &Battlefield, mover: &str, goal: &MoveGoal) -> Option<Path> {
+    let start = field.placement(mover)?.pos;
+    match goal {
+        MoveGoal::Cell(to) => find_path(field, mover, *to),
+        MoveGoal::Engage(target) => {
+            let at = field.placement(target)?.pos;
+            search(
+                field,
+                mover,
+                start,
+                |pos| field.distance(pos, at) == 1,
+                |pos| (field.distance(pos, at) - 1).max(0),
+            )
+        }
+        MoveGoal::TakeCover(cover) => {
+            let wanted = cover_rank(*cover);
+            search(
+                field,
+                mover,
+                start,
+                |pos| cover_rank(field.cover_at(pos)) >= wanted,
+                |_| 0,
+            )
+        }
+        MoveGoal::Kite { from, range } => {
+            let at = field.placement(from)?.pos;
+            search(
+                field,
+                mover,
+                start,
+                |pos| field.distance(pos, at) >= *range,
+                |pos| (*range - field.distance(pos, at)).max(0),
+            )
+        }
+    }
+}
+
+/// Path toward a goal, cut short at the mover's movement allowance
+pub fn plan_move(
+    field: &Battlefield,
+    mover: &str,
+    goal: &MoveGoal,
+    allowance: i32,
+) -> Option<Path> {
+    let full = path_to_goal(field, mover, goal)?;
+    let mut plan = Path::default();
+    for pos in full.steps {
+        let cost = field.move_cost(pos)?;
+        if plan.cost + cost > allowance {
+            break;
+        }
+        plan.cost += cost;
+        plan.steps.push(pos);
+    }
+    Some(plan)
+}
+
+/// Walk a combatant along a path, returning the movement points spent
+pub fn follow(field: &mut Battlefield, mover: &str, path: &Path) -> Result<i32, MoveError> {
+    path.steps
+        .iter()
+        .try_fold(0, |spent, pos| Ok(spent + field.step(mover, *pos)?))
+}
+
+fn cover_rank(cover: Cover) -> u8 {
+    match cover {
+        Cover::None => 0,
+        Cover::Partial => 1,
+        Cover::ThreeQuarters => 2,
+        Cover::Full => 3,
+    }
+}
+
+/// Path cost of entering a cell: terrain cost plus hazard avoidance
+fn step_cost(field: &Battlefield, mover: &str, pos: GridPos) -> Option<i32> {
+    let cost = field.move_cost(pos)?;
+    match field.occupant(pos) {
+        Some(other) if other != mover => None,
+        _ => Some(cost + field.hazard_at(pos).map_or(0, |h| h.avoid_cost)),
+    }
+}
+
+fn search<G, H>(
+    field: &Battlefield,
+    mover: &str,
+    start: GridPos,
+    is_goal: G,
+    heuristic: H,
+) -> Option<Path>
+where
+    G: Fn(GridPos) -> bool,
+    H: Fn(GridPos) -> i32,
+{
+    let mut open = BinaryHeap::new();
+    let mut best: HashMap<GridPos, i32> = HashMap::new();
+    let mut came_from: HashMap<GridPos, GridPos> = HashMap::new();
+    let mut closed = HashSet::new();
+
+    best.insert(start, 0);
+    open.push(Reverse((heuristic(start), 0, start)));
+
+    while let Some(Reverse((_, g, pos))) = open.pop() {
+        if is_goal(pos) {
+            let mut steps = vec![pos];
+            let mut current = pos;
+            while let Some(prev) = came_from.get(&current) {
+                steps.push(*prev);
+                current = *prev;
+            }
+            steps.pop();
+            steps.reverse();
+            let cost = steps.iter().filter_map(|p| field.move_cost(*p)).sum();
+            return Some(Path { steps, cost });
+        }
+        if !closed.insert(pos) {
+            continue;
+        }
+        for next in field.kind.neighbors(pos) {
+            let Some(cost) = step_cost(field, mover, next) else {
+                continue;
+            };
+            let g_next = g + cost;
+            if best.get(&next).is_none_or(|&known| g_next < known) {
+                best.insert(next, g_next);
+                came_from.insert(next, pos);
+                open.push(Reverse((g_next + heuristic(next), g_next, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::positioning::{CellTerrain, Facing, GridKind, Hazard};
+
+    fn field() -> Battlefield {
+        // A wall down column 2 with a gap at the bottom
+        let mut field = Battlefield::new(GridKind::Square, 6, 5);
+        for y in 0..4 {
+            field.set_terrain(GridPos::new(2, y), CellTerrain::Wall);
+        }
+        field
+    }
+
+    #[test]
+    fn test_path_goes_around_walls_and_occupants() {
+        let mut field = field();
+        field
+            .place("Aldric", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Guard", GridPos::new(1, 4), Facing::NORTH)
+            .unwrap();
+
+        let path = find_path(&field, "Aldric", GridPos::new(4, 0)).unwrap();
+        assert!(path.steps.contains(&GridPos::new(2, 4)));
+        assert!(!path.steps.contains(&GridPos::new(1, 4)));
+        assert_eq!(path.destination(), Some(GridPos::new(4, 0)));
+        assert_eq!(path.cost, path.steps.len() as i32);
+
+        field.set_terrain(GridPos::new(2, 4), CellTerrain::Wall);
+        assert!(find_path(&field, "Aldric", GridPos::new(4, 0)).is_none());
+    }
+
+    #[test]
+    fn test_hazards_and_rough_ground_are_avoided() {
+        let mut field = Battlefield::new(GridKind::Hex, 5, 3)
+            .with_terrain(GridPos::new(1, 1), CellTerrain::Difficult)
+            .with_hazard(GridPos::new(2, 1), Hazard::new("Burning oil", 10));
+        field
+            .place("Aldric", GridPos::new(0, 1), Facing::NORTH)
+            .unwrap();
+
+        let path = find_path(&field, "Aldric", GridPos::new(4, 1)).unwrap();
+        assert!(!path.steps.contains(&GridPos::new(1, 1)));
+        assert!(!path.steps.contains(&GridPos::new(2, 1)));
+    }
+
+    #[test]
+    fn test_goals_and_movement_allowance() {
+        let mut field = Battlefield::new(GridKind::Square, 10, 3)
+            .with_cover(GridPos::new(0, 2), Cover::ThreeQuarters);
+        field
+            .place("Archer", GridPos::new(3, 1), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Orc", GridPos::new(8, 1), Facing::NORTH)
+            .unwrap();
+
+        let engage = plan_move(&field, "Orc", &MoveGoal::Engage("Archer".into()), 2).unwrap();
+        assert_eq!(engage.steps.len(), 2);
+        assert_eq!(
+            field.distance(engage.destination().unwrap(), GridPos::new(3, 1)),
+            3
+        );
+
+        let cover = path_to_goal(&field, "Archer", &MoveGoal::TakeCover(Cover::Partial)).unwrap();
+        assert_eq!(cover.destination(), Some(GridPos::new(0, 2)));
+
+        let kite = MoveGoal::Kite {
+            from: "Orc".into(),
+            range: 7,
+        };
+        let retreat = plan_move(&field, "Archer", &kite, 5).unwrap();
+        assert_eq!(retreat.steps.len(), 2);
+        assert_eq!(follow(&mut field, "Archer", &retreat), Ok(2));
+        assert_eq!(
+            field.distance(field.placement("Archer").unwrap().pos, GridPos::new(8, 1)),
+            7
+        );
+        assert!(path_to_goal(&field, "Archer", &kite).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,251 @@
+//! Permanent injuries from healed critical wounds
+//!
+//! When a Critical wound to the head or a limb heals, roll d10 on an
+//! `InjuryTable`. A result leaves a lasting `PermanentInjury` whose effects are
+//! registered as modifiers on the character. The table is plain data: start
+//! from `InjuryTable::standard()`, replace entries, or load one with serde.
+
+use crate::d10;
+use crate::modules::dice::Dice;
+use crate::modules::hit_location::HitLocation;
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One lasting effect of a permanent injury
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InjuryEffect {
+    pub target: ModifierTarget,
+    pub value: i32,
+}
+
+/// A lasting injury such as a lost finger or a limp
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PermanentInjury {
+    pub name: String,
+    /// Where the injury was suffered (filled in when rolled)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub location: Option<HitLocation>,
+    pub effects: Vec<InjuryEffect>,
+}
+
+impl PermanentInjury {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            location: None,
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn with_effect(mut self, target: ModifierTarget, value: i32) -> Self {
+        self.effects.push(InjuryEffect { target, value });
+        self
+    }
+
+    /// Modifier source name, e.g. "Limp (Left Leg)"
+    pub fn source(&self) -> String {
+        match self.location {
+            Some(location) => format!("{} ({})", self.name, location),
+            None => self.name.clone(),
+        }
+    }
+
+    /// The injury's effects as modifiers for the character's modifier list
+    pub fn modifiers(&self) -> Vec<Modifier> {
+        let source = self.source();
+        self.effects
+            .iter()
+            .map(|e| Modifier::new(&source, e.target, e.value))
+            .collect()
+    }
+}
+
+/// A row of the injury table: d10 results `min..=max` on the listed locations
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InjuryEntry {
+    pub locations: Vec<HitLocation>,
+    pub min: i32,
+    pub max: i32,
+    pub injury: PermanentInjury,
+}
+
+/// Data-driven permanent injury table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InjuryTable {
+    pub entries: Vec<InjuryEntry>,
+}
+
+impl InjuryTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default table: results of 1-5 heal without lasting harm
+    pub fn standard() -> Self {
+        use HitLocation::*;
+        use ModifierTarget::*;
+
+        let head = [Head];
+        let arms = [LeftArm, RightArm];
+        let legs = [LeftLeg, RightLeg];
+        Self::new()
+            .with_entry(
+                &head,
+                6,
+                7,
+                PermanentInjury::new("Ringing Ears").with_effect(Perception, -1),
+            )
+            .with_entry(
+                &head,
+                8,
+                9,
+                PermanentInjury::new("Lost Eye")
+                    .with_effect(Perception, -2)
+                    .with_effect(Ranged, -2),
+            )
+            .with_entry(
+                &head,
+                10,
+                10,
+                PermanentInjury::new("Cracked Skull")
+                    .with_effect(Casting, -2)
+                    .with_effect(Perception, -1),
+            )
+            .with_entry(
+                &arms,
+                6,
+                7,
+                PermanentInjury::new("Lost Finger").with_effect(Attack, -1),
+            )
+            .with_entry(
+                &arms,
+                8,
+                9,
+                PermanentInjury::new("Stiff Arm")
+                    .with_effect(Attack, -1)
+                    .with_effect(Parry, -1),
+            )
+            .with_entry(
+                &arms,
+                10,
+                10,
+                PermanentInjury::new("Crippled Hand")
+                    .with_effect(Attack, -2)
+                    .with_effect(Parry, -2),
+            )
+            .with_entry(
+                &legs,
+                6,
+                7,
+                PermanentInjury::new("Bad Knee").with_effect(Movement, -1),
+            )
+            .with_entry(
+                &legs,
+                8,
+                9,
+                PermanentInjury::new("Limp").with_effect(Movement, -2),
+            )
+            .with_entry(
+                &legs,
+                10,
+                10,
+                PermanentInjury::new("Lame Leg").with_effect(Movement, -3),
+            )
+    }
+
+    pub fn with_entry(
+        mut self,
+        locations: &[HitLocation],
+        min: i32,
+        max: i32,
+        injury: PermanentInjury,
+    ) -> Self {
+        self.entries.push(InjuryEntry {
+            locations: locations.to_vec(),
+            min,
+            max,
+            injury,
+        });
+        self
+    }
+
+    /// Drop every entry covering `location`, e.g. before supplying custom ones
+    pub fn clear_location(&mut self, location: HitLocation) {
+        for entry in &mut self.entries {
+            entry.locations.retain(|l| *l != location);
+        }
+        self.entries.retain(|e| !e.locations.is_empty());
+    }
+
+    /// Look up the injury for a d10 `roll` at `location` (first matching row wins)
+    pub fn lookup(&self, location: HitLocation, roll: i32) -> Option<PermanentInjury> {
+        self.entries
+            .iter()
+            .find(|e| e.locations.contains(&location) && (e.min..=e.max).contains(&roll))
+            .map(|e| {
+                let mut injury = e.injury.clone();
+                injury.location = Some(location);
+                injury
+            })
+    }
+
+    /// Roll d10 on the table for `location`
+    pub fn roll(&self, location: HitLocation) -> Option<PermanentInjury> {
+        self.lookup(location, d10())
+    }
+
+    /// Roll on the table for `location` with a d10 from `dice`
+    pub fn roll_with(&self, location: HitLocation, dice: &mut dyn Dice) -> Option<PermanentInjury> {
+        self.lookup(location, dice.d10())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_table_lookup() {
+        let table = InjuryTable::standard();
+
+        assert_eq!(table.lookup(HitLocation::LeftLeg, 3), None);
+        assert_eq!(table.lookup(HitLocation::Torso, 10), None);
+
+        let limp = table.lookup(HitLocation::RightLeg, 8).unwrap();
+        assert_eq!(limp.name, "Limp");
+        assert_eq!(limp.source(), "Limp (Right Leg)");
+        assert_eq!(
+            limp.modifiers(),
+            vec![Modifier::new(
+                "Limp (Right Leg)",
+                ModifierTarget::Movement,
+                -2
+            )]
+        );
+    }
+
+    #[test]
+    fn test_table_override() {
+        let mut table = InjuryTable::standard();
+        table.clear_location(HitLocation::Head);
+        assert_eq!(table.lookup(HitLocation::Head, 9), None);
+        assert!(table.lookup(HitLocation::LeftArm, 9).is_some());
+
+        let table = table.with_entry(
+            &[HitLocation::Head],
+            1,
+            10,
+            PermanentInjury::new("Notched Ear"),
+        );
+        assert_eq!(
+            table.lookup(HitLocation::Head, 1).unwrap().name,
+            "Notched Ear"
+        );
+    }
+}
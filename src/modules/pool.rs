@@ -0,0 +1,142 @@
+//! Reusable encounter state for long simulations
+//!
+//! Monte Carlo runs fight the same roster thousands of times. Building a
+//! fresh `Encounter` for each fight clones every character and regrows every
+//! list. An `EncounterPool` keeps one working encounter and resets it from a
+//! template between fights with `Encounter::reset_to`, so names, modifier
+//! lists, turn queues and the result log keep the buffers they grew in
+//! earlier fights. That trims allocations per fight by under a tenth (see
+//! `benches/BASELINES.md`); most come from the results and wounds the fight
+//! itself creates.
+
+use crate::modules::dice::DiceMode;
+use crate::modules::encounter::Encounter;
+use crate::CombatResult;
+
+/// A template encounter refought with reused buffers
+#[derive(Debug)]
+pub struct EncounterPool {
+    template: Encounter,
+    encounter: Encounter,
+    results: Vec<CombatResult>,
+    fights: u64,
+}
+
+impl EncounterPool {
+    /// Pool fights set up like `template` (combatants, battlefield, rules)
+    pub fn new(template: Encounter) -> Self {
+        Self {
+            encounter: template.clone(),
+            template,
+            results: Vec::new(),
+            fights: 0,
+        }
+    }
+
+    pub fn template(&self) -> &Encounter {
+        &self.template
+    }
+
+    /// Fights started so far
+    pub fn fights(&self) -> u64 {
+        self.fights
+    }
+
+    /// Reset the working encounter to the template, rolling with `dice`
+    pub fn next_fight(&mut self, dice: DiceMode) -> &mut Encounter {
+        self.encounter.reset_to(&self.template);
+        self.encounter.rules.dice = dice;
+        self.results.clear();
+        self.fights += 1;
+        &mut self.encounter
+    }
+
+    /// Fight a fresh copy of the template for at most `max_rounds`
+    ///
+    /// Returns the winning team, or `None` for a draw or a fight still
+    /// going when time ran out. The attacks are kept in `results`.
+    pub fn run_fight(&mut self, dice: DiceMode, max_rounds: u32) -> Option<u32> {
+        self.next_fight(dice);
+        let encounter = &mut self.encounter;
+        while !encounter.is_over() && encounter.round <= max_rounds {
+            self.results.extend(encounter.step());
+        }
+        encounter.winner()
+    }
+
+    /// The encounter as the last fight left it
+    pub fn encounter(&self) -> &Encounter {
+        &self.encounter
+    }
+
+    /// Attacks made in the last fight, in order
+    pub fn results(&self) -> &[CombatResult] {
+        &self.results
+    }
+}
+
+#[cfg(all(test, feature = "magic", feature = "ranged"))]
+mod tests {
+    use super::*;
+    use crate::modules::npc::{generate_batch, NpcConstraints};
+
+    fn skirmish() -> Encounter {
+        let mut encounter = Encounter::new();
+        let roster = generate_batch(6, &NpcConstraints::new().melee_only(), 5).unwrap();
+        for (i, npc) in roster.into_iter().enumerate() {
+            encounter.add(npc, i as u32 % 2 + 1);
+        }
+        encounter
+    }
+
+    #[test]
+    fn test_pooled_fights_match_fresh_ones_and_keep_buffers() {
+        let mut pool = EncounterPool::new(skirmish());
+        for seed in 0..5 {
+            let dice = DiceMode::seeded(seed);
+            let winner = pool.run_fight(dice, 30);
+
+            let mut fresh = skirmish();
+            fresh.rules.dice = dice;
+            let mut results = Vec::new();
+            while !fresh.is_over() && fresh.round <= 30 {
+                results.extend(fresh.step());
+            }
+            assert_eq!(winner, fresh.winner());
+            let log = |r: &[CombatResult]| r.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+            assert_eq!(log(pool.results()), log(&results));
+            let wounds = |e: &Encounter| {
+                e.combatants
+                    .iter()
+                    .map(|c| c.character.injuries.active.len())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(wounds(pool.encounter()), wounds(&fresh));
+        }
+        assert_eq!(pool.fights(), 5);
+
+        // Resetting reuses the names' and the log's storage
+        let names: Vec<*const u8> = pool
+            .encounter()
+            .combatants
+            .iter()
+            .map(|c| c.character.name.as_ptr())
+            .collect();
+        let log = pool.results().as_ptr();
+        pool.run_fight(DiceMode::seeded(0), 30);
+        let after: Vec<*const u8> = pool
+            .encounter()
+            .combatants
+            .iter()
+            .map(|c| c.character.name.as_ptr())
+            .collect();
+        assert_eq!(names, after);
+        assert_eq!(pool.results().as_ptr(), log);
+        assert!(pool
+            .encounter()
+            .combatants
+            .iter()
+            .zip(&pool.template().combatants)
+            .all(|(c, t)| c.character.name == t.character.name));
+    }
+}
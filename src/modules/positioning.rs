@@ -0,0 +1,767 @@
+//! Battlefield positioning on square or hex grids
+//!
+//! A `Battlefield` places combatants on cells of either grid kind, chosen with
+//! `GridKind`. Square grids have eight facings (diagonals count as one step);
+//! hex grids use axial coordinates with six facings. Cells carry terrain with a
+//! movement cost, and facing decides which side of a defender an attack hits.
+//! Cells can also be raised: fighters on higher ground get a melee edge, and
+//! shots carry farther downhill than up. Doorways and narrow passes are
+//! chokepoints that only a few attackers can press at once, and the field's
+//! `Lighting` decides how much darkness hampers those without darkvision.
+//! Anti-magic zones dampen or null spellcasting in the cells they cover.
+
+use crate::modules::hit_location::AttackDirection;
+use crate::modules::modifiers::ModifierTarget;
+use crate::Character;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Movement points per round before armor, wounds and modifiers
+pub const BASE_MOVEMENT: i32 = 5;
+/// Meters across one cell, for converting weapon and spell ranges
+pub const CELL_METERS: i32 = 2;
+/// Melee attack bonus for striking from higher ground (penalty from below)
+pub const HIGH_GROUND_BONUS: i32 = 1;
+/// Extra damage for charging into melee from higher ground
+pub const DOWNHILL_CHARGE_BONUS: i32 = 2;
+/// Meters of range gained per level shooting downhill (lost shooting uphill)
+pub const RANGE_PER_LEVEL: i32 = 10;
+/// Parry bonus for a shield bearer beside a shielded ally
+pub const SHIELD_WALL_BONUS: i32 = 2;
+
+const SQUARE_OFFSETS: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Axial offsets for a flat-topped hex grid
+const HEX_OFFSETS: [(i32, i32); 6] = [(0, -1), (1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0)];
+
+/// Grid geometry used by a battlefield
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GridKind {
+    #[default]
+    Square,
+    /// Axial coordinates: `x` is the column (q), `y` the row (r)
+    Hex,
+}
+
+impl GridKind {
+    fn offsets(&self) -> &'static [(i32, i32)] {
+        match self {
+            GridKind::Square => &SQUARE_OFFSETS,
+            GridKind::Hex => &HEX_OFFSETS,
+        }
+    }
+
+    /// Number of facings: 8 on squares, 6 on hexes
+    pub fn direction_count(&self) -> u8 {
+        self.offsets().len() as u8
+    }
+
+    /// Steps between two cells
+    pub fn distance(&self, a: GridPos, b: GridPos) -> i32 {
+        let dx = b.x - a.x;
+        let dy = b.y - a.y;
+        match self {
+            GridKind::Square => dx.abs().max(dy.abs()),
+            GridKind::Hex => (dx.abs() + dy.abs() + (dx + dy).abs()) / 2,
+        }
+    }
+
+    /// The adjacent cell in a facing direction
+    pub fn step(&self, pos: GridPos, facing: Facing) -> GridPos {
+        let (dx, dy) = self.offsets()[(facing.0 % self.direction_count()) as usize];
+        GridPos::new(pos.x + dx, pos.y + dy)
+    }
+
+    /// All adjacent cells, clockwise from north
+    pub fn neighbors(&self, pos: GridPos) -> Vec<GridPos> {
+        self.offsets()
+            .iter()
+            .map(|(dx, dy)| GridPos::new(pos.x + dx, pos.y + dy))
+            .collect()
+    }
+
+    /// Cells on the straight line from `a` to `b`, both ends included
+    pub fn line(&self, a: GridPos, b: GridPos) -> Vec<GridPos> {
+        let n = self.distance(a, b);
+        if n == 0 {
+            return vec![a];
+        }
+        (0..=n)
+            .map(|i| {
+                let t = i as f32 / n as f32;
+                // Nudge off exact cell edges so ties break consistently
+                let x = a.x as f32 + (b.x - a.x) as f32 * t + 1e-4;
+                let y = a.y as f32 + (b.y - a.y) as f32 * t + 1e-4;
+                match self {
+                    GridKind::Square => GridPos::new(x.round() as i32, y.round() as i32),
+                    GridKind::Hex => hex_round(x, y),
+                }
+            })
+            .collect()
+    }
+
+    /// Facing that points most directly from one cell toward another
+    pub fn direction_to(&self, from: GridPos, to: GridPos) -> Facing {
+        let best = (0..self.direction_count())
+            .min_by_key(|&d| self.distance(self.step(from, Facing(d)), to))
+            .unwrap_or(0);
+        Facing(best)
+    }
+
+    /// Which side of a defender facing `facing` at `defender` an attack from `attacker` hits
+    pub fn attack_direction(
+        &self,
+        defender: GridPos,
+        facing: Facing,
+        attacker: GridPos,
+    ) -> AttackDirection {
+        let n = self.direction_count() as i32;
+        let toward = self.direction_to(defender, attacker).0 as i32;
+        let relative = (toward - facing.0 as i32).rem_euclid(n);
+        let angle = relative * 360 / n;
+        match angle {
+            0..=60 | 300..=360 => AttackDirection::Front,
+            61..=120 => AttackDirection::Right,
+            240..=299 => AttackDirection::Left,
+            _ => AttackDirection::Back,
+        }
+    }
+}
+
+/// Round fractional axial coordinates to the nearest hex (via cube coordinates)
+fn hex_round(q: f32, r: f32) -> GridPos {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    GridPos::new(rq as i32, rr as i32)
+}
+
+/// A cell coordinate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridPos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl GridPos {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl fmt::Display for GridPos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Direction index, clockwise from north (0 = north)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Facing(pub u8);
+
+impl Facing {
+    pub const NORTH: Facing = Facing(0);
+
+    pub fn turn_right(self, kind: GridKind) -> Self {
+        Facing((self.0 + 1) % kind.direction_count())
+    }
+
+    pub fn turn_left(self, kind: GridKind) -> Self {
+        let n = kind.direction_count();
+        Facing((self.0 + n - 1) % n)
+    }
+
+    pub fn opposite(self, kind: GridKind) -> Self {
+        let n = kind.direction_count();
+        Facing((self.0 + n / 2) % n)
+    }
+}
+
+/// Cover types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Cover {
+    None,
+    Partial,       // -2 to hit, 1/2 body exposed
+    ThreeQuarters, // -4 to hit, 1/4 body exposed
+    Full,          // -8 to hit, only small parts visible
+}
+
+impl Cover {
+    pub fn modifier(&self) -> i32 {
+        match self {
+            Cover::None => 0,
+            Cover::Partial => -2,
+            Cover::ThreeQuarters => -4,
+            Cover::Full => -8,
+        }
+    }
+}
+
+/// Ground in a battlefield cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CellTerrain {
+    #[default]
+    Open,
+    Rough,
+    Difficult,
+    Water,
+    Wall,
+}
+
+impl CellTerrain {
+    /// Movement points to enter the cell, or `None` if impassable
+    pub fn move_cost(&self) -> Option<i32> {
+        match self {
+            CellTerrain::Open => Some(1),
+            CellTerrain::Rough => Some(2),
+            CellTerrain::Difficult | CellTerrain::Water => Some(3),
+            CellTerrain::Wall => None,
+        }
+    }
+}
+
+/// How well lit the whole battlefield is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Lighting {
+    #[default]
+    Bright,
+    /// Dusk, torchlight or fog: half the darkness penalty
+    Dim,
+    Dark,
+}
+
+impl Lighting {
+    /// Penalty to `character`'s sight-based rolls in this light
+    pub fn sight_penalty(&self, character: &Character) -> i32 {
+        match self {
+            Lighting::Bright => 0,
+            Lighting::Dim => character.darkness_penalty() / 2,
+            Lighting::Dark => character.darkness_penalty(),
+        }
+    }
+}
+
+impl fmt::Display for Lighting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lighting::Bright => write!(f, "Bright"),
+            Lighting::Dim => write!(f, "Dim"),
+            Lighting::Dark => write!(f, "Dark"),
+        }
+    }
+}
+
+/// A dangerous but passable cell (fire, caltrops, a pit's edge)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hazard {
+    pub name: String,
+    /// Extra path cost so pathfinding avoids the cell when it can
+    pub avoid_cost: i32,
+}
+
+impl Hazard {
+    pub fn new(name: &str, avoid_cost: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            avoid_cost,
+        }
+    }
+}
+
+/// Magic suppression over part of the battlefield
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AntiMagic {
+    /// Casting at this penalty; effects and summons carry on
+    Dampened(i32),
+    /// Casting fails, active effects are suspended and summons wink out
+    Null,
+}
+
+/// Where a combatant stands and which way they face
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Placement {
+    pub pos: GridPos,
+    pub facing: Facing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    UnknownCombatant(String),
+    OutOfBounds(GridPos),
+    NotAdjacent(GridPos),
+    Impassable(GridPos),
+    Occupied(GridPos),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::UnknownCombatant(name) => write!(f, "No combatant named {}", name),
+            MoveError::OutOfBounds(pos) => write!(f, "{} is off the battlefield", pos),
+            MoveError::NotAdjacent(pos) => write!(f, "{} is not adjacent", pos),
+            MoveError::Impassable(pos) => write!(f, "{} is impassable", pos),
+            MoveError::Occupied(pos) => write!(f, "{} is occupied", pos),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// A rectangular grid of cells with terrain and placed combatants
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Battlefield {
+    pub kind: GridKind,
+    pub width: i32,
+    pub height: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lighting: Lighting,
+    #[cfg_attr(feature = "serde", serde(with = "cell_map"))]
+    terrain: HashMap<GridPos, CellTerrain>,
+    #[cfg_attr(feature = "serde", serde(with = "cell_map"))]
+    hazards: HashMap<GridPos, Hazard>,
+    #[cfg_attr(feature = "serde", serde(with = "cell_map"))]
+    cover: HashMap<GridPos, Cover>,
+    #[cfg_attr(feature = "serde", serde(with = "cell_map"))]
+    elevation: HashMap<GridPos, i32>,
+    #[cfg_attr(feature = "serde", serde(with = "cell_map"))]
+    chokepoints: HashMap<GridPos, usize>,
+    #[cfg_attr(feature = "serde", serde(default, with = "cell_map"))]
+    anti_magic: HashMap<GridPos, AntiMagic>,
+    placements: BTreeMap<String, Placement>,
+}
+
+/// Per-cell maps as sorted `[pos, value]` lists, since most formats only
+/// allow string keys
+#[cfg(feature = "serde")]
+mod cell_map {
+    use super::GridPos;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        map: &HashMap<GridPos, T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut cells: Vec<(&GridPos, &T)> = map.iter().collect();
+        cells.sort_by_key(|(pos, _)| **pos);
+        serializer.collect_seq(cells)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<GridPos, T>, D::Error> {
+        Vec::<(GridPos, T)>::deserialize(deserializer).map(|cells| cells.into_iter().collect())
+    }
+}
+
+impl Battlefield {
+    pub fn new(kind: GridKind, width: i32, height: i32) -> Self {
+        Self {
+            kind,
+            width,
+            height,
+            lighting: Lighting::Bright,
+            terrain: HashMap::new(),
+            hazards: HashMap::new(),
+            cover: HashMap::new(),
+            elevation: HashMap::new(),
+            chokepoints: HashMap::new(),
+            anti_magic: HashMap::new(),
+            placements: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = lighting;
+        self
+    }
+
+    pub fn with_terrain(mut self, pos: GridPos, terrain: CellTerrain) -> Self {
+        self.set_terrain(pos, terrain);
+        self
+    }
+
+    pub fn set_terrain(&mut self, pos: GridPos, terrain: CellTerrain) {
+        self.terrain.insert(pos, terrain);
+    }
+
+    pub fn terrain_at(&self, pos: GridPos) -> CellTerrain {
+        self.terrain.get(&pos).copied().unwrap_or_default()
+    }
+
+    pub fn with_hazard(mut self, pos: GridPos, hazard: Hazard) -> Self {
+        self.hazards.insert(pos, hazard);
+        self
+    }
+
+    pub fn hazard_at(&self, pos: GridPos) -> Option<&Hazard> {
+        self.hazards.get(&pos)
+    }
+
+    /// Mark a cell as giving cover to whoever stands in it
+    pub fn with_cover(mut self, pos: GridPos, cover: Cover) -> Self {
+        self.cover.insert(pos, cover);
+        self
+    }
+
+    pub fn cover_at(&self, pos: GridPos) -> Cover {
+        self.cover.get(&pos).copied().unwrap_or(Cover::None)
+    }
+
+    /// Raise (or lower) a cell by a number of levels
+    pub fn with_elevation(mut self, pos: GridPos, level: i32) -> Self {
+        self.set_elevation(pos, level);
+        self
+    }
+
+    pub fn set_elevation(&mut self, pos: GridPos, level: i32) {
+        self.elevation.insert(pos, level);
+    }
+
+    /// Height of a cell in levels (0 for flat ground)
+    pub fn elevation_at(&self, pos: GridPos) -> i32 {
+        self.elevation.get(&pos).copied().unwrap_or(0)
+    }
+
+    /// Mark a doorway or pass where at most `width` attackers can engage whoever holds it
+    pub fn with_chokepoint(mut self, pos: GridPos, width: usize) -> Self {
+        self.chokepoints.insert(pos, width.max(1));
+        self
+    }
+
+    /// Attackers that can engage a defender standing in this cell, if limited
+    pub fn chokepoint_at(&self, pos: GridPos) -> Option<usize> {
+        self.chokepoints.get(&pos).copied()
+    }
+
+    /// Cover every cell within `radius` of `center` with an anti-magic zone
+    pub fn with_anti_magic(mut self, center: GridPos, radius: i32, zone: AntiMagic) -> Self {
+        let cells: Vec<GridPos> = self
+            .cells()
+            .filter(|&pos| self.distance(center, pos) <= radius)
+            .collect();
+        for pos in cells {
+            self.anti_magic.insert(pos, zone);
+        }
+        self
+    }
+
+    pub fn anti_magic_at(&self, pos: GridPos) -> Option<AntiMagic> {
+        self.anti_magic.get(&pos).copied()
+    }
+
+    /// The anti-magic zone a placed combatant stands in, if any
+    pub fn anti_magic_for(&self, name: &str) -> Option<AntiMagic> {
+        self.placement(name)
+            .and_then(|placement| self.anti_magic_at(placement.pos))
+    }
+
+    /// Melee attack modifier for `attacker` from the height difference to `defender`
+    pub fn high_ground(&self, attacker: &str, defender: &str) -> i32 {
+        let (Some(a), Some(d)) = (self.placement(attacker), self.placement(defender)) else {
+            return 0;
+        };
+        HIGH_GROUND_BONUS * (self.elevation_at(a.pos) - self.elevation_at(d.pos)).signum()
+    }
+
+    /// Effective meters from `shooter` to `target` for ranged weapons
+    ///
+    /// Each level the shooter stands above the target shortens the shot by
+    /// `RANGE_PER_LEVEL`, and each level below lengthens it, never below the
+    /// flat distance of one cell.
+    pub fn ranged_distance(&self, shooter: &str, target: &str) -> Option<i32> {
+        let from = self.placement(shooter)?.pos;
+        let to = self.placement(target)?.pos;
+        let drop = self.elevation_at(from) - self.elevation_at(to);
+        let meters = self.distance(from, to) * CELL_METERS;
+        Some((meters - drop * RANGE_PER_LEVEL).max(meters.min(CELL_METERS)))
+    }
+
+    /// All in-bounds cells
+    pub fn cells(&self) -> impl Iterator<Item = GridPos> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| GridPos::new(x, y)))
+    }
+
+    pub fn in_bounds(&self, pos: GridPos) -> bool {
+        (0..self.width).contains(&pos.x) && (0..self.height).contains(&pos.y)
+    }
+
+    /// An in-bounds cell on the outer ring, where combatants can leave the field
+    pub fn is_edge(&self, pos: GridPos) -> bool {
+        self.in_bounds(pos)
+            && (pos.x == 0 || pos.y == 0 || pos.x == self.width - 1 || pos.y == self.height - 1)
+    }
+
+    /// Cost to enter a cell, or `None` if it is off the grid or impassable
+    pub fn move_cost(&self, pos: GridPos) -> Option<i32> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        self.terrain_at(pos).move_cost()
+    }
+
+    pub fn distance(&self, a: GridPos, b: GridPos) -> i32 {
+        self.kind.distance(a, b)
+    }
+
+    /// No wall stands between two cells (the end cells themselves don't block)
+    pub fn line_of_sight(&self, a: GridPos, b: GridPos) -> bool {
+        let line = self.kind.line(a, b);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|&pos| self.terrain_at(pos) != CellTerrain::Wall)
+    }
+
+    pub fn occupant(&self, pos: GridPos) -> Option<&str> {
+        self.placements
+            .iter()
+            .find(|(_, p)| p.pos == pos)
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn placement(&self, name: &str) -> Option<Placement> {
+        self.placements.get(name).copied()
+    }
+
+    pub fn placements(&self) -> impl Iterator<Item = (&str, Placement)> {
+        self.placements.iter().map(|(n, p)| (n.as_str(), *p))
+    }
+
+    /// Put a combatant on the field (or move them there directly)
+    pub fn place(&mut self, name: &str, pos: GridPos, facing: Facing) -> Result<(), MoveError> {
+        self.check_enterable(name, pos)?;
+        self.placements
+            .insert(name.to_string(), Placement { pos, facing });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Placement> {
+        self.placements.remove(name)
+    }
+
+    /// Move a combatant one cell, facing the way they moved; returns the cost
+    pub fn step(&mut self, name: &str, to: GridPos) -> Result<i32, MoveError> {
+        let from = self
+            .placement(name)
+            .ok_or_else(|| MoveError::UnknownCombatant(name.to_string()))?
+            .pos;
+        if self.distance(from, to) != 1 {
+            return Err(MoveError::NotAdjacent(to));
+        }
+        let cost = self.check_enterable(name, to)?;
+        let facing = self.kind.direction_to(from, to);
+        self.placements
+            .insert(name.to_string(), Placement { pos: to, facing });
+        Ok(cost)
+    }
+
+    pub fn face(&mut self, name: &str, facing: Facing) -> Result<(), MoveError> {
+        let placement = self
+            .placements
+            .get_mut(name)
+            .ok_or_else(|| MoveError::UnknownCombatant(name.to_string()))?;
+        placement.facing = Facing(facing.0 % self.kind.direction_count());
+        Ok(())
+    }
+
+    /// Side of `defender` that an attack from `attacker` strikes
+    pub fn attack_direction(&self, attacker: &str, defender: &str) -> Option<AttackDirection> {
+        let a = self.placement(attacker)?;
+        let d = self.placement(defender)?;
+        Some(self.kind.attack_direction(d.pos, d.facing, a.pos))
+    }
+
+    pub(crate) fn check_enterable(&self, name: &str, pos: GridPos) -> Result<i32, MoveError> {
+        if !self.in_bounds(pos) {
+            return Err(MoveError::OutOfBounds(pos));
+        }
+        let cost = self
+            .terrain_at(pos)
+            .move_cost()
+            .ok_or(MoveError::Impassable(pos))?;
+        match self.occupant(pos) {
+            Some(other) if other != name => Err(MoveError::Occupied(pos)),
+            _ => Ok(cost),
+        }
+    }
+}
+
+/// Movement points a character has each round (at least 1)
+pub fn movement_allowance(character: &Character) -> i32 {
+    (BASE_MOVEMENT
+        + character.armor.movement_penalty
+        + character.wounds.movement_penalty()
+        + character.modifiers.total(ModifierTarget::Movement))
+    .max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances_per_grid_kind() {
+        let a = GridPos::new(0, 0);
+        let b = GridPos::new(3, 2);
+        assert_eq!(GridKind::Square.distance(a, b), 3);
+        assert_eq!(GridKind::Hex.distance(a, b), 5);
+        assert_eq!(GridKind::Hex.distance(a, GridPos::new(2, -2)), 2);
+        assert_eq!(GridKind::Hex.neighbors(a).len(), 6);
+        assert!(GridKind::Hex
+            .neighbors(a)
+            .iter()
+            .all(|n| GridKind::Hex.distance(a, *n) == 1));
+    }
+
+    #[test]
+    fn test_hex_facing_and_attack_direction() {
+        let hex = GridKind::Hex;
+        let facing = Facing::NORTH;
+        assert_eq!(facing.turn_left(hex), Facing(5));
+        assert_eq!(facing.opposite(hex), Facing(3));
+
+        let defender = GridPos::new(2, 2);
+        let front = hex.step(defender, Facing(0));
+        let right = hex.step(defender, Facing(2));
+        let behind = hex.step(defender, Facing(3));
+        let left = hex.step(defender, Facing(4));
+        assert_eq!(
+            hex.attack_direction(defender, facing, front),
+            AttackDirection::Front
+        );
+        assert_eq!(
+            hex.attack_direction(defender, facing, right),
+            AttackDirection::Right
+        );
+        assert_eq!(
+            hex.attack_direction(defender, facing, behind),
+            AttackDirection::Back
+        );
+        assert_eq!(
+            hex.attack_direction(defender, facing, left),
+            AttackDirection::Left
+        );
+    }
+
+    #[test]
+    fn test_stepping_pays_terrain_costs() {
+        let mut field = Battlefield::new(GridKind::Hex, 5, 5)
+            .with_terrain(GridPos::new(1, 0), CellTerrain::Rough)
+            .with_terrain(GridPos::new(0, 1), CellTerrain::Wall);
+        field
+            .place("Aldric", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Grimwald", GridPos::new(2, 0), Facing::NORTH)
+            .unwrap();
+
+        assert_eq!(field.step("Aldric", GridPos::new(1, 0)), Ok(2));
+        assert_eq!(field.placement("Aldric").unwrap().facing, Facing(2));
+        assert_eq!(
+            field.step("Aldric", GridPos::new(2, 0)),
+            Err(MoveError::Occupied(GridPos::new(2, 0)))
+        );
+        assert_eq!(
+            field.step("Aldric", GridPos::new(3, 0)),
+            Err(MoveError::NotAdjacent(GridPos::new(3, 0)))
+        );
+        assert_eq!(
+            field.attack_direction("Aldric", "Grimwald"),
+            Some(AttackDirection::Front)
+        );
+        field.face("Grimwald", Facing(1)).unwrap();
+        assert_eq!(
+            field.attack_direction("Aldric", "Grimwald"),
+            Some(AttackDirection::Left)
+        );
+        assert_eq!(field.move_cost(GridPos::new(0, 1)), None);
+    }
+
+    #[test]
+    fn test_lines_and_line_of_sight() {
+        let square = GridKind::Square;
+        assert_eq!(
+            square.line(GridPos::new(0, 0), GridPos::new(3, 0)),
+            vec![
+                GridPos::new(0, 0),
+                GridPos::new(1, 0),
+                GridPos::new(2, 0),
+                GridPos::new(3, 0)
+            ]
+        );
+        let hex_line = GridKind::Hex.line(GridPos::new(0, 0), GridPos::new(3, -3));
+        assert_eq!(hex_line.len(), 4);
+        assert_eq!(hex_line[1], GridPos::new(1, -1));
+
+        let field =
+            Battlefield::new(square, 6, 6).with_terrain(GridPos::new(2, 2), CellTerrain::Wall);
+        assert!(!field.line_of_sight(GridPos::new(0, 0), GridPos::new(4, 4)));
+        assert!(field.line_of_sight(GridPos::new(0, 0), GridPos::new(4, 0)));
+        // A wall only blocks what is behind it
+        assert!(field.line_of_sight(GridPos::new(0, 0), GridPos::new(2, 2)));
+    }
+
+    #[test]
+    fn test_elevation_modifiers() {
+        let mut field = Battlefield::new(GridKind::Square, 30, 3)
+            .with_elevation(GridPos::new(0, 0), 2)
+            .with_elevation(GridPos::new(1, 0), 1);
+        field
+            .place("Archer", GridPos::new(0, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Spearman", GridPos::new(1, 0), Facing::NORTH)
+            .unwrap();
+        field
+            .place("Bandit", GridPos::new(25, 0), Facing::NORTH)
+            .unwrap();
+
+        assert_eq!(field.elevation_at(GridPos::new(5, 5)), 0);
+        assert_eq!(field.high_ground("Archer", "Spearman"), HIGH_GROUND_BONUS);
+        assert_eq!(field.high_ground("Spearman", "Archer"), -HIGH_GROUND_BONUS);
+        assert_eq!(field.high_ground("Spearman", "Nobody"), 0);
+
+        // 50m on the flat, shortened by two levels downhill and lengthened uphill
+        assert_eq!(field.ranged_distance("Archer", "Bandit"), Some(30));
+        assert_eq!(field.ranged_distance("Bandit", "Archer"), Some(70));
+        assert_eq!(field.ranged_distance("Archer", "Spearman"), Some(2));
+    }
+
+    #[test]
+    fn test_chokepoints() {
+        let field = Battlefield::new(GridKind::Hex, 5, 5)
+            .with_chokepoint(GridPos::new(2, 2), 1)
+            .with_chokepoint(GridPos::new(3, 3), 0);
+        assert_eq!(field.chokepoint_at(GridPos::new(2, 2)), Some(1));
+        assert_eq!(field.chokepoint_at(GridPos::new(3, 3)), Some(1));
+        assert_eq!(field.chokepoint_at(GridPos::new(0, 0)), None);
+    }
+}
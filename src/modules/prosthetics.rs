@@ -0,0 +1,166 @@
+//! Prosthetics and compensating equipment
+//!
+//! A peg leg, hook or crutch restores partial function to a severed or
+//! disabled location. Fitted to a `LocationalDamage` it replaces the location's
+//! penalty with a smaller one; fitted to a `Character` it registers its
+//! residual effects as modifiers.
+
+use crate::modules::hit_location::HitLocation;
+#[cfg(feature = "hit-location")]
+use crate::modules::hit_location::LocationalDamage;
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use crate::modules::permanent_injury::InjuryEffect;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A device compensating for a lost or disabled body location
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Prosthetic {
+    pub name: String,
+    /// Locations the device can be fitted to
+    pub fits: Vec<HitLocation>,
+    /// Only usable on a severed location (a peg leg), not merely a disabled one
+    pub requires_severed: bool,
+    /// Location penalty while fitted, replacing the severed/disabled penalty
+    pub location_penalty: i32,
+    /// Residual effects on the wearer
+    pub effects: Vec<InjuryEffect>,
+}
+
+impl Prosthetic {
+    pub fn new(name: &str, fits: &[HitLocation], location_penalty: i32) -> Self {
+        Self {
+            name: name.to_string(),
+            fits: fits.to_vec(),
+            requires_severed: false,
+            location_penalty,
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn replacing_severed(mut self) -> Self {
+        self.requires_severed = true;
+        self
+    }
+
+    pub fn with_effect(mut self, target: ModifierTarget, value: i32) -> Self {
+        self.effects.push(InjuryEffect { target, value });
+        self
+    }
+
+    /// Wooden leg for a severed leg: walks, but slowly
+    pub fn peg_leg() -> Self {
+        Self::new(
+            "Peg Leg",
+            &[HitLocation::LeftLeg, HitLocation::RightLeg],
+            -2,
+        )
+        .replacing_severed()
+        .with_effect(ModifierTarget::Movement, -2)
+    }
+
+    /// Hook for a severed arm: can bind a blade but not wield one
+    pub fn hook() -> Self {
+        Self::new("Hook", &[HitLocation::LeftArm, HitLocation::RightArm], -2)
+            .replacing_severed()
+            .with_effect(ModifierTarget::Parry, -1)
+    }
+
+    /// Crutch for a disabled leg or a limp; occupies a hand
+    pub fn crutch() -> Self {
+        Self::new("Crutch", &[HitLocation::LeftLeg, HitLocation::RightLeg], -2)
+            .with_effect(ModifierTarget::Movement, 1)
+            .with_effect(ModifierTarget::Attack, -1)
+    }
+
+    /// The device's effects as modifiers for the character's modifier list
+    pub fn modifiers(&self) -> Vec<Modifier> {
+        self.effects
+            .iter()
+            .map(|e| Modifier::new(&self.name, e.target, e.value))
+            .collect()
+    }
+
+    /// Check that the device can be fitted to this location in its current state
+    #[cfg(feature = "hit-location")]
+    pub fn check_fit(&self, location: &LocationalDamage) -> Result<(), ProstheticError> {
+        if !self.fits.contains(&location.location) {
+            return Err(ProstheticError::WrongLocation(location.location));
+        }
+        if self.requires_severed && !location.severed {
+            return Err(ProstheticError::LocationIntact(location.location));
+        }
+        if !location.severed && !location.disabled {
+            return Err(ProstheticError::LocationIntact(location.location));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProstheticError {
+    WrongLocation(HitLocation),
+    LocationIntact(HitLocation),
+}
+
+impl fmt::Display for ProstheticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProstheticError::WrongLocation(location) => {
+                write!(f, "Device does not fit the {}", location)
+            }
+            ProstheticError::LocationIntact(location) => {
+                write!(f, "The {} does not need this device", location)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProstheticError {}
+
+#[cfg(all(test, feature = "hit-location"))]
+mod tests {
+    use super::*;
+    use crate::modules::hit_location::WoundSeverity;
+
+    fn severed_leg() -> LocationalDamage {
+        let mut leg = LocationalDamage::new(HitLocation::LeftLeg);
+        leg.add_wound(WoundSeverity::Critical);
+        leg.add_wound(WoundSeverity::Critical);
+        leg
+    }
+
+    #[test]
+    fn test_peg_leg_restores_partial_function() {
+        let mut leg = severed_leg();
+        assert_eq!(leg.penalty(), -999);
+
+        leg.fit_prosthetic(Prosthetic::peg_leg()).unwrap();
+        assert!(leg.is_functional());
+        assert_eq!(leg.penalty(), -2);
+        assert!(leg.severed);
+    }
+
+    #[test]
+    fn test_fit_validation() {
+        let mut arm = LocationalDamage::new(HitLocation::RightArm);
+        assert_eq!(
+            arm.fit_prosthetic(Prosthetic::peg_leg()),
+            Err(ProstheticError::WrongLocation(HitLocation::RightArm))
+        );
+        assert_eq!(
+            arm.fit_prosthetic(Prosthetic::hook()),
+            Err(ProstheticError::LocationIntact(HitLocation::RightArm))
+        );
+
+        // A crutch helps a disabled leg, which a peg leg can't replace
+        let mut leg = LocationalDamage::new(HitLocation::RightLeg);
+        leg.add_wound(WoundSeverity::Critical);
+        assert!(leg.fit_prosthetic(Prosthetic::peg_leg()).is_err());
+        leg.fit_prosthetic(Prosthetic::crutch()).unwrap();
+        assert_eq!(leg.penalty(), -2);
+    }
+}
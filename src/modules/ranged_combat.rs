@@ -1,5 +1,14 @@
 //! Ranged combat mechanics based on Draft RPG Section 4.21
 
+pub use crate::modules::ancestry::TargetSize;
+use crate::modules::dice::Dice;
+use crate::modules::exhaustion::DODGE_EXHAUSTION;
+use crate::modules::modifiers::ModifierTarget;
+pub use crate::modules::positioning::Cover;
+use crate::modules::ruleset::Ruleset;
+pub use crate::modules::supplies::AmmoKind;
+use crate::modules::supplies::Supplies;
+use crate::{resolve_hit, Character, CombatResult};
 use std::fmt;
 
 #[cfg(feature = "serde")]
@@ -15,6 +24,9 @@ pub struct RangedWeapon {
     pub max_range: i32,         // meters
     pub preparation_time: i32,  // segments
     pub rate_of_fire: i32,      // shots per round (usually 1-3)
+    /// Points of the target's armor protection each hit ignores
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub penetration: i32,
 }
 
 impl RangedWeapon {
@@ -26,6 +38,7 @@ impl RangedWeapon {
             max_range: 100,
             preparation_time: 3,
             rate_of_fire: 1,
+            penetration: 0,
         }
     }
 
@@ -37,6 +50,7 @@ impl RangedWeapon {
             max_range: 120,
             preparation_time: 3,
             rate_of_fire: 1,
+            penetration: 0,
         }
     }
 
@@ -48,6 +62,7 @@ impl RangedWeapon {
             max_range: 100,
             preparation_time: 6, // Takes longer to reload
             rate_of_fire: 1,
+            penetration: 2,
         }
     }
 
@@ -59,6 +74,7 @@ impl RangedWeapon {
             max_range: 80,
             preparation_time: 1,
             rate_of_fire: 3,
+            penetration: 2,
         }
     }
 
@@ -70,6 +86,7 @@ impl RangedWeapon {
             max_range: 200,
             preparation_time: 2,
             rate_of_fire: 2,
+            penetration: 3,
         }
     }
 
@@ -81,9 +98,15 @@ impl RangedWeapon {
             max_range: 40,
             preparation_time: 1,
             rate_of_fire: 1,
+            penetration: 0,
         }
     }
 
+    pub fn with_penetration(mut self, penetration: i32) -> Self {
+        self.penetration = penetration;
+        self
+    }
+
     /// Calculate distance modifier for attack roll
     pub fn distance_modifier(&self, distance: i32) -> i32 {
         if distance <= self.point_blank_range {
@@ -107,29 +130,17 @@ impl RangedWeapon {
     pub fn in_range(&self, distance: i32) -> bool {
         distance <= self.max_range
     }
-}
-
-/// Target size modifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum TargetSize {
-    Tiny,     // -4 (rat, small bird)
-    Small,    // -2 (cat, small dog)
-    Medium,   // 0 (human)
-    Large,    // +2 (horse, car)
-    Huge,     // +4 (dragon, tank)
-    Gigantic, // +6 (whale, building)
-}
 
-impl TargetSize {
-    pub fn modifier(&self) -> i32 {
-        match self {
-            TargetSize::Tiny => -4,
-            TargetSize::Small => -2,
-            TargetSize::Medium => 0,
-            TargetSize::Large => 2,
-            TargetSize::Huge => 4,
-            TargetSize::Gigantic => 6,
+    /// Kind of ammunition the weapon uses
+    pub fn ammo_kind(&self) -> AmmoKind {
+        if self.name.contains("Crossbow") {
+            AmmoKind::Bolt
+        } else if self.name.contains("Bow") {
+            AmmoKind::Arrow
+        } else if self.name == "Javelin" {
+            AmmoKind::Javelin
+        } else {
+            AmmoKind::Bullet
         }
     }
 }
@@ -204,6 +215,21 @@ impl RangedAttackState {
         self.weapon_ready = true;
         self.shots_remaining = weapon.rate_of_fire;
     }
+
+    /// Reload from carried supplies, taking up to one round's worth of ammunition
+    pub fn reload_from(
+        &mut self,
+        weapon: &RangedWeapon,
+        supplies: &mut Supplies,
+    ) -> Result<(), RangedCombatError> {
+        let taken = supplies.take_ammo(weapon.ammo_kind(), weapon.rate_of_fire.max(0) as u32);
+        if taken == 0 {
+            return Err(RangedCombatError::NoAmmunition);
+        }
+        self.weapon_ready = true;
+        self.shots_remaining = taken as i32;
+        Ok(())
+    }
 }
 
 impl Default for RangedAttackState {
@@ -212,27 +238,6 @@ impl Default for RangedAttackState {
     }
 }
 
-/// Cover types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum Cover {
-    None,
-    Partial,       // -2 to hit, 1/2 body exposed
-    ThreeQuarters, // -4 to hit, 1/4 body exposed
-    Full,          // -8 to hit, only small parts visible
-}
-
-impl Cover {
-    pub fn modifier(&self) -> i32 {
-        match self {
-            Cover::None => 0,
-            Cover::Partial => -2,
-            Cover::ThreeQuarters => -4,
-            Cover::Full => -8,
-        }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RangedCombatError {
@@ -253,6 +258,9 @@ impl fmt::Display for RangedCombatError {
 
 impl std::error::Error for RangedCombatError {}
 
+/// Margin a penetrating missile must beat the defense by to pass through
+pub const OVER_PENETRATION_MARGIN: i32 = 5;
+
 /// Calculate total modifier for ranged attack
 pub fn calculate_ranged_modifiers(
     distance: i32,
@@ -269,6 +277,104 @@ pub fn calculate_ranged_modifiers(
     distance_mod + size_mod + cover_mod + aiming_mod
 }
 
+/// Shoot at a target `distance` meters away, taking the d10s from `dice`
+///
+/// Missiles can only be dodged, which tires the target under
+/// `Ruleset::exhaustion`. The weapon's damage replaces the strength bonus and
+/// melee weapon damage, and its penetration cancels that much of the target's
+/// armor. Returns `None` if the shooter carries no ranged weapon or the target
+/// is out of range.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(shooter = %shooter.name, target = %target.name, distance)
+    )
+)]
+pub fn ranged_attack_with_dice(
+    shooter: &Character,
+    target: &mut Character,
+    distance: i32,
+    cover: Cover,
+    ruleset: &Ruleset,
+    dice: &mut dyn Dice,
+) -> Option<CombatResult> {
+    let weapon = shooter.ranged_weapon.as_ref()?;
+    if !weapon.in_range(distance) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(weapon = %weapon.name, "target out of range");
+        return None;
+    }
+    let modifier = calculate_ranged_modifiers(
+        distance,
+        target.size(),
+        cover,
+        weapon,
+        &RangedAttackState::new(),
+    );
+    let mut attack = shooter.ranged_breakdown(dice.d10());
+    attack.other = attack.other.saturating_add(modifier);
+    let defense = target.dodge_breakdown(dice.d10());
+    let mut result = resolve_hit(
+        &shooter.name,
+        &weapon.name,
+        damage_bonus(shooter, weapon, target),
+        target,
+        attack.total(),
+        defense.total(),
+        None,
+        ruleset,
+    );
+    result.attack_breakdown = attack;
+    result.defense_breakdown = defense;
+    if ruleset.exhaustion {
+        target.exert(DODGE_EXHAUSTION);
+    }
+    Some(result)
+}
+
+/// Carry a hit from a penetrating weapon on into whoever stands behind the target
+///
+/// Only with `Ruleset::over_penetration`, and only when `first` beat the
+/// defense by more than `OVER_PENETRATION_MARGIN`. The missile strikes
+/// `behind` undodged, with its margin cut by `OVER_PENETRATION_MARGIN`.
+pub fn over_penetrate(
+    shooter: &Character,
+    first: &CombatResult,
+    behind: &mut Character,
+    ruleset: &Ruleset,
+) -> Option<CombatResult> {
+    let weapon = shooter.ranged_weapon.as_ref()?;
+    let margin = first.attack_roll - first.defense_roll;
+    if !ruleset.over_penetration || weapon.penetration <= 0 || margin <= OVER_PENETRATION_MARGIN {
+        return None;
+    }
+    let mut result = resolve_hit(
+        &shooter.name,
+        &weapon.name,
+        damage_bonus(shooter, weapon, behind),
+        behind,
+        first.attack_roll - OVER_PENETRATION_MARGIN,
+        first.defense_roll,
+        None,
+        ruleset,
+    );
+    result.attack_breakdown = first.attack_breakdown;
+    result.attack_breakdown.other -= OVER_PENETRATION_MARGIN;
+    result.defense_breakdown = first.defense_breakdown;
+    Some(result)
+}
+
+/// Weapon damage plus damage modifiers, plus the armor the missile punches through
+fn damage_bonus(shooter: &Character, weapon: &RangedWeapon, target: &Character) -> i32 {
+    let pierced = weapon.penetration.clamp(0, target.armor.protection.get());
+    weapon
+        .damage
+        .saturating_add(shooter.modifiers.total(ModifierTarget::Damage))
+        .saturating_add(pierced)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -383,4 +489,50 @@ mod tests {
         let rifle = RangedWeapon::rifle();
         assert_eq!(rifle.max_range, 200); // Long range
     }
+
+    #[test]
+    fn test_penetration_and_over_penetration() {
+        use crate::modules::dice::AverageDice;
+        use crate::{Armor, Attributes, Weapon};
+
+        let attrs = Attributes::new(5, 5, 8, 5, 5, 5, 5, 5, 5);
+        let shooter = Character::new("Vesna", attrs, 3, 3, Weapon::dagger(), Armor::none())
+            .with_ranged(RangedWeapon::crossbow(), 9);
+        let blunt = shooter
+            .clone()
+            .with_ranged(RangedWeapon::crossbow().with_penetration(0), 9);
+        let target = || Character::new("Hale", attrs, 3, 3, Weapon::dagger(), Armor::chain_mail());
+        let rules = Ruleset::default();
+
+        let pierced = ranged_attack_with_dice(
+            &shooter,
+            &mut target(),
+            10,
+            Cover::None,
+            &rules,
+            &mut AverageDice,
+        )
+        .unwrap();
+        let stopped = ranged_attack_with_dice(
+            &blunt,
+            &mut target(),
+            10,
+            Cover::None,
+            &rules,
+            &mut AverageDice,
+        )
+        .unwrap();
+        assert!(pierced.attack_roll - pierced.defense_roll > OVER_PENETRATION_MARGIN);
+        assert_eq!(pierced.damage.get(), stopped.damage.get() + 2);
+
+        assert!(over_penetrate(&shooter, &pierced, &mut target(), &rules).is_none());
+        let rules = rules.with_over_penetration(true);
+        let through = over_penetrate(&shooter, &pierced, &mut target(), &rules).unwrap();
+        assert_eq!(
+            through.attack_roll,
+            pierced.attack_roll - OVER_PENETRATION_MARGIN
+        );
+        assert!(through.hit);
+        assert!(over_penetrate(&blunt, &stopped, &mut target(), &rules).is_none());
+    }
 }
@@ -0,0 +1,177 @@
+//! Reputation and faction standing
+//!
+//! `Reputation` tracks a score per named faction, moved by encounter outcomes
+//! such as killing, sparing or looting its members. Campaign layers query the
+//! resulting `Standing` to gate prices or decide whether a faction is hostile.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const MIN_REPUTATION: i32 = -100;
+pub const MAX_REPUTATION: i32 = 100;
+
+/// Outcome of an encounter involving a faction's members
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReputationEvent {
+    Killed,
+    Spared,
+    Looted,
+    Aided,
+    CompletedTask,
+    Betrayed,
+}
+
+impl ReputationEvent {
+    pub fn reputation_change(&self) -> i32 {
+        match self {
+            ReputationEvent::Killed => -20,
+            ReputationEvent::Spared => 5,
+            ReputationEvent::Looted => -10,
+            ReputationEvent::Aided => 10,
+            ReputationEvent::CompletedTask => 15,
+            ReputationEvent::Betrayed => -40,
+        }
+    }
+}
+
+/// How a faction regards the party
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Standing {
+    Hostile,
+    Unfriendly,
+    Neutral,
+    Friendly,
+    Allied,
+}
+
+impl Standing {
+    pub fn from_score(score: i32) -> Self {
+        match score {
+            i32::MIN..=-50 => Standing::Hostile,
+            -49..=-15 => Standing::Unfriendly,
+            -14..=14 => Standing::Neutral,
+            15..=49 => Standing::Friendly,
+            _ => Standing::Allied,
+        }
+    }
+
+    /// Price multiplier when trading with this faction
+    pub fn price_multiplier(&self) -> f32 {
+        match self {
+            Standing::Hostile => 2.0,
+            Standing::Unfriendly => 1.25,
+            Standing::Neutral => 1.0,
+            Standing::Friendly => 0.9,
+            Standing::Allied => 0.75,
+        }
+    }
+}
+
+impl fmt::Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Standing::Hostile => write!(f, "Hostile"),
+            Standing::Unfriendly => write!(f, "Unfriendly"),
+            Standing::Neutral => write!(f, "Neutral"),
+            Standing::Friendly => write!(f, "Friendly"),
+            Standing::Allied => write!(f, "Allied"),
+        }
+    }
+}
+
+/// Reputation scores per faction; unknown factions start at 0
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Reputation {
+    pub scores: BTreeMap<String, i32>,
+}
+
+impl Reputation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a faction with a starting score
+    pub fn with_faction(mut self, faction: impl Into<String>, score: i32) -> Self {
+        self.scores
+            .insert(faction.into(), score.clamp(MIN_REPUTATION, MAX_REPUTATION));
+        self
+    }
+
+    pub fn score(&self, faction: &str) -> i32 {
+        self.scores.get(faction).copied().unwrap_or(0)
+    }
+
+    /// Adjust a faction's score directly, returning the new score
+    pub fn adjust(&mut self, faction: &str, delta: i32) -> i32 {
+        let score = self.scores.entry(faction.to_string()).or_insert(0);
+        *score = (*score + delta).clamp(MIN_REPUTATION, MAX_REPUTATION);
+        *score
+    }
+
+    /// Record an encounter outcome, returning the new standing
+    pub fn record(&mut self, faction: &str, event: ReputationEvent) -> Standing {
+        Standing::from_score(self.adjust(faction, event.reputation_change()))
+    }
+
+    pub fn standing(&self, faction: &str) -> Standing {
+        Standing::from_score(self.score(faction))
+    }
+
+    pub fn is_hostile(&self, faction: &str) -> bool {
+        self.standing(faction) == Standing::Hostile
+    }
+
+    /// Price of goods bought from a faction after standing is applied
+    pub fn price(&self, faction: &str, base: u32) -> u32 {
+        (base as f32 * self.standing(faction).price_multiplier()).round() as u32
+    }
+
+    /// Factions at or above the given standing
+    pub fn factions_at_least(&self, standing: Standing) -> Vec<&str> {
+        self.scores
+            .iter()
+            .filter(|(_, s)| Standing::from_score(**s) >= standing)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encounter_outcomes_move_standing() {
+        let mut rep = Reputation::new().with_faction("Town Guard", 20);
+        assert_eq!(rep.standing("Town Guard"), Standing::Friendly);
+        assert_eq!(rep.standing("Bandits"), Standing::Neutral);
+
+        rep.record("Bandits", ReputationEvent::Killed);
+        assert_eq!(
+            rep.record("Bandits", ReputationEvent::Looted),
+            Standing::Unfriendly
+        );
+        rep.record("Bandits", ReputationEvent::Killed);
+        assert!(rep.is_hostile("Bandits"));
+
+        rep.record("Town Guard", ReputationEvent::Betrayed);
+        assert_eq!(rep.score("Town Guard"), -20);
+    }
+
+    #[test]
+    fn test_scores_clamp_and_gate_prices() {
+        let mut rep = Reputation::new().with_faction("Merchants", 500);
+        assert_eq!(rep.score("Merchants"), MAX_REPUTATION);
+        assert_eq!(rep.price("Merchants", 100), 75);
+
+        rep.adjust("Cult", -30);
+        assert_eq!(rep.price("Cult", 100), 125);
+        assert_eq!(rep.factions_at_least(Standing::Neutral), vec!["Merchants"]);
+    }
+}
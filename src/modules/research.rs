@@ -0,0 +1,238 @@
+//! Spell research: inventing new spells during downtime
+//!
+//! A caster designs a `Spell` whose range and duration fit a budget set by
+//! their lore in its branch; easier spells cost more of the budget. The
+//! design is then researched one week at a time, and once enough weekly
+//! checks succeed the spell is added to the caster's grimoire.
+
+use crate::modules::magic::{
+    MagicBranch, MagicError, MagicUser, Spell, SpellDifficulty, SpellDuration, SpellRange,
+};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Target for a week's research check (lore + empathy + roll)
+pub const RESEARCH_TARGET: i32 = 14;
+/// Skill level a freshly invented spell is known at
+pub const INVENTED_SPELL_LEVEL: i32 = 1;
+
+/// Design points a spell uses: its range, duration and ease of casting
+pub fn design_cost(spell: &Spell) -> i32 {
+    let range = match spell.range {
+        SpellRange::Personal | SpellRange::Touch => 0,
+        SpellRange::Short(_) => 1,
+        SpellRange::Medium(_) => 2,
+        SpellRange::Long(_) => 3,
+        SpellRange::Unlimited => 5,
+    };
+    let duration = match spell.duration {
+        SpellDuration::Instant => 0,
+        SpellDuration::Rounds(_) | SpellDuration::Concentration => 1,
+        SpellDuration::Minutes(_) => 2,
+        SpellDuration::Hours(_) => 3,
+        SpellDuration::Permanent => 5,
+    };
+    let ease = match spell.difficulty {
+        SpellDifficulty::Hard => 0,
+        SpellDifficulty::Normal => 1,
+        SpellDifficulty::Easy => 2,
+    };
+    range + duration + ease
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ResearchProgress {
+    Advanced,
+    NoProgress,
+    /// The spell has been added to the grimoire
+    Complete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResearchError {
+    LoreNotKnown(MagicBranch),
+    /// The design uses more points than the caster's lore level allows
+    OverBudget {
+        cost: i32,
+        budget: i32,
+    },
+    AlreadyKnown(String),
+    /// Research on this spell is already finished
+    Finished,
+    Magic(MagicError),
+}
+
+impl fmt::Display for ResearchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResearchError::LoreNotKnown(branch) => write!(f, "Lore not known: {}", branch),
+            ResearchError::OverBudget { cost, budget } => {
+                write!(f, "Design costs {} points, lore allows {}", cost, budget)
+            }
+            ResearchError::AlreadyKnown(name) => write!(f, "Spell already known: {}", name),
+            ResearchError::Finished => write!(f, "Research is already finished"),
+            ResearchError::Magic(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ResearchError {}
+
+impl From<MagicError> for ResearchError {
+    fn from(e: MagicError) -> Self {
+        ResearchError::Magic(e)
+    }
+}
+
+/// An in-progress spell design
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ResearchProject {
+    pub spell: Spell,
+    /// Successful weeks needed: the design cost, at least one
+    pub required: i32,
+    pub successes: i32,
+    pub weeks: i32,
+    pub complete: bool,
+}
+
+impl ResearchProject {
+    /// Start researching `spell`, checking its design against the caster's lore
+    pub fn new(researcher: &MagicUser, spell: Spell) -> Result<Self, ResearchError> {
+        if researcher.spells.contains_key(&spell.name) {
+            return Err(ResearchError::AlreadyKnown(spell.name));
+        }
+        let budget = researcher
+            .lores
+            .get(&spell.branch)
+            .ok_or(ResearchError::LoreNotKnown(spell.branch))?
+            .level;
+        let cost = design_cost(&spell);
+        if cost > budget {
+            return Err(ResearchError::OverBudget { cost, budget });
+        }
+        Ok(Self {
+            spell,
+            required: cost.max(1),
+            successes: 0,
+            weeks: 0,
+            complete: false,
+        })
+    }
+
+    /// Spend a week researching; on the final success the spell is learned
+    pub fn research_week(
+        &mut self,
+        researcher: &mut MagicUser,
+        roll: i32,
+    ) -> Result<ResearchProgress, ResearchError> {
+        if self.complete {
+            return Err(ResearchError::Finished);
+        }
+        let lore = researcher
+            .lores
+            .get(&self.spell.branch)
+            .ok_or(ResearchError::LoreNotKnown(self.spell.branch))?
+            .level;
+        self.weeks += 1;
+        if lore + researcher.empathy + roll < RESEARCH_TARGET {
+            return Ok(ResearchProgress::NoProgress);
+        }
+        self.successes += 1;
+        if self.successes < self.required {
+            return Ok(ResearchProgress::Advanced);
+        }
+        researcher.learn_spell(self.spell.clone(), INVENTED_SPELL_LEVEL)?;
+        self.complete = true;
+        Ok(ResearchProgress::Complete)
+    }
+
+    /// Weeks of successful research still needed
+    pub fn remaining(&self) -> i32 {
+        (self.required - self.successes).max(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::magic::SpellComponents;
+
+    fn ward(range: SpellRange, duration: SpellDuration) -> Spell {
+        Spell {
+            name: "Ember Ward".to_string(),
+            branch: MagicBranch::Elementalism,
+            difficulty: SpellDifficulty::Normal,
+            preparation_time: 5,
+            casting_time: 1,
+            range,
+            duration,
+            components: SpellComponents::default(),
+        }
+    }
+
+    fn scholar(lore: i32) -> MagicUser {
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Elementalism, lore);
+        magic
+    }
+
+    #[test]
+    fn test_design_budget() {
+        let spell = ward(SpellRange::Medium(50), SpellDuration::Minutes(10));
+        assert_eq!(design_cost(&spell), 5);
+
+        assert_eq!(
+            ResearchProject::new(&scholar(4), spell.clone()).unwrap_err(),
+            ResearchError::OverBudget { cost: 5, budget: 4 }
+        );
+        assert_eq!(
+            ResearchProject::new(&scholar(5), spell).unwrap().required,
+            5
+        );
+        assert_eq!(
+            ResearchProject::new(
+                &MagicUser::new(6),
+                ward(SpellRange::Touch, SpellDuration::Instant)
+            )
+            .unwrap_err(),
+            ResearchError::LoreNotKnown(MagicBranch::Elementalism)
+        );
+    }
+
+    #[test]
+    fn test_weekly_research_adds_spell_to_grimoire() {
+        let mut mage = scholar(3);
+        let spell = ward(SpellRange::Touch, SpellDuration::Rounds(5));
+        let mut project = ResearchProject::new(&mage, spell).unwrap();
+        assert_eq!(project.required, 2);
+
+        // 3 lore + 6 empathy + roll vs 14
+        assert_eq!(
+            project.research_week(&mut mage, 4),
+            Ok(ResearchProgress::NoProgress)
+        );
+        assert_eq!(
+            project.research_week(&mut mage, 5),
+            Ok(ResearchProgress::Advanced)
+        );
+        assert!(!mage.spells.contains_key("Ember Ward"));
+        assert_eq!(
+            project.research_week(&mut mage, 9),
+            Ok(ResearchProgress::Complete)
+        );
+        assert_eq!(project.weeks, 3);
+        assert_eq!(mage.spells["Ember Ward"].skill_level, INVENTED_SPELL_LEVEL);
+        assert_eq!(
+            project.research_week(&mut mage, 9),
+            Err(ResearchError::Finished)
+        );
+        assert!(matches!(
+            ResearchProject::new(&mage, ward(SpellRange::Touch, SpellDuration::Instant)),
+            Err(ResearchError::AlreadyKnown(_))
+        ));
+    }
+}
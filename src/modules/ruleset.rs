@@ -0,0 +1,112 @@
+//! Optional rule toggles for combat resolution
+//!
+//! A `Ruleset` collects the optional and variant rules a table plays with.
+//! `Ruleset::default()` is the plain Draft 0.4 rules used by `combat_round`;
+//! pass a customized one to `combat_round_with_rules`.
+
+use crate::modules::dice::DiceMode;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Failed death checks before a dying character dies (default)
+pub const DEFAULT_DEATH_CHECK_FAILURES: u32 = 3;
+
+/// Optional rules that change how combat is resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ruleset {
+    /// Lethal wounds leave the character dying instead of killing outright
+    pub dying_state: bool,
+    /// Failed CON checks before a dying character dies
+    pub death_check_failures: u32,
+    /// Practice bouts: damage becomes stun instead of wounds, so nobody dies
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sparring: bool,
+    /// Random rolls, or every die at its average for reproducible fights
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dice: DiceMode,
+    /// Shooting with an enemy in melee reach gives that enemy a free attack
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shots_provoke: bool,
+    /// Strong hits from penetrating missiles carry on into a target behind
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub over_penetration: bool,
+    /// Wounds cost blood, and enough of it tires, dizzies and fells a character
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blood_loss: bool,
+    /// Attacking, dodging and charging tire the character (see `exhaustion`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exhaustion: bool,
+    /// Every melee hit rolls a location, from the front unless the caller
+    /// says otherwise (`hit-location` feature)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hit_locations: bool,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self {
+            dying_state: false,
+            death_check_failures: DEFAULT_DEATH_CHECK_FAILURES,
+            sparring: false,
+            dice: DiceMode::Random,
+            shots_provoke: false,
+            over_penetration: false,
+            blood_loss: false,
+            exhaustion: false,
+            hit_locations: false,
+        }
+    }
+}
+
+impl Ruleset {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dying_state(mut self, enabled: bool) -> Self {
+        self.dying_state = enabled;
+        self
+    }
+
+    pub fn with_death_check_failures(mut self, failures: u32) -> Self {
+        self.death_check_failures = failures.max(1);
+        self
+    }
+
+    pub fn with_sparring(mut self, enabled: bool) -> Self {
+        self.sparring = enabled;
+        self
+    }
+
+    pub fn with_dice(mut self, dice: DiceMode) -> Self {
+        self.dice = dice;
+        self
+    }
+
+    pub fn with_shots_provoke(mut self, enabled: bool) -> Self {
+        self.shots_provoke = enabled;
+        self
+    }
+
+    pub fn with_over_penetration(mut self, enabled: bool) -> Self {
+        self.over_penetration = enabled;
+        self
+    }
+
+    pub fn with_blood_loss(mut self, enabled: bool) -> Self {
+        self.blood_loss = enabled;
+        self
+    }
+
+    pub fn with_exhaustion(mut self, enabled: bool) -> Self {
+        self.exhaustion = enabled;
+        self
+    }
+
+    pub fn with_hit_locations(mut self, enabled: bool) -> Self {
+        self.hit_locations = enabled;
+        self
+    }
+}
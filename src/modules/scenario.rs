@@ -0,0 +1,716 @@
+//! Scripted encounter scenarios
+//!
+//! A `Scenario` describes an encounter as data: participants and their teams,
+//! starting positions on an optional battlefield, triggers ("reinforcements
+//! arrive in round 3", "the boss enrages at half health") and a victory
+//! condition. `Scenario::start` builds a `ScenarioRun` around an `Encounter`
+//! that fires triggers between rounds. With the `toml` feature scenarios load
+//! from TOML files via `Scenario::from_toml`, and a run in progress, even
+//! mid-round, can be `save`d and later `resume`d; with seeded dice
+//! (`DiceMode::seeded`) it carries on with exactly the rolls it would have made.
+
+use crate::modules::ai::vulnerability;
+use crate::modules::boss::Boss;
+use crate::modules::encounter::{Encounter, Readiness, Wielding};
+use crate::modules::modifiers::Modifier;
+use crate::modules::positioning::{Battlefield, CellTerrain, Facing, GridKind, GridPos, MoveError};
+use crate::modules::ruleset::Ruleset;
+use crate::{Character, CombatResult};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rounds a scenario runs before it is called a draw, unless it says otherwise
+pub const DEFAULT_MAX_ROUNDS: u32 = 50;
+
+#[cfg(feature = "serde")]
+fn default_max_rounds() -> u32 {
+    DEFAULT_MAX_ROUNDS
+}
+
+/// Battlefield layout for a scenario
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Environment {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grid: GridKind,
+    pub width: i32,
+    pub height: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub terrain: Vec<TerrainCell>,
+}
+
+/// Terrain for one battlefield cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TerrainCell {
+    pub x: i32,
+    pub y: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub terrain: CellTerrain,
+    /// Height in levels (see `Battlefield::elevation_at`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub elevation: i32,
+}
+
+/// A character joining the scenario, with team and starting position
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Participant {
+    pub team: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub position: Option<GridPos>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub facing: Facing,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub boss: Option<Boss>,
+    /// Use the simplified minion rules (see `Combatant::minion`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub minion: bool,
+    /// Weapon state when the fight starts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub readiness: Readiness,
+    /// Weapon in hand when the fight starts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wielding: Wielding,
+    pub character: Character,
+}
+
+impl Participant {
+    pub fn new(character: Character, team: u32) -> Self {
+        Self {
+            team,
+            position: None,
+            facing: Facing::default(),
+            boss: None,
+            minion: false,
+            readiness: Readiness::Set,
+            wielding: Wielding::Melee,
+            character,
+        }
+    }
+
+    pub fn at(mut self, position: GridPos, facing: Facing) -> Self {
+        self.position = Some(position);
+        self.facing = facing;
+        self
+    }
+
+    pub fn as_boss(mut self, boss: Boss) -> Self {
+        self.boss = Some(boss);
+        self
+    }
+
+    pub fn as_minion(mut self) -> Self {
+        self.minion = true;
+        self
+    }
+
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    pub fn with_wielding(mut self, wielding: Wielding) -> Self {
+        self.wielding = wielding;
+        self
+    }
+}
+
+/// When a trigger fires
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum TriggerCondition {
+    /// At the start of the given round
+    Round { round: u32 },
+    /// Once a combatant's wounds reach a fraction (0.0-1.0) of lethal
+    Wounded { combatant: String, fraction: f32 },
+    /// Once a combatant can no longer act
+    Defeated { combatant: String },
+}
+
+/// What a trigger does when it fires
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum TriggerEffect {
+    /// Bring new participants into the fight
+    Reinforce { participants: Vec<Participant> },
+    /// Register a modifier on a combatant (an enrage, a curse lifting)
+    Modify {
+        combatant: String,
+        modifier: Modifier,
+    },
+    /// Add a line to the scenario log
+    Message { text: String },
+}
+
+/// A scripted event that fires once when its condition is met
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trigger {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: String,
+    pub when: TriggerCondition,
+    pub effects: Vec<TriggerEffect>,
+}
+
+impl Trigger {
+    pub fn new(name: &str, when: TriggerCondition) -> Self {
+        Self {
+            name: name.to_string(),
+            when,
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn with_effect(mut self, effect: TriggerEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+}
+
+/// How a scenario is won
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum VictoryCondition {
+    /// The last team with anyone able to fight wins
+    #[default]
+    LastTeamStanding,
+    /// `team` wins as soon as the named combatant is down
+    DefeatTarget { combatant: String, team: u32 },
+    /// `team` wins if anyone on it is still fighting after `rounds` rounds
+    Survive { team: u32, rounds: u32 },
+}
+
+/// Result of a finished scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioOutcome {
+    Victory(u32),
+    Draw,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioError {
+    DuplicateName(String),
+    Placement(MoveError),
+    #[cfg(feature = "toml")]
+    Parse(String),
+    #[cfg(feature = "toml")]
+    Save(String),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::DuplicateName(name) => {
+                write!(f, "More than one participant named {}", name)
+            }
+            ScenarioError::Placement(err) => write!(f, "Cannot place participant: {}", err),
+            #[cfg(feature = "toml")]
+            ScenarioError::Parse(msg) => write!(f, "Invalid scenario: {}", msg),
+            #[cfg(feature = "toml")]
+            ScenarioError::Save(msg) => write!(f, "Cannot save scenario: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<MoveError> for ScenarioError {
+    fn from(err: MoveError) -> Self {
+        ScenarioError::Placement(err)
+    }
+}
+
+/// An encounter described as data
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scenario {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub description: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub environment: Option<Environment>,
+    pub participants: Vec<Participant>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub triggers: Vec<Trigger>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub victory: VictoryCondition,
+    #[cfg_attr(feature = "serde", serde(default = "default_max_rounds"))]
+    pub max_rounds: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Ruleset,
+}
+
+impl Scenario {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            environment: None,
+            participants: Vec::new(),
+            triggers: Vec::new(),
+            victory: VictoryCondition::default(),
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            rules: Ruleset::default(),
+        }
+    }
+
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    pub fn with_participant(mut self, participant: Participant) -> Self {
+        self.participants.push(participant);
+        self
+    }
+
+    pub fn with_trigger(mut self, trigger: Trigger) -> Self {
+        self.triggers.push(trigger);
+        self
+    }
+
+    pub fn with_victory(mut self, victory: VictoryCondition) -> Self {
+        self.victory = victory;
+        self
+    }
+
+    /// Parse a scenario from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self, ScenarioError> {
+        toml::from_str(source).map_err(|e| ScenarioError::Parse(e.to_string()))
+    }
+
+    /// Set up the encounter and return a run ready for its first round
+    pub fn start(&self) -> Result<ScenarioRun, ScenarioError> {
+        let mut encounter = Encounter::new().with_rules(self.rules.clone());
+        if let Some(env) = &self.environment {
+            let mut field = Battlefield::new(env.grid, env.width, env.height);
+            for cell in &env.terrain {
+                let pos = GridPos::new(cell.x, cell.y);
+                field.set_terrain(pos, cell.terrain);
+                field.set_elevation(pos, cell.elevation);
+            }
+            encounter = encounter.with_battlefield(field);
+        }
+        for participant in &self.participants {
+            join(&mut encounter, participant)?;
+        }
+        Ok(ScenarioRun {
+            encounter,
+            triggers: self.triggers.clone(),
+            fired: vec![false; self.triggers.len()],
+            victory: self.victory.clone(),
+            max_rounds: self.max_rounds,
+            log: Vec::new(),
+        })
+    }
+}
+
+fn join(encounter: &mut Encounter, participant: &Participant) -> Result<(), ScenarioError> {
+    let name = &participant.character.name;
+    if encounter.find(name).is_some() {
+        return Err(ScenarioError::DuplicateName(name.clone()));
+    }
+    if let (Some(field), Some(pos)) = (encounter.battlefield.as_mut(), participant.position) {
+        field.place(name, pos, participant.facing)?;
+    }
+    let id = match &participant.boss {
+        Some(boss) => encounter.add_boss(
+            participant.character.clone(),
+            participant.team,
+            boss.clone(),
+        ),
+        None => encounter.add(participant.character.clone(), participant.team),
+    };
+    encounter.combatants[id].minion = participant.minion;
+    encounter.set_readiness(id, participant.readiness);
+    encounter.combatants[id].wielding = participant.wielding;
+    Ok(())
+}
+
+/// A scenario in progress
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScenarioRun {
+    pub encounter: Encounter,
+    triggers: Vec<Trigger>,
+    fired: Vec<bool>,
+    victory: VictoryCondition,
+    max_rounds: u32,
+    /// Trigger names and messages, in firing order
+    pub log: Vec<String>,
+}
+
+impl ScenarioRun {
+    /// Fire due triggers, fight one round, then fire triggers it set off
+    pub fn step(&mut self) -> Result<Vec<CombatResult>, ScenarioError> {
+        self.fire_triggers()?;
+        let results = self.encounter.run_round();
+        self.fire_triggers()?;
+        Ok(results)
+    }
+
+    /// Like `step`, but fight only the next turn (see `Encounter::step`)
+    pub fn step_turn(&mut self) -> Result<Vec<CombatResult>, ScenarioError> {
+        self.fire_triggers()?;
+        let results = self.encounter.step();
+        self.fire_triggers()?;
+        Ok(results)
+    }
+
+    /// Write the whole run (combatants, positions, effects, initiative and
+    /// dice) as TOML
+    #[cfg(feature = "toml")]
+    pub fn save(&self) -> Result<String, ScenarioError> {
+        toml::to_string(self).map_err(|e| ScenarioError::Save(e.to_string()))
+    }
+
+    /// Pick up a run written by `save`
+    #[cfg(feature = "toml")]
+    pub fn resume(source: &str) -> Result<Self, ScenarioError> {
+        toml::from_str(source).map_err(|e| ScenarioError::Parse(e.to_string()))
+    }
+
+    /// Step until the victory condition or the round limit decides the scenario
+    pub fn run(&mut self) -> Result<ScenarioOutcome, ScenarioError> {
+        loop {
+            if let Some(outcome) = self.outcome() {
+                return Ok(outcome);
+            }
+            self.step()?;
+        }
+    }
+
+    /// The result, once the scenario is decided
+    pub fn outcome(&self) -> Option<ScenarioOutcome> {
+        let encounter = &self.encounter;
+        let decided = match &self.victory {
+            VictoryCondition::LastTeamStanding => None,
+            VictoryCondition::DefeatTarget { combatant, team } => encounter
+                .find(combatant)
+                .filter(|&id| !encounter.combatants[id].character.can_act())
+                .map(|_| ScenarioOutcome::Victory(*team)),
+            VictoryCondition::Survive { team, rounds } => (encounter.round > *rounds
+                && encounter.teams_standing().contains(team))
+            .then_some(ScenarioOutcome::Victory(*team)),
+        };
+        decided.or_else(|| {
+            if encounter.is_over() {
+                Some(
+                    encounter
+                        .winner()
+                        .map_or(ScenarioOutcome::Draw, ScenarioOutcome::Victory),
+                )
+            } else if encounter.round > self.max_rounds {
+                Some(ScenarioOutcome::Draw)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn fire_triggers(&mut self) -> Result<(), ScenarioError> {
+        for i in 0..self.triggers.len() {
+            if self.fired[i] || !self.condition_met(&self.triggers[i].when) {
+                continue;
+            }
+            self.fired[i] = true;
+            let trigger = self.triggers[i].clone();
+            if !trigger.name.is_empty() {
+                self.log.push(trigger.name.clone());
+            }
+            for effect in &trigger.effects {
+                self.apply(effect)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn condition_met(&self, when: &TriggerCondition) -> bool {
+        let character = |name: &str| {
+            self.encounter
+                .find(name)
+                .map(|id| &self.encounter.combatants[id].character)
+        };
+        match when {
+            TriggerCondition::Round { round } => self.encounter.round >= *round,
+            TriggerCondition::Wounded {
+                combatant,
+                fraction,
+            } => character(combatant).is_some_and(|c| vulnerability(c) >= *fraction),
+            TriggerCondition::Defeated { combatant } => {
+                character(combatant).is_some_and(|c| !c.can_act())
+            }
+        }
+    }
+
+    fn apply(&mut self, effect: &TriggerEffect) -> Result<(), ScenarioError> {
+        match effect {
+            TriggerEffect::Reinforce { participants } => {
+                for participant in participants {
+                    join(&mut self.encounter, participant)?;
+                }
+            }
+            TriggerEffect::Modify {
+                combatant,
+                modifier,
+            } => {
+                if let Some(id) = self.encounter.find(combatant) {
+                    self.encounter.combatants[id]
+                        .character
+                        .add_modifier(modifier.clone());
+                }
+            }
+            TriggerEffect::Message { text } => self.log.push(text.clone()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::modifiers::ModifierTarget;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+
+    fn fighter(name: &str) -> Character {
+        let mut c = Character::new(
+            name,
+            Attributes::new(6, 6, 6, 5, 5, 5, 5, 5, 5),
+            6,
+            4,
+            Weapon::long_sword(),
+            Armor::leather(),
+        );
+        // Nobody lands a blow, so tests control the pace of the fight
+        c.add_modifier(Modifier::new("Ward", ModifierTarget::Parry, 100));
+        c
+    }
+
+    fn siege() -> Scenario {
+        Scenario::new("Siege")
+            .with_environment(Environment {
+                grid: GridKind::Hex,
+                width: 8,
+                height: 8,
+                terrain: vec![TerrainCell {
+                    x: 3,
+                    y: 3,
+                    terrain: CellTerrain::Wall,
+                    elevation: 0,
+                }],
+            })
+            .with_participant(
+                Participant::new(fighter("Aldric"), 1).at(GridPos::new(0, 0), Facing(2)),
+            )
+            .with_participant(
+                Participant::new(fighter("Ogre"), 2).at(GridPos::new(1, 0), Facing(5)),
+            )
+            .with_trigger(
+                Trigger::new(
+                    "Reinforcements arrive",
+                    TriggerCondition::Round { round: 3 },
+                )
+                .with_effect(TriggerEffect::Reinforce {
+                    participants: vec![Participant::new(fighter("Goblin"), 2)
+                        .at(GridPos::new(7, 7), Facing::NORTH)],
+                }),
+            )
+            .with_trigger(
+                Trigger::new(
+                    "The ogre enrages",
+                    TriggerCondition::Wounded {
+                        combatant: "Ogre".to_string(),
+                        fraction: 0.5,
+                    },
+                )
+                .with_effect(TriggerEffect::Modify {
+                    combatant: "Ogre".to_string(),
+                    modifier: Modifier::new("Enraged", ModifierTarget::Attack, 3),
+                }),
+            )
+            .with_victory(VictoryCondition::Survive { team: 1, rounds: 4 })
+    }
+
+    #[test]
+    fn test_triggers_fire_once_at_their_moment() {
+        let mut run = siege().start().unwrap();
+        run.step().unwrap();
+        assert_eq!(run.encounter.combatants.len(), 2);
+
+        // Round 3 triggers fire as soon as round 2 ends
+        run.step().unwrap();
+        assert_eq!(run.encounter.round, 3);
+        assert_eq!(run.encounter.combatants.len(), 3);
+        assert_eq!(run.log, vec!["Reinforcements arrive"]);
+
+        let ogre = run.encounter.find("Ogre").unwrap();
+        run.encounter.combatants[ogre]
+            .character
+            .wounds
+            .add_wound(WoundLevel::Critical);
+        run.step().unwrap();
+        run.step().unwrap();
+        let ogre = &run.encounter.combatants[ogre].character;
+        assert_eq!(ogre.modifiers.total(ModifierTarget::Attack), 3);
+        assert_eq!(run.log.len(), 2);
+    }
+
+    #[test]
+    fn test_victory_conditions() {
+        let mut run = siege().start().unwrap();
+        assert_eq!(run.run(), Ok(ScenarioOutcome::Victory(1)));
+        assert_eq!(run.encounter.round, 5);
+
+        let mut hunt = siege()
+            .with_victory(VictoryCondition::DefeatTarget {
+                combatant: "Ogre".to_string(),
+                team: 1,
+            })
+            .start()
+            .unwrap();
+        assert_eq!(hunt.outcome(), None);
+        let ogre = hunt.encounter.find("Ogre").unwrap();
+        hunt.encounter.combatants[ogre]
+            .character
+            .wounds
+            .add_wound(WoundLevel::Critical);
+        assert_eq!(hunt.outcome(), Some(ScenarioOutcome::Victory(1)));
+    }
+
+    #[test]
+    fn test_start_rejects_bad_setups() {
+        let twins = Scenario::new("Twins")
+            .with_participant(Participant::new(fighter("Aldric"), 1))
+            .with_participant(Participant::new(fighter("Aldric"), 2));
+        assert!(matches!(
+            twins.start(),
+            Err(ScenarioError::DuplicateName(_))
+        ));
+
+        let walled = siege().with_participant(
+            Participant::new(fighter("Mason"), 1).at(GridPos::new(3, 3), Facing::NORTH),
+        );
+        assert!(matches!(
+            walled.start(),
+            Err(ScenarioError::Placement(MoveError::Impassable(_)))
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_save_mid_round_and_resume() {
+        use crate::modules::dice::DiceMode;
+
+        let mut scenario = Scenario::new("Tavern Brawl")
+            .with_participant(Participant::new(fighter("Aldric"), 1))
+            .with_participant(Participant::new(fighter("Ogre"), 2));
+        scenario.rules = Ruleset::default().with_dice(DiceMode::seeded(7));
+        let mut run = scenario.start().unwrap();
+        // Drop the wards but pull the punches, so the fight lasts a while
+        for combatant in &mut run.encounter.combatants {
+            let character = &mut combatant.character;
+            character.modifiers.remove_source("Ward");
+            character.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -4));
+        }
+        run.step().unwrap();
+        run.step_turn().unwrap();
+        assert_eq!(run.encounter.initiative.len(), 1);
+
+        let mut resumed = ScenarioRun::resume(&run.save().unwrap()).unwrap();
+        assert_eq!(resumed.encounter.initiative, run.encounter.initiative);
+        assert_eq!(resumed.encounter.rules.dice, run.encounter.rules.dice);
+
+        let fight = |run: &mut ScenarioRun| {
+            (0..4)
+                .flat_map(|_| run.step().unwrap())
+                .map(|result| result.to_string())
+                .collect::<Vec<_>>()
+        };
+        let original = fight(&mut run);
+        let replayed = fight(&mut resumed);
+        assert!(!original.is_empty());
+        assert_eq!(original, replayed);
+        assert_eq!(run.encounter.round, resumed.encounter.round);
+
+        assert!(matches!(
+            ScenarioRun::resume("encounter = 3"),
+            Err(ScenarioError::Parse(_))
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_from_toml() {
+        let source = r#"
+            name = "Bridge Ambush"
+            max_rounds = 10
+
+            [environment]
+            grid = "Hex"
+            width = 6
+            height = 4
+            terrain = [{ x = 2, y = 1, terrain = "Water" }, { x = 0, y = 0, elevation = 2 }]
+
+            [victory]
+            kind = "DefeatTarget"
+            combatant = "Bandit Chief"
+            team = 1
+
+            [[participants]]
+            team = 1
+            position = { x = 0, y = 0 }
+            [participants.character]
+            name = "Aldric"
+            attributes = { strength = 8, dexterity = 6, constitution = 7, reason = 5, intuition = 6, willpower = 5, charisma = 5, perception = 7, empathy = 4 }
+            weapon_skill = 7
+            dodge_skill = 5
+            weapon = { name = "Long Sword", impact = "Medium", damage = 5 }
+            armor = { name = "Chain Mail", armor_type = "Chain", protection = 3, movement_penalty = -1 }
+
+            [[participants]]
+            team = 2
+            position = { x = 5, y = 3 }
+            facing = 3
+            readiness = "Sheathed"
+            [participants.character]
+            name = "Bandit Chief"
+            attributes = { strength = 7, dexterity = 7, constitution = 6, reason = 5, intuition = 5, willpower = 6, charisma = 6, perception = 6, empathy = 3 }
+            weapon_skill = 6
+            dodge_skill = 6
+            weapon = { name = "Dagger", impact = "Small", damage = 3 }
+            armor = { name = "Leather Armor", armor_type = "Leather", protection = 2, movement_penalty = 0 }
+
+            [[triggers]]
+            name = "The chief calls for help"
+            when = { kind = "Round", round = 2 }
+            effects = [{ kind = "Message", text = "Archers appear on the bank" }]
+        "#;
+
+        let scenario = Scenario::from_toml(source).unwrap();
+        assert_eq!(scenario.participants.len(), 2);
+        assert_eq!(scenario.max_rounds, 10);
+        assert!(matches!(
+            scenario.victory,
+            VictoryCondition::DefeatTarget { team: 1, .. }
+        ));
+
+        let run = scenario.start().unwrap();
+        let field = run.encounter.battlefield.as_ref().unwrap();
+        assert_eq!(field.terrain_at(GridPos::new(2, 1)), CellTerrain::Water);
+        assert_eq!(field.elevation_at(GridPos::new(0, 0)), 2);
+        assert_eq!(field.placement("Bandit Chief").unwrap().facing, Facing(3));
+        assert_eq!(run.encounter.combatants[1].readiness, Readiness::Sheathed);
+
+        assert!(matches!(
+            Scenario::from_toml("name = 3"),
+            Err(ScenarioError::Parse(_))
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 //! Skill development and progression system based on Draft RPG Section 3.13
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Difficulty of learning a skill
@@ -14,7 +14,7 @@ pub enum SkillDifficulty {
 
 impl SkillDifficulty {
     /// Calculate the multiplier for skill cost
-    pub fn cost_multiplier(&self) -> i32 {
+    pub const fn cost_multiplier(&self) -> i32 {
         match self {
             SkillDifficulty::Easy => 1,
             SkillDifficulty::Normal => 1,
@@ -24,6 +24,24 @@ impl SkillDifficulty {
     }
 }
 
+/// Broad grouping of skills
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SkillCategory {
+    #[default]
+    General,
+    /// Spoken and written languages (see `modules::languages`)
+    Language,
+    /// History, monster lore, heraldry and similar (see `modules::knowledge`)
+    Knowledge,
+    /// Weapon and dodge skill mirrored from a character (see `Character::sync_skills_to`)
+    Combat,
+}
+
+/// Name of the weapon skill in a `SkillSet`
+pub const WEAPON_SKILL: &str = "Weapon";
+/// Name of the dodge skill in a `SkillSet`
+pub const DODGE_SKILL: &str = "Dodge";
+
 /// A skill with its current level and associated attribute
 #[derive(Debug, Clone)]
 pub struct Skill {
@@ -31,6 +49,7 @@ pub struct Skill {
     pub level: i32,
     pub associated_attribute: i32,
     pub difficulty: SkillDifficulty,
+    pub category: SkillCategory,
     pub prerequisites: Vec<SkillPrerequisite>,
 }
 
@@ -48,10 +67,21 @@ impl Skill {
             level: 0,
             associated_attribute,
             difficulty,
+            category: SkillCategory::General,
             prerequisites: Vec::new(),
         }
     }
 
+    pub fn with_category(mut self, category: SkillCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
     pub fn with_prerequisite(mut self, skill_name: &str, minimum_level: i32) -> Self {
         self.prerequisites.push(SkillPrerequisite {
             skill_name: skill_name.to_string(),
@@ -95,14 +125,15 @@ impl Skill {
 /// Manages a character's skills and skill points
 #[derive(Debug, Clone)]
 pub struct SkillSet {
-    pub skills: HashMap<String, Skill>,
+    /// Sorted by name, so iteration order is stable
+    pub skills: BTreeMap<String, Skill>,
     pub available_points: i32,
 }
 
 impl SkillSet {
     pub fn new(initial_points: i32) -> Self {
         Self {
-            skills: HashMap::new(),
+            skills: BTreeMap::new(),
             available_points: initial_points,
         }
     }
@@ -170,6 +201,11 @@ impl SkillSet {
         Ok(())
     }
 
+    /// Iterate over the skills in a category
+    pub fn in_category(&self, category: SkillCategory) -> impl Iterator<Item = &Skill> {
+        self.skills.values().filter(move |s| s.category == category)
+    }
+
     /// Grant skill points (e.g., from character advancement)
     pub fn grant_points(&mut self, points: i32) {
         self.available_points += points;
@@ -301,4 +337,14 @@ mod tests {
         // Now Calculus should be learnable
         assert!(skill_set.raise_skill("Calculus").is_ok());
     }
+
+    #[test]
+    fn test_skills_iterate_in_name_order() {
+        let mut skill_set = SkillSet::new(0);
+        for name in ["Swimming", "Archery", "Lockpicking"] {
+            skill_set.add_skill(Skill::new(name, 5, SkillDifficulty::Normal));
+        }
+        let names: Vec<&str> = skill_set.skills.keys().map(String::as_str).collect();
+        assert_eq!(names, vec!["Archery", "Lockpicking", "Swimming"]);
+    }
 }
@@ -0,0 +1,275 @@
+//! Food, water, ammunition and bandages
+//!
+//! `Supplies` tracks rations, water, ammunition and bandages for a party. Each
+//! day of travel every member needs one ration and one day's water; going
+//! without adds exhaustion. Ranged weapons reload from the ammunition stock,
+//! bandages are used up by `healing::triage`, and `warnings` flags anything
+//! about to run out.
+
+use crate::modules::exhaustion::Exhaustion;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ammunition carried for ranged weapons
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AmmoKind {
+    Arrow,
+    Bolt,
+    Bullet,
+    Javelin,
+}
+
+impl fmt::Display for AmmoKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AmmoKind::Arrow => write!(f, "Arrows"),
+            AmmoKind::Bolt => write!(f, "Bolts"),
+            AmmoKind::Bullet => write!(f, "Bullets"),
+            AmmoKind::Javelin => write!(f, "Javelins"),
+        }
+    }
+}
+
+/// Exhaustion points per day without food
+pub const STARVATION_EXHAUSTION: i32 = 2;
+/// Exhaustion points per day without water
+pub const DEHYDRATION_EXHAUSTION: i32 = 4;
+/// Days of food or water left that trigger a low-supply warning
+pub const LOW_SUPPLY_DAYS: u32 = 2;
+/// Ammunition count that triggers a low-ammunition warning
+pub const LOW_AMMO: u32 = 5;
+
+/// Consumables carried by a party
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Supplies {
+    /// One ration feeds one person for a day
+    pub rations: u32,
+    /// One unit of water lasts one person for a day
+    pub water: u32,
+    pub ammo: BTreeMap<AmmoKind, u32>,
+    /// Dressings used when treating wounds (see `healing::triage`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bandages: u32,
+    /// Spell components by name (see `magic::SpellComponents`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub materials: BTreeMap<String, u32>,
+}
+
+impl Supplies {
+    pub fn new(rations: u32, water: u32) -> Self {
+        Self {
+            rations,
+            water,
+            ammo: BTreeMap::new(),
+            bandages: 0,
+            materials: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_bandages(mut self, count: u32) -> Self {
+        self.bandages += count;
+        self
+    }
+
+    /// Use one bandage, if any are left
+    pub fn take_bandage(&mut self) -> bool {
+        if self.bandages == 0 {
+            return false;
+        }
+        self.bandages -= 1;
+        true
+    }
+
+    pub fn with_ammo(mut self, kind: AmmoKind, count: u32) -> Self {
+        self.add_ammo(kind, count);
+        self
+    }
+
+    pub fn add_ammo(&mut self, kind: AmmoKind, count: u32) {
+        *self.ammo.entry(kind).or_insert(0) += count;
+    }
+
+    pub fn ammo_count(&self, kind: AmmoKind) -> u32 {
+        self.ammo.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Take up to `count` rounds of ammunition, returning how many were taken
+    pub fn take_ammo(&mut self, kind: AmmoKind, count: u32) -> u32 {
+        let stock = self.ammo.entry(kind).or_insert(0);
+        let taken = count.min(*stock);
+        *stock -= taken;
+        taken
+    }
+
+    pub fn with_material(mut self, name: &str, count: u32) -> Self {
+        *self.materials.entry(name.to_string()).or_insert(0) += count;
+        self
+    }
+
+    pub fn material_count(&self, name: &str) -> u32 {
+        self.materials.get(name).copied().unwrap_or(0)
+    }
+
+    /// Take `count` of a material if there are that many; false otherwise
+    pub fn take_material(&mut self, name: &str, count: u32) -> bool {
+        match self.materials.get_mut(name) {
+            Some(stock) if *stock >= count => {
+                *stock -= count;
+                true
+            }
+            _ => count == 0,
+        }
+    }
+
+    /// Feed and water `people` for one day; returns who went without
+    ///
+    /// Supplies are shared out in order, so the last members go short first.
+    pub fn consume_day(&mut self, people: usize) -> Vec<Deprivation> {
+        (0..people)
+            .map(|_| {
+                let fed = self.rations > 0;
+                let watered = self.water > 0;
+                self.rations = self.rations.saturating_sub(1);
+                self.water = self.water.saturating_sub(1);
+                Deprivation {
+                    hungry: !fed,
+                    thirsty: !watered,
+                }
+            })
+            .collect()
+    }
+
+    /// Warnings for supplies that will run out soon for a party of `people`
+    pub fn warnings(&self, people: usize) -> Vec<SupplyWarning> {
+        let people = people.max(1) as u32;
+        let mut warnings = Vec::new();
+        match self.rations / people {
+            0 => warnings.push(SupplyWarning::OutOfFood),
+            days if days <= LOW_SUPPLY_DAYS => warnings.push(SupplyWarning::LowFood { days }),
+            _ => {}
+        }
+        match self.water / people {
+            0 => warnings.push(SupplyWarning::OutOfWater),
+            days if days <= LOW_SUPPLY_DAYS => warnings.push(SupplyWarning::LowWater { days }),
+            _ => {}
+        }
+        for (kind, count) in &self.ammo {
+            if *count <= LOW_AMMO {
+                warnings.push(SupplyWarning::LowAmmo {
+                    kind: *kind,
+                    count: *count,
+                });
+            }
+        }
+        warnings
+    }
+}
+
+/// Whether one person went without food or water today
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Deprivation {
+    pub hungry: bool,
+    pub thirsty: bool,
+}
+
+impl Deprivation {
+    /// Exhaustion points caused by the day's deprivation
+    pub fn exhaustion(&self) -> i32 {
+        let mut points = 0;
+        if self.hungry {
+            points += STARVATION_EXHAUSTION;
+        }
+        if self.thirsty {
+            points += DEHYDRATION_EXHAUSTION;
+        }
+        points
+    }
+
+    /// Add the day's deprivation exhaustion to a tracker
+    pub fn apply(&self, exhaustion: &mut Exhaustion) {
+        exhaustion.add_points(self.exhaustion());
+    }
+}
+
+/// Supply shortfalls worth telling the players about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyWarning {
+    LowFood { days: u32 },
+    OutOfFood,
+    LowWater { days: u32 },
+    OutOfWater,
+    LowAmmo { kind: AmmoKind, count: u32 },
+}
+
+impl fmt::Display for SupplyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SupplyWarning::LowFood { days } => write!(f, "Food for {} day(s) left", days),
+            SupplyWarning::OutOfFood => write!(f, "Out of food"),
+            SupplyWarning::LowWater { days } => write!(f, "Water for {} day(s) left", days),
+            SupplyWarning::OutOfWater => write!(f, "Out of water"),
+            SupplyWarning::LowAmmo { kind, count } => write!(f, "Only {} {} left", count, kind),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "ranged")]
+    use crate::modules::ranged_combat::{RangedAttackState, RangedCombatError, RangedWeapon};
+
+    #[test]
+    fn test_daily_consumption_and_deprivation() {
+        let mut supplies = Supplies::new(3, 2);
+        let first = supplies.consume_day(2);
+        assert_eq!(first, vec![Deprivation::default(); 2]);
+
+        let second = supplies.consume_day(2);
+        assert!(!second[0].hungry);
+        assert!(second[0].thirsty && second[1].thirsty && second[1].hungry);
+        assert_eq!(second[1].exhaustion(), 6);
+
+        let mut exhaustion = Exhaustion::new(6);
+        second[1].apply(&mut exhaustion);
+        assert_eq!(exhaustion.points, 6);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let supplies = Supplies::new(4, 20).with_ammo(AmmoKind::Arrow, 3);
+        assert_eq!(
+            supplies.warnings(2),
+            vec![
+                SupplyWarning::LowFood { days: 2 },
+                SupplyWarning::LowAmmo {
+                    kind: AmmoKind::Arrow,
+                    count: 3
+                }
+            ]
+        );
+        assert_eq!(Supplies::new(0, 0).warnings(1).len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "ranged")]
+    fn test_ranged_reload_consumes_ammo() {
+        let bow = RangedWeapon::long_bow();
+        let mut supplies = Supplies::new(0, 0).with_ammo(AmmoKind::Arrow, 1);
+        let mut state = RangedAttackState::new();
+
+        state.reload_from(&bow, &mut supplies).unwrap();
+        assert_eq!(supplies.ammo_count(AmmoKind::Arrow), 0);
+        state.fire().unwrap();
+        assert_eq!(
+            state.reload_from(&bow, &mut supplies),
+            Err(RangedCombatError::NoAmmunition)
+        );
+        assert_eq!(RangedWeapon::crossbow().ammo_kind(), AmmoKind::Bolt);
+    }
+}
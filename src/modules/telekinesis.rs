@@ -0,0 +1,425 @@
+//! Thaumaturgy's telekinesis in combat
+//!
+//! Each action casts a known Thaumaturgy spell at the target first, checked
+//! for range and line of sight and tiring the caster like any casting. The
+//! casting's quality then decides how hard the push is:
+//! - `shove`: force against Strength + d10, or the target is knocked down
+//! - `disarm`: force against the target's parry, or the weapon is torn away
+//! - `hurl`: an object thrown as an improvised missile, resolved like any
+//!   other hit and dodged as one, with damage growing with quality
+
+use crate::modules::dice::Dice;
+use crate::modules::magic::{CastingResult, MagicBranch, MagicError, SpellTarget, TargetInfo};
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use crate::modules::ruleset::Ruleset;
+use crate::{
+    resolve_hit, Character, CombatResult, RollBreakdown, Weapon, WeaponImpact, WeaponSpec,
+};
+use std::fmt;
+
+/// Force of a successful telekinetic casting, before its quality is added
+pub const TELEKINETIC_FORCE: i32 = 8;
+/// Attack penalty for throwing something that was never meant to be thrown
+pub const IMPROVISED_PENALTY: i32 = -2;
+/// Source of the penalties on a knocked-down character
+pub const KNOCKED_DOWN: &str = "Knocked Down";
+/// Attack, parry and dodge penalty while knocked down
+pub const KNOCKDOWN_PENALTY: i32 = -2;
+
+/// Something loose enough to be hurled: a stool, a rock, a brazier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HurledObject {
+    pub name: String,
+    pub impact: WeaponImpact,
+}
+
+impl HurledObject {
+    pub fn new(name: &str, impact: WeaponImpact) -> Self {
+        Self {
+            name: name.to_string(),
+            impact,
+        }
+    }
+
+    /// Damage bonus when thrown by a casting of `quality`
+    pub fn damage(&self, quality: i32) -> i32 {
+        self.impact.base_damage() + quality.max(0)
+    }
+}
+
+/// What a telekinetic action did
+#[derive(Debug)]
+pub enum TelekineticEffect {
+    /// The casting failed; nothing moved
+    Fizzled,
+    /// The target stood firm or kept hold of the weapon
+    Resisted {
+        force: i32,
+        resistance: i32,
+    },
+    KnockedDown {
+        force: i32,
+        resistance: i32,
+    },
+    Disarmed {
+        force: i32,
+        resistance: i32,
+        weapon: Weapon,
+    },
+    Hurled(CombatResult),
+}
+
+/// A telekinetic action: the casting and what it did
+#[derive(Debug)]
+pub struct Telekinesis {
+    pub casting: CastingResult,
+    pub effect: TelekineticEffect,
+}
+
+/// Errors from telekinetic actions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelekinesisError {
+    /// The spell isn't a Thaumaturgy spell
+    NotThaumaturgy(String),
+    /// The target holds no weapon to take
+    Unarmed,
+    Magic(MagicError),
+}
+
+impl fmt::Display for TelekinesisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TelekinesisError::NotThaumaturgy(spell) => {
+                write!(f, "{} is not a Thaumaturgy spell", spell)
+            }
+            TelekinesisError::Unarmed => write!(f, "Target holds no weapon"),
+            TelekinesisError::Magic(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TelekinesisError {}
+
+impl From<MagicError> for TelekinesisError {
+    fn from(e: MagicError) -> Self {
+        TelekinesisError::Magic(e)
+    }
+}
+
+/// Cast `spell_name` at `target`, rolling on `dice`
+fn cast_at(
+    caster: &mut Character,
+    target: &Character,
+    spell_name: &str,
+    info: &TargetInfo,
+    dice: &mut dyn Dice,
+) -> Result<CastingResult, TelekinesisError> {
+    let branch = caster
+        .magic
+        .as_ref()
+        .and_then(|m| m.spells.get(spell_name))
+        .map(|learned| learned.spell.branch)
+        .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
+    if branch != MagicBranch::Thaumaturgy {
+        return Err(TelekinesisError::NotThaumaturgy(spell_name.to_string()));
+    }
+    let aim = SpellTarget::Character(target.name.clone());
+    Ok(caster.cast(spell_name, aim, info, dice.d10())?)
+}
+
+/// Shove `target` with the mind: force against Strength + d10
+///
+/// A target that can't withstand the force is knocked down (see `stand_up`).
+pub fn shove(
+    caster: &mut Character,
+    target: &mut Character,
+    spell_name: &str,
+    info: &TargetInfo,
+    dice: &mut dyn Dice,
+) -> Result<Telekinesis, TelekinesisError> {
+    let casting = cast_at(caster, target, spell_name, info, dice)?;
+    if !casting.success {
+        return Ok(Telekinesis {
+            casting,
+            effect: TelekineticEffect::Fizzled,
+        });
+    }
+    let force = TELEKINETIC_FORCE + casting.quality;
+    let resistance = target.attributes.strength.get() + dice.d10();
+    let effect = if force > resistance {
+        knock_down(target);
+        TelekineticEffect::KnockedDown { force, resistance }
+    } else {
+        TelekineticEffect::Resisted { force, resistance }
+    };
+    Ok(Telekinesis { casting, effect })
+}
+
+/// Wrench the weapon from `target`'s grip: force against their parry roll
+///
+/// A disarmed target is left with `Weapon::unarmed`; the weapon taken is
+/// returned in the effect.
+pub fn disarm(
+    caster: &mut Character,
+    target: &mut Character,
+    spell_name: &str,
+    info: &TargetInfo,
+    dice: &mut dyn Dice,
+) -> Result<Telekinesis, TelekinesisError> {
+    if target.weapon.name == WeaponSpec::UNARMED.name {
+        return Err(TelekinesisError::Unarmed);
+    }
+    let casting = cast_at(caster, target, spell_name, info, dice)?;
+    if !casting.success {
+        return Ok(Telekinesis {
+            casting,
+            effect: TelekineticEffect::Fizzled,
+        });
+    }
+    let force = TELEKINETIC_FORCE + casting.quality;
+    let resistance = target.parry_roll_with(dice.d10());
+    let effect = if force > resistance {
+        TelekineticEffect::Disarmed {
+            force,
+            resistance,
+            weapon: target.equip_weapon(Weapon::unarmed()),
+        }
+    } else {
+        TelekineticEffect::Resisted { force, resistance }
+    };
+    Ok(Telekinesis { casting, effect })
+}
+
+/// Hurl `object` at `target` as an improvised missile
+///
+/// The attack is Thaumaturgy lore + d10 with `IMPROVISED_PENALTY` and the
+/// caster's wound and exhaustion penalties, against the target's dodge. A
+/// hit does the object's damage plus the casting quality, less armor.
+pub fn hurl(
+    caster: &mut Character,
+    target: &mut Character,
+    spell_name: &str,
+    object: &HurledObject,
+    info: &TargetInfo,
+    ruleset: &Ruleset,
+    dice: &mut dyn Dice,
+) -> Result<Telekinesis, TelekinesisError> {
+    let casting = cast_at(caster, target, spell_name, info, dice)?;
+    if !casting.success {
+        return Ok(Telekinesis {
+            casting,
+            effect: TelekineticEffect::Fizzled,
+        });
+    }
+    let lore = caster
+        .magic
+        .as_ref()
+        .and_then(|m| m.lores.get(&MagicBranch::Thaumaturgy))
+        .map_or(0, |lore| lore.level);
+    let attack = RollBreakdown {
+        skill: lore,
+        die: dice.d10(),
+        wound_penalty: caster.wounds.ranged_penalty(),
+        exhaustion_penalty: caster.fatigue_penalty(),
+        other: IMPROVISED_PENALTY,
+        ..RollBreakdown::default()
+    };
+    let defense = target.dodge_breakdown(dice.d10());
+    let mut result = resolve_hit(
+        &caster.name,
+        &object.name,
+        object.damage(casting.quality),
+        target,
+        attack.total(),
+        defense.total(),
+        None,
+        ruleset,
+    );
+    result.attack_breakdown = attack;
+    result.defense_breakdown = defense;
+    Ok(Telekinesis {
+        casting,
+        effect: TelekineticEffect::Hurled(result),
+    })
+}
+
+/// Put `character` on the ground, at `KNOCKDOWN_PENALTY` until they stand
+pub fn knock_down(character: &mut Character) {
+    if is_knocked_down(character) {
+        return;
+    }
+    for target in [
+        ModifierTarget::Attack,
+        ModifierTarget::Parry,
+        ModifierTarget::Dodge,
+    ] {
+        character.add_modifier(Modifier::new(KNOCKED_DOWN, target, KNOCKDOWN_PENALTY));
+    }
+}
+
+pub fn is_knocked_down(character: &Character) -> bool {
+    character.modifiers.iter().any(|m| m.source == KNOCKED_DOWN)
+}
+
+/// Get back up, returning whether the character was down
+pub fn stand_up(character: &mut Character) -> bool {
+    character.modifiers.remove_source(KNOCKED_DOWN) > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::dice::ScriptedDice;
+    use crate::modules::magic::{
+        MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration, SpellRange,
+    };
+    use crate::{Armor, Attributes};
+
+    fn mage() -> Character {
+        let mut magic = MagicUser::new(6);
+        magic.add_lore(MagicBranch::Thaumaturgy, 6);
+        magic.add_lore(MagicBranch::Elementalism, 6);
+        for (name, branch) in [
+            ("Telekinesis", MagicBranch::Thaumaturgy),
+            ("Fire Bolt", MagicBranch::Elementalism),
+        ] {
+            magic
+                .learn_spell(
+                    Spell {
+                        name: name.to_string(),
+                        branch,
+                        difficulty: SpellDifficulty::Hard,
+                        preparation_time: 0,
+                        casting_time: 1,
+                        range: SpellRange::Short(20),
+                        duration: SpellDuration::Instant,
+                        components: SpellComponents::default(),
+                    },
+                    4,
+                )
+                .unwrap();
+        }
+        Character::new_with_magic(
+            "Ysolde",
+            Attributes::new(4, 6, 6, 7, 7, 7, 6, 6, 6),
+            2,
+            3,
+            Weapon::dagger(),
+            Armor::none(),
+            magic,
+        )
+    }
+
+    fn brute() -> Character {
+        Character::new(
+            "Ogra",
+            Attributes::new(8, 5, 7, 4, 4, 5, 4, 5, 4),
+            6,
+            3,
+            Weapon::mace(),
+            Armor::leather(),
+        )
+    }
+
+    #[test]
+    fn test_shove_and_disarm_against_resistance() {
+        let info = TargetInfo::new(10, true);
+        let (mut ysolde, mut ogra) = (mage(), brute());
+
+        // Skill 4 + empathy 6 + 1 misses the Hard target of 12
+        let fizzle = shove(
+            &mut ysolde,
+            &mut ogra,
+            "Telekinesis",
+            &info,
+            &mut ScriptedDice::new([1]),
+        )
+        .unwrap();
+        assert!(matches!(fizzle.effect, TelekineticEffect::Fizzled));
+
+        // A roll of 10 gives quality 8: force 16 against Strength 8 + d10
+        let mut dice = ScriptedDice::new([10, 9]);
+        let held = shove(&mut ysolde, &mut ogra, "Telekinesis", &info, &mut dice).unwrap();
+        assert_eq!(held.casting.quality, 8);
+        assert!(matches!(
+            held.effect,
+            TelekineticEffect::Resisted {
+                force: 16,
+                resistance: 17
+            }
+        ));
+        let parry = ogra.parry_roll_with(5);
+        let mut dice = ScriptedDice::new([10, 2]);
+        let down = shove(&mut ysolde, &mut ogra, "Telekinesis", &info, &mut dice).unwrap();
+        assert!(matches!(down.effect, TelekineticEffect::KnockedDown { .. }));
+        assert!(is_knocked_down(&ogra));
+        assert_eq!(ogra.parry_roll_with(5), parry + KNOCKDOWN_PENALTY);
+        assert!(stand_up(&mut ogra));
+        assert!(!stand_up(&mut ogra));
+
+        // The mace is torn away, and there's nothing left to take
+        let mut dice = ScriptedDice::new([10, 1]);
+        let taken = disarm(&mut ysolde, &mut ogra, "Telekinesis", &info, &mut dice).unwrap();
+        match taken.effect {
+            TelekineticEffect::Disarmed { weapon, .. } => assert_eq!(weapon.name, "Mace"),
+            other => panic!("expected a disarm, got {:?}", other),
+        }
+        assert_eq!(ogra.weapon.name, WeaponSpec::UNARMED.name);
+        assert_eq!(
+            disarm(&mut ysolde, &mut ogra, "Telekinesis", &info, &mut dice).unwrap_err(),
+            TelekinesisError::Unarmed
+        );
+
+        // Only Thaumaturgy moves things, and only within the spell's range
+        let mut ogra = brute();
+        assert_eq!(
+            shove(&mut ysolde, &mut ogra, "Fire Bolt", &info, &mut dice).unwrap_err(),
+            TelekinesisError::NotThaumaturgy("Fire Bolt".to_string())
+        );
+        assert!(matches!(
+            disarm(
+                &mut ysolde,
+                &mut ogra,
+                "Telekinesis",
+                &TargetInfo::new(40, true),
+                &mut dice
+            ),
+            Err(TelekinesisError::Magic(MagicError::OutOfRange { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_hurled_objects_hit_harder_with_quality() {
+        let info = TargetInfo::new(10, true);
+        let rules = Ruleset::default();
+        let anvil = HurledObject::new("Anvil", WeaponImpact::Large);
+        let throw = |cast: i32| {
+            let (mut ysolde, mut ogra) = (mage(), brute());
+            let mut dice = ScriptedDice::new([cast, 8, 1]);
+            let thrown = hurl(
+                &mut ysolde,
+                &mut ogra,
+                "Telekinesis",
+                &anvil,
+                &info,
+                &rules,
+                &mut dice,
+            )
+            .unwrap();
+            match thrown.effect {
+                TelekineticEffect::Hurled(result) => (thrown.casting.quality, result),
+                other => panic!("expected a throw, got {:?}", other),
+            }
+        };
+
+        let (low, weak) = throw(3);
+        let (high, strong) = throw(10);
+        assert_eq!((low, high), (1, 8));
+        assert!(weak.hit && strong.hit);
+        assert_eq!(strong.attack_breakdown.skill, 6);
+        assert_eq!(strong.attack_breakdown.other, IMPROVISED_PENALTY);
+        assert_eq!(strong.attack_roll, 6 + 8 + IMPROVISED_PENALTY);
+        assert_eq!(strong.damage.get() - weak.damage.get(), high - low);
+        assert!(strong.wound_level.is_some());
+    }
+}
@@ -0,0 +1,68 @@
+//! Combat time scale
+//!
+//! A combat round is `SEGMENTS_PER_ROUND` segments of one second each. Spell
+//! casting and weapon preparation are measured in segments, while spell
+//! durations, bleeding and rest are usually given in minutes or hours; these
+//! helpers convert everything to whole rounds so the encounter engine can
+//! count effects down (e.g. `character.rest(minutes_to_rounds(10))`).
+
+/// Seconds in one segment
+pub const SECONDS_PER_SEGMENT: i32 = 1;
+/// Segments in one combat round
+pub const SEGMENTS_PER_ROUND: i32 = 5;
+/// Seconds in one combat round
+pub const SECONDS_PER_ROUND: i32 = SECONDS_PER_SEGMENT * SEGMENTS_PER_ROUND;
+/// Combat rounds in a minute
+pub const ROUNDS_PER_MINUTE: i32 = 60 / SECONDS_PER_ROUND;
+/// Combat rounds in an hour
+pub const ROUNDS_PER_HOUR: i32 = ROUNDS_PER_MINUTE * 60;
+
+/// Rounds needed to finish `segments` of work (a started round counts)
+pub const fn segments_to_rounds(segments: i32) -> i32 {
+    if segments <= 0 {
+        0
+    } else {
+        (segments + SEGMENTS_PER_ROUND - 1) / SEGMENTS_PER_ROUND
+    }
+}
+
+pub const fn seconds_to_rounds(seconds: i32) -> i32 {
+    segments_to_rounds(seconds / SECONDS_PER_SEGMENT)
+}
+
+pub const fn minutes_to_rounds(minutes: i32) -> i32 {
+    minutes * ROUNDS_PER_MINUTE
+}
+
+pub const fn hours_to_rounds(hours: i32) -> i32 {
+    hours * ROUNDS_PER_HOUR
+}
+
+pub const fn rounds_to_seconds(rounds: i32) -> i32 {
+    rounds * SECONDS_PER_ROUND
+}
+
+/// Whole minutes covered by `rounds` (partial minutes are dropped)
+pub const fn rounds_to_minutes(rounds: i32) -> i32 {
+    rounds / ROUNDS_PER_MINUTE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(SECONDS_PER_ROUND, 5);
+        assert_eq!(minutes_to_rounds(1), 12);
+        assert_eq!(hours_to_rounds(1), 720);
+        assert_eq!(rounds_to_minutes(hours_to_rounds(2)), 120);
+        assert_eq!(rounds_to_seconds(3), 15);
+
+        assert_eq!(segments_to_rounds(0), 0);
+        assert_eq!(segments_to_rounds(1), 1);
+        assert_eq!(segments_to_rounds(5), 1);
+        assert_eq!(segments_to_rounds(6), 2);
+        assert_eq!(seconds_to_rounds(11), 3);
+    }
+}
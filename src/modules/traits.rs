@@ -0,0 +1,214 @@
+//! Character traits: advantages and disadvantages
+//!
+//! A `Trait` is plain data chosen at character creation. Its effects are
+//! registered as modifiers like a permanent injury's, while `TraitRule`s hook
+//! into checks the modifier list can't express (off-hand fighting, darkness,
+//! willpower). Start from the presets or load traits with serde.
+
+use crate::modules::modifiers::{Modifier, ModifierTarget};
+use crate::modules::permanent_injury::InjuryEffect;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Penalty for attacking with the off hand
+pub const OFF_HAND_PENALTY: i32 = -2;
+/// Penalty to sight-based rolls in darkness (see `Character::darkness_penalty`)
+pub const DARKNESS_PENALTY: i32 = -3;
+
+/// A special rule attached to a trait
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TraitRule {
+    /// Ignore the off-hand penalty
+    Ambidextrous,
+    /// Extra penalty to rolls made in darkness
+    DarknessPenalty(i32),
+    /// Bonus to willpower checks
+    WillpowerBonus(i32),
+    /// Feels no wound penalties, never bleeds and never lingers dying
+    Undead,
+}
+
+/// An advantage or disadvantage such as Iron Will or Lame
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trait {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub effects: Vec<InjuryEffect>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Vec<TraitRule>,
+}
+
+impl Trait {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            effects: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+
+    pub fn with_effect(mut self, target: ModifierTarget, value: i32) -> Self {
+        self.effects.push(InjuryEffect { target, value });
+        self
+    }
+
+    pub fn with_rule(mut self, rule: TraitRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Fights equally well with either hand
+    pub fn ambidextrous() -> Self {
+        Self::new("Ambidextrous").with_rule(TraitRule::Ambidextrous)
+    }
+
+    /// Poor depth perception
+    pub fn one_eyed() -> Self {
+        Self::new("One-Eyed")
+            .with_effect(ModifierTarget::Ranged, -2)
+            .with_effect(ModifierTarget::Perception, -1)
+    }
+
+    /// All but blind after dusk
+    pub fn night_blind() -> Self {
+        Self::new("Night Blind").with_rule(TraitRule::DarknessPenalty(-3))
+    }
+
+    /// Hard to frighten, break or wear down
+    pub fn iron_will() -> Self {
+        Self::new("Iron Will").with_rule(TraitRule::WillpowerBonus(2))
+    }
+
+    /// A bad leg from birth or an old wound
+    pub fn lame() -> Self {
+        Self::new("Lame").with_effect(ModifierTarget::Movement, -2)
+    }
+
+    /// A shambling corpse: slow, but past caring about its wounds
+    pub fn undead() -> Self {
+        Self::new("Undead")
+            .with_effect(ModifierTarget::Dodge, -2)
+            .with_effect(ModifierTarget::Movement, -2)
+            .with_rule(TraitRule::Undead)
+    }
+
+    /// The trait's effects as modifiers for the character's modifier list
+    pub fn modifiers(&self) -> Vec<Modifier> {
+        self.effects
+            .iter()
+            .map(|e| Modifier::new(&self.name, e.target, e.value))
+            .collect()
+    }
+}
+
+/// Traits a character was created with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Traits(Vec<Trait>);
+
+impl Traits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, t: Trait) {
+        self.0.push(t);
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.0.iter().any(|t| t.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Trait> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn rules(&self) -> impl Iterator<Item = &TraitRule> {
+        self.0.iter().flat_map(|t| t.rules.iter())
+    }
+
+    /// Penalty for attacking with the off hand (none when ambidextrous)
+    pub fn off_hand_penalty(&self) -> i32 {
+        if self.rules().any(|r| *r == TraitRule::Ambidextrous) {
+            0
+        } else {
+            OFF_HAND_PENALTY
+        }
+    }
+
+    /// Extra penalty to rolls made in darkness
+    pub fn darkness_penalty(&self) -> i32 {
+        self.rules()
+            .map(|r| match r {
+                TraitRule::DarknessPenalty(v) => *v,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Follows the undead wound model (see `TraitRule::Undead`)
+    pub fn is_undead(&self) -> bool {
+        self.rules().any(|r| *r == TraitRule::Undead)
+    }
+
+    /// Bonus to willpower checks
+    pub fn willpower_bonus(&self) -> i32 {
+        self.rules()
+            .map(|r| match r {
+                TraitRule::WillpowerBonus(v) => *v,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Armor, Attributes, Character, Weapon};
+
+    #[test]
+    fn test_traits_apply_modifiers_and_rules() {
+        let plain = Character::new(
+            "Rurik",
+            Attributes::new(6, 6, 6, 6, 6, 6, 6, 6, 6),
+            6,
+            5,
+            Weapon::long_sword(),
+            Armor::none(),
+        );
+        #[cfg(feature = "ranged")]
+        let plain = plain.with_ranged(crate::modules::RangedWeapon::short_bow(), 5);
+        let veteran = plain
+            .clone()
+            .with_trait(Trait::one_eyed())
+            .with_trait(Trait::lame())
+            .with_trait(Trait::iron_will())
+            .with_trait(Trait::night_blind());
+
+        #[cfg(feature = "ranged")]
+        assert_eq!(veteran.ranged_roll_with(5), plain.ranged_roll_with(5) - 2);
+        assert_eq!(veteran.dodge_roll_with(5), plain.dodge_roll_with(5) - 2);
+        assert_eq!(
+            veteran.willpower_roll_with(5),
+            plain.willpower_roll_with(5) + 2
+        );
+        assert_eq!(veteran.traits.darkness_penalty(), -3);
+        assert_eq!(veteran.traits.off_hand_penalty(), OFF_HAND_PENALTY);
+        assert!(veteran.traits.has("Lame"));
+
+        let duelist = plain.with_trait(Trait::ambidextrous());
+        assert_eq!(duelist.traits.off_hand_penalty(), 0);
+    }
+}
@@ -0,0 +1,137 @@
+//! Transportation magic in combat
+//!
+//! Blinks and teleports are Transportation spells cast during an
+//! `Encounter`: a blink jumps the caster to a free cell within the spell's
+//! range (or, readied as a reaction, out of the path of a blow), and a
+//! teleport takes them out of the fight altogether. Failing badly is worse
+//! than failing: a casting that misses by `MISHAP_QUALITY` or more tears at
+//! the caster on the way through.
+
+use crate::modules::injuries::WoundDescription;
+use crate::modules::magic::{CastingResult, MagicBranch, MagicError, SpellTarget, TargetInfo};
+use crate::modules::positioning::MoveError;
+use crate::{Character, WoundLevel};
+use std::fmt;
+
+/// Casting quality at or below which a failed jump wounds the caster
+pub const MISHAP_QUALITY: i32 = -4;
+/// Casting quality at or below which the mishap wound is Severe
+pub const SEVERE_MISHAP_QUALITY: i32 = -7;
+/// Cause recorded on mishap wounds
+pub const MISHAP_CAUSE: &str = "Transportation mishap";
+
+/// Wound suffered by a caster whose jump failed with `quality`
+pub const fn mishap_wound(quality: i32) -> Option<WoundLevel> {
+    if quality <= SEVERE_MISHAP_QUALITY {
+        Some(WoundLevel::Severe)
+    } else if quality <= MISHAP_QUALITY {
+        Some(WoundLevel::Light)
+    } else {
+        None
+    }
+}
+
+/// A blink or teleport: the casting, and any mishap it caused
+#[derive(Debug)]
+pub struct Displacement {
+    pub casting: CastingResult,
+    /// Wound the caster took from a botched jump
+    pub mishap: Option<WoundLevel>,
+}
+
+impl Displacement {
+    /// The caster went where they meant to
+    pub fn arrived(&self) -> bool {
+        self.casting.success
+    }
+}
+
+/// Errors from blinking or teleporting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportError {
+    /// The spell isn't a Transportation spell
+    NotTransportation(String),
+    /// Blinks need a battlefield with the caster on it
+    NotPlaced,
+    /// The destination can't be stood in
+    Blocked(MoveError),
+    /// The combatant can't act this round
+    CannotAct,
+    /// No combatant in the encounter has this id
+    UnknownCombatant(usize),
+    Magic(MagicError),
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransportError::NotTransportation(spell) => {
+                write!(f, "{} is not a Transportation spell", spell)
+            }
+            TransportError::NotPlaced => write!(f, "Caster is not on a battlefield"),
+            TransportError::Blocked(e) => write!(f, "Cannot arrive there: {}", e),
+            TransportError::CannotAct => write!(f, "Combatant cannot act this round"),
+            TransportError::UnknownCombatant(id) => write!(f, "No combatant with id {}", id),
+            TransportError::Magic(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+impl From<MagicError> for TransportError {
+    fn from(e: MagicError) -> Self {
+        TransportError::Magic(e)
+    }
+}
+
+/// Check `spell_name` is a Transportation spell the caster knows
+pub fn check_spell(caster: &Character, spell_name: &str) -> Result<(), TransportError> {
+    let branch = caster
+        .magic
+        .as_ref()
+        .and_then(|m| m.spells.get(spell_name))
+        .map(|learned| learned.spell.branch)
+        .ok_or_else(|| MagicError::SpellNotKnown(spell_name.to_string()))?;
+    if branch != MagicBranch::Transportation {
+        return Err(TransportError::NotTransportation(spell_name.to_string()));
+    }
+    Ok(())
+}
+
+/// Cast a Transportation spell at `target`, wounding the caster on a mishap
+pub fn jump(
+    caster: &mut Character,
+    spell_name: &str,
+    target: SpellTarget,
+    info: &TargetInfo,
+    roll: i32,
+) -> Result<Displacement, TransportError> {
+    check_spell(caster, spell_name)?;
+    let casting = caster.cast(spell_name, target, info, roll)?;
+    let mishap = if casting.success {
+        None
+    } else {
+        mishap_wound(casting.quality)
+    };
+    if let Some(level) = mishap {
+        caster.inflict_wound(WoundDescription::new(level, MISHAP_CAUSE));
+    }
+    Ok(Displacement { casting, mishap })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mishaps_worsen_with_quality() {
+        assert_eq!(mishap_wound(-1), None);
+        assert_eq!(mishap_wound(MISHAP_QUALITY), Some(WoundLevel::Light));
+        assert_eq!(mishap_wound(-6), Some(WoundLevel::Light));
+        assert_eq!(
+            mishap_wound(SEVERE_MISHAP_QUALITY),
+            Some(WoundLevel::Severe)
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Overland travel over multiple days
+//!
+//! A `TravelParty` moves at the pace of its slowest member, which depends on
+//! armor, wounds, movement modifiers, encumbrance and exhaustion. A `Journey`
+//! lists legs of terrain plus the weather and any forced-march hours;
+//! `TravelParty::travel` resolves it day by day, adding exhaustion, rolling
+//! encounter checks, eating into any carried `Supplies`, and dating arrival
+//! on the campaign calendar.
+
+use crate::modules::calendar::CampaignDate;
+use crate::modules::encounter_table::EncounterTable;
+use crate::modules::exhaustion::Exhaustion;
+use crate::modules::modifiers::ModifierTarget;
+use crate::modules::supplies::{Supplies, SupplyWarning};
+use crate::Character;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Distance an unhindered traveller covers in a normal marching day
+pub const BASE_SPEED_KM: f32 = 30.0;
+/// Hours in a normal marching day
+pub const MARCH_HOURS: u32 = 8;
+/// Exhaustion points per forced-march hour beyond `MARCH_HOURS`
+pub const FORCED_MARCH_EXHAUSTION: i32 = 2;
+/// Slowest possible pace, however hindered
+const MIN_SPEED_KM: f32 = 5.0;
+
+/// Ground being crossed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Terrain {
+    Road,
+    Plains,
+    Forest,
+    Hills,
+    Desert,
+    Swamp,
+    Mountains,
+}
+
+impl Terrain {
+    /// Fraction of road speed achievable on this terrain
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            Terrain::Road => 1.0,
+            Terrain::Plains => 0.8,
+            Terrain::Forest | Terrain::Hills | Terrain::Desert => 0.6,
+            Terrain::Swamp | Terrain::Mountains => 0.4,
+        }
+    }
+}
+
+impl fmt::Display for Terrain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Terrain::Road => write!(f, "Road"),
+            Terrain::Plains => write!(f, "Plains"),
+            Terrain::Forest => write!(f, "Forest"),
+            Terrain::Hills => write!(f, "Hills"),
+            Terrain::Desert => write!(f, "Desert"),
+            Terrain::Swamp => write!(f, "Swamp"),
+            Terrain::Mountains => write!(f, "Mountains"),
+        }
+    }
+}
+
+/// Weather for the journey
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Weather {
+    Clear,
+    Rain,
+    Storm,
+    Snow,
+}
+
+impl Weather {
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            Weather::Clear => 1.0,
+            Weather::Rain => 0.8,
+            Weather::Storm | Weather::Snow => 0.5,
+        }
+    }
+}
+
+/// How burdened a traveller is, from carried load vs. strength
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Encumbrance {
+    Unencumbered,
+    Burdened,
+    Overloaded,
+}
+
+impl Encumbrance {
+    /// Carrying capacity is STR × 5 kg; up to twice that is Burdened
+    pub fn from_load(load_kg: f32, strength: i32) -> Self {
+        let capacity = (strength.max(1) * 5) as f32;
+        if load_kg <= capacity {
+            Encumbrance::Unencumbered
+        } else if load_kg <= capacity * 2.0 {
+            Encumbrance::Burdened
+        } else {
+            Encumbrance::Overloaded
+        }
+    }
+
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            Encumbrance::Unencumbered => 1.0,
+            Encumbrance::Burdened => 0.75,
+            Encumbrance::Overloaded => 0.5,
+        }
+    }
+
+    /// Extra exhaustion points per marching day
+    pub fn daily_exhaustion(&self) -> i32 {
+        match self {
+            Encumbrance::Unencumbered => 0,
+            Encumbrance::Burdened => 1,
+            Encumbrance::Overloaded => 2,
+        }
+    }
+}
+
+/// A party member on the road
+#[derive(Debug, Clone)]
+pub struct Traveller {
+    pub character: Character,
+    pub exhaustion: Exhaustion,
+    pub load_kg: f32,
+}
+
+impl Traveller {
+    pub fn new(character: Character) -> Self {
+        let exhaustion = Exhaustion::new(character.attributes.stamina());
+        Self {
+            character,
+            exhaustion,
+            load_kg: 0.0,
+        }
+    }
+
+    pub fn with_load(mut self, load_kg: f32) -> Self {
+        self.load_kg = load_kg;
+        self
+    }
+
+    pub fn encumbrance(&self) -> Encumbrance {
+        Encumbrance::from_load(self.load_kg, self.character.attributes.strength.get())
+    }
+
+    /// Kilometres per normal marching day on a road in clear weather
+    pub fn daily_speed(&self) -> f32 {
+        let c = &self.character;
+        let movement = c.armor.movement_penalty
+            + c.wounds.movement_penalty()
+            + c.modifiers.total(ModifierTarget::Movement);
+        let base = (BASE_SPEED_KM + 3.0 * movement as f32).max(MIN_SPEED_KM);
+        let fatigue = (1.0 + 0.1 * self.exhaustion.penalty() as f32).max(0.5);
+        base * self.encumbrance().speed_factor() * fatigue
+    }
+}
+
+/// A stretch of the route over one kind of terrain
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TravelLeg {
+    pub distance_km: f32,
+    pub terrain: Terrain,
+}
+
+/// A planned route with its travel conditions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Journey {
+    pub legs: Vec<TravelLeg>,
+    pub weather: Weather,
+    /// Hours marched each day beyond `MARCH_HOURS`
+    pub forced_march_hours: u32,
+}
+
+impl Default for Journey {
+    fn default() -> Self {
+        Self {
+            legs: Vec::new(),
+            weather: Weather::Clear,
+            forced_march_hours: 0,
+        }
+    }
+}
+
+impl Journey {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_leg(mut self, distance_km: f32, terrain: Terrain) -> Self {
+        self.legs.push(TravelLeg {
+            distance_km,
+            terrain,
+        });
+        self
+    }
+
+    pub fn with_weather(mut self, weather: Weather) -> Self {
+        self.weather = weather;
+        self
+    }
+
+    pub fn with_forced_march(mut self, hours: u32) -> Self {
+        self.forced_march_hours = hours;
+        self
+    }
+
+    pub fn total_distance(&self) -> f32 {
+        self.legs.iter().map(|l| l.distance_km).sum()
+    }
+}
+
+/// What happened on one day of travel
+#[derive(Debug, Clone, PartialEq)]
+pub struct TravelDay {
+    pub date: CampaignDate,
+    pub distance_km: f32,
+    /// Terrain where the party made camp
+    pub terrain: Terrain,
+    pub exhaustion_gained: i32,
+    pub encounter: Option<String>,
+    /// Supply warnings at the end of the day
+    pub warnings: Vec<SupplyWarning>,
+}
+
+/// Day-by-day account of a completed journey
+#[derive(Debug, Clone, PartialEq)]
+pub struct TravelReport {
+    pub days: Vec<TravelDay>,
+    pub arrival: CampaignDate,
+}
+
+impl TravelReport {
+    pub fn duration_days(&self) -> usize {
+        self.days.len()
+    }
+
+    pub fn total_distance(&self) -> f32 {
+        self.days.iter().map(|d| d.distance_km).sum()
+    }
+
+    pub fn encounters(&self) -> impl Iterator<Item = &str> {
+        self.days.iter().filter_map(|d| d.encounter.as_deref())
+    }
+}
+
+/// Travellers moving together
+#[derive(Debug, Clone, Default)]
+pub struct TravelParty {
+    pub members: Vec<Traveller>,
+    /// Shared food, water and ammunition; `None` ignores provisioning
+    pub supplies: Option<Supplies>,
+}
+
+impl TravelParty {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_member(mut self, member: Traveller) -> Self {
+        self.members.push(member);
+        self
+    }
+
+    pub fn with_supplies(mut self, supplies: Supplies) -> Self {
+        self.supplies = Some(supplies);
+        self
+    }
+
+    /// Kilometres per normal day on a clear road: the slowest member's pace
+    pub fn daily_speed(&self) -> f32 {
+        self.members
+            .iter()
+            .map(|m| m.daily_speed())
+            .fold(None, |slowest: Option<f32>, s| {
+                Some(slowest.map_or(s, |v| v.min(s)))
+            })
+            .unwrap_or(BASE_SPEED_KM)
+    }
+
+    /// Travel the whole journey starting on `start`, checking `encounters` each day
+    pub fn travel(
+        &mut self,
+        journey: &Journey,
+        start: CampaignDate,
+        encounters: Option<&EncounterTable>,
+    ) -> TravelReport {
+        let hours_per_day = (MARCH_HOURS + journey.forced_march_hours) as f32;
+        let mut legs = journey
+            .legs
+            .iter()
+            .filter(|l| l.distance_km > 0.0)
+            .copied()
+            .peekable();
+        let mut leg_remaining = legs.peek().map_or(0.0, |l| l.distance_km);
+        let mut days = Vec::new();
+        let mut date = start;
+
+        while let Some(leg) = legs.peek().copied() {
+            let hourly = self.daily_speed() / MARCH_HOURS as f32 * journey.weather.speed_factor();
+            let mut hours_left = hours_per_day;
+            let mut covered = 0.0;
+            let mut terrain = leg.terrain;
+
+            while hours_left > 0.0 {
+                let Some(leg) = legs.peek().copied() else {
+                    break;
+                };
+                terrain = leg.terrain;
+                let rate = hourly * leg.terrain.speed_factor();
+                let reachable = rate * hours_left;
+                if reachable >= leg_remaining {
+                    covered += leg_remaining;
+                    hours_left -= leg_remaining / rate;
+                    legs.next();
+                    leg_remaining = legs.peek().map_or(0.0, |l| l.distance_km);
+                } else {
+                    covered += reachable;
+                    leg_remaining -= reachable;
+                    hours_left = 0.0;
+                }
+            }
+
+            let deprivation = match self.supplies.as_mut() {
+                Some(supplies) => supplies.consume_day(self.members.len()),
+                None => Vec::new(),
+            };
+            let mut exhaustion_gained = 0;
+            for (i, member) in self.members.iter_mut().enumerate() {
+                let points = journey.forced_march_hours as i32 * FORCED_MARCH_EXHAUSTION
+                    + member.encumbrance().daily_exhaustion()
+                    + deprivation.get(i).map_or(0, |d| d.exhaustion());
+                member.exhaustion.add_points(points);
+                exhaustion_gained = exhaustion_gained.max(points);
+                // A night's rest recovers one stamina's worth of points
+                let threshold = member.exhaustion.stamina_threshold;
+                member.exhaustion.rest(threshold * 2);
+            }
+
+            let encounter = encounters
+                .and_then(|table| table.check(terrain))
+                .map(|e| e.name.clone());
+
+            let warnings = self
+                .supplies
+                .as_ref()
+                .map_or_else(Vec::new, |s| s.warnings(self.members.len()));
+
+            days.push(TravelDay {
+                date,
+                distance_km: covered,
+                terrain,
+                exhaustion_gained,
+                encounter,
+                warnings,
+            });
+            date = date.add_days(1);
+        }
+
+        TravelReport {
+            arrival: days.last().map_or(start, |d| d.date),
+            days,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Armor, Attributes, Weapon};
+
+    fn walker(armor: Armor) -> Traveller {
+        Traveller::new(Character::new(
+            "Walker",
+            Attributes::new(6, 6, 6, 5, 5, 5, 5, 5, 5),
+            5,
+            5,
+            Weapon::long_sword(),
+            armor,
+        ))
+    }
+
+    #[test]
+    fn test_party_moves_at_slowest_pace() {
+        let party = TravelParty::new()
+            .with_member(walker(Armor::leather()))
+            .with_member(walker(Armor::full_plate()).with_load(45.0));
+
+        assert_eq!(party.members[0].daily_speed(), 30.0);
+        // Full plate (-2 → 24 km) while Burdened (×0.75)
+        assert_eq!(party.members[1].encumbrance(), Encumbrance::Burdened);
+        assert_eq!(party.daily_speed(), 18.0);
+    }
+
+    #[test]
+    fn test_journey_arrival_date() {
+        let mut party = TravelParty::new().with_member(walker(Armor::leather()));
+        let journey = Journey::new()
+            .with_leg(45.0, Terrain::Road)
+            .with_leg(18.0, Terrain::Forest);
+        let start = CampaignDate::new(1012, 3, 29);
+
+        // 30 km/day on the road, 18 km/day in the forest
+        let report = party.travel(&journey, start, None);
+        assert_eq!(report.duration_days(), 3);
+        assert_eq!(report.arrival, CampaignDate::new(1012, 4, 1));
+        assert!((report.total_distance() - 63.0).abs() < 0.01);
+        assert_eq!(report.days[1].terrain, Terrain::Forest);
+        assert_eq!(report.encounters().count(), 0);
+    }
+
+    #[test]
+    fn test_forced_march_is_faster_but_tiring() {
+        let journey = Journey::new()
+            .with_leg(120.0, Terrain::Road)
+            .with_forced_march(4);
+        let mut party = TravelParty::new().with_member(walker(Armor::leather()));
+
+        let report = party.travel(&journey, CampaignDate::new(1, 1, 1), None);
+        assert!(report.duration_days() < 4);
+        assert_eq!(report.days[0].exhaustion_gained, 8);
+        assert!(party.members[0].exhaustion.points > 0);
+    }
+
+    #[test]
+    fn test_supplies_consumed_on_the_road() {
+        let mut party = TravelParty::new()
+            .with_member(walker(Armor::leather()))
+            .with_supplies(Supplies::new(2, 10));
+        let journey = Journey::new().with_leg(90.0, Terrain::Road);
+
+        let report = party.travel(&journey, CampaignDate::new(1, 1, 1), None);
+        assert_eq!(report.duration_days(), 3);
+        assert_eq!(
+            report.days[0].warnings,
+            vec![SupplyWarning::LowFood { days: 1 }]
+        );
+        assert_eq!(report.days[1].warnings, vec![SupplyWarning::OutOfFood]);
+        // Third day without food costs starvation exhaustion
+        assert_eq!(report.days[2].exhaustion_gained, 2);
+        assert_eq!(party.supplies.unwrap().water, 7);
+    }
+
+    #[test]
+    fn test_daily_encounter_checks() {
+        let table = EncounterTable::new(10).with_entry("Bandits", 1, &[]);
+        let mut party = TravelParty::new().with_member(walker(Armor::none()));
+        let journey = Journey::new()
+            .with_leg(60.0, Terrain::Plains)
+            .with_weather(Weather::Rain);
+
+        let report = party.travel(&journey, CampaignDate::new(1, 1, 1), Some(&table));
+        assert_eq!(report.encounters().count(), report.duration_days());
+    }
+}
@@ -0,0 +1,165 @@
+//! Decision deadlines for servers and bots
+//!
+//! An `Encounter` normally picks each defender's parry or dodge itself
+//! (`recommend_defense`). Given a `DefenseProvider` (a player's client, a bot)
+//! it asks that instead. With a `TurnTimer` the provider has `limit_ms` to
+//! answer: a late answer, or none at all, is replaced by the timer's default
+//! action and the encounter logs `EncounterEvent::TimedOut`.
+
+use crate::modules::ai::recommend_defense;
+use crate::{Character, DefenseAction};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Something outside the engine that decides how defenders react
+pub trait DefenseProvider: fmt::Debug + Send {
+    /// How `defender` meets `attacker`'s blow, or `None` if no answer came
+    ///
+    /// `deadline` is how long the engine will wait, for providers that block
+    /// on a player (e.g. with `Receiver::recv_timeout`).
+    fn choose_defense(
+        &mut self,
+        defender: &Character,
+        attacker: &Character,
+        deadline: Option<Duration>,
+    ) -> Option<DefenseAction>;
+}
+
+/// A provider shared between the encounter and whoever feeds it answers
+pub type SharedDefenseProvider = Arc<Mutex<dyn DefenseProvider>>;
+
+/// How long a provider may take to decide, and what happens if it doesn't
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TurnTimer {
+    pub limit_ms: u64,
+    /// Applied when the provider misses the deadline
+    pub default_action: DefenseAction,
+}
+
+impl TurnTimer {
+    pub fn new(limit_ms: u64, default_action: DefenseAction) -> Self {
+        Self {
+            limit_ms,
+            default_action,
+        }
+    }
+
+    pub fn limit(&self) -> Duration {
+        Duration::from_millis(self.limit_ms)
+    }
+}
+
+/// Ask `provider` for a defense, holding it to `timer` if there is one
+///
+/// Returns the defense and whether the provider timed out. Without a timer a
+/// provider that gives no answer gets the recommended defense instead.
+pub fn decide_defense(
+    provider: &SharedDefenseProvider,
+    timer: Option<&TurnTimer>,
+    defender: &Character,
+    attacker: &Character,
+) -> (DefenseAction, bool) {
+    let started = Instant::now();
+    let answer = provider
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .choose_defense(defender, attacker, timer.map(TurnTimer::limit));
+    match (answer, timer) {
+        (Some(action), Some(timer)) if started.elapsed() <= timer.limit() => (action, false),
+        (_, Some(timer)) => (timer.default_action, true),
+        (Some(action), None) => (action, false),
+        (None, None) => (recommend_defense(defender, attacker), false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::dice::DiceMode;
+    use crate::modules::encounter::{Encounter, EncounterEvent};
+    use crate::modules::modifiers::{Modifier, ModifierTarget};
+    use crate::{Armor, Attributes, Weapon};
+    use std::thread;
+
+    /// Always parries, after `delay_ms`
+    #[derive(Debug)]
+    struct Player {
+        delay_ms: u64,
+        asked: u32,
+    }
+
+    impl DefenseProvider for Player {
+        fn choose_defense(
+            &mut self,
+            _defender: &Character,
+            _attacker: &Character,
+            _deadline: Option<Duration>,
+        ) -> Option<DefenseAction> {
+            self.asked += 1;
+            thread::sleep(Duration::from_millis(self.delay_ms));
+            Some(DefenseAction::Parry)
+        }
+    }
+
+    fn duel(provider: SharedDefenseProvider, timer: Option<TurnTimer>) -> Encounter {
+        let fighter = |name: &str| {
+            let mut c = Character::new(
+                name,
+                Attributes::new(6, 6, 6, 5, 5, 5, 5, 5, 5),
+                6,
+                4,
+                Weapon::long_sword(),
+                Armor::leather(),
+            );
+            c.add_modifier(Modifier::new("Blunt", ModifierTarget::Damage, -100));
+            c.add_modifier(Modifier::new("Ward", ModifierTarget::Dodge, 100));
+            c
+        };
+        let mut encounter = Encounter::new().with_defense_provider(provider);
+        if let Some(timer) = timer {
+            encounter = encounter.with_turn_timer(timer);
+        }
+        encounter.rules.dice = DiceMode::Average;
+        encounter.add(fighter("Hero"), 1);
+        encounter.add(fighter("Rival"), 2);
+        encounter
+    }
+
+    #[test]
+    fn test_slow_provider_times_out_to_default_action() {
+        // A prompt answer is used as given: parrying, the ward doesn't help
+        let prompt = Arc::new(Mutex::new(Player {
+            delay_ms: 0,
+            asked: 0,
+        }));
+        let mut encounter = duel(
+            prompt.clone(),
+            Some(TurnTimer::new(1_000, DefenseAction::Dodge)),
+        );
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.defense_roll < 100));
+        assert!(encounter.events.is_empty());
+        assert_eq!(prompt.lock().unwrap().asked, 2);
+
+        // Too slow: each defender dodges by default and the timeout is logged
+        let slow = Arc::new(Mutex::new(Player {
+            delay_ms: 5,
+            asked: 0,
+        }));
+        let mut encounter = duel(slow, Some(TurnTimer::new(1, DefenseAction::Dodge)));
+        let results = encounter.run_round();
+        assert!(results.iter().all(|r| r.defense_roll >= 100));
+        assert_eq!(encounter.events.len(), 2);
+        for combatant in [0, 1] {
+            assert!(encounter.events.contains(&EncounterEvent::TimedOut {
+                combatant,
+                action: DefenseAction::Dodge,
+            }));
+        }
+    }
+}
@@ -0,0 +1,469 @@
+//! Terminal combat front-end built on ratatui
+//!
+//! Enabled with the `tui` feature. `CombatTui` runs an `Encounter` one turn at
+//! a time with `Encounter::step`, and renders a panel per combatant, the
+//! combat log, and a menu of what the combatant up next may do (from
+//! `Encounter::legal_actions`); hosts feed it key presses and draw it each
+//! frame (see `examples/tui_combat.rs`).
+
+use crate::modules::encounter::{ActionKind, CombatantId, Encounter, WeaponSwitch, Wielding};
+use crate::modules::maneuvers::CombatManeuver;
+use crate::modules::turn_timer::{DefenseProvider, SharedDefenseProvider};
+use crate::{Character, DefenseAction};
+use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Actions offered in the menu panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuAction {
+    /// Take the turn; the defender parries
+    AttackParry,
+    /// Take the turn; the defender dodges
+    AttackDodge,
+    /// Take the turn and let the defender choose (see `ai::recommend_defense`)
+    AutoTurn,
+    /// Sheathe the weapon in hand and draw the other, spending the turn
+    SwitchWeapon,
+    /// Fight with this maneuver from this turn on
+    Maneuver(CombatManeuver),
+    Quit,
+}
+
+impl fmt::Display for MenuAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MenuAction::AttackParry => write!(f, "Attack (defender parries)"),
+            MenuAction::AttackDodge => write!(f, "Attack (defender dodges)"),
+            MenuAction::AutoTurn => write!(f, "Auto turn"),
+            MenuAction::SwitchWeapon => write!(f, "Switch weapon"),
+            MenuAction::Maneuver(maneuver) => write!(f, "Maneuver: {}", maneuver),
+            MenuAction::Quit => write!(f, "Quit"),
+        }
+    }
+}
+
+/// Hands the defense picked from the menu to the encounter
+#[derive(Debug, Default)]
+struct ChosenDefense(Option<DefenseAction>);
+
+impl DefenseProvider for ChosenDefense {
+    fn choose_defense(
+        &mut self,
+        _defender: &Character,
+        _attacker: &Character,
+        _deadline: Option<Duration>,
+    ) -> Option<DefenseAction> {
+        self.0
+    }
+}
+
+/// State of a terminal fight
+pub struct CombatTui {
+    pub encounter: Encounter,
+    pub log: Vec<String>,
+    pub selected: usize,
+    pub quit: bool,
+    defense: Arc<Mutex<ChosenDefense>>,
+}
+
+impl CombatTui {
+    /// One-on-one fight between two characters
+    pub fn new(first: Character, second: Character) -> Self {
+        let log = format!("{} faces {}", first.name, second.name);
+        let mut encounter = Encounter::new();
+        encounter.add(first, 0);
+        encounter.add(second, 1);
+        let mut tui = Self::from_encounter(encounter);
+        tui.log.push(log);
+        tui
+    }
+
+    /// Fight out any encounter, replacing its defense provider with the menu
+    pub fn from_encounter(encounter: Encounter) -> Self {
+        let defense = Arc::new(Mutex::new(ChosenDefense::default()));
+        let provider: SharedDefenseProvider = defense.clone();
+        Self {
+            encounter: encounter.with_defense_provider(provider),
+            log: Vec::new(),
+            selected: 0,
+            quit: false,
+            defense,
+        }
+    }
+
+    /// Check if the fight is over (at most one team can still fight)
+    pub fn is_finished(&self) -> bool {
+        self.encounter.is_over()
+    }
+
+    /// Check if the host loop should keep running
+    pub fn is_running(&self) -> bool {
+        !self.quit
+    }
+
+    /// The combatant whose turn is next, while the fight goes on
+    pub fn up_next(&self) -> Option<CombatantId> {
+        if self.is_finished() {
+            return None;
+        }
+        match self.encounter.initiative.first() {
+            Some(&id) => Some(id),
+            None => self
+                .encounter
+                .turn_order()
+                .into_iter()
+                .find(|&id| self.encounter.combatants[id].is_fighting()),
+        }
+    }
+
+    /// Menu for the combatant up next, offering what its legal actions allow
+    pub fn menu(&self) -> Vec<MenuAction> {
+        let mut menu = Vec::new();
+        if let Some(id) = self.up_next() {
+            let legal = self.encounter.legal_actions(id);
+            if legal
+                .iter()
+                .any(|a| matches!(a, ActionKind::Attack | ActionKind::Shoot))
+            {
+                menu.extend([MenuAction::AttackParry, MenuAction::AttackDodge]);
+            }
+            menu.push(MenuAction::AutoTurn);
+            if legal.contains(&ActionKind::SwitchWeapon) {
+                menu.push(MenuAction::SwitchWeapon);
+            }
+            let current = self.encounter.combatants[id].stance.current_maneuver;
+            menu.extend(legal.iter().filter_map(|action| match *action {
+                ActionKind::Maneuver(m) if m != current => Some(MenuAction::Maneuver(m)),
+                _ => None,
+            }));
+        }
+        menu.push(MenuAction::Quit);
+        menu
+    }
+
+    /// Get the currently highlighted menu action
+    pub fn selected_action(&self) -> MenuAction {
+        let menu = self.menu();
+        menu[self.selected.min(menu.len() - 1)]
+    }
+
+    /// Handle a key press: arrows move the selection, Enter applies it, q quits
+    pub fn handle_key(&mut self, key: KeyCode) {
+        let len = self.menu().len();
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.min(len - 1).checked_sub(1).unwrap_or(len - 1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected.min(len - 1) + 1) % len;
+            }
+            KeyCode::Enter => self.apply(self.selected_action()),
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            _ => {}
+        }
+    }
+
+    /// Apply a menu action to the combatant up next
+    ///
+    /// Attacks and weapon switches resolve their turn with `Encounter::step`;
+    /// choosing a maneuver leaves the turn to be taken.
+    pub fn apply(&mut self, action: MenuAction) {
+        if action == MenuAction::Quit {
+            self.quit = true;
+            return;
+        }
+        let Some(id) = self.up_next() else {
+            return;
+        };
+        let name = self.encounter.combatants[id].character.name.clone();
+        let defense = match action {
+            MenuAction::AttackParry => Some(DefenseAction::Parry),
+            MenuAction::AttackDodge => Some(DefenseAction::Dodge),
+            MenuAction::SwitchWeapon => {
+                if self
+                    .encounter
+                    .switch_weapon(id, WeaponSwitch::SheatheAndDraw)
+                {
+                    self.log
+                        .push(format!("{} draws {}", name, self.wielded(id)));
+                }
+                None
+            }
+            MenuAction::Maneuver(maneuver) => {
+                match self.encounter.set_maneuver(id, maneuver) {
+                    Ok(()) => self.log.push(format!("{} adopts {}", name, maneuver)),
+                    Err(e) => self.log.push(e.to_string()),
+                }
+                return;
+            }
+            _ => None,
+        };
+
+        self.choose_defense(defense);
+        let round = self.encounter.round;
+        let results = self.encounter.step();
+        self.choose_defense(None);
+        self.selected = 0;
+
+        if results.is_empty() {
+            self.log
+                .push(format!("Round {}: {} makes no attack", round, name));
+        }
+        for result in &results {
+            self.log.push(format!("Round {}: {}", round, result));
+        }
+
+        if self.is_finished() {
+            let victors: Vec<&str> = self
+                .encounter
+                .combatants
+                .iter()
+                .filter(|c| c.is_fighting())
+                .map(|c| c.character.name.as_str())
+                .collect();
+            self.log.push(match victors.as_slice() {
+                [] => "No one is left standing.".to_string(),
+                [victor] => format!("{} is victorious!", victor),
+                _ => format!("{} are victorious!", victors.join(", ")),
+            });
+        }
+    }
+
+    fn choose_defense(&self, defense: Option<DefenseAction>) {
+        self.defense
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .0 = defense;
+    }
+
+    /// Name of the weapon the combatant has in hand
+    fn wielded(&self, id: CombatantId) -> &str {
+        let combatant = &self.encounter.combatants[id];
+        let character = &combatant.character;
+        match (combatant.wielding, &character.ranged_weapon) {
+            (Wielding::Ranged, Some(ranged)) => &ranged.name,
+            _ => &character.weapon.name,
+        }
+    }
+
+    /// Draw combatant panels, the log and the action menu
+    pub fn render(&self, frame: &mut Frame) {
+        let menu = self.menu();
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(7),
+                Constraint::Min(5),
+                Constraint::Length(menu.len() as u16 + 2),
+            ])
+            .split(frame.area());
+        let count = self.encounter.combatants.len().max(1) as u32;
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints((0..count).map(|_| Constraint::Ratio(1, count)))
+            .split(rows[0]);
+
+        for (id, area) in columns.iter().enumerate() {
+            if id < self.encounter.combatants.len() {
+                self.render_fighter(frame, *area, id);
+            }
+        }
+
+        let visible = rows[1].height.saturating_sub(2) as usize;
+        let start = self.log.len().saturating_sub(visible);
+        let log: Vec<ListItem> = self.log[start..]
+            .iter()
+            .map(|line| ListItem::new(line.as_str()))
+            .collect();
+        frame.render_widget(
+            List::new(log).block(Block::default().borders(Borders::ALL).title("Combat Log")),
+            rows[1],
+        );
+
+        let title = match self.up_next() {
+            Some(id) => format!(
+                "Round {} - {} acts",
+                self.encounter.round, self.encounter.combatants[id].character.name
+            ),
+            None => "Fight over - q to quit".to_string(),
+        };
+        let items: Vec<ListItem> = menu.iter().map(|a| ListItem::new(a.to_string())).collect();
+        let selected = self.selected.min(menu.len() - 1);
+        let mut menu_state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("> "),
+            rows[2],
+            &mut menu_state,
+        );
+    }
+
+    fn render_fighter(&self, frame: &mut Frame, area: Rect, id: CombatantId) {
+        let combatant = &self.encounter.combatants[id];
+        let fighter = &combatant.character;
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(fighter.name.as_str());
+        if self.up_next() == Some(id) {
+            block = block.border_style(Style::default().fg(Color::Yellow));
+        }
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Length(1)])
+            .split(inner);
+
+        let status = if !fighter.is_alive() {
+            "Dead"
+        } else if !fighter.can_act() {
+            "Incapacitated"
+        } else if !combatant.is_fighting() {
+            "Out of the fight"
+        } else {
+            "Ready"
+        };
+        let lines = vec![
+            Line::from(format!("{} / {}", self.wielded(id), fighter.armor.name)),
+            Line::from(format!(
+                "Skill {}  Dodge {}  Penalty {:+}",
+                fighter.weapon_skill(),
+                fighter.dodge_skill(),
+                fighter.wounds.movement_penalty()
+            )),
+            Line::from(format!(
+                "{} - {}",
+                status, combatant.stance.current_maneuver
+            )),
+        ];
+        frame.render_widget(Paragraph::new(lines), parts[0]);
+
+        let w = &fighter.wounds;
+        let load = (w.light + w.severe * 4 + w.critical * 12).clamp(0, 24);
+        frame.render_widget(
+            Gauge::default()
+                .gauge_style(Style::default().fg(Color::Red))
+                .ratio(load as f64 / 24.0)
+                .label(format!("L{} S{} C{}", w.light, w.severe, w.critical)),
+            parts[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::dice::DiceMode;
+    use crate::modules::ranged_combat::RangedWeapon;
+    use crate::{Armor, Attributes, Weapon, WoundLevel};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn fighter(name: &str) -> Character {
+        Character::new(
+            name,
+            Attributes::new(7, 7, 7, 7, 7, 7, 7, 7, 7),
+            7,
+            5,
+            Weapon::long_sword(),
+            Armor::leather(),
+        )
+    }
+
+    #[test]
+    fn test_menu_navigation_wraps() {
+        let mut tui = CombatTui::new(fighter("Aldric"), fighter("Grimwald"));
+        assert_eq!(tui.selected_action(), MenuAction::AttackParry);
+
+        tui.handle_key(KeyCode::Up);
+        assert_eq!(tui.selected_action(), MenuAction::Quit);
+
+        tui.handle_key(KeyCode::Down);
+        tui.handle_key(KeyCode::Down);
+        assert_eq!(tui.selected_action(), MenuAction::AttackDodge);
+    }
+
+    #[test]
+    fn test_turns_alternate_and_rounds_advance() {
+        let mut tui = CombatTui::new(fighter("Aldric"), fighter("Grimwald"));
+        tui.encounter.rules.dice = DiceMode::seeded(7);
+
+        tui.apply(MenuAction::AttackParry);
+        assert_eq!(tui.up_next(), Some(1));
+        assert_eq!(tui.encounter.round, 1);
+        assert_eq!(tui.log.len(), 2);
+
+        tui.apply(MenuAction::AutoTurn);
+        assert_eq!(tui.up_next(), Some(0));
+        assert_eq!(tui.encounter.round, 2);
+    }
+
+    #[test]
+    fn test_menu_follows_legal_actions() {
+        let mut archer = fighter("Elyndra");
+        archer.ranged_weapon = Some(RangedWeapon::long_bow());
+        let mut tui = CombatTui::new(archer, fighter("Grimwald"));
+        assert!(tui.menu().contains(&MenuAction::SwitchWeapon));
+        assert!(tui
+            .menu()
+            .contains(&MenuAction::Maneuver(CombatManeuver::AllOutAttack)));
+
+        // A maneuver is set up without taking the turn
+        tui.apply(MenuAction::Maneuver(CombatManeuver::AllOutAttack));
+        assert_eq!(tui.up_next(), Some(0));
+        assert!(!tui
+            .menu()
+            .contains(&MenuAction::Maneuver(CombatManeuver::AllOutAttack)));
+
+        // Switching weapons spends it
+        tui.apply(MenuAction::SwitchWeapon);
+        assert_eq!(tui.up_next(), Some(1));
+        assert_eq!(tui.encounter.combatants[0].wielding, Wielding::Ranged);
+        assert!(tui.log.iter().any(|line| line == "Elyndra draws Long Bow"));
+    }
+
+    #[test]
+    fn test_finished_fight_ignores_attacks() {
+        let mut tui = CombatTui::new(fighter("Aldric"), fighter("Grimwald"));
+        tui.encounter.combatants[1]
+            .character
+            .wounds
+            .add_wound(WoundLevel::Critical);
+        assert!(tui.is_finished());
+
+        tui.apply(MenuAction::AttackParry);
+        assert_eq!(tui.log.len(), 1);
+
+        tui.handle_key(KeyCode::Char('q'));
+        assert!(!tui.is_running());
+    }
+
+    #[test]
+    fn test_render_shows_panels() {
+        let tui = CombatTui::new(fighter("Aldric"), fighter("Grimwald"));
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| tui.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("Aldric"));
+        assert!(screen.contains("Grimwald"));
+        assert!(screen.contains("Combat Log"));
+        assert!(screen.contains("Attack (defender parries)"));
+    }
+}
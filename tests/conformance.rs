@@ -0,0 +1,302 @@
+//! Golden-master conformance tests against the Draft 0.4 rules
+//!
+//! Each YAML file in `tests/conformance/` names a rulebook section and lists
+//! cases: two fighters, the defense, the scripted d10s (attacker's first) and
+//! the results the rules call for. Every case is run through
+//! `combat_round_with_dice` and all mismatches are reported together, tagged
+//! with the file, case and section, so a rule fix is checked against the book
+//! rather than against whatever the code did before.
+
+use serde::Deserialize;
+use std::fmt::{Debug, Write};
+use std::fs;
+use std::path::Path;
+use steelkilt::modules::dice::ScriptedDice;
+use steelkilt::modules::ruleset::Ruleset;
+use steelkilt::{
+    combat_round_with_dice, Armor, Attributes, Character, DefenseAction, Fatigue, Weapon,
+    WoundLevel,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Suite {
+    section: String,
+    cases: Vec<Case>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Case {
+    name: String,
+    /// The rule being checked, as the rulebook puts it
+    rule: String,
+    /// Overrides the suite's section for this case
+    section: Option<String>,
+    #[serde(default)]
+    attacker: FighterSpec,
+    #[serde(default)]
+    defender: FighterSpec,
+    defense: Defense,
+    dice: [i32; 2],
+    #[serde(default)]
+    rules: RuleSpec,
+    expect: Expected,
+}
+
+/// A fighter with average attributes and skills unless stated
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FighterSpec {
+    strength: i32,
+    constitution: i32,
+    weapon_skill: i32,
+    dodge_skill: i32,
+    weapon: String,
+    armor: String,
+    fatigue: i32,
+    wounds: WoundSpec,
+}
+
+impl Default for FighterSpec {
+    fn default() -> Self {
+        Self {
+            strength: 5,
+            constitution: 5,
+            weapon_skill: 5,
+            dodge_skill: 5,
+            weapon: "long_sword".to_string(),
+            armor: "none".to_string(),
+            fatigue: 0,
+            wounds: WoundSpec::default(),
+        }
+    }
+}
+
+impl FighterSpec {
+    fn build(&self, name: &str) -> Result<Character, String> {
+        let weapon = match self.weapon.as_str() {
+            "dagger" => Weapon::dagger(),
+            "long_sword" => Weapon::long_sword(),
+            "two_handed_sword" => Weapon::two_handed_sword(),
+            "club" => Weapon::club(),
+            "mace" => Weapon::mace(),
+            "war_hammer" => Weapon::war_hammer(),
+            "spear" => Weapon::spear(),
+            "shield" => Weapon::shield(),
+            other => return Err(format!("unknown weapon '{}'", other)),
+        };
+        let armor = match self.armor.as_str() {
+            "none" => Armor::none(),
+            "heavy_cloth" => Armor::heavy_cloth(),
+            "leather" => Armor::leather(),
+            "chain_mail" => Armor::chain_mail(),
+            "plate" => Armor::plate(),
+            "full_plate" => Armor::full_plate(),
+            other => return Err(format!("unknown armor '{}'", other)),
+        };
+        let attributes = Attributes::new(self.strength, 5, self.constitution, 5, 5, 5, 5, 5, 5);
+        let mut character = Character::new(
+            name,
+            attributes,
+            self.weapon_skill,
+            self.dodge_skill,
+            weapon,
+            armor,
+        );
+        character.fatigue = Fatigue::clamped(self.fatigue);
+        character.wounds.light = self.wounds.light;
+        character.wounds.severe = self.wounds.severe;
+        character.wounds.critical = self.wounds.critical;
+        Ok(character)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WoundSpec {
+    light: i32,
+    severe: i32,
+    critical: i32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Defense {
+    Parry,
+    Dodge,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RuleSpec {
+    sparring: bool,
+    dying_state: bool,
+    blood_loss: bool,
+    exhaustion: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum WoundExpect {
+    None,
+    Light,
+    Severe,
+    Critical,
+}
+
+impl From<Option<WoundLevel>> for WoundExpect {
+    fn from(level: Option<WoundLevel>) -> Self {
+        match level {
+            None => WoundExpect::None,
+            Some(WoundLevel::Light) => WoundExpect::Light,
+            Some(WoundLevel::Severe) => WoundExpect::Severe,
+            Some(WoundLevel::Critical) => WoundExpect::Critical,
+        }
+    }
+}
+
+/// Only the stated results are checked
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Expected {
+    attack_roll: Option<i32>,
+    defense_roll: Option<i32>,
+    hit: Option<bool>,
+    damage: Option<i32>,
+    wound: Option<WoundExpect>,
+    died: Option<bool>,
+    dying: Option<bool>,
+    /// The defender's wound tally afterwards, with stacking applied
+    wounds: Option<WoundSpec>,
+    stun: Option<i32>,
+    blood_loss: Option<i32>,
+    /// Each fighter's fatigue afterwards
+    attacker_fatigue: Option<i32>,
+    defender_fatigue: Option<i32>,
+}
+
+/// Run one case, returning every way it departs from the rules
+fn run_case(case: &Case) -> Result<Vec<String>, String> {
+    let mut attacker = case.attacker.build("Attacker")?;
+    let mut defender = case.defender.build("Defender")?;
+    let action = match case.defense {
+        Defense::Parry => DefenseAction::Parry,
+        Defense::Dodge => DefenseAction::Dodge,
+    };
+    let ruleset = Ruleset::new()
+        .with_sparring(case.rules.sparring)
+        .with_dying_state(case.rules.dying_state)
+        .with_blood_loss(case.rules.blood_loss)
+        .with_exhaustion(case.rules.exhaustion);
+    let mut dice = ScriptedDice::new(case.dice);
+    let result = combat_round_with_dice(&mut attacker, &mut defender, action, &ruleset, &mut dice);
+
+    let wounds = WoundSpec {
+        light: defender.wounds.light,
+        severe: defender.wounds.severe,
+        critical: defender.wounds.critical,
+    };
+    let expect = &case.expect;
+    let mut failures = Vec::new();
+    check(
+        &mut failures,
+        "attack roll",
+        expect.attack_roll,
+        result.attack_roll,
+    );
+    check(
+        &mut failures,
+        "defense roll",
+        expect.defense_roll,
+        result.defense_roll,
+    );
+    check(&mut failures, "hit", expect.hit, result.hit);
+    check(&mut failures, "damage", expect.damage, result.damage.get());
+    check(
+        &mut failures,
+        "wound",
+        expect.wound,
+        result.wound_level.into(),
+    );
+    check(&mut failures, "died", expect.died, result.defender_died);
+    check(&mut failures, "dying", expect.dying, result.defender_dying);
+    check(&mut failures, "wounds", expect.wounds, wounds);
+    check(&mut failures, "stun", expect.stun, defender.stun);
+    check(
+        &mut failures,
+        "blood loss",
+        expect.blood_loss,
+        defender.blood_loss,
+    );
+    check(
+        &mut failures,
+        "attacker fatigue",
+        expect.attacker_fatigue,
+        attacker.fatigue.get(),
+    );
+    check(
+        &mut failures,
+        "defender fatigue",
+        expect.defender_fatigue,
+        defender.fatigue.get(),
+    );
+    Ok(failures)
+}
+
+fn check<T: PartialEq + Debug>(
+    failures: &mut Vec<String>,
+    what: &str,
+    expected: Option<T>,
+    actual: T,
+) {
+    if let Some(expected) = expected {
+        if expected != actual {
+            failures.push(format!(
+                "{}: expected {:?}, got {:?}",
+                what, expected, actual
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_rules_conformance() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance");
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .expect("conformance directory")
+        .map(|entry| entry.expect("directory entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty(), "no suites in {}", dir.display());
+
+    let mut report = String::new();
+    let mut cases = 0;
+    for path in &files {
+        let file = path.file_name().unwrap().to_string_lossy();
+        let text = fs::read_to_string(path).expect("readable suite");
+        let suite: Suite = match serde_yaml::from_str(&text) {
+            Ok(suite) => suite,
+            Err(e) => {
+                writeln!(report, "{}: {}", file, e).unwrap();
+                continue;
+            }
+        };
+        for case in &suite.cases {
+            cases += 1;
+            let section = case.section.as_ref().unwrap_or(&suite.section);
+            let failures = run_case(case).unwrap_or_else(|e| vec![e]);
+            for failure in failures {
+                writeln!(
+                    report,
+                    "{} / {} (section {}, {}): {}",
+                    file, case.name, section, case.rule, failure
+                )
+                .unwrap();
+            }
+        }
+    }
+    assert!(report.is_empty(), "rules conformance failures:\n{}", report);
+    assert!(cases > 0);
+}
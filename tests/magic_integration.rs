@@ -3,7 +3,8 @@
 //! Tests magic following Draft RPG Chapter 5
 
 use steelkilt::modules::magic::{
-    MagicBranch, MagicError, MagicUser, Spell, SpellDifficulty, SpellDuration, SpellRange,
+    MagicBranch, MagicError, MagicUser, Spell, SpellComponents, SpellDifficulty, SpellDuration,
+    SpellRange,
 };
 
 /// Helper to create a test spell
@@ -16,6 +17,7 @@ fn create_test_spell(name: &str, branch: MagicBranch, difficulty: SpellDifficult
         casting_time: 1,
         range: SpellRange::Short(10),
         duration: SpellDuration::Minutes(10),
+        components: SpellComponents::default(),
     }
 }
 
@@ -345,6 +347,7 @@ fn test_spell_range_types() {
         casting_time: 1,
         range: personal,
         duration: SpellDuration::Minutes(10),
+        components: SpellComponents::default(),
     };
 
     let spell2 = Spell {
@@ -355,6 +358,7 @@ fn test_spell_range_types() {
         casting_time: 1,
         range: touch,
         duration: SpellDuration::Instant,
+        components: SpellComponents::default(),
     };
 
     assert_eq!(spell1.name, "Self Buff");
@@ -385,6 +389,7 @@ fn test_spell_duration_types() {
         casting_time: 1,
         range: SpellRange::Short(10),
         duration: instant,
+        components: SpellComponents::default(),
     };
 
     let _spell2 = Spell {
@@ -395,6 +400,7 @@ fn test_spell_duration_types() {
         casting_time: 1,
         range: SpellRange::Touch,
         duration: concentration,
+        components: SpellComponents::default(),
     };
 
     // Verify all duration types compile
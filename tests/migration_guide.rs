@@ -0,0 +1,161 @@
+//! Migration guide for the deprecated `weapon_skill` and `dodge_skill` fields
+//!
+//! Old code read and wrote the fields directly. They are still plain `i32`s
+//! and still work (the accessors clamp whatever was written), but new code
+//! goes through `weapon_skill()`/`set_weapon_skill()` and the dodge
+//! equivalents, or keeps them in a `SkillSet` with `sync_skills_to` and
+//! `sync_skills_from`. Each step below is checked to fight exactly as before.
+//! The last test walks through the bounded number types that replaced other
+//! `i32` fields (see `CHANGELOG.md`).
+#![allow(deprecated)]
+
+use steelkilt::modules::dice::DiceMode;
+use steelkilt::modules::ruleset::Ruleset;
+use steelkilt::{
+    combat_round_with_dice, Armor, AttributeScore, Attributes, BoundsError, Character, Damage,
+    DefenseAction, SkillLevel, Weapon,
+};
+
+fn base(name: &str) -> Character {
+    Character::new(
+        name,
+        Attributes::new(7, 6, 6, 5, 5, 5, 5, 5, 5),
+        0,
+        0,
+        Weapon::long_sword(),
+        Armor::leather(),
+    )
+}
+
+/// Before: skills written straight into the fields
+fn old_style(name: &str, weapon: i32, dodge: i32) -> Character {
+    let mut character = base(name);
+    character.weapon_skill = weapon;
+    character.dodge_skill = dodge;
+    character
+}
+
+/// After: the same through the accessors
+fn new_style(name: &str, weapon: i32, dodge: i32) -> Character {
+    let mut character = base(name);
+    character.set_weapon_skill(SkillLevel::clamped(weapon));
+    character.set_dodge_skill(SkillLevel::clamped(dodge));
+    character
+}
+
+/// Fight to the end on seeded dice, returning the log
+fn duel(mut a: Character, mut b: Character, seed: u64) -> Vec<String> {
+    let ruleset = Ruleset::new();
+    let mut dice = DiceMode::seeded(seed);
+    let mut log = Vec::new();
+    for round in 0..50 {
+        if !a.can_act() || !b.can_act() {
+            break;
+        }
+        let (attacker, defender) = if round % 2 == 0 {
+            (&mut a, &mut b)
+        } else {
+            (&mut b, &mut a)
+        };
+        let action = if round % 3 == 0 {
+            DefenseAction::Dodge
+        } else {
+            DefenseAction::Parry
+        };
+        log.push(
+            combat_round_with_dice(attacker, defender, action, &ruleset, &mut dice).to_string(),
+        );
+    }
+    log
+}
+
+#[test]
+fn test_old_style_fields_fight_like_the_accessors() {
+    let old = old_style("Rurik", 7, 4);
+    let new = new_style("Rurik", 7, 4);
+    assert_eq!(new.weapon_skill(), old.weapon_skill);
+    assert_eq!(old.dodge_skill(), new.dodge_skill);
+
+    let built = Character::builder()
+        .name("Rurik")
+        .attributes(Attributes::new(7, 6, 6, 5, 5, 5, 5, 5, 5))
+        .weapon_skill(7)
+        .dodge_skill(4)
+        .weapon(Weapon::long_sword())
+        .armor(Armor::leather())
+        .build();
+
+    for seed in 0..20 {
+        let expected = duel(old_style("Rurik", 7, 4), old_style("Orm", 5, 6), seed);
+        assert!(!expected.is_empty());
+        assert_eq!(duel(new.clone(), new_style("Orm", 5, 6), seed), expected);
+        assert_eq!(duel(built.clone(), old_style("Orm", 5, 6), seed), expected);
+    }
+}
+
+#[test]
+fn test_old_style_field_arithmetic_matches_the_accessors() {
+    // Training and rolls written against the raw `i32` fields
+    let mut character = old_style("Rurik", 6, 4);
+    character.weapon_skill += 1;
+    character.dodge_skill -= 1;
+    assert_eq!(character.weapon_skill(), 7);
+    assert_eq!(character.dodge_skill(), 3);
+
+    let roll = 5;
+    assert_eq!(
+        character.weapon_skill + roll,
+        character.weapon_skill().get() + roll
+    );
+
+    // Out-of-range writes read back clamped through the accessors
+    character.weapon_skill = 15;
+    character.dodge_skill = -2;
+    assert_eq!(character.weapon_skill(), SkillLevel::MAX);
+    assert_eq!(character.dodge_skill(), SkillLevel::MIN);
+}
+
+#[cfg(feature = "skills")]
+#[test]
+fn test_skill_set_round_trip_keeps_the_fields_in_step() {
+    use steelkilt::modules::skills::{SkillSet, DODGE_SKILL, WEAPON_SKILL};
+
+    let mut character = old_style("Rurik", 7, 4);
+    let mut skills = SkillSet::new(10);
+    character.sync_skills_to(&mut skills);
+    assert_eq!(skills.get_skill_level(WEAPON_SKILL), 7);
+    assert_eq!(skills.get_skill_level(DODGE_SKILL), 4);
+
+    skills.raise_skill(DODGE_SKILL).unwrap();
+    character.sync_skills_from(&skills);
+    assert_eq!(character.dodge_skill, 5);
+    assert_eq!(character.dodge_skill(), 5);
+    assert_eq!(character.weapon_skill(), 7);
+
+    // Syncing again updates rather than duplicates
+    character.set_weapon_skill(SkillLevel::clamped(8));
+    character.sync_skills_to(&mut skills);
+    assert_eq!(skills.get_skill_level(WEAPON_SKILL), 8);
+    assert_eq!(skills.skills.len(), 2);
+}
+
+#[test]
+fn test_bounded_fields_convert_as_the_changelog_describes() {
+    let mut attributes = Attributes::new(7, 6, 6, 5, 5, 5, 5, 5, 5);
+    assert_eq!(attributes.strength.get(), 7);
+    assert_eq!(i32::from(attributes.strength), 7);
+
+    attributes.strength = AttributeScore::clamped(12);
+    assert_eq!(attributes.strength, AttributeScore::MAX);
+    assert_eq!(
+        AttributeScore::try_from(12),
+        Err(BoundsError::Attribute(12))
+    );
+
+    let mut weapon = Weapon::long_sword();
+    let damage = weapon.damage.get();
+    weapon.damage = weapon.damage.saturating_add(2);
+    assert_eq!(weapon.damage, damage + 2);
+    assert!(weapon.damage > 0);
+    assert_eq!(weapon.damage.checked_add(Damage::MAX), None);
+}
@@ -0,0 +1,82 @@
+//! Allocation counts for pooled versus freshly built encounters
+//!
+//! A counting global allocator backs this test binary only. The figures
+//! quoted in `benches/BASELINES.md` come from the same measurement.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use steelkilt::modules::dice::DiceMode;
+use steelkilt::modules::encounter::Encounter;
+use steelkilt::modules::npc::{generate_batch, NpcConstraints};
+use steelkilt::modules::pool::EncounterPool;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const FIGHTS: u64 = 20;
+
+/// Average allocations per call of `f` over `FIGHTS` seeds
+fn per_fight(mut f: impl FnMut(u64)) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for seed in 0..FIGHTS {
+        f(seed);
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) / FIGHTS as usize
+}
+
+fn ten_vs_ten() -> Encounter {
+    let roster = generate_batch(20, &NpcConstraints::new().no_magic(), 11).unwrap();
+    let mut encounter = Encounter::new();
+    for (i, npc) in roster.into_iter().enumerate() {
+        encounter.add(npc, (i % 2) as u32 + 1);
+    }
+    encounter
+}
+
+#[test]
+fn test_pool_allocates_less_than_fresh_encounters() {
+    let template = ten_vs_ten();
+    let fresh = per_fight(|seed| {
+        let mut encounter = template.clone();
+        encounter.rules.dice = DiceMode::seeded(seed);
+        let mut results = Vec::new();
+        while !encounter.is_over() && encounter.round <= 50 {
+            results.extend(encounter.step());
+        }
+    });
+
+    let mut pool = EncounterPool::new(template.clone());
+    // Warm up so the pool's buffers have grown to fit
+    pool.run_fight(DiceMode::seeded(0), 50);
+    let pooled = per_fight(|seed| {
+        pool.run_fight(DiceMode::seeded(seed), 50);
+    });
+    assert!(pooled < fresh, "pooled {} vs fresh {}", pooled, fresh);
+
+    // Setting up alone: resetting in place beats cloning the template
+    let cloned = per_fight(|_| drop(template.clone()));
+    let reset = per_fight(|seed| {
+        pool.next_fight(DiceMode::seeded(seed));
+    });
+    assert!(reset < cloned, "reset {} vs clone {}", reset, cloned);
+}